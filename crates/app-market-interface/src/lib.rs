@@ -0,0 +1,35 @@
+//! CPI interface for the App Market escrow program
+//!
+//! Generated from `idls/app_market.json` with `declare_program!`, so launchpads, DAO tooling
+//! and other programs can CPI into `create_listing`, `buy_now`, `make_offer` and the rest with
+//! typed accounts, without compiling the program itself:
+//!
+//! ```ignore
+//! use app_market_interface::app_market;
+//!
+//! app_market::cpi::buy_now(
+//!     CpiContext::new(app_market_program, app_market::cpi::accounts::BuyNow { .. }),
+//!     None, // recipient
+//!     Some(expected_price),
+//!     None, // agreement_hash
+//!     Some(withdrawal_bump),
+//! )?;
+//! ```
+//!
+//! Instruction arguments are positional Borsh: existing arguments are never reordered or
+//! retyped, and new ones are only appended. Appending still breaks older callers: Borsh does
+//! not read a missing trailing `Option` as `None`, so instruction data built against an older
+//! IDL fails with `InstructionDidNotDeserialize`. Upgrade this crate together with the
+//! program. Its version tracks the program release its IDL was built from; regenerate the
+//! IDL after every program change with
+//! `anchor idl build -p app_market -o crates/app-market-interface/idls/app_market.json`.
+//!
+//! Programs that prefer depending on the program crate directly can still use
+//! `app-market = { features = ["cpi"] }` (`cpi` implies `no-entrypoint`).
+
+// Generated CPI helpers take one argument per instruction argument (create_listing has 19)
+#![allow(clippy::too_many_arguments)]
+
+use anchor_lang::prelude::*;
+
+declare_program!(app_market);
@@ -20,7 +20,7 @@ import { useCallback } from "react";
 // Account types for Anchor program deserialization
 interface ListingAccount {
   seller: PublicKey;
-  listingId: string;
+  salt: BN;
   startingPrice: BN;
   reservePrice: BN | null;
   buyNowPrice: BN | null;
@@ -88,6 +88,7 @@ export function useSolanaContract() {
       .accounts({
         config: configPDA,
         admin: wallet.publicKey!,
+        payer: wallet.publicKey!,
         systemProgram: SystemProgram.programId,
       })
       .rpc();
@@ -484,7 +485,7 @@ export function useSolanaContract() {
 
     return {
       seller: listing.seller,
-      listingId: listing.listingId,
+      listingId: `${listing.seller.toBase58()}-${listing.salt.toString()}`,
       startingPrice: lamportsToSol(listing.startingPrice),
       reservePrice: listing.reservePrice ? lamportsToSol(listing.reservePrice) : null,
       buyNowPrice: listing.buyNowPrice ? lamportsToSol(listing.buyNowPrice) : null,
@@ -540,7 +541,7 @@ export function useSolanaContract() {
     const listings = await program.account.listing.all([
       {
         memcmp: {
-          offset: 8, // Skip discriminator
+          offset: 8 + 1, // Skip discriminator and layout version
           bytes: sellerPublicKey.toBase58(),
         },
       },
@@ -564,14 +565,11 @@ export function useSolanaContract() {
   // Fetch all active listings
   const fetchActiveListings = useCallback(async () => {
     const { program } = getProvider();
-    const listings = await program.account.listing.all([
-      {
-        memcmp: {
-          offset: 8 + 32 + 64 + 1, // discriminator + seller pubkey + listingId (estimate)
-          bytes: Buffer.from([0]).toString('base64'), // ListingStatus::Active = 0
-        },
-      },
-    ]);
+    // status sits behind variable-length Option fields, so it has no fixed offset to
+    // memcmp on; filter the decoded accounts instead
+    const listings = (await program.account.listing.all()).filter(
+      ({ account }) => 'active' in (account.status as object)
+    );
 
     return listings.map(({ publicKey, account: raw }) => {
       const account = raw as unknown as ListingAccount;
@@ -92,10 +92,11 @@ export async function placeBid(params: PlaceBidParams): Promise<string> {
 
   const [escrow] = getEscrowPDA(params.listing);
   const [config] = getConfigPDA();
-  const [pendingWithdrawal] = getWithdrawalPDA(params.listing, params.withdrawalCount + 1);
+  // Passing the bump saves the program a find_program_address bump search
+  const [pendingWithdrawal, withdrawalBump] = getWithdrawalPDA(params.listing, params.withdrawalCount + 1);
 
   const tx = await program.methods
-    .placeBid(solToLamports(params.amount))
+    .placeBid(solToLamports(params.amount), withdrawalBump)
     .accounts({
       listing: params.listing,
       escrow,
@@ -159,10 +160,10 @@ export async function buyNow(params: BuyNowParams): Promise<string> {
   // SECURITY: Include pending_withdrawal account required by on-chain program
   const listing = await program.account.listing.fetch(params.listing);
   const withdrawalCount = (listing as any).withdrawalCount ?? 0;
-  const [pendingWithdrawal] = getWithdrawalPDA(params.listing, withdrawalCount + 1);
+  const [pendingWithdrawal, withdrawalBump] = getWithdrawalPDA(params.listing, withdrawalCount + 1);
 
   const tx = await program.methods
-    .buyNow()
+    .buyNow(null, null, null, withdrawalBump)
     .accounts({
       listing: params.listing,
       escrow,
@@ -382,6 +383,8 @@ export interface OpenDisputeParams {
   provider: AnchorProvider;
   listing: PublicKey;
   treasury: PublicKey;
+  category: "notDelivered" | "misrepresented" | "partialDelivery" | "postSaleClawback" | "other";
+  severity: "low" | "medium" | "high" | "critical";
   reason: string;
 }
 
@@ -394,7 +397,7 @@ export async function openDispute(params: OpenDisputeParams): Promise<string> {
   const [config] = getConfigPDA();
 
   const tx = await program.methods
-    .openDispute(params.reason)
+    .openDispute(params.reason, { [params.category]: {} }, { [params.severity]: {} })
     .accounts({
       transaction,
       dispute,
@@ -521,6 +524,35 @@ export async function executeDisputeResolution(params: ExecuteDisputeResolutionP
   return tx;
 }
 
+// Pays the final ruling once the appeal window has lapsed or the appeal was ruled on
+export async function settleDisputeRuling(params: ExecuteDisputeResolutionParams): Promise<string> {
+  const program = getProgram(params.provider);
+  const caller = params.provider.wallet.publicKey;
+
+  const [transaction] = getTransactionPDA(params.listing);
+  const [dispute] = getDisputePDA(transaction);
+  const [escrow] = getEscrowPDA(params.listing);
+  const [config] = getConfigPDA();
+
+  const tx = await program.methods
+    .settleDisputeRuling()
+    .accounts({
+      dispute,
+      transaction,
+      escrow,
+      listing: params.listing,
+      seller: params.seller,
+      buyer: params.buyer,
+      treasury: params.treasury,
+      config,
+      caller,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+
+  return tx;
+}
+
 // ============================================
 // CANCELLATION
 // ============================================
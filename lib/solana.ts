@@ -163,7 +163,7 @@ export const calculateSellerProceeds = (salePrice: number, currency?: string): {
   return { fee: result.fee, proceeds: result.proceeds, feeBps: result.feeBps };
 };
 
-// Listing status enum (matches on-chain - 9 statuses)
+// Listing status enum (matches on-chain - 11 statuses)
 export enum ListingStatus {
   Active = 0,
   Ended = 1,
@@ -174,6 +174,8 @@ export enum ListingStatus {
   Disputed = 6,
   Completed = 7,
   Refunded = 8,
+  Draft = 9,
+  Paused = 10,
 }
 
 // Transaction status enum (matches on-chain - 10 statuses)
@@ -193,7 +195,7 @@ export enum TransactionStatus {
 // Types for on-chain data
 export interface OnChainListing {
   seller: PublicKey;
-  listingId: string;
+  salt: BN;
   startingPrice: BN;
   reservePrice: BN | null;
   buyNowPrice: BN | null;
@@ -0,0 +1,255 @@
+//! Protocol constants
+//!
+//! Deployment keys and the long timelocks depend on the cluster feature: `mainnet`
+//! (default), `devnet` or `localnet`. Devnet and localnet builds take the admin and APP
+//! mint from the `APP_MARKET_EXPECTED_ADMIN` / `APP_MARKET_APP_TOKEN_MINT` environment
+//! variables at compile time, e.g.
+//! `anchor build -- --no-default-features --features devnet`.
+
+use anchor_lang::prelude::*;
+
+/// Basis points divisor (100% = 10000 basis points)
+pub const BASIS_POINTS_DIVISOR: u64 = 10000;
+
+/// Platform fee: 5% (500 basis points)
+pub const PLATFORM_FEE_BPS: u64 = 500;
+/// APP token fee: 3% (300 basis points) - discounted rate for $APP payments
+pub const APP_FEE_BPS: u64 = 300;
+/// Dispute fee: 2% (200 basis points)
+pub const DISPUTE_FEE_BPS: u64 = 200;
+
+/// APP token mint address
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const APP_TOKEN_MINT: Pubkey = pubkey!("Ansto3G3SzGt6bXo3pMddiM4YkW9Yt8y7Qvwy47dBAGS");
+#[cfg(any(feature = "devnet", feature = "localnet"))]
+pub const APP_TOKEN_MINT: Pubkey = Pubkey::from_str_const(env!("APP_MARKET_APP_TOKEN_MINT"));
+
+/// Maximum platform fee: 10% (prevents accidental/malicious fee rug)
+pub const MAX_PLATFORM_FEE_BPS: u64 = 1000;
+/// Maximum dispute fee: 5%
+pub const MAX_DISPUTE_FEE_BPS: u64 = 500;
+
+/// Transfer deadline: 7 days in seconds
+pub const TRANSFER_DEADLINE_SECONDS: i64 = 7 * 24 * 60 * 60;
+/// Maximum auction duration: 30 days
+pub const MAX_AUCTION_DURATION_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Minimum bid increment: 5% (500 basis points)
+pub const MIN_BID_INCREMENT_BPS: u64 = 500;
+/// Absolute minimum bid increment: 0.1 SOL (100,000,000 lamports)
+/// SPL listings use the same 0.1 whole-token amount, scaled to the mint's decimals
+pub const MIN_BID_INCREMENT_LAMPORTS: u64 = 100_000_000;
+/// Decimals of native SOL prices (lamports)
+pub const NATIVE_DECIMALS: u8 = 9;
+
+/// Anti-sniping window: 15 minutes before auction end
+pub const ANTI_SNIPE_WINDOW: i64 = 15 * 60;
+/// Extension time when bid placed in anti-snipe window
+pub const ANTI_SNIPE_EXTENSION: i64 = 15 * 60;
+/// Default cap on anti-snipe extensions per auction (12 x 15 minutes = 3 hours)
+pub const DEFAULT_MAX_ANTI_SNIPE_EXTENSIONS: u16 = 12;
+
+/// Offer lifetime bounds until the admin sets others (a config value of 0 means these)
+pub const DEFAULT_MIN_OFFER_DURATION_SECONDS: i64 = 60 * 60;
+pub const DEFAULT_MAX_OFFER_DURATION_SECONDS: i64 = 30 * 24 * 60 * 60;
+/// Longest offer lifetime the admin can allow
+pub const MAX_OFFER_DURATION_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+/// Admin timelock: 48 hours for sensitive operations (10 minutes on devnet, 10 seconds on localnet)
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const ADMIN_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+#[cfg(feature = "devnet")]
+pub const ADMIN_TIMELOCK_SECONDS: i64 = 10 * 60;
+#[cfg(feature = "localnet")]
+pub const ADMIN_TIMELOCK_SECONDS: i64 = 10;
+
+/// Finalize grace period: 7 days after seller confirmation
+pub const FINALIZE_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+/// Longest validity of a backend buyer attestation (1 year)
+pub const MAX_ATTESTATION_VALIDITY_SECONDS: i64 = 365 * 24 * 60 * 60;
+
+/// Longest lifetime of a bidding session key
+pub const MAX_SESSION_DURATION_SECONDS: i64 = 24 * 60 * 60;
+
+/// Maximum bids per listing (prevents DoS via bid spam)
+pub const MAX_BIDS_PER_LISTING: u64 = 1000;
+/// Maximum total offers per listing (prevents DoS via offer spam)
+pub const MAX_OFFERS_PER_LISTING: u32 = 100;
+/// Maximum consecutive offers per buyer without being outbid
+pub const MAX_CONSECUTIVE_OFFERS: u32 = 10;
+/// Maximum consecutive bids per bidder without being outbid
+pub const MAX_CONSECUTIVE_BIDS: u32 = 10;
+
+/// Backend verification timeout: 30 days (fallback if backend unresponsive)
+pub const BACKEND_TIMEOUT_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Time a seller has to fix a rejected delivery before the buyer can be refunded
+pub const VERIFICATION_CURE_PERIOD_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Dispute resolution timelock: 48 hours for parties to contest
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const DISPUTE_RESOLUTION_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+#[cfg(feature = "devnet")]
+pub const DISPUTE_RESOLUTION_TIMELOCK_SECONDS: i64 = 10 * 60;
+#[cfg(feature = "localnet")]
+pub const DISPUTE_RESOLUTION_TIMELOCK_SECONDS: i64 = 10;
+/// Appeal resolution timelock: 96 hours (appeals get a longer contest window)
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const APPEAL_RESOLUTION_TIMELOCK_SECONDS: i64 = 96 * 60 * 60;
+#[cfg(feature = "devnet")]
+pub const APPEAL_RESOLUTION_TIMELOCK_SECONDS: i64 = 20 * 60;
+#[cfg(feature = "localnet")]
+pub const APPEAL_RESOLUTION_TIMELOCK_SECONDS: i64 = 20;
+/// Small-claim resolution timelock: 12 hours (fast track below config.small_claims_threshold)
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const SMALL_CLAIM_RESOLUTION_TIMELOCK_SECONDS: i64 = 12 * 60 * 60;
+#[cfg(feature = "devnet")]
+pub const SMALL_CLAIM_RESOLUTION_TIMELOCK_SECONDS: i64 = 5 * 60;
+#[cfg(feature = "localnet")]
+pub const SMALL_CLAIM_RESOLUTION_TIMELOCK_SECONDS: i64 = 5;
+/// Respondent bond window: 72 hours after a dispute opens to post the matching fee
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const RESPONDENT_BOND_WINDOW_SECONDS: i64 = 72 * 60 * 60;
+#[cfg(feature = "devnet")]
+pub const RESPONDENT_BOND_WINDOW_SECONDS: i64 = 10 * 60;
+#[cfg(feature = "localnet")]
+pub const RESPONDENT_BOND_WINDOW_SECONDS: i64 = 10;
+/// Maximum configurable appeal window after dispute execution: 30 days
+pub const MAX_APPEAL_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+/// Maximum evidence submissions per party per dispute
+pub const MAX_EVIDENCE_PER_PARTY: u8 = 10;
+/// Maximum evidence URI length
+pub const MAX_EVIDENCE_URI_LEN: usize = 200;
+/// Maximum arbitrators in the dispute committee
+pub const MAX_ARBITRATORS: usize = 7;
+/// Grace period after auction end before anyone can settle: 24 hours
+pub const SETTLEMENT_GRACE_PERIOD_SECONDS: i64 = 24 * 60 * 60;
+/// Permissionless settlement tip: 10% of the platform fee (1000 basis points)
+pub const SETTLEMENT_TIP_BPS: u64 = 1000;
+/// Default dispute SLA: initiator may claim the default ruling after 30 days of inactivity
+pub const DEFAULT_DISPUTE_SLA_SECONDS: i64 = 30 * 24 * 60 * 60;
+/// Dispute SLA bounds: 7 to 90 days
+pub const MIN_DISPUTE_SLA_SECONDS: i64 = 7 * 24 * 60 * 60;
+pub const MAX_DISPUTE_SLA_SECONDS: i64 = 90 * 24 * 60 * 60;
+/// Longest a single pause can block user exits before they reopen automatically: 14 days
+pub const MAX_PAUSE_DURATION_SECONDS: i64 = 14 * 24 * 60 * 60;
+/// Maximum length of a pause reason
+pub const MAX_PAUSE_REASON_LEN: usize = 200;
+/// Smallest configurable circuit breaker multiple of trailing daily volume
+pub const MIN_CIRCUIT_BREAKER_MULTIPLE: u64 = 2;
+/// Shortest configurable escheat period for unclaimed withdrawals: 180 days after expiry
+pub const MIN_ESCHEAT_PERIOD_SECONDS: i64 = 180 * 24 * 60 * 60;
+/// Arbitrator unstake cooldown: 35 days (outlasts max appeal window + appeal timelock)
+pub const ARBITRATOR_UNSTAKE_COOLDOWN_SECONDS: i64 = 35 * 24 * 60 * 60;
+/// Shortest step of a buy-now price drop schedule: 1 hour
+pub const MIN_PRICE_DROP_INTERVAL_SECONDS: i64 = 60 * 60;
+/// SPL stake pool program (bSOL, jitoSOL, ...) escrowed SOL can be staked into
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+/// Accounts a payout path takes ahead of its other remaining_accounts while the escrow is
+/// staked (see unwind_escrow_stake)
+pub const ESCROW_STAKE_ACCOUNT_COUNT: usize = 17;
+/// Byte offset of the pool mint in an SPL StakePool account (after the 1-byte account type)
+pub const STAKE_POOL_POOL_MINT_OFFSET: usize = 162;
+/// Recurring escrow bounds: periods of 1 to 366 days, at most 120 of them
+pub const MIN_RECURRING_PERIOD_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_RECURRING_PERIOD_SECONDS: i64 = 366 * 24 * 60 * 60;
+pub const MAX_RECURRING_PERIODS: u16 = 120;
+/// Rent-to-own: installments use the recurring period bounds; a missed installment can
+/// be called as a default after this grace period: 7 days
+pub const RENT_TO_OWN_GRACE_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Expected admin pubkey (prevents initialization frontrunning)
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const EXPECTED_ADMIN: Pubkey = pubkey!("63jQ3qffMgacpUw8ebDZPuyUHf7DsfsYnQ7sk8fmFaF1");
+#[cfg(any(feature = "devnet", feature = "localnet"))]
+pub const EXPECTED_ADMIN: Pubkey = Pubkey::from_str_const(env!("APP_MARKET_EXPECTED_ADMIN"));
+
+/// Minimum earnest-money deposit for deposit-mode auctions: 10%
+pub const MIN_DEPOSIT_BPS: u64 = 1000;
+/// Payment window for deposit-mode winners to pay the remainder: 3 days
+pub const DEPOSIT_PAYMENT_WINDOW_SECONDS: i64 = 3 * 24 * 60 * 60;
+
+/// Maximum vesting period for streamed seller proceeds: 365 days
+pub const MAX_VESTING_DAYS: u16 = 365;
+
+/// Maximum share of seller proceeds held back for the warranty period: 50%
+pub const MAX_HOLDBACK_BPS: u64 = 5000;
+/// Warranty period after completion during which held-back proceeds stay escrowed: 30 days
+pub const WARRANTY_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Timelock on changing a listing's payout wallet: 48 hours
+#[cfg(not(any(feature = "devnet", feature = "localnet")))]
+pub const PAYOUT_WALLET_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+#[cfg(feature = "devnet")]
+pub const PAYOUT_WALLET_TIMELOCK_SECONDS: i64 = 10 * 60;
+#[cfg(feature = "localnet")]
+pub const PAYOUT_WALLET_TIMELOCK_SECONDS: i64 = 10;
+
+/// Maximum recipients in a listing's proceeds split
+pub const MAX_SPLIT_RECIPIENTS: usize = 5;
+
+/// Maximum units a multi-unit listing can offer
+pub const MAX_LISTING_QUANTITY: u64 = 10_000;
+
+/// Maximum apps in a bundle listing (bounded by the u16 checklist bitmask)
+pub const MAX_BUNDLE_ASSETS: usize = 10;
+
+/// Furthest ahead a listing can schedule its start (30 days)
+pub const MAX_START_DELAY_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Listings created per create_listings_batch call (transaction size / compute bound)
+pub const MAX_BATCH_LISTINGS: usize = 8;
+
+/// Longest paid promotion bought in one call
+pub const MAX_PROMOTION_DAYS: u16 = 30;
+
+/// Discovery tags per listing, and the length of each (lowercase a-z, 0-9, '-')
+pub const MAX_LISTING_TAGS: usize = 5;
+pub const MAX_TAG_LEN: usize = 24;
+
+/// Alternative currencies a listing can price its buy-now in, besides its payment_mint
+pub const MAX_ACCEPTED_CURRENCIES: usize = 4;
+
+/// Deliverable content hashes / IPFS CIDs a seller can record per transaction
+pub const MAX_DELIVERABLES: usize = 8;
+pub const MAX_DELIVERABLE_LEN: usize = 64;
+
+/// Bids kept in a listing's on-chain bid book
+pub const BID_BOOK_SIZE: usize = 5;
+/// Most recent bids kept in a listing's BidHistory ring buffer
+pub const BID_HISTORY_SIZE: usize = 32;
+
+/// Minimum buyer pool contribution: 0.01 SOL (keeps the pool vault rent-exempt)
+pub const MIN_POOL_CONTRIBUTION_LAMPORTS: u64 = 10_000_000;
+/// Buyer pool share tokens are lamport-denominated (1 share = 1 SOL contributed)
+pub const POOL_SHARE_DECIMALS: u8 = 9;
+/// Fraction share tokens are whole shares of an app deed
+pub const FRACTION_SHARE_DECIMALS: u8 = 0;
+/// Smallest and largest number of shares a deed can be split into
+pub const MIN_FRACTION_SHARES: u64 = 2;
+pub const MAX_FRACTION_SHARES: u64 = 1_000_000;
+/// Maximum share of each platform fee diverted to the insurance fund: 20%
+pub const MAX_INSURANCE_FEE_BPS: u64 = 2000;
+/// Buyers can file an insurance claim up to 180 days after a sale completes
+pub const INSURANCE_CLAIM_WINDOW_SECONDS: i64 = 180 * 24 * 60 * 60;
+/// Maximum recipients in the platform fee split
+pub const MAX_FEE_SPLIT_RECIPIENTS: usize = 5;
+/// Fixed-point scale for staker rewards per staked APP unit
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+/// Seconds per day (vesting schedules are configured in whole days)
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Lamports per SOL (loyalty points are configured per whole SOL of volume)
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+/// Maximum fee discount a user can hold from redeemed loyalty points
+pub const MAX_LOYALTY_DISCOUNT_BPS: u64 = MAX_PLATFORM_FEE_BPS;
+
+/// Layout version every account stores in its first field, the byte right after the
+/// discriminator (BidBook keeps it in its padding); see migrate_account.
+/// 1: compact integer types and the version byte, plus every field added since the
+///    first deployment (v0 accounts carry no version byte; see legacy.rs)
+pub const ACCOUNT_LAYOUT_VERSION: u8 = 1;
+
+/// Bumped whenever an upgrade changes a value mirrored in ProtocolParams
+pub const PROTOCOL_PARAMS_VERSION: u16 = 1;
@@ -0,0 +1,4791 @@
+//! Account validation structs for every instruction
+
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MarketConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, MarketConfig>,
+
+    /// CHECK: Treasury wallet to receive fees
+    pub treasury: AccountInfo<'info>,
+
+    /// Read-only so a multisig vault PDA (signing via CPI) can be the admin from day one
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeTreasuryChange<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTreasuryChange<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestTreasuryWithdrawal<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TreasuryWithdrawal::INIT_SPACE,
+        seeds = [b"treasury_withdrawal".as_ref(), &config.treasury_withdrawal_count.to_le_bytes()],
+        bump
+    )]
+    pub treasury_withdrawal: Account<'info, TreasuryWithdrawal>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"treasury_withdrawal".as_ref(), &treasury_withdrawal.id.to_le_bytes()],
+        bump = treasury_withdrawal.bump
+    )]
+    pub treasury_withdrawal: Account<'info, TreasuryWithdrawal>,
+
+    /// Program-owned SOL vault holding protocol fees
+    #[account(mut, seeds = [b"treasury_vault"], bump)]
+    pub treasury_vault: SystemAccount<'info>,
+
+    /// CHECK: Receives the withdrawal (validated against treasury_withdrawal.recipient)
+    #[account(
+        mut,
+        constraint = recipient.key() == treasury_withdrawal.recipient @ AppMarketError::InvalidRecipient
+    )]
+    pub recipient: AccountInfo<'info>,
+
+    /// CHECK: Receives the request's rent (validated against treasury_withdrawal.rent_payer)
+    #[account(
+        mut,
+        constraint = rent_payer.key() == treasury_withdrawal.rent_payer @ AppMarketError::InvalidRecipient
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelTreasuryWithdrawal<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"treasury_withdrawal".as_ref(), &treasury_withdrawal.id.to_le_bytes()],
+        bump = treasury_withdrawal.bump
+    )]
+    pub treasury_withdrawal: Account<'info, TreasuryWithdrawal>,
+
+    /// CHECK: Receives the request's rent (validated against treasury_withdrawal.rent_payer)
+    #[account(
+        mut,
+        constraint = rent_payer.key() == treasury_withdrawal.rent_payer @ AppMarketError::InvalidRecipient
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminChange<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminChange<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetLoyaltyConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitUserProfile<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetContactKey<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SyncContactKeys<'info> {
+    #[account(mut)]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        seeds = [b"user_profile", transaction.buyer.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_profile", transaction.seller.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Account<'info, UserProfile>,
+
+    /// Buyer or seller
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct InitVolumeStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VolumeStats::INIT_SPACE,
+        seeds = [b"volume_stats", mint.as_ref()],
+        bump
+    )]
+    pub volume_stats: Account<'info, VolumeStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitProtocolParams<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ProtocolParams::INIT_SPACE,
+        seeds = [b"protocol_params"],
+        bump
+    )]
+    pub protocol_params: Account<'info, ProtocolParams>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SyncProtocolParams<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_params"],
+        bump = protocol_params.bump
+    )]
+    pub protocol_params: Account<'info, ProtocolParams>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Program-owned; the handler matches its discriminator and size against the
+    /// known old layouts (they cannot be deserialized as the current structs)
+    #[account(mut, owner = crate::ID)]
+    pub target: UncheckedAccount<'info>,
+
+    /// Tops up rent when the current layout is larger
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPoints<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(salt: u64)]
+pub struct CreateListing<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Listing::INIT_SPACE,
+        seeds = [b"listing", seller.key().as_ref(), &salt.to_le_bytes()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Initialize escrow atomically with listing (seller pays rent)
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Optional: seller profile whose redeemed fee discount is applied to this listing
+    #[account(
+        mut,
+        seeds = [b"user_profile", seller.key().as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: admin approval to list above config.max_sale_price
+    #[account(
+        seeds = [b"price_cap_override", seller.key().as_ref()],
+        bump = price_cap_override.bump
+    )]
+    pub price_cap_override: Option<Account<'info, PriceCapOverride>>,
+
+    /// Required when payment_mint is set: the mint, read for its decimals
+    pub payment_mint_account: Option<Account<'info, Mint>>,
+
+    /// Required once config.has_fee_schedule: per-category platform fee rates
+    #[account(seeds = [b"fee_schedule"], bump = fee_schedule.bump)]
+    pub fee_schedule: Option<Account<'info, FeeSchedule>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateListingsBatch<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    /// Optional: admin approval to list above config.max_sale_price
+    #[account(
+        seeds = [b"price_cap_override", seller.key().as_ref()],
+        bump = price_cap_override.bump
+    )]
+    pub price_cap_override: Option<Account<'info, PriceCapOverride>>,
+
+    /// Required once config.has_fee_schedule: per-category platform fee rates
+    #[account(seeds = [b"fee_schedule"], bump = fee_schedule.bump)]
+    pub fee_schedule: Option<Account<'info, FeeSchedule>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct PlaceBid<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Escrow must already exist (no init_if_needed race condition)
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // SECURITY: Pending withdrawal for previous bidder (only created when needed)
+    /// CHECK: Only created if there's a previous bidder to refund
+    #[account(mut)]
+    pub pending_withdrawal: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Required when the listing has a bid book (checked in the handler)
+    #[account(mut)]
+    pub bid_book: Option<AccountLoader<'info, BidBook>>,
+
+    /// Required when the bid book has a bid history (checked in the handler)
+    #[account(mut)]
+    pub bid_history: Option<AccountLoader<'info, BidHistory>>,
+
+    /// Required on bid-escrow listings: the bidder's BidEscrow (created on first bid)
+    /// CHECK: PDA verified by seeds; created or deserialized in the handler
+    #[account(
+        mut,
+        seeds = [b"bid_escrow", listing.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_escrow: Option<UncheckedAccount<'info>>,
+
+    /// Optional: bidding session (bidder is then the session key, checked in the handler)
+    #[account(
+        mut,
+        seeds = [b"bid_session", session.owner.as_ref(), session.session_key.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, BidSession>>,
+
+    /// Required with `session`: its SOL vault (validated in the handler)
+    #[account(mut)]
+    pub session_vault: Option<SystemAccount<'info>>,
+
+    /// Optional: backend attestation, required when listing.requires_verification
+    #[account(
+        seeds = [b"buyer_attestation", buyer_attestation.buyer.as_ref()],
+        bump = buyer_attestation.bump
+    )]
+    pub buyer_attestation: Option<Account<'info, BuyerAttestation>>,
+
+    /// Optional: bidder profile tracking locked exposure (required while a cap is set)
+    #[account(
+        mut,
+        seeds = [b"user_profile", bidder_profile.user.as_ref()],
+        bump = bidder_profile.bump
+    )]
+    pub bidder_profile: Option<Account<'info, UserProfile>>,
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + BidSession::INIT_SPACE,
+        seeds = [b"bid_session", owner.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, BidSession>,
+
+    /// SOL vault the session key bids from (system-owned PDA)
+    #[account(
+        mut,
+        seeds = [b"session_vault", session.key().as_ref()],
+        bump
+    )]
+    pub session_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSession<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"bid_session", owner.key().as_ref(), session.session_key.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, BidSession>,
+
+    #[account(
+        mut,
+        seeds = [b"session_vault", session.key().as_ref()],
+        bump = session.vault_bump
+    )]
+    pub session_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFunds<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // SECURITY: Close withdrawal account and return rent to whoever paid it (not the claimant)
+    // Uses withdrawal_id from PendingWithdrawal struct (not seeds - we look it up)
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [
+            b"withdrawal",
+            listing.key().as_ref(),
+            &pending_withdrawal.withdrawal_id.to_le_bytes()
+        ],
+        bump = pending_withdrawal.bump,
+        constraint = pending_withdrawal.user == user.key() @ AppMarketError::NotWithdrawalOwner
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// CHECK: Receives the withdrawal PDA rent back (validated against pending_withdrawal.rent_payer)
+    #[account(
+        mut,
+        address = pending_withdrawal.rent_payer @ AppMarketError::InvalidRentPayer
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    /// Optional: user profile whose bid exposure the refund releases
+    /// (required when pending_withdrawal.exposure > 0)
+    #[account(
+        mut,
+        seeds = [b"user_profile", pending_withdrawal.user.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyEscrow<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseBidExposure<'info> {
+    #[account(mut)]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", transaction.buyer.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Account<'info, UserProfile>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireWithdrawal<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // Close the expired withdrawal account, return rent to its payer (not user or caller)
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [
+            b"withdrawal",
+            listing.key().as_ref(),
+            &pending_withdrawal.withdrawal_id.to_le_bytes()
+        ],
+        bump = pending_withdrawal.bump,
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// The original user who was outbid — funds go back to them
+    /// CHECK: Validated against pending_withdrawal.user
+    #[account(
+        mut,
+        constraint = recipient.key() == pending_withdrawal.user @ AppMarketError::NotWithdrawalOwner
+    )]
+    pub recipient: AccountInfo<'info>,
+
+    /// CHECK: Receives the withdrawal PDA rent back (validated against pending_withdrawal.rent_payer)
+    #[account(
+        mut,
+        address = pending_withdrawal.rent_payer @ AppMarketError::InvalidRentPayer
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    /// Optional: user profile whose bid exposure the refund releases
+    /// (required when pending_withdrawal.exposure > 0)
+    #[account(
+        mut,
+        seeds = [b"user_profile", pending_withdrawal.user.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Option<Account<'info, UserProfile>>,
+
+    /// Anyone can call this after expiry (permissionless cleanup)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EscheatWithdrawal<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // Funds go to the treasury; rent still goes back to its payer
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [
+            b"withdrawal",
+            listing.key().as_ref(),
+            &pending_withdrawal.withdrawal_id.to_le_bytes()
+        ],
+        bump = pending_withdrawal.bump,
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    /// CHECK: Validated against config.treasury
+    #[account(
+        mut,
+        address = config.treasury @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Receives the withdrawal PDA rent back (validated against pending_withdrawal.rent_payer)
+    #[account(
+        mut,
+        address = pending_withdrawal.rent_payer @ AppMarketError::InvalidRentPayer
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    /// Anyone can call this after the escheat period (permissionless cleanup)
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional: user profile whose bid exposure the escheat releases
+    /// (required when pending_withdrawal.exposure > 0)
+    #[account(
+        mut,
+        seeds = [b"user_profile", pending_withdrawal.user.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Option<Account<'info, UserProfile>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseEscrow<'info> {
+    #[account(
+        constraint = listing.payout_wallet == payout_wallet.key() @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump,
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    // Close escrow — rent goes to the seller's payout wallet (the seller created the listing)
+    #[account(
+        mut,
+        close = payout_wallet,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Seller's payout wallet receives escrow rent — validated against listing.payout_wallet
+    #[account(mut)]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Anyone can call this (permissionless cleanup)
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BuyNow<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Escrow must already exist
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    // SECURITY: Pending withdrawal for previous bidder (only initialized if previous bidder exists)
+    /// CHECK: Only used if listing.current_bidder exists, manually initialized in instruction
+    #[account(mut)]
+    pub pending_withdrawal: UncheckedAccount<'info>,
+
+    /// Optional: the seller's admin approval to sell above config.max_sale_price
+    #[account(
+        seeds = [b"price_cap_override", listing.seller.as_ref()],
+        bump = price_cap_override.bump
+    )]
+    pub price_cap_override: Option<Account<'info, PriceCapOverride>>,
+
+    /// Optional: backend attestation, required when listing.requires_verification
+    #[account(
+        seeds = [b"buyer_attestation", buyer_attestation.buyer.as_ref()],
+        bump = buyer_attestation.bump
+    )]
+    pub buyer_attestation: Option<Account<'info, BuyerAttestation>>,
+
+    /// Optional: buyer's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", buyer_profile.user.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", seller_profile.user.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyUnit<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // One transaction per unit, indexed by the units sold before this purchase
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref(), &listing.units_sold.to_le_bytes()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Optional: buyer's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", buyer_profile.user.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", seller_profile.user.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CreateBuyerPool<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BuyerPool::INIT_SPACE,
+        seeds = [
+            b"buyer_pool",
+            listing.key().as_ref(),
+            creator.key().as_ref(),
+            &pool_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub buyer_pool: Account<'info, BuyerPool>,
+
+    /// SOL vault (system-owned PDA so it can pay escrow and act as the transaction buyer)
+    #[account(
+        seeds = [b"pool_vault", buyer_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    /// Share token minted to contributors when the purchase completes
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"pool_shares", buyer_pool.key().as_ref()],
+        bump,
+        mint::decimals = POOL_SHARE_DECIMALS,
+        mint::authority = buyer_pool
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ContributeToPool<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_pool",
+            buyer_pool.listing.as_ref(),
+            buyer_pool.creator.as_ref(),
+            &buyer_pool.pool_id.to_le_bytes()
+        ],
+        bump = buyer_pool.bump
+    )]
+    pub buyer_pool: Account<'info, BuyerPool>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault", buyer_pool.key().as_ref()],
+        bump = buyer_pool.vault_bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = contributor,
+        space = 8 + PoolContribution::INIT_SPACE,
+        seeds = [b"pool_contribution", buyer_pool.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, PoolContribution>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecutePoolPurchase<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_pool",
+            listing.key().as_ref(),
+            buyer_pool.creator.as_ref(),
+            &buyer_pool.pool_id.to_le_bytes()
+        ],
+        bump = buyer_pool.bump
+    )]
+    pub buyer_pool: Account<'info, BuyerPool>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault", buyer_pool.key().as_ref()],
+        bump = buyer_pool.vault_bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = executor,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPoolContribution<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_pool",
+            listing.key().as_ref(),
+            buyer_pool.creator.as_ref(),
+            &buyer_pool.pool_id.to_le_bytes()
+        ],
+        bump = buyer_pool.bump
+    )]
+    pub buyer_pool: Account<'info, BuyerPool>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_vault", buyer_pool.key().as_ref()],
+        bump = buyer_pool.vault_bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [b"pool_contribution", buyer_pool.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, PoolContribution>,
+
+    /// Pool purchase transaction (required once the pool has bought)
+    pub transaction: Option<Account<'info, Transaction>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_shares", buyer_pool.key().as_ref()],
+        bump = buyer_pool.mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    /// Contributor's share token account (required when shares are minted)
+    #[account(mut)]
+    pub contributor_share_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order: SignedOrder)]
+pub struct FulfillSignedOrder<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    // Same PDA as create_listing: an order's listing can only ever be created once
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Listing::INIT_SPACE,
+        seeds = [b"listing", order.seller.as_ref(), &order.salt.to_le_bytes()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Seller's OrderNonce PDA (may not exist yet = nonce 0), read in the handler
+    #[account(seeds = [b"order_nonce", order.seller.as_ref()], bump)]
+    pub order_nonce: UncheckedAccount<'info>,
+
+    /// CHECK: Cancellation marker PDA for this order; must not exist
+    #[account(
+        seeds = [b"order_cancel", order.seller.as_ref(), &order.salt.to_le_bytes()],
+        bump
+    )]
+    pub order_cancellation: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar (address-checked), used to find the ed25519 verification
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(salt: u64)]
+pub struct CancelSignedOrder<'info> {
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + OrderCancellation::INIT_SPACE,
+        seeds = [b"order_cancel", seller.key().as_ref(), &salt.to_le_bytes()],
+        bump
+    )]
+    pub order_cancellation: Account<'info, OrderCancellation>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct IncrementNonce<'info> {
+    /// CHECK: Seller's OrderNonce PDA, created on first use and (de)serialized in the handler
+    #[account(
+        mut,
+        seeds = [b"order_nonce", seller.key().as_ref()],
+        bump
+    )]
+    pub order_nonce: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Optional: buyer's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", buyer_profile.user.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", seller_profile.user.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    /// Required on bid-escrow auctions: the winner's BidEscrow (validated in the handler)
+    #[account(
+        mut,
+        seeds = [b"bid_escrow", listing.key().as_ref(), bidder.key().as_ref()],
+        bump = bid_escrow.bump
+    )]
+    pub bid_escrow: Option<Account<'info, BidEscrow>>,
+
+    /// CHECK: Current bidder (validated in instruction)
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+
+    /// Seller, winner, admin - or anyone after the grace period (receives a tip)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptCurrentBid<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Optional: buyer's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", buyer_profile.user.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", seller_profile.user.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    /// Required on bid-escrow auctions: the listing escrow receiving the winning bid
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Option<Account<'info, Escrow>>,
+
+    /// Required on bid-escrow auctions: the winner's BidEscrow (validated in the handler)
+    #[account(mut)]
+    pub bid_escrow: Option<Account<'info, BidEscrow>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Shared accounts for keeper crank batches (items are passed as remaining_accounts)
+#[derive(Accounts)]
+pub struct CrankBatch<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    /// CHECK: Treasury for released platform fees - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Insurance fund vault (required while config.insurance_fee_bps > 0)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    /// Sales aggregate updated by release_due_transactions (one currency per crank)
+    #[account(
+        mut,
+        seeds = [b"volume_stats", volume_stats.mint.as_ref()],
+        bump = volume_stats.bump
+    )]
+    pub volume_stats: Option<Account<'info, VolumeStats>>,
+
+    /// Anyone can crank (pays for any accounts created, receives settlement tips)
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CompletePurchase<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitDeposit<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Seller receives the forfeited deposit (validated via transaction.seller)
+    #[account(
+        mut,
+        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
+    )]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    /// CHECK: Treasury (or fee-split vault) - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Insurance fund vault (required while config.insurance_fee_bps > 0)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    /// Anyone can call this after the payment window (permissionless cleanup)
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Close escrow and refund rent to seller when auction cancelled (no bids)
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Relist<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Fresh escrow (the previous one must have been closed)
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReopenListing<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // Refunded sale is closed so the next sale can create the transaction PDA again
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Dispute PDA for the refunded transaction; must already be closed
+    #[account(seeds = [b"dispute", transaction.key().as_ref()], bump)]
+    pub dispute: UncheckedAccount<'info>,
+
+    /// CHECK: Listing escrow PDA, recreated in the handler if it was closed
+    #[account(mut, seeds = [b"escrow", listing.key().as_ref()], bump)]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet that funded the refunded purchase receives the transaction rent
+    #[account(
+        mut,
+        constraint = rent_recipient.key() == transaction.payer @ AppMarketError::InvalidBuyer
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    /// Seller or listing operator (pays rent if the escrow is recreated)
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitBidBook<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<BidBook>(),
+        seeds = [b"bid_book", listing.key().as_ref()],
+        bump
+    )]
+    pub bid_book: AccountLoader<'info, BidBook>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitBidHistory<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"bid_book", listing.key().as_ref()],
+        bump
+    )]
+    pub bid_book: AccountLoader<'info, BidBook>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<BidHistory>(),
+        seeds = [b"bid_history", listing.key().as_ref()],
+        bump
+    )]
+    pub bid_history: AccountLoader<'info, BidHistory>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EnableBidEscrows<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitWinningBid<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"bid_escrow", listing.key().as_ref(), bid_escrow.bidder.as_ref()],
+        bump = bid_escrow.bump
+    )]
+    pub bid_escrow: Account<'info, BidEscrow>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimBidEscrow<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [b"bid_escrow", listing.key().as_ref(), bidder.key().as_ref()],
+        bump = bid_escrow.bump
+    )]
+    pub bid_escrow: Account<'info, BidEscrow>,
+
+    /// Optional: bidder profile whose locked exposure the reclaim releases
+    #[account(
+        mut,
+        seeds = [b"user_profile", bidder.key().as_ref()],
+        bump = bidder_profile.bump
+    )]
+    pub bidder_profile: Option<Account<'info, UserProfile>>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LowerReserve<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBundleAssets<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ActivateListing<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetListingPaused<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetListingOperator<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequiresVerification<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetListingTags<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPriceDropSchedule<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PromoteListing<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    /// CHECK: Treasury (or fee-split vault) - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmBundleAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireListing<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Close escrow when listing expires without bids
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump,
+        constraint = listing.seller == seller.key() @ AppMarketError::NotSeller
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Seller receives rent
+    #[account(mut)]
+    pub seller: AccountInfo<'info>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordDeliverable<'info> {
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub listing: Account<'info, Listing>,
+
+    /// Seller or listing operator
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SellerConfirmTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(buyer: Pubkey)]
+pub struct AttestBuyer<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = backend_authority,
+        space = 8 + BuyerAttestation::INIT_SPACE,
+        seeds = [b"buyer_attestation", buyer.as_ref()],
+        bump
+    )]
+    pub buyer_attestation: Account<'info, BuyerAttestation>,
+
+    #[account(mut)]
+    pub backend_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeBuyerAttestation<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        close = backend_authority,
+        seeds = [b"buyer_attestation", buyer_attestation.buyer.as_ref()],
+        bump = buyer_attestation.bump
+    )]
+    pub buyer_attestation: Account<'info, BuyerAttestation>,
+
+    #[account(mut)]
+    pub backend_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyUploads<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Backend authority that verifies uploads
+    pub backend_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyUploadsV2<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        init,
+        payer = backend_authority,
+        space = 8 + VerificationRound::INIT_SPACE,
+        seeds = [
+            b"verification_round",
+            transaction.key().as_ref(),
+            &transaction.created_at.to_le_bytes(),
+            &transaction.verification_rounds.to_le_bytes()
+        ],
+        bump
+    )]
+    pub verification_round: Account<'info, VerificationRound>,
+
+    /// Backend authority that verifies uploads (pays rent for the round record)
+    #[account(mut)]
+    pub backend_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectUploads<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        init,
+        payer = backend_authority,
+        space = 8 + VerificationRound::INIT_SPACE,
+        seeds = [
+            b"verification_round",
+            transaction.key().as_ref(),
+            &transaction.created_at.to_le_bytes(),
+            &transaction.verification_rounds.to_le_bytes()
+        ],
+        bump
+    )]
+    pub verification_round: Account<'info, VerificationRound>,
+
+    /// Backend authority that rejects uploads (pays rent for the round record)
+    #[account(mut)]
+    pub backend_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyAutoVerify<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Buyer who triggers emergency verification
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminEmergencyVerify<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Admin who triggers emergency verification
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeTransaction<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Seller to receive funds and escrow rent (validated via transaction.seller)
+    #[account(
+        mut,
+        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
+    )]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Treasury (or fee-split vault) to receive fees - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Insurance fund vault (required while config.insurance_fee_bps > 0)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    /// Optional: buyer profile to credit loyalty points
+    #[account(
+        mut,
+        seeds = [b"user_profile", transaction.buyer.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller profile to credit loyalty points
+    #[account(
+        mut,
+        seeds = [b"user_profile", transaction.seller.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    /// Sales aggregate for the listing's payment currency
+    #[account(
+        mut,
+        seeds = [b"volume_stats", volume_stats.mint.as_ref()],
+        bump = volume_stats.bump,
+        constraint = volume_stats.mint == listing.payment_currency() @ AppMarketError::InvalidVolumeStats
+    )]
+    pub volume_stats: Account<'info, VolumeStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmReceipt<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: Seller to receive funds and escrow rent (validated via transaction.seller)
+    #[account(
+        mut,
+        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
+    )]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Treasury (or fee-split vault) to receive fees - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Insurance fund vault (required while config.insurance_fee_bps > 0)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    /// Optional: buyer profile to credit loyalty points
+    #[account(
+        mut,
+        seeds = [b"user_profile", transaction.buyer.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller profile to credit loyalty points
+    #[account(
+        mut,
+        seeds = [b"user_profile", transaction.seller.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    /// Sales aggregate for the listing's payment currency
+    #[account(
+        mut,
+        seeds = [b"volume_stats", volume_stats.mint.as_ref()],
+        bump = volume_stats.bump,
+        constraint = volume_stats.mint == listing.payment_currency() @ AppMarketError::InvalidVolumeStats
+    )]
+    pub volume_stats: Account<'info, VolumeStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVestedProceeds<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChangePayoutWallet<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePayoutSplit<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + PayoutSplit::INIT_SPACE,
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump
+    )]
+    pub payout_split: Account<'info, PayoutSplit>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimHoldback<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenWarrantyDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveWarrantyDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Buyer (validated via transaction.buyer)
+    #[account(
+        mut,
+        constraint = buyer.key() == transaction.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Seller (validated via transaction.seller)
+    #[account(
+        mut,
+        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
+    )]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, deadline: i64, offer_seed: u64)]
+pub struct MakeOffer<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    // Mutable: offer counters (and the status, when the offer is auto-accepted)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Use deterministic offer_seed instead of Clock::get() to prevent consensus issues
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + Offer::INIT_SPACE,
+        seeds = [
+            b"offer",
+            listing.key().as_ref(),
+            buyer.key().as_ref(),
+            &offer_seed.to_le_bytes()
+        ],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + OfferEscrow::INIT_SPACE,
+        seeds = [b"offer_escrow", offer.key().as_ref()],
+        bump
+    )]
+    pub offer_escrow: Account<'info, OfferEscrow>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Required when the offer meets listing.auto_accept_price: receives the funds
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = listing_escrow.bump
+    )]
+    pub listing_escrow: Option<Account<'info, Escrow>>,
+
+    /// CHECK: Required with listing_escrow: the sale's Transaction PDA (created in the handler)
+    #[account(mut)]
+    pub transaction: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SetOfferThresholds<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut)]
+    pub offer: Account<'info, Offer>,
+
+    // SECURITY: Close escrow and return rent to buyer
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"offer_escrow", offer.key().as_ref()],
+        bump = offer_escrow.bump
+    )]
+    pub offer_escrow: Account<'info, OfferEscrow>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireOffer<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(mut)]
+    pub offer: Account<'info, Offer>,
+
+    // SECURITY: Close escrow and return rent to buyer
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"offer_escrow", offer.key().as_ref()],
+        bump = offer_escrow.bump
+    )]
+    pub offer_escrow: Account<'info, OfferEscrow>,
+
+    /// Buyer receives refund (from offer.buyer, not caller)
+    #[account(
+        mut,
+        constraint = buyer.key() == offer.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: SystemAccount<'info>,
+
+    /// Caller pays gas (can be anyone)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOffer<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        constraint = offer.listing == listing.key() @ AppMarketError::InvalidOffer
+    )]
+    pub offer: Account<'info, Offer>,
+
+    // Transfer funds from offer escrow to listing escrow
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"offer_escrow", offer.key().as_ref()],
+        bump = offer_escrow.bump,
+        constraint = offer.buyer == buyer.key() @ AppMarketError::InvalidBuyer
+    )]
+    pub offer_escrow: Account<'info, OfferEscrow>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = listing_escrow.bump
+    )]
+    pub listing_escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Optional: buyer's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", buyer_profile.user.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", seller_profile.user.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    // SECURITY FIX M-3: Pending withdrawal only created when needed (previous bidder exists)
+    /// CHECK: Only created if listing.current_bidder exists and has a non-zero bid
+    #[account(mut)]
+    pub pending_withdrawal: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Buyer - rent recipient for offer escrow
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptExpiredOffer<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        constraint = offer.listing == listing.key() @ AppMarketError::InvalidOffer
+    )]
+    pub offer: Account<'info, Offer>,
+
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"offer_escrow", offer.key().as_ref()],
+        bump = offer_escrow.bump,
+        constraint = offer.buyer == buyer.key() @ AppMarketError::InvalidBuyer
+    )]
+    pub offer_escrow: Account<'info, OfferEscrow>,
+
+    // SECURITY: Fresh escrow (expire_listing closed the previous one)
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump
+    )]
+    pub listing_escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Transaction::INIT_SPACE,
+        seeds = [b"transaction", listing.key().as_ref()],
+        bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Optional: buyer's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", buyer_profile.user.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller's profile, copies their contact key onto the transaction
+    #[account(
+        seeds = [b"user_profile", seller_profile.user.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    pub seller: Signer<'info>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Buyer - rent recipient for offer escrow
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = initiator,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub initiator: Signer<'info>,
+
+    /// CHECK: Treasury to receive dispute fees - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + Evidence::INIT_SPACE,
+        seeds = [
+            b"evidence",
+            dispute.key().as_ref(),
+            submitter.key().as_ref(),
+            &[if submitter.key() == transaction.buyer {
+                dispute.buyer_evidence_count
+            } else {
+                dispute.seller_evidence_count
+            }],
+        ],
+        bump
+    )]
+    pub evidence: Account<'info, Evidence>,
+
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeDisputeResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Platform admin, or the transaction's designated arbitrator
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ContestDisputeResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Buyer or seller contesting the resolution
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDisputeResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Buyer (validated via transaction.buyer)
+    #[account(
+        mut,
+        constraint = buyer.key() == transaction.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Seller to receive escrow rent (validated via transaction.seller)
+    #[account(
+        mut,
+        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
+    )]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // Closed here unless an appeal window is configured (then close_dispute handles cleanup)
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: Treasury (or fee-split vault) - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Insurance fund vault (required while config.insurance_fee_bps > 0)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    /// Anyone can execute after timelock (typically admin, arbitrator or party)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// Optional: buyer profile to credit loyalty points
+    #[account(
+        mut,
+        seeds = [b"user_profile", transaction.buyer.as_ref()],
+        bump = buyer_profile.bump
+    )]
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+
+    /// Optional: seller profile to credit loyalty points
+    #[account(
+        mut,
+        seeds = [b"user_profile", transaction.seller.as_ref()],
+        bump = seller_profile.bump
+    )]
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AppealDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Buyer or seller appealing the ruling (pays the appeal fee)
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAppealResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: Appellant receives a refunded appeal fee (validated via dispute.appealed_by)
+    #[account(mut)]
+    pub appellant: AccountInfo<'info>,
+
+    /// CHECK: Treasury (or fee-split vault) - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDispute<'info> {
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [b"dispute", dispute.transaction.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: Dispute initiator receives the account rent (validated via dispute.initiator)
+    #[account(
+        mut,
+        constraint = initiator.key() == dispute.initiator @ AppMarketError::NotPartyToTransaction
+    )]
+    pub initiator: AccountInfo<'info>,
+
+    /// Anyone can call this (permissionless cleanup)
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawDisputeResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// Platform admin, or the transaction's designated arbitrator
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDisputeDefault<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub initiator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDisputeFeePolicy<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDisputeSla<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxBidderExposure<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxAntiSnipeExtensions<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPromotionFee<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxSalePrice<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(seller: Pubkey)]
+pub struct ApprovePriceCapOverride<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PriceCapOverride::INIT_SPACE,
+        seeds = [b"price_cap_override", seller.as_ref()],
+        bump
+    )]
+    pub price_cap_override: Account<'info, PriceCapOverride>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitFeeSchedule<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeSchedule::INIT_SPACE,
+        seeds = [b"fee_schedule"],
+        bump
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSchedule<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut, seeds = [b"fee_schedule"], bump = fee_schedule.bump)]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokePriceCapOverride<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"price_cap_override", price_cap_override.seller.as_ref()],
+        bump = price_cap_override.bump
+    )]
+    pub price_cap_override: Account<'info, PriceCapOverride>,
+
+    /// CHECK: Receives the override rent back (validated against price_cap_override.rent_payer)
+    #[account(
+        mut,
+        address = price_cap_override.rent_payer @ AppMarketError::InvalidRentPayer
+    )]
+    pub rent_payer: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEscheatPeriod<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + InsuranceFund::INIT_SPACE,
+        seeds = [b"insurance_fund"],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    /// SOL vault (system-owned PDA funded by platform fee slices)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceFeeBps<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    // Fee slices need somewhere to go: the fund must exist first
+    #[account(seeds = [b"insurance_fund"], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FileClaim<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + InsuranceClaim::INIT_SPACE,
+        seeds = [b"insurance_claim", transaction.key().as_ref()],
+        bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveClaim<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_fund"],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump = insurance_fund.vault_bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_claim", insurance_claim.transaction.as_ref()],
+        bump = insurance_claim.bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+
+    /// CHECK: Claimant receiving the payout - SECURITY: validated against the claim
+    #[account(
+        mut,
+        constraint = claimant.key() == insurance_claim.claimant @ AppMarketError::InvalidClaimant
+    )]
+    pub claimant: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeSplit<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeSplit::INIT_SPACE,
+        seeds = [b"fee_split"],
+        bump
+    )]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    /// SOL vault (system-owned PDA collecting fees while the split is active)
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeFeeSplitChange<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut, seeds = [b"fee_split"], bump = fee_split.bump)]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteFeeSplitChange<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut, seeds = [b"fee_split"], bump = fee_split.bump)]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    #[account(seeds = [b"fee_vault"], bump = fee_split.vault_bump)]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    #[account(seeds = [b"fee_split"], bump = fee_split.bump)]
+    pub fee_split: Account<'info, FeeSplit>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = fee_split.vault_bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// Anyone can call this (permissionless distribution)
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewardsPool<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RewardsPool::INIT_SPACE,
+        seeds = [b"rewards_pool"],
+        bump
+    )]
+    pub rewards_pool: Account<'info, RewardsPool>,
+
+    /// SOL vault (system-owned PDA; add it to the fee split to fund staker rewards)
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: SystemAccount<'info>,
+
+    /// APP token vault holding staked tokens, owned by the pool PDA
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"rewards_stake_vault"],
+        bump,
+        token::mint = app_mint,
+        token::authority = rewards_pool
+    )]
+    pub rewards_stake_vault: Account<'info, TokenAccount>,
+
+    #[account(address = APP_TOKEN_MINT @ AppMarketError::InvalidPaymentMint)]
+    pub app_mint: Account<'info, Mint>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitRewardStake<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RewardStake::INIT_SPACE,
+        seeds = [b"reward_stake", owner.key().as_ref()],
+        bump
+    )]
+    pub reward_stake: Account<'info, RewardStake>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeForRewards<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut, seeds = [b"rewards_pool"], bump = rewards_pool.bump)]
+    pub rewards_pool: Account<'info, RewardsPool>,
+
+    #[account(seeds = [b"rewards_vault"], bump = rewards_pool.vault_bump)]
+    pub rewards_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rewards_stake_vault"],
+        bump = rewards_pool.stake_vault_bump
+    )]
+    pub rewards_stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_stake", owner.key().as_ref()],
+        bump = reward_stake.bump
+    )]
+    pub reward_stake: Account<'info, RewardStake>,
+
+    #[account(
+        mut,
+        token::mint = APP_TOKEN_MINT,
+        token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeFromRewards<'info> {
+    #[account(mut, seeds = [b"rewards_pool"], bump = rewards_pool.bump)]
+    pub rewards_pool: Account<'info, RewardsPool>,
+
+    #[account(seeds = [b"rewards_vault"], bump = rewards_pool.vault_bump)]
+    pub rewards_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rewards_stake_vault"],
+        bump = rewards_pool.stake_vault_bump
+    )]
+    pub rewards_stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_stake", owner.key().as_ref()],
+        bump = reward_stake.bump
+    )]
+    pub reward_stake: Account<'info, RewardStake>,
+
+    #[account(
+        mut,
+        token::mint = APP_TOKEN_MINT,
+        token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut, seeds = [b"rewards_pool"], bump = rewards_pool.bump)]
+    pub rewards_pool: Account<'info, RewardsPool>,
+
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump = rewards_pool.vault_bump
+    )]
+    pub rewards_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_stake", owner.key().as_ref()],
+        bump = reward_stake.bump
+    )]
+    pub reward_stake: Account<'info, RewardStake>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetArbitratorStaking<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitArbitratorStake<'info> {
+    #[account(
+        init,
+        payer = arbitrator,
+        space = 8 + ArbitratorStake::INIT_SPACE,
+        seeds = [b"arbitrator_stake", arbitrator.key().as_ref()],
+        bump
+    )]
+    pub arbitrator_stake: Account<'info, ArbitratorStake>,
+
+    /// APP token vault owned by the stake PDA
+    #[account(
+        init,
+        payer = arbitrator,
+        seeds = [b"stake_vault", arbitrator.key().as_ref()],
+        bump,
+        token::mint = app_mint,
+        token::authority = arbitrator_stake
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(address = APP_TOKEN_MINT @ AppMarketError::InvalidPaymentMint)]
+    pub app_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub arbitrator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeArbitrator<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"arbitrator_stake", arbitrator.key().as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Account<'info, ArbitratorStake>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_vault", arbitrator.key().as_ref()],
+        bump = arbitrator_stake.vault_bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = APP_TOKEN_MINT,
+        token::authority = arbitrator
+    )]
+    pub arbitrator_token_account: Account<'info, TokenAccount>,
+
+    pub arbitrator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"arbitrator_stake", arbitrator.key().as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Account<'info, ArbitratorStake>,
+
+    pub arbitrator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeArbitrator<'info> {
+    #[account(
+        mut,
+        seeds = [b"arbitrator_stake", arbitrator.key().as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Account<'info, ArbitratorStake>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_vault", arbitrator.key().as_ref()],
+        bump = arbitrator_stake.vault_bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = APP_TOKEN_MINT,
+        token::authority = arbitrator
+    )]
+    pub arbitrator_token_account: Account<'info, TokenAccount>,
+
+    pub arbitrator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SlashArbitrator<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", dispute.transaction.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(
+        mut,
+        seeds = [b"arbitrator_stake", arbitrator_stake.arbitrator.as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Account<'info, ArbitratorStake>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_vault", arbitrator_stake.arbitrator.as_ref()],
+        bump = arbitrator_stake.vault_bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    /// SECURITY: Slashed stake can only go to the appellant
+    #[account(
+        mut,
+        token::mint = APP_TOKEN_MINT,
+        constraint = Some(appellant_token_account.owner) == dispute.appealed_by @ AppMarketError::InvalidAppellant
+    )]
+    pub appellant_token_account: Account<'info, TokenAccount>,
+
+    /// Anyone can call this (permissionless)
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeArbitratorSet<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ArbitratorSet::INIT_SPACE,
+        seeds = [b"arbitrator_set"],
+        bump
+    )]
+    pub arbitrator_set: Account<'info, ArbitratorSet>,
+
+    pub admin: Signer<'info>,
+
+    /// Pays rent (lets a governance PDA admin use its native treasury)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateArbitratorSet<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"arbitrator_set"],
+        bump = arbitrator_set.bump
+    )]
+    pub arbitrator_set: Account<'info, ArbitratorSet>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VoteResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        seeds = [b"arbitrator_set"],
+        bump = arbitrator_set.bump
+    )]
+    pub arbitrator_set: Account<'info, ArbitratorSet>,
+
+    /// Voter's APP stake (required when config.min_arbitrator_stake > 0)
+    #[account(
+        seeds = [b"arbitrator_stake", arbitrator.key().as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Option<Account<'info, ArbitratorStake>>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub arbitrator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAppealConfig<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyRefund<'info> {
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // Transaction stays open so close_escrow can verify terminal state later
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundOnFailedVerification<'info> {
+    // Mutable: the listing status follows the sale (ListingStatusChanged)
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Buyer receives the refund (validated against transaction.buyer)
+    #[account(
+        mut,
+        constraint = buyer.key() == transaction.buyer @ AppMarketError::NotBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// Anyone may trigger the refund once the cure period has passed
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AssignBuyer<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Current buyer assigning their rights
+    pub buyer: Signer<'info>,
+
+    /// Wallet receiving the buyer rights (must co-sign)
+    pub new_buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeMutualCancel<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Buyer or seller proposing the cancellation
+    pub proposer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptMutualCancel<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// CHECK: Buyer receives the refund and transaction rent (validated via transaction.buyer)
+    #[account(
+        mut,
+        constraint = buyer.key() == transaction.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// Counterparty to the proposal (buyer or seller)
+    pub acceptor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // SECURITY: Close escrow when cancelling (rent returns to seller)
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GuardianPause<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPlatformFees<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetEscrowYield<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakeEscrow<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + EscrowStake::INIT_SPACE,
+        seeds = [b"escrow_stake", escrow.key().as_ref()],
+        bump
+    )]
+    pub escrow_stake: Account<'info, EscrowStake>,
+
+    /// Deposits into the pool from here (system-owned PDA, empty between stake and unstake)
+    #[account(
+        mut,
+        seeds = [b"stake_vault", escrow_stake.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    /// Pool tokens for the staked principal, owned by the escrow_stake PDA
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [b"escrow_stake_tokens", escrow_stake.key().as_ref()],
+        bump,
+        token::mint = pool_mint,
+        token::authority = escrow_stake
+    )]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Must be config.escrow_stake_pool (checked in handler)
+    #[account(mut)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    pub withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: SPL stake pool program
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// Co-signs: a shortfall at release can come off the seller's proceeds
+    #[account(constraint = seller.key() == transaction.seller @ AppMarketError::NotSeller)]
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeEscrow<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump,
+        constraint = escrow_stake.transaction == transaction.key() @ AppMarketError::InvalidStakePool
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    // Closed to rent_recipient by unwind_escrow_stake
+    #[account(
+        mut,
+        seeds = [b"escrow_stake", escrow.key().as_ref()],
+        bump = escrow_stake.bump
+    )]
+    pub escrow_stake: Account<'info, EscrowStake>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_vault", escrow_stake.key().as_ref()],
+        bump = escrow_stake.vault_bump
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow_stake_tokens", escrow_stake.key().as_ref()],
+        bump
+    )]
+    pub pool_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: Must be the pool recorded on escrow_stake
+    #[account(
+        mut,
+        address = escrow_stake.stake_pool @ AppMarketError::InvalidStakePool
+    )]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    pub withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = pool_tokens.mint @ AppMarketError::InvalidStakePool
+    )]
+    pub pool_mint: Account<'info, Mint>,
+
+    /// CHECK: Receives the buyer's share of the yield
+    #[account(
+        mut,
+        address = transaction.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Receives the protocol's share of the yield
+    #[account(
+        mut,
+        address = config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Receives the escrow_stake and token account rent
+    #[account(
+        mut,
+        address = escrow_stake.rent_payer @ AppMarketError::InvalidRentPayer
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    /// CHECK: Clock sysvar
+    #[account(address = solana_sdk_ids::sysvar::clock::ID)]
+    pub clock: UncheckedAccount<'info>,
+
+    /// CHECK: Stake history sysvar
+    #[account(address = solana_sdk_ids::sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native stake program
+    #[account(address = solana_sdk_ids::stake::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL stake pool program
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    /// Anyone can call (releases and refunds also unwind the stake themselves)
+    pub caller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRecurringEscrow<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RecurringEscrow::INIT_SPACE,
+        seeds = [b"recurring_escrow", transaction.key().as_ref()],
+        bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    // Both parties sign the schedule
+    pub buyer: Signer<'info>,
+    pub seller: Signer<'info>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundRecurringPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"recurring_escrow", recurring_escrow.transaction.as_ref()],
+        bump = recurring_escrow.bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    #[account(
+        mut,
+        address = recurring_escrow.buyer @ AppMarketError::NotBuyer
+    )]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseRecurringPeriod<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"recurring_escrow", recurring_escrow.transaction.as_ref()],
+        bump = recurring_escrow.bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    /// CHECK: Receives the period payment
+    #[account(
+        mut,
+        address = recurring_escrow.seller @ AppMarketError::NotSeller
+    )]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Receives the platform fee
+    #[account(
+        mut,
+        address = config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SkipRecurringPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"recurring_escrow", recurring_escrow.transaction.as_ref()],
+        bump = recurring_escrow.bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    #[account(address = recurring_escrow.seller @ AppMarketError::NotSeller)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Refunded if the skipped period was funded
+    #[account(
+        mut,
+        address = recurring_escrow.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeRecurringPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"recurring_escrow", recurring_escrow.transaction.as_ref()],
+        bump = recurring_escrow.bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    /// Buyer or seller
+    pub party: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveRecurringDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"recurring_escrow", recurring_escrow.transaction.as_ref()],
+        bump = recurring_escrow.bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    /// CHECK: Receives the period payment if released
+    #[account(
+        mut,
+        address = recurring_escrow.seller @ AppMarketError::NotSeller
+    )]
+    pub seller: AccountInfo<'info>,
+
+    /// CHECK: Receives the refund otherwise
+    #[account(
+        mut,
+        address = recurring_escrow.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Receives the platform fee on a release
+    #[account(
+        mut,
+        address = config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Platform admin, or the transaction's designated arbitrator
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TerminateRecurringEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"recurring_escrow", recurring_escrow.transaction.as_ref()],
+        bump = recurring_escrow.bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    /// CHECK: Refunded for funded periods not yet due
+    #[account(
+        mut,
+        address = recurring_escrow.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// Buyer or seller
+    pub party: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRecurringEscrow<'info> {
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"recurring_escrow", recurring_escrow.transaction.as_ref()],
+        bump = recurring_escrow.bump
+    )]
+    pub recurring_escrow: Account<'info, RecurringEscrow>,
+
+    /// CHECK: Receives the rent
+    #[account(
+        mut,
+        address = recurring_escrow.rent_payer @ AppMarketError::InvalidRentPayer
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OfferRentToOwn<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RentToOwn::INIT_SPACE,
+        seeds = [b"rent_to_own", listing.key().as_ref()],
+        bump
+    )]
+    pub rent_to_own: Account<'info, RentToOwn>,
+
+    #[account(
+        seeds = [b"app_deed", listing.key().as_ref()],
+        bump = app_deed.bump
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    pub seller: Signer<'info>,
+
+    /// Pays rent for accounts created here (may differ from the actor: relayers, sponsors)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StartRentToOwn<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_to_own", listing.key().as_ref()],
+        bump = rent_to_own.bump
+    )]
+    pub rent_to_own: Account<'info, RentToOwn>,
+
+    #[account(
+        mut,
+        address = rent_to_own.deed @ AppMarketError::NotDeedHolder
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PayRentToOwnInstallment<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_to_own", listing.key().as_ref()],
+        bump = rent_to_own.bump
+    )]
+    pub rent_to_own: Account<'info, RentToOwn>,
+
+    #[account(
+        mut,
+        address = rent_to_own.deed @ AppMarketError::NotDeedHolder
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    /// CHECK: Paid out when the final installment lands
+    #[account(
+        mut,
+        address = listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// CHECK: Receives the platform fee when the final installment lands
+    #[account(
+        mut,
+        address = config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DefaultRentToOwn<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_to_own", listing.key().as_ref()],
+        bump = rent_to_own.bump
+    )]
+    pub rent_to_own: Account<'info, RentToOwn>,
+
+    #[account(
+        mut,
+        address = rent_to_own.deed @ AppMarketError::NotDeedHolder
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    /// CHECK: Receives the forfeited share
+    #[account(
+        mut,
+        address = listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// CHECK: Receives the platform fee on the forfeited share
+    #[account(
+        mut,
+        address = config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// CHECK: Refunded the unforfeited share (checked against rent_to_own.buyer)
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+
+    /// Anyone once an installment is overdue; the buyer at any time
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRentToOwn<'info> {
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"rent_to_own", rent_to_own.listing.as_ref()],
+        bump = rent_to_own.bump
+    )]
+    pub rent_to_own: Account<'info, RentToOwn>,
+
+    /// CHECK: Receives the rent
+    #[account(
+        mut,
+        address = rent_to_own.rent_payer @ AppMarketError::InvalidRentPayer
+    )]
+    pub rent_recipient: AccountInfo<'info>,
+
+    /// Anyone once settled; the seller while the terms are still only offered
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseOpenListing<'info> {
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    // Rent returns to the seller; offers stay live for accept_offer_on_expired
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAcceptedCurrencies<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    /// Optional: the seller's admin approval to sell above config.max_sale_price
+    #[account(
+        seeds = [b"price_cap_override", listing.seller.as_ref()],
+        bump = price_cap_override.bump
+    )]
+    pub price_cap_override: Option<Account<'info, PriceCapOverride>>,
+
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOfferDurationBounds<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleFailedAuction<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    /// High bidder's refund (required unless the bid sits in a BidEscrow)
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + PendingWithdrawal::INIT_SPACE,
+        seeds = [
+            b"withdrawal",
+            listing.key().as_ref(),
+            &(listing.withdrawal_count + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub pending_withdrawal: Option<Account<'info, PendingWithdrawal>>,
+
+    /// Anyone can call once the auction has ended (funds the withdrawal rent)
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFailedAuctionEscrow<'info> {
+    pub listing: Account<'info, Listing>,
+
+    // Rent returns to the seller once every refund has been paid out
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Receives the escrow rent (validated against listing.seller)
+    #[account(mut, address = listing.seller @ AppMarketError::NotSeller)]
+    pub seller: AccountInfo<'info>,
+
+    /// Anyone can call
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PostRespondentBond<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", dispute.transaction.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    #[account(mut)]
+    pub respondent: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RespondToDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"dispute", dispute.transaction.as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub respondent: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSmallClaimsThreshold<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeSmallClaimResolution<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        seeds = [b"arbitrator_set"],
+        bump = arbitrator_set.bump
+    )]
+    pub arbitrator_set: Account<'info, ArbitratorSet>,
+
+    /// Moderator's APP stake (required when config.min_arbitrator_stake > 0)
+    #[account(
+        seeds = [b"arbitrator_stake", arbitrator.key().as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Option<Account<'info, ArbitratorStake>>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", transaction.key().as_ref()],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub arbitrator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBuyerPool<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            b"buyer_pool",
+            buyer_pool.listing.as_ref(),
+            buyer_pool.creator.as_ref(),
+            &buyer_pool.pool_id.to_le_bytes()
+        ],
+        bump = buyer_pool.bump
+    )]
+    pub buyer_pool: Account<'info, BuyerPool>,
+
+    /// CHECK: Pool creator receiving the rent (validated via buyer_pool.creator)
+    #[account(
+        mut,
+        address = buyer_pool.creator @ AppMarketError::Unauthorized
+    )]
+    pub creator: AccountInfo<'info>,
+
+    /// Anyone can close a failed, fully refunded pool
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct IssueAppDeed<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + AppDeed::INIT_SPACE,
+        seeds = [b"app_deed", listing.key().as_ref()],
+        bump
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    #[account(
+        mut,
+        constraint = seller.key() == listing.seller @ AppMarketError::NotSeller
+    )]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAppDeed<'info> {
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"app_deed", listing.key().as_ref()],
+        bump = app_deed.bump
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    #[account(
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    /// Anyone can move the deed to the buyer of a completed sale
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FractionalizeApp<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"app_deed", listing.key().as_ref()],
+        bump = app_deed.bump
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + FractionVault::INIT_SPACE,
+        seeds = [b"fraction_vault", listing.key().as_ref()],
+        bump
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    /// Share token minted to fraction buyers
+    #[account(
+        init,
+        payer = seller,
+        seeds = [b"fraction_shares", fraction_vault.key().as_ref()],
+        bump,
+        mint::decimals = FRACTION_SHARE_DECIMALS,
+        mint::authority = fraction_vault
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = seller.key() == listing.seller @ AppMarketError::NotSeller
+    )]
+    pub seller: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyFractions<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        mut,
+        seeds = [b"fraction_vault", listing.key().as_ref()],
+        bump = fraction_vault.bump
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(
+        mut,
+        seeds = [b"fraction_shares", fraction_vault.key().as_ref()],
+        bump = fraction_vault.mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = buyer_share_account.mint == share_mint.key()
+            && buyer_share_account.owner == buyer.key() @ AppMarketError::InvalidShareAccount
+    )]
+    pub buyer_share_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFractionProceeds<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        seeds = [b"fraction_vault", listing.key().as_ref()],
+        bump = fraction_vault.bump
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    /// CHECK: Treasury (or fee-split vault) - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Insurance fund vault (required while config.insurance_fee_bps > 0)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    #[account(
+        constraint = seller.key() == listing.seller @ AppMarketError::NotSeller
+    )]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyoutFractions<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(mut)]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"app_deed", listing.key().as_ref()],
+        bump = app_deed.bump
+    )]
+    pub app_deed: Account<'info, AppDeed>,
+
+    #[account(
+        mut,
+        seeds = [b"fraction_vault", listing.key().as_ref()],
+        bump = fraction_vault.bump
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    /// CHECK: Seller's payout wallet receiving the unsold shares' cut (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    /// CHECK: Treasury (or fee-split vault) - SECURITY: validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.fee_destination() @ AppMarketError::InvalidTreasury
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    /// Insurance fund vault (required while config.insurance_fee_bps > 0)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemFractionShares<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"fraction_vault", fraction_vault.listing.as_ref()],
+        bump = fraction_vault.bump
+    )]
+    pub fraction_vault: Account<'info, FractionVault>,
+
+    #[account(
+        mut,
+        seeds = [b"fraction_shares", fraction_vault.key().as_ref()],
+        bump = fraction_vault.mint_bump
+    )]
+    pub share_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = holder_share_account.mint == share_mint.key()
+            && holder_share_account.owner == holder.key() @ AppMarketError::InvalidShareAccount
+    )]
+    pub holder_share_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct OpenVestingDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    pub buyer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveVestingDispute<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        mut,
+        seeds = [b"transaction", listing.key().as_ref(), transaction.unit_seed()],
+        bump = transaction.bump
+    )]
+    pub transaction: Account<'info, Transaction>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", listing.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: Buyer receiving the clawback (validated via transaction.buyer)
+    #[account(
+        mut,
+        constraint = buyer.key() == transaction.buyer @ AppMarketError::InvalidBuyer
+    )]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Seller's payout wallet receiving proceeds (validated via listing.payout_wallet)
+    #[account(
+        mut,
+        constraint = payout_wallet.key() == listing.payout_wallet @ AppMarketError::InvalidPayoutWallet
+    )]
+    pub payout_wallet: AccountInfo<'info>,
+
+    /// Optional: proceeds split among co-founders (required when the listing has one)
+    #[account(
+        seeds = [b"payout_split", listing.key().as_ref()],
+        bump = payout_split.bump
+    )]
+    pub payout_split: Option<Account<'info, PayoutSplit>>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRespondentBonds<'info> {
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, MarketConfig>,
+    pub admin: Signer<'info>,
+}
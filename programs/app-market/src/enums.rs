@@ -0,0 +1,242 @@
+//! Enums stored in accounts and instruction arguments
+
+use crate::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ListingType {
+    Auction,
+    BuyNow,
+    // No expiry and no bidding: offers (and buy_now, if priced) until the seller closes it
+    OpenOffers,
+}
+
+// Mirrors the Category enum in prisma/schema.prisma
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum Category {
+    Saas,
+    MobileApp,
+    WebApp,
+    BrowserExtension,
+    Api,
+    AiMl,
+    Ecommerce,
+    Marketplace,
+    Social,
+    Productivity,
+    DeveloperTools,
+    Fintech,
+    Gaming,
+    CryptoWeb3,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ListingStatus {
+    Active,
+    Ended, // Expired unsold, or a deposit-mode sale fell through (relist)
+    Sold, // Sale agreed, deposit-mode remainder outstanding; or every unit sold
+    Cancelled,
+    InEscrow, // Sale paid, funds held in escrow
+    TransferPending, // Seller confirmed the transfer, awaiting the buyer
+    Disputed,
+    Completed,
+    Refunded,
+    Draft, // Created and funded but not yet live (activate_listing)
+    Paused, // Hidden by the seller (resume_listing), bid-free only
+    ReserveNotMet, // Auction ended below its reserve; bidder refunded (relist)
+}
+
+impl ListingStatus {
+    /// Transitions allowed by set_listing_status. Sold also reaches every post-sale state:
+    /// listings sold before post-sale tracking existed still read Sold.
+    pub fn can_transition_to(&self, next: &ListingStatus) -> bool {
+        use ListingStatus::*;
+        matches!(
+            (self, next),
+            (Draft, Active | Cancelled)
+                | (Active, Paused | Sold | InEscrow | Ended | Cancelled | ReserveNotMet)
+                | (Paused, Active | Cancelled)
+                | (Ended | Cancelled | Refunded | ReserveNotMet, Active)
+                | (
+                    Sold,
+                    InEscrow | TransferPending | Disputed | Completed | Refunded | Ended
+                        | Cancelled | Active
+                )
+                | (InEscrow, TransferPending | Disputed | Completed | Refunded | Cancelled | Active)
+                | (TransferPending, Disputed | Completed | Refunded | Cancelled | Active)
+                | (Disputed, Completed | Refunded)
+        )
+    }
+}
+
+/// Why Escrow.amount changed (EscrowBalanceChanged)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EscrowChangeReason {
+    Bid,
+    Purchase,
+    RemainderPaid,
+    WithdrawalClaimed,
+    WithdrawalExpired,
+    WithdrawalEscheated,
+    DepositForfeited,
+    PlatformFee,
+    SellerPayout,
+    VestingRelease,
+    HoldbackRelease,
+    WarrantyResolution,
+    DisputeRefund,
+    EmergencyRefund,
+    VerificationRefund,
+    MutualCancel,
+    SettlementTip,
+    BidEscrowCommitted,
+    StakeShortfall,
+    VestingDisputeResolution,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum TransactionStatus {
+    Pending, // Deposit-mode winner has not yet paid the remainder
+    Paid,
+    InEscrow,
+    TransferPending,
+    TransferInProgress,
+    AwaitingConfirmation,
+    Disputed,
+    Completed,
+    Refunded,
+    Cancelled,
+}
+
+/// Evidence submitted by a dispute party (content stored off-chain, hash on-chain)
+#[account]
+#[derive(InitSpace)]
+pub struct Evidence {
+    pub version: u8,
+    pub dispute: Pubkey,
+    pub party: Pubkey,
+    pub index: u8,
+    pub content_hash: [u8; 32],
+    #[max_len(200)]
+    pub uri: String,
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum DisputeStatus {
+    Open,
+    UnderReview,
+    Resolved,
+    Appealed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum ClaimStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum DisputeResolution {
+    FullRefund,
+    ReleaseToSeller,
+    // Platform fee waived (the amounts split the whole sale price)
+    PartialRefund { buyer_amount: u64, seller_amount: u64 },
+    // The amounts split the sale price; the platform fee on the seller's share is waived or
+    // taken out of one party's share
+    PartialRefundWithFee { buyer_amount: u64, seller_amount: u64, fee_disposition: FeeDisposition },
+}
+
+/// Who absorbs the platform fee in a partial-refund ruling
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum FeeDisposition {
+    Waived,
+    ChargedToSeller,
+    ChargedToBuyer,
+}
+
+/// Pause flag behind a ContractPaused error (logged as its reason code)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseFlag {
+    Admin = 0,          // MarketConfig.paused (admin or guardian)
+    CircuitBreaker = 1, // MarketConfig.circuit_breaker_tripped (volume spike)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum CrankKind {
+    SettleAuctions,
+    ExpireOffers,
+    ReleaseTransactions,
+    SettleAuctionsBatch,
+}
+
+/// Respondent's recorded position on a dispute (respond_to_dispute)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DisputeResponseStatus {
+    Acknowledged,
+    Contested,
+}
+
+/// Initiator's typed grounds for a dispute (open_dispute), used for committee triage
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DisputeCategory {
+    NotDelivered,
+    Misrepresented,
+    PartialDelivery,
+    PostSaleClawback,
+    Other,
+}
+
+/// Initiator's assessment of how serious the dispute is, lowest first
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, InitSpace)]
+pub enum DisputeSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum DisputeFeePolicy {
+    /// Fee refunded to the buyer on FullRefund, otherwise sent to treasury
+    RefundOnFullRefund,
+    /// Fee refunded to the initiator in proportion to the share of the sale they were awarded
+    RefundPrevailingInitiator,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum VerificationMethod {
+    Backend,       // verify_uploads_v2 report
+    LegacyHash,    // verify_uploads (free-form hash in verification_hash)
+    BuyerTimeout,  // emergency_auto_verify
+    AdminOverride, // admin_emergency_verify
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum OfferStatus {
+    Active,
+    Accepted,
+    Cancelled,
+    Expired,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum RecurringStatus {
+    Active,
+    // Ended early: no further funding; periods already due still settle
+    Terminated,
+    // Every period released, skipped or refunded
+    Completed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub enum RentToOwnStatus {
+    // Seller's terms, open to any buyer
+    Offered,
+    // Buyer operates the app while paying installments
+    Active,
+    Completed,
+    Defaulted,
+}
@@ -0,0 +1,567 @@
+//! Program error codes
+
+use crate::*;
+
+// Codes are part of the client ABI (6000 + discriminant): append new variants at the end
+// with the next number and never renumber or remove one (retire it in place instead).
+// error_codes_are_append_only in the lib.rs tests enforces this.
+#[error_code]
+pub enum AppMarketError {
+    #[msg("Invalid price: must be greater than 0")]
+    InvalidPrice = 0,
+    #[msg("Invalid duration: must be between 1 second and 30 days")]
+    InvalidDuration = 1,
+    #[msg("Listing is not active")]
+    ListingNotActive = 2,
+    #[msg("Auction has ended")]
+    AuctionEnded = 3,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded = 4,
+    #[msg("Listing has expired")]
+    ListingExpired = 5,
+    #[msg("Listing has not expired yet")]
+    ListingNotExpired = 6,
+    #[msg("Bid is too low")]
+    BidTooLow = 7,
+    #[msg("Seller cannot bid on their own listing")]
+    SellerCannotBid = 8,
+    #[msg("Seller cannot buy their own listing")]
+    SellerCannotBuy = 9,
+    #[msg("Seller cannot make offers on their own listing")]
+    SellerCannotOffer = 10,
+    #[msg("Buy now is not enabled for this listing")]
+    BuyNowNotEnabled = 11,
+    #[msg("Invalid transaction status")]
+    InvalidTransactionStatus = 12,
+    #[msg("Only the buyer can confirm receipt")]
+    NotBuyer = 13,
+    #[msg("Only the seller can perform this action")]
+    NotSeller = 14,
+    #[msg("Only admin can perform this action")]
+    NotAdmin = 15,
+    #[msg("Not a party to this transaction")]
+    NotPartyToTransaction = 16,
+    #[msg("Dispute is not open")]
+    DisputeNotOpen = 17,
+    #[msg("Listing already has bids")]
+    HasBids = 18,
+    #[msg("Math overflow occurred")]
+    MathOverflow = 19,
+    #[msg("Invalid previous bidder address")]
+    InvalidPreviousBidder = 20,
+    #[msg("Invalid treasury address")]
+    InvalidTreasury = 21,
+    #[msg("Invalid seller address")]
+    InvalidSeller = 22,
+    #[msg("Invalid buyer address")]
+    InvalidBuyer = 23,
+    #[msg("Invalid bidder address")]
+    InvalidBidder = 24,
+    #[msg("Insufficient escrow balance")]
+    InsufficientEscrowBalance = 25,
+    #[msg("Escrow tracked amount doesn't match actual balance")]
+    EscrowBalanceMismatch = 26,
+    #[msg("Insufficient balance")]
+    InsufficientBalance = 27,
+    #[msg("Deadline has not passed yet")]
+    DeadlineNotPassed = 28,
+    #[msg("Invalid refund amounts: total exceeds sale price or both amounts are zero")]
+    InvalidRefundAmounts = 29,
+    #[msg("Unauthorized settlement: only seller, winner, or admin can settle")]
+    UnauthorizedSettlement = 30,
+    #[msg("Bid increment too small: must be at least 5% or 0.1 SOL")]
+    BidIncrementTooSmall = 31,
+    #[msg("Marketplace is paused (the program log names the pause flag)")]
+    ContractPaused = 32,
+    #[msg("Fee too high: platform fee capped at 10%, dispute fee at 5%")]
+    FeeTooHigh = 33,
+    #[msg("No pending change to execute")]
+    NoPendingChange = 34,
+    #[msg("Timelock has not expired: must wait 48 hours")]
+    TimelockNotExpired = 35,
+    #[msg("Seller has confirmed transfer: buyer must open dispute if there's an issue")]
+    MustOpenDispute = 36,
+    #[msg("Transfer already confirmed by seller")]
+    AlreadyConfirmed = 37,
+    #[msg("Not the owner of this withdrawal")]
+    NotWithdrawalOwner = 38,
+    #[msg("Not the owner of this offer")]
+    NotOfferOwner = 39,
+    #[msg("Offer is not active")]
+    OfferNotActive = 40,
+    #[msg("Offer has expired")]
+    OfferExpired = 41,
+    #[msg("Offer has not expired yet")]
+    OfferNotExpired = 42,
+    #[msg("Invalid deadline: must be in the future")]
+    InvalidDeadline = 43,
+    #[msg("This is not an auction listing")]
+    NotAnAuction = 44,
+    #[msg("Seller has not confirmed transfer yet")]
+    SellerNotConfirmed = 45,
+    #[msg("Grace period has not expired: must wait 7 days after seller confirmation")]
+    GracePeriodNotExpired = 46,
+    #[msg("Starting price must equal reserve price for reserve auctions")]
+    StartingPriceMustEqualReserve = 47,
+    #[msg("Buy now price is required for BuyNow listings")]
+    BuyNowPriceRequired = 48,
+    #[msg("Uploads not verified by backend")]
+    UploadsNotVerified = 49,
+    #[msg("Uploads already verified")]
+    AlreadyVerified = 50,
+    #[msg("Not backend authority")]
+    NotBackendAuthority = 51,
+    #[msg("Bid below reserve price")]
+    BidBelowReserve = 52,
+    #[msg("Cannot finalize disputed transaction")]
+    CannotFinalizeDisputed = 53,
+    #[msg("Seller must sign to finalize")]
+    SellerMustSign = 54,
+    #[msg("No bids to settle - use cancel_auction instead")]
+    NoBidsToSettle = 55,
+    #[msg("Cannot cancel auction with active bids")]
+    CannotCancelWithBids = 56,
+    #[msg("Cannot close escrow: pending withdrawals exist")]
+    PendingWithdrawalsExist = 57,
+    #[msg("Transaction must be in Completed or Refunded state")]
+    TransactionNotComplete = 58,
+    #[msg("Invalid GitHub username: max 39 chars, alphanumeric/hyphens, no start/end/consecutive hyphens")]
+    InvalidGithubUsername = 59,
+    #[msg("Dispute deadline expired: must dispute within grace period")]
+    DisputeDeadlineExpired = 60,
+    #[msg("Maximum bids per listing exceeded")]
+    MaxBidsExceeded = 61,
+    #[msg("Maximum offers per listing exceeded")]
+    MaxOffersExceeded = 62,
+    #[msg("Maximum consecutive offers from same buyer exceeded (max 10 without being outbid)")]
+    MaxConsecutiveOffersExceeded = 63,
+    #[msg("Maximum consecutive bids from same bidder exceeded (max 10 without being outbid)")]
+    MaxConsecutiveBidsExceeded = 64,
+    #[msg("Backend timeout not expired: must wait 30 days from seller confirmation")]
+    BackendTimeoutNotExpired = 65,
+    #[msg("Only expected admin can initialize marketplace")]
+    NotExpectedAdmin = 66,
+    #[msg("Partial refund amounts must equal sale price")]
+    PartialRefundMustEqualSalePrice = 67,
+    #[msg("Dispute resolution timelock not expired: must wait 48 hours")]
+    DisputeTimelockNotExpired = 68,
+    #[msg("Resolution already contested")]
+    AlreadyContested = 69,
+    #[msg("Invalid offer seed: counter mismatch")]
+    InvalidOfferSeed = 70,
+    #[msg("Invalid withdrawal ID: counter mismatch")]
+    InvalidWithdrawalId = 71,
+    #[msg("Invalid payment mint: APP token fee discount requires actual SPL token transfer")]
+    InvalidPaymentMint = 72,
+    #[msg("Invalid offer: offer does not belong to this listing")]
+    InvalidOffer = 73,
+    #[msg("Unauthorized: only admin can perform this action")]
+    Unauthorized = 74,
+    // Retired: pause checks now all return ContractPaused (kept so later codes don't shift)
+    #[msg("Platform is paused")]
+    PlatformPaused = 75,
+    #[msg("Withdrawal has not expired yet")]
+    WithdrawalNotExpired = 76,
+    #[msg("Loyalty point redemption is disabled")]
+    LoyaltyRedemptionDisabled = 77,
+    #[msg("Invalid points amount: must redeem at least one basis point of discount")]
+    InvalidPointsAmount = 78,
+    #[msg("Insufficient loyalty points")]
+    InsufficientPoints = 79,
+    #[msg("Loyalty fee discount too high: capped at the maximum platform fee")]
+    LoyaltyDiscountTooHigh = 80,
+    #[msg("Invalid deposit: must be at least 10% and below 100% of the bid")]
+    InvalidDepositBps = 81,
+    #[msg("Deposit mode is only available for auctions")]
+    DepositModeAuctionOnly = 82,
+    #[msg("Payment window has expired: deposit is forfeited")]
+    PaymentWindowExpired = 83,
+    #[msg("Payment window has not expired yet")]
+    PaymentWindowNotExpired = 84,
+    #[msg("Invalid vesting period: must be between 1 and 365 days")]
+    InvalidVestingPeriod = 85,
+    #[msg("Transaction has no active vesting schedule")]
+    NoVestingSchedule = 86,
+    #[msg("Nothing to claim yet")]
+    NothingToClaim = 87,
+    #[msg("Cannot accept your own proposal")]
+    CannotAcceptOwnProposal = 88,
+    #[msg("Invalid recipient: cannot be empty or the seller")]
+    InvalidRecipient = 89,
+    #[msg("Invalid appeal window: must be between 0 and 30 days")]
+    InvalidAppealWindow = 90,
+    #[msg("Appeal reason too long: max 500 characters")]
+    AppealReasonTooLong = 91,
+    #[msg("Dispute has not been resolved")]
+    DisputeNotResolved = 92,
+    #[msg("Dispute has already been appealed")]
+    AlreadyAppealed = 93,
+    #[msg("Appeal window has expired")]
+    AppealWindowExpired = 94,
+    #[msg("Appeal window is still open")]
+    AppealWindowOpen = 95,
+    #[msg("Dispute is under appeal: use execute_appeal_resolution")]
+    DisputeUnderAppeal = 96,
+    #[msg("Dispute is not under appeal")]
+    NotUnderAppeal = 97,
+    #[msg("Invalid appellant address")]
+    InvalidAppellant = 98,
+    #[msg("Evidence URI too long: max 200 characters")]
+    EvidenceUriTooLong = 99,
+    #[msg("Maximum evidence submissions reached for this party")]
+    TooManyEvidenceSubmissions = 100,
+    #[msg("Dispute resolutions must be proposed by arbitrator committee vote")]
+    CommitteeResolutionRequired = 101,
+    #[msg("Invalid arbitrator set: 1-7 unique arbitrators required")]
+    InvalidArbitratorSet = 102,
+    #[msg("Invalid quorum: must be between 1 and the number of arbitrators")]
+    InvalidQuorum = 103,
+    #[msg("Caller is not a committee arbitrator")]
+    NotArbitrator = 104,
+    #[msg("Arbitrator has already voted for this resolution")]
+    AlreadyVoted = 105,
+    #[msg("Invalid arbitrator: cannot be a party to the sale")]
+    InvalidArbitrator = 106,
+    #[msg("Transaction has a designated arbitrator")]
+    ArbitratorAssigned = 107,
+    #[msg("Arbitrator stake below the required minimum")]
+    InsufficientArbitratorStake = 108,
+    #[msg("Invalid slash rate: max 10000 basis points")]
+    InvalidSlashBps = 109,
+    #[msg("Invalid stake amount")]
+    InvalidStakeAmount = 110,
+    #[msg("No unstake request pending")]
+    NoUnstakeRequested = 111,
+    #[msg("Unstake cooldown has not elapsed")]
+    UnstakeCooldownActive = 112,
+    #[msg("Ruling was not overturned on appeal")]
+    RulingNotOverturned = 113,
+    #[msg("Arbitrator slashes are still pending for this dispute")]
+    SlashPending = 114,
+    #[msg("Invalid dispute SLA: must be between 7 and 90 days")]
+    InvalidDisputeSla = 115,
+    #[msg("Only the dispute initiator can do this")]
+    NotDisputeInitiator = 116,
+    #[msg("Dispute SLA period has not elapsed")]
+    DisputeSlaNotExpired = 117,
+    #[msg("SLA default resolutions cannot be contested")]
+    SlaDefaultNotContestable = 118,
+    #[msg("Invalid batch accounts: expected non-empty groups of matching accounts")]
+    InvalidBatchAccounts = 119,
+    #[msg("Buy now price must exceed the current bid")]
+    BuyNowBelowCurrentBid = 120,
+    #[msg("Reserve price has not been met")]
+    ReserveNotMet = 121,
+    #[msg("Auction has already started")]
+    AuctionAlreadyStarted = 122,
+    #[msg("Listing has no reserve price")]
+    NoReservePrice = 123,
+    #[msg("New reserve must be positive and below the current reserve")]
+    InvalidReserveReduction = 124,
+    #[msg("Only expired or cancelled listings can be relisted")]
+    CannotRelist = 125,
+    #[msg("Quantity must be between 1 and the maximum; multiple units require a fixed-price listing")]
+    InvalidQuantity = 126,
+    #[msg("Multi-unit listings sell through buy_unit")]
+    MultiUnitListing = 127,
+    #[msg("Listing is not a multi-unit listing")]
+    NotMultiUnitListing = 128,
+    #[msg("All units of this listing have been sold")]
+    SoldOut = 129,
+    #[msg("Listing is still active")]
+    ListingStillActive = 130,
+    #[msg("Invalid bundle: 2-10 distinct, non-zero asset identifiers")]
+    InvalidBundle = 131,
+    #[msg("Asset index is not part of this bundle")]
+    InvalidBundleAsset = 132,
+    #[msg("Every bundled asset must be transferred before confirming")]
+    BundleIncomplete = 133,
+    #[msg("Buyer pool has already purchased the listing")]
+    PoolAlreadyPurchased = 134,
+    #[msg("Buyer pool deadline has passed")]
+    PoolExpired = 135,
+    #[msg("Buyer pool deadline has not passed yet")]
+    PoolDeadlineNotPassed = 136,
+    #[msg("Buyer pool has not reached its target")]
+    PoolNotFunded = 137,
+    #[msg("Pool contribution below the 0.01 SOL minimum")]
+    PoolContributionTooSmall = 138,
+    #[msg("Pool contribution exceeds the amount still needed")]
+    PoolContributionExceedsTarget = 139,
+    #[msg("Listing price no longer matches the pool target")]
+    PoolTargetMismatch = 140,
+    #[msg("Pool purchase is not final yet")]
+    PoolPurchasePending = 141,
+    #[msg("Invalid share token account")]
+    InvalidShareAccount = 142,
+    #[msg("Transaction is not this pool's purchase")]
+    InvalidPoolTransaction = 143,
+    #[msg("Invalid signed order")]
+    InvalidSignedOrder = 144,
+    #[msg("Signed order has expired")]
+    SignedOrderExpired = 145,
+    #[msg("Missing ed25519 signature verification instruction")]
+    MissingSignatureVerification = 146,
+    #[msg("Signature verification does not match the order or seller")]
+    InvalidSignatureVerification = 147,
+    #[msg("Signed order was cancelled or its nonce is stale")]
+    SignedOrderCancelled = 148,
+    #[msg("This listing's bid book must be provided")]
+    BidBookRequired = 149,
+    #[msg("Bid book does not belong to this listing")]
+    InvalidBidBook = 150,
+    #[msg("Withdrawal escheatment is disabled")]
+    EscheatDisabled = 151,
+    #[msg("Escheat period after withdrawal expiry has not elapsed")]
+    EscheatPeriodNotElapsed = 152,
+    #[msg("Invalid escheat period: 0 (disabled) or at least 180 days")]
+    InvalidEscheatPeriod = 153,
+    #[msg("Rent recipient does not match the withdrawal's rent payer")]
+    InvalidRentPayer = 154,
+    #[msg("Insurance fee too high: at most 20% of the platform fee")]
+    InsuranceFeeTooHigh = 155,
+    #[msg("Insurance vault must be provided while an insurance fee is configured")]
+    InsuranceVaultRequired = 156,
+    #[msg("Insurance claim reason too long (max 500 characters)")]
+    ClaimReasonTooLong = 157,
+    #[msg("Insurance claim window has expired")]
+    ClaimWindowExpired = 158,
+    #[msg("Invalid insurance claim amount")]
+    InvalidClaimAmount = 159,
+    #[msg("Insurance claim has already been resolved")]
+    ClaimAlreadyResolved = 160,
+    #[msg("Insufficient funds in the insurance vault")]
+    InsufficientInsuranceFunds = 161,
+    #[msg("Claimant does not match the insurance claim")]
+    InvalidClaimant = 162,
+    #[msg("Invalid holdback: 1 to 5000 bps and not combined with vesting")]
+    InvalidHoldback = 163,
+    #[msg("Warranty period has not ended")]
+    WarrantyPeriodActive = 164,
+    #[msg("Warranty period has ended")]
+    WarrantyPeriodExpired = 165,
+    #[msg("Holdback is under warranty dispute")]
+    WarrantyDisputed = 166,
+    #[msg("Transaction has no warranty holdback")]
+    NoHoldback = 167,
+    #[msg("No warranty dispute is open")]
+    NoWarrantyDispute = 168,
+    #[msg("Dispute reason too long (max 500 characters)")]
+    DisputeReasonTooLong = 169,
+    #[msg("Payout wallet does not match the listing")]
+    InvalidPayoutWallet = 170,
+    #[msg("Payout wallet can only change before any sale")]
+    PayoutWalletLocked = 171,
+    #[msg("Invalid payout split: 1-5 distinct recipients, non-zero shares totalling at most 100%")]
+    InvalidPayoutSplit = 172,
+    #[msg("This listing's payout split must be provided")]
+    PayoutSplitRequired = 173,
+    #[msg("Split recipient accounts missing or out of order")]
+    InvalidSplitRecipient = 174,
+    #[msg("Invalid fee split: 1-5 distinct recipients with shares totalling exactly 100%")]
+    InvalidFeeSplit = 175,
+    #[msg("No fee split is active")]
+    FeeSplitInactive = 176,
+    #[msg("Only the guardian can perform this action")]
+    NotGuardian = 177,
+    #[msg("Pause reason too long (max 200 characters)")]
+    PauseReasonTooLong = 178,
+    #[msg("Pause must end in the future and within 14 days")]
+    InvalidPauseDuration = 179,
+    #[msg("Invalid circuit breaker multiple: 0 (disabled) or at least 2")]
+    InvalidCircuitBreaker = 180,
+    #[msg("Circuit breaker has not tripped")]
+    CircuitBreakerNotTripped = 181,
+    #[msg("Price cap must be greater than zero")]
+    InvalidPriceCap = 182,
+    #[msg("Price exceeds the marketplace maximum sale price")]
+    PriceAboveCap = 183,
+    #[msg("Invalid tags: up to 5 distinct tags of 1-24 lowercase letters, digits or hyphens")]
+    InvalidListingTag = 184,
+    #[msg("Listing promotions are disabled")]
+    PromotionsDisabled = 185,
+    #[msg("Promotion must last 1-30 days")]
+    InvalidPromotionDays = 186,
+    #[msg("Start time must be between now and 30 days from now")]
+    InvalidStartTime = 187,
+    #[msg("Listing has not started yet")]
+    ListingNotStarted = 188,
+    #[msg("Listing is not a draft")]
+    ListingNotDraft = 189,
+    #[msg("Listing is not paused")]
+    ListingNotPaused = 190,
+    #[msg("Batch must contain 1-8 listings")]
+    InvalidBatchSize = 191,
+    #[msg("Invalid operator")]
+    InvalidOperator = 192,
+    #[msg("Signer is not this session's key")]
+    InvalidSessionKey = 193,
+    #[msg("Session must expire within 24 hours")]
+    InvalidSessionExpiry = 194,
+    #[msg("Session has expired")]
+    SessionExpired = 195,
+    #[msg("Bid exceeds the session's remaining spend")]
+    SessionLimitExceeded = 196,
+    #[msg("Missing or invalid session vault")]
+    InvalidSessionVault = 197,
+    #[msg("Attestation must expire within one year")]
+    InvalidAttestationExpiry = 198,
+    #[msg("Listing requires a valid buyer attestation")]
+    BuyerNotVerified = 199,
+    #[msg("User profile does not belong to this user")]
+    InvalidUserProfile = 200,
+    #[msg("User profile required while a bidder exposure cap is set")]
+    UserProfileRequired = 201,
+    #[msg("Bid would exceed the per-wallet exposure cap")]
+    ExposureLimitExceeded = 202,
+    #[msg("No bid exposure to release")]
+    NoBidExposure = 203,
+    #[msg("State changed since the transaction was built")]
+    StateChanged = 204,
+    #[msg("Deliverable hash must be 1-64 characters and not already recorded")]
+    InvalidDeliverable = 205,
+    #[msg("Transaction already has the maximum number of deliverables")]
+    TooManyDeliverables = 206,
+    #[msg("Verification report needs a non-zero merkle root and item count")]
+    InvalidVerificationReport = 207,
+    #[msg("Uploads have not been rejected")]
+    UploadsNotRejected = 208,
+    #[msg("Only sold listings whose transaction was refunded can be reopened")]
+    CannotReopen = 209,
+    #[msg("Close the dispute before reopening the listing")]
+    DisputeNotClosed = 210,
+    #[msg("Volume stats account is for a different payment currency")]
+    InvalidVolumeStats = 211,
+    #[msg("Fee schedule account required")]
+    FeeScheduleRequired = 212,
+    #[msg("Withdrawal amount must be greater than zero")]
+    InvalidWithdrawalAmount = 213,
+    #[msg("Account is already on the current layout")]
+    NothingToMigrate = 214,
+    #[msg("This listing's bid history must be provided")]
+    BidHistoryRequired = 215,
+    #[msg("Bid history does not belong to this listing")]
+    InvalidBidHistory = 216,
+    #[msg("This listing's bid escrow accounts must be provided")]
+    BidEscrowRequired = 217,
+    #[msg("Bid escrow does not match this listing or its high bidder")]
+    InvalidBidEscrow = 218,
+    #[msg("Bid escrows need a SOL auction without deposit mode or sessions")]
+    BidEscrowUnsupported = 219,
+    #[msg("The winning bid has not been committed from its bid escrow")]
+    WinningBidNotCommitted = 220,
+    #[msg("Bid escrow backs the standing high bid")]
+    BidEscrowLocked = 221,
+    #[msg("Listing cannot move to that status from its current one")]
+    InvalidListingTransition = 222,
+    #[msg("Listing already has sold units")]
+    HasUnitSales = 223,
+    #[msg("No dispute resolution is pending")]
+    NoPendingResolution = 224,
+    #[msg("No mutual cancellation has been proposed")]
+    NoCancelProposal = 225,
+    #[msg("Listing does not hold bids in bid escrows")]
+    BidEscrowsNotEnabled = 226,
+    #[msg("Offer is below the listing's minimum offer")]
+    OfferBelowMinimum = 227,
+    #[msg("Auto-accept price must be positive and at least the minimum offer")]
+    InvalidOfferThresholds = 228,
+    #[msg("Auto-accepted offers need the listing escrow and transaction accounts")]
+    AutoAcceptAccountsRequired = 229,
+    #[msg("Invalid price drop schedule")]
+    InvalidPriceDropSchedule = 230,
+    #[msg("Escrow yield is not enabled for this stake pool")]
+    EscrowYieldDisabled = 231,
+    #[msg("Stake pool accounts do not match the configured pool")]
+    InvalidStakePool = 232,
+    #[msg("Staked escrow is worth less than its principal")]
+    EscrowYieldShortfall = 233,
+    #[msg("Escrow yield share must be at most 10000 basis points")]
+    InvalidYieldShare = 234,
+    #[msg("Invalid recurring escrow terms")]
+    InvalidRecurringTerms = 235,
+    #[msg("Recurring escrow is not active")]
+    RecurringEscrowNotActive = 236,
+    #[msg("Every recurring period is already funded")]
+    AllPeriodsFunded = 237,
+    #[msg("Recurring period is not funded")]
+    RecurringPeriodNotFunded = 238,
+    #[msg("Recurring period is not due yet")]
+    RecurringPeriodNotDue = 239,
+    #[msg("Recurring period is disputed")]
+    RecurringPeriodDisputed = 240,
+    #[msg("No recurring period is disputed")]
+    NoRecurringDispute = 241,
+    #[msg("Recurring escrow still has unsettled periods")]
+    RecurringPeriodsOutstanding = 242,
+    #[msg("Invalid rent-to-own terms")]
+    InvalidRentToOwnTerms = 243,
+    #[msg("Rent-to-own terms are not open")]
+    RentToOwnNotOffered = 244,
+    #[msg("Rent-to-own agreement is not active")]
+    RentToOwnNotActive = 245,
+    #[msg("No rent-to-own installment is past its grace period")]
+    InstallmentNotOverdue = 246,
+    #[msg("Rent-to-own agreement is still running")]
+    RentToOwnOpen = 247,
+    #[msg("Listing is not an open-offers listing")]
+    NotOpenOffersListing = 248,
+    #[msg("Open-offers listings cannot have a reserve price")]
+    OpenOffersReserveNotAllowed = 249,
+    #[msg("Invalid accepted currencies")]
+    InvalidAcceptedCurrencies = 250,
+    #[msg("Listing does not accept this currency")]
+    CurrencyNotAccepted = 251,
+    #[msg("Invalid offer duration bounds")]
+    InvalidOfferDurationBounds = 252,
+    #[msg("High bid meets the reserve; settle the auction instead")]
+    ReserveWasMet = 253,
+    #[msg("Only the dispute respondent can do this")]
+    NotDisputeRespondent = 254,
+    #[msg("Respondent bond already posted")]
+    RespondentBondPosted = 255,
+    #[msg("Response window has closed")]
+    ResponseWindowClosed = 256,
+    #[msg("Response window is still open")]
+    ResponseWindowOpen = 257,
+    #[msg("Dispute response too long (max 500 characters)")]
+    DisputeResponseTooLong = 258,
+    #[msg("Respondent has already answered this dispute")]
+    DisputeAlreadyAnswered = 259,
+    #[msg("Sale is not below the small-claims threshold")]
+    NotSmallClaim = 260,
+    #[msg("A resolution is already pending; withdraw it before proposing another")]
+    ResolutionAlreadyPending = 261,
+    #[msg("Dispute ruling has already been paid out")]
+    RulingAlreadySettled = 262,
+    #[msg("Dispute ruling has not been paid out yet")]
+    RulingNotSettled = 263,
+    #[msg("Pool contributions have not all been refunded")]
+    PoolContributionsOutstanding = 264,
+    #[msg("Fraction terms invalid: share count out of range, zero share price, or buyout below the full share value")]
+    InvalidFractionTerms = 265,
+    #[msg("Not enough unsold shares")]
+    FractionSharesUnavailable = 266,
+    #[msg("Fractions have already been bought out")]
+    FractionsBoughtOut = 267,
+    #[msg("Fractions have not been bought out")]
+    FractionsNotBoughtOut = 268,
+    #[msg("App deed is not held by this wallet")]
+    NotDeedHolder = 269,
+    #[msg("Vesting period has ended")]
+    VestingPeriodEnded = 270,
+    #[msg("Vested proceeds are under dispute")]
+    VestingDisputed = 271,
+    #[msg("No vesting dispute to resolve")]
+    NoVestingDispute = 272,
+    #[msg("Respondent bonds were disabled when this dispute was opened")]
+    RespondentBondsDisabled = 273,
+    #[msg("Starting price cannot exceed the reserve price")]
+    StartingPriceAboveReserve = 274,
+    #[msg("Pending withdrawal account required for the refund")]
+    WithdrawalAccountRequired = 275,
+    #[msg("Auction did not end below its reserve")]
+    AuctionNotFailed = 276,
+    #[msg("Withdrawal bump does not derive a valid PDA")]
+    InvalidWithdrawalBump = 277,
+}
@@ -0,0 +1,1705 @@
+//! Events emitted by the program
+
+use crate::*;
+
+#[event]
+pub struct MarketplaceInitialized {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub backend_authority: Pubkey,
+    pub platform_fee_bps: u64,
+    pub dispute_fee_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingCreated {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub listing_id: String,
+    pub listing_type: ListingType,
+    pub starting_price: u64,
+    pub end_time: i64,
+    pub platform_fee_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionEndedByBuyNow {
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    // High bidder refunded via a pending withdrawal (if any)
+    pub displaced_bidder: Option<Pubkey>,
+    pub displaced_bid: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuyerAttested {
+    pub buyer: Pubkey,
+    pub buyer_attestation: Pubkey,
+    pub expires_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuyerAttestationRevoked {
+    pub buyer: Pubkey,
+    pub buyer_attestation: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingVerificationRequirementSet {
+    pub listing: Pubkey,
+    pub requires_verification: bool,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub session: Pubkey,
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub max_spend: u64,
+    pub expires_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionClosed {
+    pub session: Pubkey,
+    pub owner: Pubkey,
+    pub spent: u64,
+    pub refunded: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Tripwire: escrow lamports disagree with escrow.amount + rent, or outstanding
+/// withdrawals exceed the tracked amount
+#[event]
+pub struct EscrowInconsistent {
+    pub listing: Pubkey,
+    pub tracked_amount: u64,
+    pub rent: u64,
+    pub lamports: u64,
+    pub pending_withdrawals: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowVerified {
+    pub listing: Pubkey,
+    pub tracked_amount: u64,
+    pub pending_withdrawals: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted on every Escrow.amount change so indexers can replay escrow state exactly
+#[event]
+pub struct EscrowBalanceChanged {
+    pub listing: Pubkey,
+    pub delta: i64,
+    pub new_amount: u64,
+    pub reason: EscrowChangeReason,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub listing: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SaleCompleted {
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub payer: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnitPurchased {
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub unit_index: u64,
+    pub units_remaining: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingActivated {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingPauseToggled {
+    pub listing: Pubkey,
+    pub paused: bool,
+    pub end_time: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingOperatorSet {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub operator: Option<Pubkey>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingPromoted {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub days: u16,
+    pub fee: u64,
+    pub featured_until: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxBidderExposureUpdated {
+    pub max_bidder_exposure: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidExposureReleased {
+    pub user: Pubkey,
+    pub transaction: Pubkey,
+    pub amount: u64,
+    pub locked_exposure: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PromotionFeeUpdated {
+    pub fee_per_day: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingStatusChanged {
+    pub listing: Pubkey,
+    pub previous: ListingStatus,
+    pub status: ListingStatus,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingTagsSet {
+    pub listing: Pubkey,
+    pub tags: Vec<String>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceDropScheduleSet {
+    pub listing: Pubkey,
+    pub initial_price: u64,
+    pub drop_amount: u64,
+    pub interval_seconds: i64,
+    pub floor_price: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BundleAssetsSet {
+    pub listing: Pubkey,
+    pub assets: Vec<[u8; 32]>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BundleAssetTransferred {
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub asset: [u8; 32],
+    pub asset_index: u8,
+    pub remaining: u8,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuyerPoolCreated {
+    pub pool: Pubkey,
+    pub listing: Pubkey,
+    pub creator: Pubkey,
+    pub target_amount: u64,
+    pub deadline: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolContributionMade {
+    pub pool: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub total_contributed: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolPurchaseExecuted {
+    pub pool: Pubkey,
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolContributionClaimed {
+    pub pool: Pubkey,
+    pub contributor: Pubkey,
+    pub contributed: u64,
+    pub refund: u64,
+    pub shares: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignedOrderFulfilled {
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub salt: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignedOrderCancelled {
+    pub seller: Pubkey,
+    pub order_cancellation: Pubkey,
+    pub salt: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderNonceIncremented {
+    pub seller: Pubkey,
+    pub order_nonce: Pubkey,
+    pub nonce: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidBookInitialized {
+    pub listing: Pubkey,
+    pub bid_book: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidHistoryInitialized {
+    pub listing: Pubkey,
+    pub bid_history: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AntiSnipeCapReached {
+    pub listing: Pubkey,
+    pub extensions: u16,
+    // Final, no longer extendable
+    pub end_time: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxAntiSnipeExtensionsUpdated {
+    pub max_anti_snipe_extensions: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidEscrowsEnabled {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningBidCommitted {
+    pub listing: Pubkey,
+    pub bidder: Pubkey,
+    pub bid_escrow: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidEscrowReclaimed {
+    pub listing: Pubkey,
+    pub bidder: Pubkey,
+    pub bid_escrow: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowYieldConfigured {
+    pub stake_pool: Option<Pubkey>,
+    pub buyer_share_bps: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowStaked {
+    pub escrow: Pubkey,
+    pub transaction: Pubkey,
+    pub escrow_stake: Pubkey,
+    pub stake_pool: Pubkey,
+    pub principal: u64,
+    pub pool_tokens: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowUnstaked {
+    pub escrow: Pubkey,
+    pub transaction: Pubkey,
+    pub escrow_stake: Pubkey,
+    pub principal: u64,
+    pub buyer_yield: u64,
+    pub protocol_yield: u64,
+    pub shortfall: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeliverableRecorded {
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub content_hash: String,
+    pub index: u8,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SellerConfirmedTransfer {
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UploadsVerified {
+    pub transaction: Pubkey,
+    pub verification_hash: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationReportRecorded {
+    pub transaction: Pubkey,
+    pub report: VerificationReport,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingReopened {
+    pub listing: Pubkey,
+    pub refunded_transaction: Pubkey,
+    pub end_time: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerificationFailureRefunded {
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub rejection_code: Option<u16>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UploadsRejected {
+    pub transaction: Pubkey,
+    pub round: u16,
+    pub reason_code: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyVerification {
+    pub transaction: Pubkey,
+    pub verification_type: String, // "buyer_timeout" or "admin_override"
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeFeePolicyUpdated {
+    pub policy: DisputeFeePolicy,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeFeeDistributed {
+    pub dispute: Pubkey,
+    pub policy: DisputeFeePolicy,
+    pub to_buyer: u64,
+    pub to_seller: u64,
+    pub to_treasury: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolutionWithdrawn {
+    pub dispute: Pubkey,
+    pub resolution: DisputeResolution,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeSplitProposed {
+    pub recipients: Vec<FeeRecipient>,
+    pub executable_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeSplitChanged {
+    pub recipients: Vec<FeeRecipient>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsPoolInitialized {
+    pub rewards_vault: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsStakeChanged {
+    pub owner: Pubkey,
+    pub reward_stake: Pubkey,
+    pub amount: u64,
+    pub staked: bool,
+    pub total_staked: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub owner: Pubkey,
+    pub reward_stake: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceFeeUpdated {
+    pub insurance_fee_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceFunded {
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimFiled {
+    pub claim: Pubkey,
+    pub transaction: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimResolved {
+    pub claim: Pubkey,
+    pub transaction: Pubkey,
+    pub claimant: Pubkey,
+    pub approved: bool,
+    pub payout: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeScheduleUpdated {
+    // None = the schedule's default rate
+    pub category: Option<Category>,
+    pub fee_bps: Option<u64>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxSalePriceUpdated {
+    pub max_sale_price: Option<u64>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceCapOverrideApproved {
+    pub seller: Pubkey,
+    pub price_cap_override: Pubkey,
+    pub max_price: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceCapOverrideRevoked {
+    pub seller: Pubkey,
+    pub price_cap_override: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerUpdated {
+    pub multiple: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub volume_today: u64,
+    pub volume_this_slot: u64,
+    pub trailing_daily_volume: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerReset {
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscheatPeriodUpdated {
+    pub escheat_period_seconds: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalEscheated {
+    pub user: Pubkey,
+    pub listing: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub withdrawal_id: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub expired_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeSlaUpdated {
+    pub dispute_sla_seconds: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeSlaDefaultClaimed {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub initiator: Pubkey,
+    pub resolution: DisputeResolution,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbitratorStakingUpdated {
+    pub min_arbitrator_stake: u64,
+    pub arbitrator_slash_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbitratorStaked {
+    pub arbitrator: Pubkey,
+    pub arbitrator_stake: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub arbitrator: Pubkey,
+    pub arbitrator_stake: Pubkey,
+    pub amount: u64,
+    pub available_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbitratorUnstaked {
+    pub arbitrator: Pubkey,
+    pub arbitrator_stake: Pubkey,
+    pub amount: u64,
+    pub remaining_stake: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbitratorSlashed {
+    pub arbitrator: Pubkey,
+    pub arbitrator_stake: Pubkey,
+    pub dispute: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub remaining_stake: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbitratorSetUpdated {
+    pub arbitrators: Vec<Pubkey>,
+    pub quorum: u8,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResolutionVoteCast {
+    pub dispute: Pubkey,
+    pub arbitrator: Pubkey,
+    pub resolution: DisputeResolution,
+    pub votes: u8,
+    pub quorum: u8,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolutionProposed {
+    pub dispute: Pubkey,
+    pub resolution: DisputeResolution,
+    pub buyer_amount: u64,
+    pub seller_amount: u64,
+    pub executable_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeContested {
+    pub dispute: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransactionCompleted {
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub platform_fee: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub listing: Pubkey,
+    pub reason: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingRelisted {
+    pub listing: Pubkey,
+    pub listing_id: String,
+    pub relist_count: u32,
+    pub end_time: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReserveLowered {
+    pub listing: Pubkey,
+    pub old_reserve: u64,
+    pub new_reserve: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingExpired {
+    pub listing: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub initiator: Pubkey,
+    pub category: DisputeCategory,
+    pub severity: DisputeSeverity,
+    pub reason: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EvidenceSubmitted {
+    pub dispute: Pubkey,
+    pub evidence: Pubkey,
+    pub party: Pubkey,
+    pub index: u8,
+    pub content_hash: [u8; 32],
+    pub uri: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub resolution: DisputeResolution,
+    pub notes: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub guardian: Option<Pubkey>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformFeesUpdated {
+    pub platform_fee_bps: u64,
+    pub dispute_fee_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContractPausedEvent {
+    pub paused: bool,
+    pub reason: String,
+    pub paused_until: Option<i64>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryChangeProposed {
+    pub old_treasury: Pubkey,
+    pub new_treasury: Pubkey,
+    pub executable_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryChanged {
+    pub new_treasury: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawalRequested {
+    pub id: u64,
+    pub treasury_withdrawal: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub id: u64,
+    pub treasury_withdrawal: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    // Withdrawable balance left in the vault
+    pub remaining: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawalCancelled {
+    pub id: u64,
+    pub treasury_withdrawal: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminChangeProposed {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub executable_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminChanged {
+    pub new_admin: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalCreated {
+    pub user: Pubkey,
+    pub listing: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub amount: u64,
+    pub withdrawal_id: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalClaimed {
+    pub user: Pubkey,
+    pub listing: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalExpired {
+    pub user: Pubkey,
+    pub listing: Pubkey,
+    pub pending_withdrawal: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowClosed {
+    pub listing: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferCreated {
+    pub offer: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub deadline: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferThresholdsSet {
+    pub listing: Pubkey,
+    pub min_offer: u64,
+    pub auto_accept_price: Option<u64>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BestOfferChanged {
+    pub listing: Pubkey,
+    pub best_offer: Option<Pubkey>,
+    pub best_offer_amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferCancelled {
+    pub offer: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferExpired {
+    pub offer: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferAccepted {
+    pub offer: Pubkey,
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub payer: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchCranked {
+    pub crank: CrankKind,
+    pub items: u16,
+    pub processed: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CurrentBidAccepted {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionSettlementResult {
+    pub listing: Pubkey,
+    pub success: bool,
+    // Error code of the failed check (0 on success)
+    pub error_code: u32,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SettlementCranked {
+    pub listing: Pubkey,
+    pub cranker: Pubkey,
+    pub tip: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RemainderPaid {
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub deposit_amount: u64,
+    pub remainder: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositForfeited {
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub deposit_amount: u64,
+    pub platform_fee: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProceedsVestingStarted {
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub vesting_end: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutWalletChangeProposed {
+    pub listing: Pubkey,
+    pub old_payout_wallet: Pubkey,
+    pub new_payout_wallet: Pubkey,
+    pub executable_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutWalletChanged {
+    pub listing: Pubkey,
+    pub payout_wallet: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutSplitCreated {
+    pub listing: Pubkey,
+    pub recipients: Vec<SplitRecipient>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HoldbackStarted {
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub release_at: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HoldbackClaimed {
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WarrantyDisputeOpened {
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub holdback_amount: u64,
+    pub reason: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WarrantyDisputeResolved {
+    pub transaction: Pubkey,
+    pub buyer_amount: u64,
+    pub seller_amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedProceedsClaimed {
+    pub transaction: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub remaining: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuyerAssigned {
+    pub transaction: Pubkey,
+    pub previous_buyer: Pubkey,
+    pub new_buyer: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MutualCancelProposed {
+    pub transaction: Pubkey,
+    pub relist: bool,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MutualCancelCompleted {
+    pub listing: Pubkey,
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub refund_amount: u64,
+    pub relisted: bool,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppealConfigUpdated {
+    pub appeal_window_seconds: i64,
+    pub appeal_fee_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeAppealed {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub appellant: Pubkey,
+    pub original_resolution: Option<DisputeResolution>,
+    pub appeal_fee: u64,
+    pub reason: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppealResolved {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub appellant: Pubkey,
+    pub original_resolution: Option<DisputeResolution>,
+    pub final_resolution: DisputeResolution,
+    pub overturned: bool,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeClosed {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoyaltyConfigUpdated {
+    pub points_per_sol: u64,
+    pub points_per_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserProfileCreated {
+    pub user: Pubkey,
+    pub profile: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContactKeySet {
+    pub user: Pubkey,
+    pub user_profile: Pubkey,
+    pub contact_key: Option<[u8; 32]>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContactKeysSynced {
+    pub transaction: Pubkey,
+    pub buyer_contact_key: Option<[u8; 32]>,
+    pub seller_contact_key: Option<[u8; 32]>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VolumeStatsInitialized {
+    pub mint: Pubkey,
+    pub volume_stats: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub version: u8,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolParamsSynced {
+    pub protocol_params: Pubkey,
+    pub params_version: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoyaltyPointsAccrued {
+    pub user: Pubkey,
+    pub user_profile: Pubkey,
+    pub points: u64,
+    pub total_points: u64,
+    pub sale_amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoyaltyPointsRedeemed {
+    pub user: Pubkey,
+    pub user_profile: Pubkey,
+    pub points_spent: u64,
+    pub discount_bps: u64,
+    pub total_discount_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LoyaltyDiscountApplied {
+    pub user: Pubkey,
+    pub listing: Pubkey,
+    pub discount_bps: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringEscrowCreated {
+    pub recurring_escrow: Pubkey,
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    pub amount_per_period: u64,
+    pub period_seconds: i64,
+    pub total_periods: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringPeriodFunded {
+    pub recurring_escrow: Pubkey,
+    pub period: u16,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringPeriodReleased {
+    pub recurring_escrow: Pubkey,
+    pub period: u16,
+    pub seller_amount: u64,
+    pub platform_fee: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringPeriodSkipped {
+    pub recurring_escrow: Pubkey,
+    pub period: u16,
+    pub refunded: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringPeriodDisputed {
+    pub recurring_escrow: Pubkey,
+    pub period: u16,
+    pub reason: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringDisputeResolved {
+    pub recurring_escrow: Pubkey,
+    pub period: u16,
+    pub released_to_seller: bool,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringEscrowTerminated {
+    pub recurring_escrow: Pubkey,
+    pub total_periods: u16,
+    pub refunded: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecurringEscrowClosed {
+    pub recurring_escrow: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RentToOwnOffered {
+    pub rent_to_own: Pubkey,
+    pub listing: Pubkey,
+    pub upfront_amount: u64,
+    pub installment_amount: u64,
+    pub installments: u16,
+    pub period_seconds: i64,
+    pub forfeiture_bps: u16,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RentToOwnStarted {
+    pub rent_to_own: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub upfront_amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RentToOwnInstallmentPaid {
+    pub rent_to_own: Pubkey,
+    pub installment: u16,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RentToOwnCompleted {
+    pub rent_to_own: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller_amount: u64,
+    pub platform_fee: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RentToOwnDefaulted {
+    pub rent_to_own: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller_amount: u64,
+    pub platform_fee: u64,
+    pub buyer_refund: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RentToOwnClosed {
+    pub rent_to_own: Pubkey,
+    pub listing: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OpenListingClosed {
+    pub listing: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AcceptedCurrenciesSet {
+    pub listing: Pubkey,
+    pub currencies: Vec<CurrencyPrice>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferDurationBoundsUpdated {
+    pub min_seconds: i64,
+    pub max_seconds: i64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionReserveNotMet {
+    pub listing: Pubkey,
+    pub bidder: Pubkey,
+    pub high_bid: u64,
+    pub reserve_price: u64,
+    // Queued as a PendingWithdrawal from the listing escrow (0 when the bid sits in a BidEscrow)
+    pub refund: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RespondentBondPosted {
+    pub dispute: Pubkey,
+    pub respondent: Pubkey,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DefaultJudgmentClaimed {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub initiator: Pubkey,
+    pub resolution: DisputeResolution,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RespondentBondDistributed {
+    pub dispute: Pubkey,
+    pub to_buyer: u64,
+    pub to_seller: u64,
+    pub to_treasury: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResponded {
+    pub dispute: Pubkey,
+    pub respondent: Pubkey,
+    pub status: DisputeResponseStatus,
+    pub response: String,
+    pub response_hash: Option<[u8; 32]>,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SmallClaimsThresholdUpdated {
+    pub threshold: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeRulingSettled {
+    pub dispute: Pubkey,
+    pub transaction: Pubkey,
+    pub resolution: DisputeResolution,
+    pub appealed: bool,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuyerPoolClosed {
+    pub pool: Pubkey,
+    pub listing: Pubkey,
+    pub creator: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppDeedIssued {
+    pub deed: Pubkey,
+    pub listing: Pubkey,
+    pub holder: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppDeedTransferred {
+    pub deed: Pubkey,
+    pub listing: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppFractionalized {
+    pub vault: Pubkey,
+    pub listing: Pubkey,
+    pub deed: Pubkey,
+    pub share_mint: Pubkey,
+    pub total_shares: u64,
+    pub share_price: u64,
+    pub buyout_price: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FractionsPurchased {
+    pub vault: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+    pub shares_sold: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FractionProceedsClaimed {
+    pub vault: Pubkey,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub platform_fee: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FractionsBoughtOut {
+    pub vault: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub buyout_price: u64,
+    pub platform_fee: u64,
+    pub holder_pot: u64,
+    pub seller_amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FractionSharesRedeemed {
+    pub vault: Pubkey,
+    pub holder: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingDisputeOpened {
+    pub transaction: Pubkey,
+    pub buyer: Pubkey,
+    pub frozen_amount: u64,
+    pub reason: String,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingDisputeResolved {
+    pub transaction: Pubkey,
+    pub buyer_amount: u64,
+    pub seller_amount: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransactionReleaseResult {
+    pub transaction: Pubkey,
+    pub success: bool,
+    // Error code of the failed check (0 on success)
+    pub error_code: u32,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RespondentBondsUpdated {
+    pub enabled: bool,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+}
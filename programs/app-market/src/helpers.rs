@@ -0,0 +1,2631 @@
+//! Free functions shared by instruction handlers
+
+use crate::*;
+
+/// Amount locked in escrow for a bid: the full bid, or the earnest-money
+/// deposit when the listing is a deposit-mode auction (`deposit_bps > 0`).
+pub(crate) fn escrowed_bid_amount(deposit_bps: u16, amount: u64) -> Result<u64> {
+    if deposit_bps == 0 {
+        return Ok(amount);
+    }
+
+    let deposit = amount
+        .checked_mul(deposit_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    Ok(deposit)
+}
+
+/// Move a listing along the ListingStatus state machine and emit ListingStatusChanged
+pub(crate) fn set_listing_status(
+    listing: &mut Account<Listing>,
+    status: ListingStatus,
+    actor: Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        listing.status.can_transition_to(&status),
+        AppMarketError::InvalidListingTransition
+    );
+    let previous = std::mem::replace(&mut listing.status, status.clone());
+
+    emit!(ListingStatusChanged {
+        listing: listing.key(),
+        previous,
+        status,
+        actor,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Announce a listing's best offer after note_offer / drop_offer / clear_best_offer changed it
+pub(crate) fn emit_best_offer_changed(listing: &Account<Listing>, actor: Pubkey, now: i64) {
+    emit!(BestOfferChanged {
+        listing: listing.key(),
+        best_offer: listing.best_offer,
+        best_offer_amount: listing.best_offer_amount,
+        actor,
+        timestamp: now,
+    });
+}
+
+/// Mirror a sale's progress onto its listing. Unit sales leave a multi-unit listing alone:
+/// its other units keep selling independently.
+pub(crate) fn set_sale_status(
+    listing: &mut Account<Listing>,
+    transaction: &Transaction,
+    status: ListingStatus,
+    actor: Pubkey,
+    now: i64,
+) -> Result<()> {
+    if transaction.unit_index.is_some() {
+        return Ok(());
+    }
+    set_listing_status(listing, status, actor, now)
+}
+
+/// Listing status right after a sale: Sold while a deposit-mode winner owes the remainder
+pub(crate) fn sold_listing_status(transaction: &Transaction) -> ListingStatus {
+    if transaction.status == TransactionStatus::Pending {
+        ListingStatus::Sold
+    } else {
+        ListingStatus::InEscrow
+    }
+}
+
+/// Credit loyalty points for a completed sale to an (optional) user profile.
+/// `is_buyer` selects which counter (purchases vs sales) is incremented.
+/// Points are priced per SOL: sales in other currencies only count towards the
+/// purchase/sale totals (there is no on-chain rate to convert them at).
+pub(crate) fn accrue_loyalty_points(
+    profile: Option<&mut Account<UserProfile>>,
+    amount: u64,
+    payment_currency: Pubkey,
+    points_per_sol: u64,
+    is_buyer: bool,
+    actor: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    let Some(profile) = profile else {
+        return Ok(());
+    };
+
+    if is_buyer {
+        profile.total_purchases = profile.total_purchases.saturating_add(1);
+    } else {
+        profile.total_sales = profile.total_sales.saturating_add(1);
+    }
+
+    if points_per_sol == 0 || amount == 0 || payment_currency != Pubkey::default() {
+        return Ok(());
+    }
+
+    // u128 intermediate: amount * rate can exceed u64 for large sales
+    let points = (amount as u128)
+        .checked_mul(points_per_sol as u128)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(LAMPORTS_PER_SOL as u128)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let points = u64::try_from(points).map_err(|_| AppMarketError::MathOverflow)?;
+
+    if points == 0 {
+        return Ok(());
+    }
+
+    // SECURITY: Use saturating_add so a full balance never blocks completion
+    profile.points = profile.points.saturating_add(points);
+    profile.lifetime_points = profile.lifetime_points.saturating_add(points);
+
+    emit!(LoyaltyPointsAccrued {
+        user: profile.user,
+        user_profile: profile.key(),
+        points,
+        total_points: profile.points,
+        sale_amount: amount,
+        actor,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Fill a freshly created Transaction for an auction won by `listing.current_bidder`
+/// (everything except the PDA bump).
+pub(crate) fn populate_auction_transaction(
+    transaction: &mut Transaction,
+    listing: &Listing,
+    listing_key: Pubkey,
+    now: i64,
+) -> Result<()> {
+    transaction.listing = listing_key;
+    transaction.seller = listing.seller;
+    transaction.buyer = listing.current_bidder
+        .ok_or(AppMarketError::NoBidsToSettle)?;
+    // The winning bid stops being an open bid; its exposure is released separately
+    transaction.bid_exposure = if listing.uses_bid_escrows {
+        escrowed_bid_amount(listing.deposit_bps, listing.current_bid)?
+    } else {
+        listing.current_bid_exposure
+    };
+    transaction.agreement_hash = None;
+    transaction.payer = transaction.buyer;
+    transaction.sale_price = listing.current_bid;
+
+    // SECURITY: Use LOCKED fees from listing, not current config
+    transaction.platform_fee = listing.current_bid
+        .checked_mul(listing.platform_fee_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.seller_proceeds = listing.current_bid
+        .checked_sub(transaction.platform_fee)
+        .ok_or(AppMarketError::MathOverflow)?;
+
+    if listing.deposit_bps > 0 {
+        // Deposit mode: winner must pay the remainder before the transfer period starts
+        transaction.deposit_amount = escrowed_bid_amount(listing.deposit_bps, listing.current_bid)?;
+        transaction.payment_deadline = Some(
+            now.checked_add(DEPOSIT_PAYMENT_WINDOW_SECONDS)
+                .ok_or(AppMarketError::MathOverflow)?
+        );
+        transaction.status = TransactionStatus::Pending;
+    } else {
+        transaction.deposit_amount = 0;
+        transaction.payment_deadline = None;
+        transaction.status = TransactionStatus::InEscrow;
+    }
+    transaction.transfer_deadline = now
+        .checked_add(TRANSFER_DEADLINE_SECONDS)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.created_at = now;
+    transaction.seller_confirmed_transfer = false;
+    transaction.seller_confirmed_at = None;
+    transaction.completed_at = None;
+    transaction.vesting_duration = (listing.vesting_days as i64)
+        .checked_mul(SECONDS_PER_DAY)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.vesting_start = None;
+    transaction.vested_claimed = 0;
+    transaction.arbitrator = listing.arbitrator;
+
+    Ok(())
+}
+
+/// Pay a permissionless settler their tip out of escrow. The tip is carved out of the
+/// platform fee: sale price (and any deposit) drop by the tip so escrow accounting stays
+/// exact; seller proceeds are unchanged. Returns the tip paid.
+pub(crate) fn pay_settlement_tip<'info>(
+    transaction: &mut Transaction,
+    escrow: &mut Account<'info, Escrow>,
+    listing_key: Pubkey,
+    cranker: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<u64> {
+    let tip = settlement_tip(transaction.platform_fee)?;
+    if tip == 0 {
+        return Ok(0);
+    }
+
+    // SECURITY: Validate escrow balance before transfer
+    let escrow_balance = escrow.to_account_info().lamports();
+    let rent = Rent::get()?.minimum_balance(escrow.to_account_info().data_len());
+    require!(
+        escrow_balance >= tip + rent && escrow.amount >= tip,
+        AppMarketError::InsufficientEscrowBalance
+    );
+
+    // EFFECTS
+    deduct_settlement_tip(transaction, tip)?;
+    escrow.debit(tip, EscrowChangeReason::SettlementTip, cranker.key())?;
+
+    // INTERACTIONS
+    let seeds = &[
+        b"escrow",
+        listing_key.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program,
+        anchor_lang::system_program::Transfer {
+            from: escrow.to_account_info(),
+            to: cranker,
+        },
+        signer,
+    );
+    anchor_lang::system_program::transfer(cpi_ctx, tip)?;
+
+    Ok(tip)
+}
+
+/// The cranker's settlement tip: a share of the platform fee
+pub(crate) fn settlement_tip(platform_fee: u64) -> Result<u64> {
+    platform_fee
+        .checked_mul(SETTLEMENT_TIP_BPS)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow.into())
+}
+
+/// Take a paid settlement tip out of the sale: it comes out of the platform fee, so the
+/// seller's proceeds are untouched and fee + proceeds still equals the (lower) sale price
+pub(crate) fn deduct_settlement_tip(transaction: &mut Transaction, tip: u64) -> Result<()> {
+    transaction.platform_fee = transaction.platform_fee
+        .checked_sub(tip)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.sale_price = transaction.sale_price
+        .checked_sub(tip)
+        .ok_or(AppMarketError::MathOverflow)?;
+    if transaction.deposit_amount > 0 {
+        transaction.deposit_amount = transaction.deposit_amount
+            .checked_sub(tip)
+            .ok_or(AppMarketError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+/// Create a program-owned PDA account outside of Anchor's `init` (batch instructions).
+/// Mirrors `init`: handles addresses that were pre-funded with lamports.
+pub(crate) fn create_pda_account<'info>(
+    payer: AccountInfo<'info>,
+    target: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+    space: usize,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer = &[signer_seeds];
+    let current_lamports = target.lamports();
+
+    if current_lamports == 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            anchor_lang::system_program::CreateAccount {
+                from: payer,
+                to: target,
+            },
+            signer,
+        );
+        return anchor_lang::system_program::create_account(cpi_ctx, rent, space as u64, program_id);
+    }
+
+    let shortfall = rent.saturating_sub(current_lamports);
+    if shortfall > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: payer,
+                to: target.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, shortfall)?;
+    }
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        anchor_lang::system_program::Allocate {
+            account_to_allocate: target.clone(),
+        },
+        signer,
+    );
+    anchor_lang::system_program::allocate(cpi_ctx, space as u64)?;
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program,
+        anchor_lang::system_program::Assign {
+            account_to_assign: target,
+        },
+        signer,
+    );
+    anchor_lang::system_program::assign(cpi_ctx, program_id)
+}
+
+/// Batch auction item: listing, escrow and transaction PDA bump
+pub(crate) type AuctionBatchItem<'info> = (Account<'info, Listing>, Account<'info, Escrow>, u8);
+
+/// Load a batch auction item and verify its escrow/transaction accounts belong to it.
+pub(crate) fn load_auction_batch_item<'info>(
+    listing_info: &'info AccountInfo<'info>,
+    escrow_info: &'info AccountInfo<'info>,
+    transaction_info: &'info AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<AuctionBatchItem<'info>> {
+    let listing = Account::<Listing>::try_from(listing_info)?;
+    let listing_key = listing.key();
+
+    // SECURITY: Escrow and transaction must be this listing's accounts
+    let escrow = Account::<Escrow>::try_from(escrow_info)?;
+    require_keys_eq!(escrow.listing, listing_key, AppMarketError::InvalidBatchAccounts);
+    let (transaction_pda, transaction_bump) = Pubkey::find_program_address(
+        &[b"transaction", listing_key.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(transaction_info.key(), transaction_pda, AppMarketError::InvalidBatchAccounts);
+
+    Ok((listing, escrow, transaction_bump))
+}
+
+/// Create the Transaction for a validated batch auction item, pay any crank tip and
+/// persist all accounts. Callers must finish validation first: this performs CPIs.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn complete_auction_settlement<'info>(
+    mut listing: Account<'info, Listing>,
+    mut escrow: Account<'info, Escrow>,
+    transaction_info: &'info AccountInfo<'info>,
+    transaction_bump: u8,
+    payer: AccountInfo<'info>,
+    pay_tip: bool,
+    system_program: AccountInfo<'info>,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    let listing_key = listing.key();
+
+    create_pda_account(
+        payer.clone(),
+        transaction_info.clone(),
+        system_program.clone(),
+        &[b"transaction", listing_key.as_ref(), &[transaction_bump]],
+        8 + Transaction::INIT_SPACE,
+        program_id,
+    )?;
+    transaction_info.try_borrow_mut_data()?[..8].copy_from_slice(Transaction::DISCRIMINATOR);
+    let mut transaction = Account::<Transaction>::try_from(transaction_info)?;
+
+    // Auction successful - create transaction
+    populate_auction_transaction(&mut transaction, &listing, listing_key, now)?;
+    set_listing_status(&mut listing, sold_listing_status(&transaction), payer.key(), now)?;
+    transaction.version = ACCOUNT_LAYOUT_VERSION;
+    transaction.bump = transaction_bump;
+
+    if pay_tip {
+        let tip = pay_settlement_tip(
+            &mut transaction,
+            &mut escrow,
+            listing_key,
+            payer.clone(),
+            system_program,
+        )?;
+
+        emit!(SettlementCranked {
+            listing: listing_key,
+            cranker: payer.key(),
+            tip,
+            actor: payer.key(),
+            timestamp: now,
+        });
+    }
+
+    listing.exit(program_id)?;
+    escrow.exit(program_id)?;
+    transaction.exit(program_id)?;
+
+    emit!(SaleCompleted {
+        listing: listing_key,
+        transaction: transaction.key(),
+        buyer: transaction.buyer,
+        payer: transaction.payer,
+        seller: listing.seller,
+        amount: listing.current_bid,
+        actor: payer.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Move the winning bid of a bid-escrow auction from the winner's BidEscrow into the
+/// listing escrow, where settlement expects it. No-op for other listings or once committed.
+pub(crate) fn commit_winning_bid_funds(
+    listing: &Account<Listing>,
+    escrow: &mut Account<Escrow>,
+    bid_escrow: Option<&mut Account<BidEscrow>>,
+    actor: Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !listing.uses_bid_escrows {
+        return Ok(());
+    }
+    let bid_escrow = bid_escrow.ok_or(AppMarketError::BidEscrowRequired)?;
+    require_keys_eq!(bid_escrow.listing, listing.key(), AppMarketError::InvalidBidEscrow);
+    require!(
+        listing.current_bidder == Some(bid_escrow.bidder),
+        AppMarketError::InvalidBidEscrow
+    );
+    if bid_escrow.committed {
+        return Ok(());
+    }
+
+    let amount = listing.current_bid;
+    bid_escrow.amount = bid_escrow.amount
+        .checked_sub(amount)
+        .ok_or(AppMarketError::InsufficientEscrowBalance)?;
+    bid_escrow.committed = true;
+    bid_escrow.sub_lamports(amount)?;
+    escrow.add_lamports(amount)?;
+    escrow.credit(amount, EscrowChangeReason::BidEscrowCommitted, actor)?;
+
+    emit!(WinningBidCommitted {
+        listing: listing.key(),
+        bidder: bid_escrow.bidder,
+        bid_escrow: bid_escrow.key(),
+        amount,
+        actor,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Validate one settle_due_auctions item: due once the settlement grace has passed.
+/// Returns None if the item is not due; the crank reports errors per item.
+pub(crate) fn check_due_auction<'info>(
+    listing_info: &'info AccountInfo<'info>,
+    escrow_info: &'info AccountInfo<'info>,
+    transaction_info: &'info AccountInfo<'info>,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<Option<AuctionBatchItem<'info>>> {
+    let (listing, escrow, transaction_bump) =
+        load_auction_batch_item(listing_info, escrow_info, transaction_info, program_id)?;
+
+    // Saturating: open-ended listings in a batch are skipped, not an overflow error
+    let grace_end = listing.end_time.saturating_add(SETTLEMENT_GRACE_PERIOD_SECONDS);
+    let due = listing.status == ListingStatus::Active
+        && listing.listing_type == ListingType::Auction
+        && listing.auction_started
+        && listing.current_bidder.is_some()
+        && now >= grace_end
+        && transaction_info.data_is_empty()
+        // Bid-escrow auctions settle here only once the winning bid is committed
+        && (!listing.uses_bid_escrows || escrow.amount >= listing.current_bid);
+    if !due {
+        return Ok(None);
+    }
+
+    // Tip must be payable before any account is created
+    check_settlement_tip(&listing, &escrow)?;
+
+    Ok(Some((listing, escrow, transaction_bump)))
+}
+
+/// Validate one settle_auctions_batch item with the same rules as settle_auction.
+/// Returns whether the caller settles as a permissionless cranker (earns the tip).
+pub(crate) fn check_batch_settlement(
+    listing: &Listing,
+    escrow: &Account<Escrow>,
+    transaction_info: &AccountInfo,
+    caller: Pubkey,
+    admin: Pubkey,
+    now: i64,
+) -> Result<bool> {
+    require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+    require!(
+        listing.listing_type == ListingType::Auction,
+        AppMarketError::NotAnAuction
+    );
+    if listing.auction_started {
+        require!(now >= listing.end_time, AppMarketError::AuctionNotEnded);
+    }
+
+    let is_seller = caller == listing.seller;
+    let is_winner = listing.current_bidder == Some(caller);
+    let is_cranker = !(is_seller || is_winner || caller == admin);
+    if is_cranker {
+        let grace_end = listing.end_time
+            .checked_add(SETTLEMENT_GRACE_PERIOD_SECONDS)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            listing.auction_started && now >= grace_end,
+            AppMarketError::UnauthorizedSettlement
+        );
+    }
+
+    require!(listing.current_bidder.is_some(), AppMarketError::NoBidsToSettle);
+    require!(transaction_info.data_is_empty(), AppMarketError::InvalidBatchAccounts);
+    require!(
+        !listing.uses_bid_escrows || escrow.amount >= listing.current_bid,
+        AppMarketError::WinningBidNotCommitted
+    );
+
+    // Tip must be payable before any account is created
+    if is_cranker {
+        check_settlement_tip(listing, escrow)?;
+    }
+
+    Ok(is_cranker)
+}
+
+/// Fail unless the escrow can pay the settlement tip on top of its rent
+pub(crate) fn check_settlement_tip(listing: &Listing, escrow: &Account<Escrow>) -> Result<()> {
+    let platform_fee = listing.current_bid
+        .checked_mul(listing.platform_fee_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let tip = settlement_tip(platform_fee)?;
+    let escrow_info = escrow.to_account_info();
+    let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+    require!(
+        escrow_info.lamports() >= tip + rent && escrow.amount >= tip,
+        AppMarketError::InsufficientEscrowBalance
+    );
+    Ok(())
+}
+
+/// Numeric code for a per-item failure event (custom program errors keep their code).
+pub(crate) fn error_code_number(err: &Error) -> u32 {
+    match err {
+        Error::AnchorError(e) => e.error_code_number,
+        Error::ProgramError(e) => match e.program_error {
+            ProgramError::Custom(code) => code,
+            // Builtin program errors are encoded in the upper 32 bits
+            ref other => (u64::from(other.clone()) >> 32) as u32,
+        },
+    }
+}
+
+/// Expire and refund one offer for expire_due_offers. Returns false if not expirable.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn expire_due_offer<'info>(
+    listing_info: &'info AccountInfo<'info>,
+    offer_info: &'info AccountInfo<'info>,
+    offer_escrow_info: &'info AccountInfo<'info>,
+    buyer_info: &'info AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    program_id: &Pubkey,
+    actor: Pubkey,
+    now: i64,
+) -> Result<bool> {
+    let mut offer = Account::<Offer>::try_from(offer_info)?;
+    if offer.status != OfferStatus::Active || now <= offer.deadline {
+        return Ok(false);
+    }
+
+    // SECURITY: Verify account relationships (refund only ever goes to offer.buyer)
+    require_keys_eq!(offer.listing, listing_info.key(), AppMarketError::InvalidOffer);
+    require_keys_eq!(offer.buyer, buyer_info.key(), AppMarketError::InvalidBuyer);
+    let offer_escrow = Account::<OfferEscrow>::try_from(offer_escrow_info)?;
+    require_keys_eq!(offer_escrow.offer, offer.key(), AppMarketError::InvalidBatchAccounts);
+    let mut listing = Account::<Listing>::try_from(listing_info)?;
+
+    // SECURITY: Validate escrow balance
+    let escrow_balance = offer_escrow_info.lamports();
+    let rent = Rent::get()?.minimum_balance(offer_escrow_info.data_len());
+    require!(
+        escrow_balance >= offer.amount + rent,
+        AppMarketError::InsufficientEscrowBalance
+    );
+
+    // EFFECTS
+    offer.status = OfferStatus::Expired;
+    if let Some(last_buyer) = listing.last_offer_buyer {
+        if last_buyer == offer.buyer && listing.consecutive_offer_count > 0 {
+            listing.consecutive_offer_count = listing.consecutive_offer_count.saturating_sub(1);
+        }
+    }
+    if listing.drop_offer(offer.key()) {
+        emit_best_offer_changed(&listing, actor, now);
+    }
+
+    // INTERACTIONS: Refund buyer, then close offer escrow (rent to buyer)
+    let offer_key = offer.key();
+    let seeds = &[
+        b"offer_escrow",
+        offer_key.as_ref(),
+        &[offer_escrow.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        system_program,
+        anchor_lang::system_program::Transfer {
+            from: offer_escrow_info.clone(),
+            to: buyer_info.clone(),
+        },
+        signer,
+    );
+    anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+    offer_escrow.close(buyer_info.clone())?;
+
+    offer.exit(program_id)?;
+    listing.exit(program_id)?;
+
+    emit!(OfferExpired {
+        offer: offer_key,
+        listing: listing_info.key(),
+        buyer: offer.buyer,
+        actor,
+        timestamp: now,
+    });
+
+    Ok(true)
+}
+
+/// Rewards vault balance available to stakers (everything above its rent-exempt minimum)
+pub(crate) fn rewards_available(rewards_vault: &AccountInfo) -> Result<u64> {
+    let vault_rent = Rent::get()?.minimum_balance(0);
+    Ok(rewards_vault.lamports().saturating_sub(vault_rent))
+}
+
+/// Convert a lamport-denominated amount to the same whole-unit amount at `decimals`
+/// (never below one raw unit)
+pub(crate) fn scale_native_amount(lamports: u64, decimals: u8) -> Result<u64> {
+    let native = 10u128.pow(NATIVE_DECIMALS as u32);
+    let target = 10u128
+        .checked_pow(decimals as u32)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let scaled = (lamports as u128)
+        .checked_mul(target)
+        .ok_or(AppMarketError::MathOverflow)?
+        / native;
+    Ok(u64::try_from(scaled).map_err(|_| AppMarketError::MathOverflow)?.max(1))
+}
+
+/// Price, duration and type rules shared by create_listing and create_listings_batch
+pub(crate) fn validate_listing_terms(
+    listing_type: &ListingType,
+    starting_price: u64,
+    reserve_price: Option<u64>,
+    buy_now_price: Option<u64>,
+    duration_seconds: i64,
+    deposit_bps: Option<u64>,
+) -> Result<()> {
+    require!(starting_price > 0, AppMarketError::InvalidPrice);
+    check_listing_duration(listing_type, duration_seconds)?;
+
+    // Validate listing type requirements
+    match listing_type {
+        ListingType::Auction => {
+            // Auction with reserve: bids may open below it but it must be met to sell
+            if let Some(reserve) = reserve_price {
+                require!(
+                    starting_price <= reserve,
+                    AppMarketError::StartingPriceAboveReserve
+                );
+            }
+            // ENHANCEMENT: Auctions can have buy_now_price for instant purchase during bidding
+            // If someone hits buy_now during auction, they win immediately
+            if let Some(price) = buy_now_price {
+                require!(price > starting_price, AppMarketError::BuyNowBelowCurrentBid);
+            }
+
+            // Deposit mode: bids lock only a percentage, winner pays remainder later
+            if let Some(bps) = deposit_bps {
+                require!(
+                    (MIN_DEPOSIT_BPS..BASIS_POINTS_DIVISOR).contains(&bps),
+                    AppMarketError::InvalidDepositBps
+                );
+            }
+        },
+        ListingType::BuyNow => {
+            require!(
+                buy_now_price.is_some(),
+                AppMarketError::BuyNowPriceRequired
+            );
+            require!(
+                deposit_bps.is_none(),
+                AppMarketError::DepositModeAuctionOnly
+            );
+            // Note: BuyNow can also have reserve_price for dual listing functionality
+        },
+        ListingType::OpenOffers => {
+            // Asking price only; buy_now_price is optional
+            require!(
+                reserve_price.is_none(),
+                AppMarketError::OpenOffersReserveNotAllowed
+            );
+            require!(
+                deposit_bps.is_none(),
+                AppMarketError::DepositModeAuctionOnly
+            );
+        },
+    }
+
+    Ok(())
+}
+
+/// Alternative currencies: up to 4 distinct mints other than the primary one, each priced
+pub(crate) fn validate_accepted_currencies(
+    listing: &Listing,
+    currencies: &[CurrencyPrice],
+) -> Result<()> {
+    require!(
+        currencies.len() <= MAX_ACCEPTED_CURRENCIES,
+        AppMarketError::InvalidAcceptedCurrencies
+    );
+    for (i, currency) in currencies.iter().enumerate() {
+        require!(
+            currency.price > 0
+                && currency.mint != listing.payment_currency()
+                // APP payments need the SPL path, which purchases do not have
+                && currency.mint != APP_TOKEN_MINT
+                && !currencies[..i].iter().any(|c| c.mint == currency.mint),
+            AppMarketError::InvalidAcceptedCurrencies
+        );
+    }
+    Ok(())
+}
+
+/// Sale windows run 1 second to 30 days; OpenOffers listings have none (duration 0)
+pub(crate) fn check_listing_duration(listing_type: &ListingType, duration_seconds: i64) -> Result<()> {
+    let valid = match listing_type {
+        ListingType::OpenOffers => duration_seconds == 0,
+        _ => duration_seconds > 0 && duration_seconds <= MAX_AUCTION_DURATION_SECONDS,
+    };
+    require!(valid, AppMarketError::InvalidDuration);
+    Ok(())
+}
+
+/// Listing tags: at most 5 distinct, 1-24 chars of lowercase a-z, 0-9 or '-'
+/// (normalized on-chain so indexers can match them exactly)
+pub(crate) fn validate_listing_tags(tags: &[String]) -> Result<()> {
+    require!(tags.len() <= MAX_LISTING_TAGS, AppMarketError::InvalidListingTag);
+    for (i, tag) in tags.iter().enumerate() {
+        require!(
+            !tag.is_empty() && tag.len() <= MAX_TAG_LEN,
+            AppMarketError::InvalidListingTag
+        );
+        require!(
+            tag.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'),
+            AppMarketError::InvalidListingTag
+        );
+        require!(!tags[..i].contains(tag), AppMarketError::InvalidListingTag);
+    }
+    Ok(())
+}
+
+/// Copy the parties' contact keys from their profiles onto a transaction
+pub(crate) fn apply_contact_keys(
+    transaction: &mut Transaction,
+    buyer_profile: Option<&UserProfile>,
+    seller_profile: Option<&UserProfile>,
+) -> Result<()> {
+    if let Some(profile) = buyer_profile {
+        require_keys_eq!(profile.user, transaction.buyer, AppMarketError::InvalidUserProfile);
+        transaction.buyer_contact_key = profile.contact_key;
+    }
+    if let Some(profile) = seller_profile {
+        require_keys_eq!(profile.user, transaction.seller, AppMarketError::InvalidUserProfile);
+        transaction.seller_contact_key = profile.contact_key;
+    }
+    Ok(())
+}
+
+/// Offer validations and listing-side spam counters shared by make_offer and
+/// make_offer_on_expired (the caller checks listing status)
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn register_offer(
+    config: &MarketConfig,
+    listing: &mut Listing,
+    buyer: Pubkey,
+    amount: u64,
+    deadline: i64,
+    offer_seed: u64,
+    recipient: Option<Pubkey>,
+    now: i64,
+) -> Result<()> {
+    require!(amount > 0, AppMarketError::InvalidPrice);
+    require!(amount >= listing.min_offer, AppMarketError::OfferBelowMinimum);
+    require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
+    // SECURITY: Bounded lifetimes: no event-spamming blink offers, no offers pinning
+    // the consecutive-offer counters for years
+    let (min_duration, max_duration) = config.offer_duration_bounds();
+    let lifetime = deadline.saturating_sub(now);
+    require!(
+        (min_duration..=max_duration).contains(&lifetime),
+        AppMarketError::InvalidDeadline
+    );
+    require!(
+        buyer != listing.seller,
+        AppMarketError::SellerCannotOffer
+    );
+
+    // Optional third-party recipient (chosen by the offerer, not the accepting seller)
+    if let Some(recipient) = recipient {
+        require!(recipient != Pubkey::default(), AppMarketError::InvalidRecipient);
+        require!(recipient != listing.seller, AppMarketError::InvalidRecipient);
+    }
+
+    // SECURITY: Prevent DoS via total offer spam
+    require!(
+        listing.offer_count < MAX_OFFERS_PER_LISTING,
+        AppMarketError::MaxOffersExceeded
+    );
+
+    // SECURITY: Check consecutive offers from same buyer (max 10 if no one else is outbidding)
+    if let Some(last_buyer) = listing.last_offer_buyer {
+        if last_buyer == buyer {
+            // Same buyer making consecutive offers
+            require!(
+                listing.consecutive_offer_count < MAX_CONSECUTIVE_OFFERS,
+                AppMarketError::MaxConsecutiveOffersExceeded
+            );
+            // Increment consecutive counter
+            listing.consecutive_offer_count = listing.consecutive_offer_count
+                .checked_add(1)
+                .ok_or(AppMarketError::MathOverflow)?;
+        } else {
+            // Different buyer - reset consecutive counter
+            listing.last_offer_buyer = Some(buyer);
+            listing.consecutive_offer_count = 1;
+        }
+    } else {
+        // First offer on this listing
+        listing.last_offer_buyer = Some(buyer);
+        listing.consecutive_offer_count = 1;
+    }
+
+    // SECURITY: Validate offer_seed matches current counter (prevents arbitrary seeds)
+    require!(
+        offer_seed == listing.offer_count as u64,
+        AppMarketError::InvalidOfferSeed
+    );
+
+    // Increment total offer counter
+    listing.offer_count = listing.offer_count
+        .checked_add(1)
+        .ok_or(AppMarketError::MathOverflow)?;
+    Ok(())
+}
+
+/// Fill in the Transaction for an accepted offer (fees locked on the listing, bump left to the caller)
+pub(crate) fn populate_offer_transaction(
+    transaction: &mut Transaction,
+    listing: &Listing,
+    listing_key: Pubkey,
+    offer: &Offer,
+    agreement_hash: Option<[u8; 32]>,
+    now: i64,
+) -> Result<()> {
+    transaction.listing = listing_key;
+    transaction.seller = listing.seller;
+    transaction.buyer = offer.recipient.unwrap_or(offer.buyer);
+    transaction.payer = offer.buyer;
+    transaction.agreement_hash = agreement_hash;
+    transaction.sale_price = offer.amount;
+
+    // SECURITY: Use LOCKED fees from listing
+    transaction.platform_fee = offer.amount
+        .checked_mul(listing.platform_fee_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.seller_proceeds = offer.amount
+        .checked_sub(transaction.platform_fee)
+        .ok_or(AppMarketError::MathOverflow)?;
+
+    transaction.status = TransactionStatus::InEscrow;
+    transaction.transfer_deadline = now
+        .checked_add(TRANSFER_DEADLINE_SECONDS)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.created_at = now;
+    transaction.seller_confirmed_transfer = false;
+    transaction.seller_confirmed_at = None;
+    transaction.completed_at = None;
+    transaction.vesting_duration = (listing.vesting_days as i64)
+        .checked_mul(SECONDS_PER_DAY)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.vesting_start = None;
+    transaction.vested_claimed = 0;
+    transaction.arbitrator = listing.arbitrator;
+    transaction.version = ACCOUNT_LAYOUT_VERSION;
+    Ok(())
+}
+
+/// Listings with requires_verification need a live backend attestation for the buyer
+pub(crate) fn check_buyer_attestation(
+    listing: &Listing,
+    attestation: Option<&BuyerAttestation>,
+    buyer: Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !listing.requires_verification {
+        return Ok(());
+    }
+    let attestation = attestation.ok_or(AppMarketError::BuyerNotVerified)?;
+    require_keys_eq!(attestation.buyer, buyer, AppMarketError::BuyerNotVerified);
+    require!(now < attestation.expires_at, AppMarketError::BuyerNotVerified);
+    Ok(())
+}
+
+/// Platform fee to lock into a new listing: the FeeSchedule rate for its category
+/// while a schedule exists, otherwise the flat config rate
+pub(crate) fn category_platform_fee_bps(
+    config: &MarketConfig,
+    schedule: Option<&FeeSchedule>,
+    category: &Category,
+) -> Result<u16> {
+    if !config.has_fee_schedule {
+        return Ok(config.platform_fee_bps);
+    }
+    let schedule = schedule.ok_or(AppMarketError::FeeScheduleRequired)?;
+    // Category rates are capped at MAX_PLATFORM_FEE_BPS when set
+    Ok(schedule.fee_bps(category).map_or(config.platform_fee_bps, |bps| bps as u16))
+}
+
+/// Release a refunded bid's exposure from the user's profile. The profile is required
+/// whenever the withdrawal counted exposure, so a caller cannot leave it locked.
+pub(crate) fn release_withdrawal_exposure(
+    withdrawal: &PendingWithdrawal,
+    profile: Option<&mut UserProfile>,
+) -> Result<()> {
+    if withdrawal.exposure == 0 {
+        return Ok(());
+    }
+    let profile = profile.ok_or(AppMarketError::UserProfileRequired)?;
+    profile.locked_exposure = profile.locked_exposure.saturating_sub(withdrawal.exposure);
+    Ok(())
+}
+
+/// Pending withdrawal PDA for `withdrawal_id`. A client-supplied bump costs one hash instead
+/// of find_program_address's bump search (callers passing None still search).
+/// The supplied bump is not checked to be canonical: any bump that derives a valid PDA is
+/// accepted and stored on the withdrawal, which later instructions verify against. Each
+/// withdrawal_id is used once, so this cannot create two withdrawals for the same refund,
+/// but a non-canonical address will not be found by clients deriving it with
+/// findProgramAddress (they must read it from WithdrawalCreated instead)
+pub(crate) fn withdrawal_pda(
+    listing: &Pubkey,
+    withdrawal_id: u64,
+    bump: Option<u8>,
+    program_id: &Pubkey,
+) -> Result<(Pubkey, u8)> {
+    let id_bytes = withdrawal_id.to_le_bytes();
+    match bump {
+        Some(bump) => {
+            let pda = Pubkey::create_program_address(
+                &[b"withdrawal", listing.as_ref(), &id_bytes, &[bump]],
+                program_id,
+            )
+            .map_err(|_| AppMarketError::InvalidWithdrawalBump)?;
+            Ok((pda, bump))
+        }
+        None => Ok(Pubkey::find_program_address(
+            &[b"withdrawal", listing.as_ref(), &id_bytes],
+            program_id,
+        )),
+    }
+}
+
+/// Read an account in its old layout, resize it to the current space and write it back
+pub(crate) fn migrate_layout<'info, Old, New>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()>
+where
+    Old: AnchorDeserialize,
+    New: From<Old> + AccountSerialize + Space,
+{
+    let old = Old::deserialize(&mut &target.try_borrow_data()?[8..])?;
+    let space = 8 + New::INIT_SPACE;
+
+    top_up_rent(target, payer, system_program, space)?;
+    target.resize(space)?;
+    New::from(old).try_serialize(&mut &mut target.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+/// Insert the version byte right after the discriminator of a v0 account (grows it by one byte)
+pub(crate) fn insert_version_byte<'info>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let old_len = target.data_len();
+    top_up_rent(target, payer, system_program, 8 + space)?;
+    target.resize(8 + space)?;
+
+    let mut data = target.try_borrow_mut_data()?;
+    data.copy_within(8..old_len, 9);
+    data[8] = ACCOUNT_LAYOUT_VERSION;
+    Ok(())
+}
+
+/// Fund a program account up to the rent-exempt minimum for `space` bytes
+pub(crate) fn top_up_rent<'info>(
+    target: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let shortfall = rent.saturating_sub(target.lamports());
+    if shortfall > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: target.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, shortfall)?;
+    }
+    Ok(())
+}
+
+/// Current INIT_SPACE of the v0 accounts whose only layout change is the version byte
+pub(crate) fn version_byte_space(discriminator: &[u8]) -> Option<usize> {
+    [
+        (Escrow::DISCRIMINATOR, Escrow::INIT_SPACE),
+        (OfferEscrow::DISCRIMINATOR, OfferEscrow::INIT_SPACE),
+    ]
+    .into_iter()
+    .find(|(known, _)| *known == discriminator)
+    .map(|(_, space)| space)
+}
+
+/// Enforce the global max_sale_price unless the seller's override covers the price
+pub(crate) fn check_price_cap(
+    config: &MarketConfig,
+    price_override: Option<&PriceCapOverride>,
+    price: u64,
+) -> Result<()> {
+    let cap = match config.max_sale_price {
+        Some(cap) => cap,
+        None => return Ok(()),
+    };
+    if price <= cap {
+        return Ok(());
+    }
+    let allowed = price_override.map(|o| o.max_price).unwrap_or(0);
+    require!(price <= allowed, AppMarketError::PriceAboveCap);
+    Ok(())
+}
+
+/// Fee split recipients: 1-5 distinct wallets whose shares total exactly 100%
+pub(crate) fn validate_fee_split(recipients: &[FeeRecipient]) -> Result<()> {
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_FEE_SPLIT_RECIPIENTS,
+        AppMarketError::InvalidFeeSplit
+    );
+
+    let mut total_bps: u64 = 0;
+    for (i, recipient) in recipients.iter().enumerate() {
+        require!(
+            recipient.bps > 0 && recipient.wallet != Pubkey::default(),
+            AppMarketError::InvalidFeeSplit
+        );
+        require!(
+            recipients[..i].iter().all(|other| other.wallet != recipient.wallet),
+            AppMarketError::InvalidFeeSplit
+        );
+        total_bps = total_bps
+            .checked_add(recipient.bps)
+            .ok_or(AppMarketError::MathOverflow)?;
+    }
+    require!(
+        total_bps == BASIS_POINTS_DIVISOR,
+        AppMarketError::InvalidFeeSplit
+    );
+
+    Ok(())
+}
+
+/// Pay seller proceeds out of escrow. With a payout split, each recipient receives its bps
+/// share (recipient wallets passed in split order as remaining accounts) and the payout
+/// wallet receives the rest.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_seller_proceeds<'info>(
+    escrow: AccountInfo<'info>,
+    payout_wallet: AccountInfo<'info>,
+    listing: &Listing,
+    payout_split: Option<&PayoutSplit>,
+    split_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    system_program: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    // SECURITY: A listing's split cannot be bypassed by omitting it
+    require!(
+        !listing.has_payout_split || payout_split.is_some(),
+        AppMarketError::PayoutSplitRequired
+    );
+
+    let mut remainder = amount;
+    if let Some(split) = payout_split {
+        require!(
+            split_accounts.len() >= split.recipients.len(),
+            AppMarketError::InvalidSplitRecipient
+        );
+        for (recipient, recipient_info) in split.recipients.iter().zip(split_accounts) {
+            require_keys_eq!(
+                recipient_info.key(),
+                recipient.wallet,
+                AppMarketError::InvalidSplitRecipient
+            );
+            let share = amount
+                .checked_mul(recipient.bps)
+                .ok_or(AppMarketError::MathOverflow)?
+                .checked_div(BASIS_POINTS_DIVISOR)
+                .ok_or(AppMarketError::MathOverflow)?;
+            if share == 0 {
+                continue;
+            }
+            let cpi_ctx = CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: escrow.clone(),
+                    to: recipient_info.clone(),
+                },
+                signer,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, share)?;
+            remainder = remainder
+                .checked_sub(share)
+                .ok_or(AppMarketError::MathOverflow)?;
+        }
+    }
+
+    if remainder > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            anchor_lang::system_program::Transfer {
+                from: escrow,
+                to: payout_wallet,
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, remainder)?;
+    }
+
+    Ok(())
+}
+
+/// Hold back the listing's warranty share of seller proceeds at completion.
+/// Returns the amount held (0 if the listing has no holdback).
+pub(crate) fn start_holdback(
+    transaction: &mut Transaction,
+    transaction_key: Pubkey,
+    holdback_bps: u16,
+    actor: Pubkey,
+    now: i64,
+) -> Result<u64> {
+    let holdback = transaction.seller_proceeds
+        .checked_mul(holdback_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    if holdback == 0 {
+        return Ok(0);
+    }
+
+    let release_at = now
+        .checked_add(WARRANTY_PERIOD_SECONDS)
+        .ok_or(AppMarketError::MathOverflow)?;
+    transaction.holdback_amount = holdback;
+    transaction.holdback_release_at = Some(release_at);
+
+    emit!(HoldbackStarted {
+        transaction: transaction_key,
+        seller: transaction.seller,
+        amount: holdback,
+        release_at,
+        actor,
+        timestamp: now,
+    });
+
+    Ok(holdback)
+}
+
+/// Pay a platform fee out of escrow: the configured insurance slice goes to the
+/// insurance vault (required whenever the slice is non-zero), the rest to the treasury
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_platform_fee<'info>(
+    escrow: AccountInfo<'info>,
+    treasury: AccountInfo<'info>,
+    insurance_vault: Option<AccountInfo<'info>>,
+    insurance_fee_bps: u16,
+    fee: u64,
+    actor: Pubkey,
+    system_program: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let (insurance_cut, treasury_cut) = split_insurance_cut(fee, insurance_fee_bps)?;
+
+    if insurance_cut > 0 {
+        let insurance_vault = insurance_vault.ok_or(AppMarketError::InsuranceVaultRequired)?;
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: escrow.clone(),
+                to: insurance_vault,
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, insurance_cut)?;
+
+        emit!(InsuranceFunded {
+            amount: insurance_cut,
+            actor,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    if treasury_cut > 0 {
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            anchor_lang::system_program::Transfer {
+                from: escrow,
+                to: treasury,
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, treasury_cut)?;
+    }
+
+    Ok(())
+}
+
+/// Split a platform fee into (insurance fund slice, treasury remainder)
+pub(crate) fn split_insurance_cut(fee: u64, insurance_fee_bps: u16) -> Result<(u64, u64)> {
+    let insurance_cut = fee
+        .checked_mul(insurance_fee_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let treasury_cut = fee
+        .checked_sub(insurance_cut)
+        .ok_or(AppMarketError::MathOverflow)?;
+    Ok((insurance_cut, treasury_cut))
+}
+
+/// A release_due_transactions item that passed validation
+pub(crate) struct DueRelease<'info> {
+    pub listing: Account<'info, Listing>,
+    pub transaction: Account<'info, Transaction>,
+    pub escrow: Account<'info, Escrow>,
+    pub buyer_profile: Option<Account<'info, UserProfile>>,
+    pub seller_profile: Option<Account<'info, UserProfile>>,
+}
+
+/// Load an optional user profile from a crank group (the program id stands in for none)
+fn load_batch_profile<'info>(
+    profile_info: &'info AccountInfo<'info>,
+    user: Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<Account<'info, UserProfile>>> {
+    if profile_info.key() == *program_id {
+        return Ok(None);
+    }
+    require!(profile_info.is_writable, AppMarketError::InvalidBatchAccounts);
+    let profile = Account::<UserProfile>::try_from(profile_info)?;
+    require_keys_eq!(profile.user, user, AppMarketError::InvalidBatchAccounts);
+    Ok(Some(profile))
+}
+
+/// Validate one release_due_transactions item with the finalize_transaction conditions.
+/// Returns None if the crank does not release it; the crank reports errors per item.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn check_due_release<'info>(
+    listing_info: &'info AccountInfo<'info>,
+    transaction_info: &'info AccountInfo<'info>,
+    escrow_info: &'info AccountInfo<'info>,
+    seller_info: &'info AccountInfo<'info>,
+    buyer_profile_info: &'info AccountInfo<'info>,
+    seller_profile_info: &'info AccountInfo<'info>,
+    volume_stats_mint: Option<Pubkey>,
+    insurance_fee_bps: u16,
+    has_insurance_vault: bool,
+    program_id: &Pubkey,
+    now: i64,
+) -> Result<Option<DueRelease<'info>>> {
+    let transaction = Account::<Transaction>::try_from(transaction_info)?;
+
+    // Same conditions as finalize_transaction; staked escrows go through finalize_transaction,
+    // which takes the stake unwind accounts
+    let releasable = transaction.status == TransactionStatus::InEscrow
+        && !transaction.escrow_staked
+        && transaction.seller_confirmed_transfer
+        && transaction.uploads_verified
+        && transaction.seller_confirmed_at
+            .map(|confirmed_at| now >= confirmed_at + FINALIZE_GRACE_PERIOD)
+            .unwrap_or(false);
+    if !releasable {
+        return Ok(None);
+    }
+
+    // SECURITY: Verify account relationships
+    let listing_key = listing_info.key();
+    require_keys_eq!(transaction.listing, listing_key, AppMarketError::InvalidBatchAccounts);
+    let listing = Account::<Listing>::try_from(listing_info)?;
+    if listing.has_payout_split {
+        return Ok(None);
+    }
+    // Only sales in the currency of the supplied VolumeStats are released
+    if volume_stats_mint != Some(listing.payment_currency()) {
+        return Ok(None);
+    }
+    require_keys_eq!(listing.payout_wallet, seller_info.key(), AppMarketError::InvalidPayoutWallet);
+    let escrow = Account::<Escrow>::try_from(escrow_info)?;
+    require_keys_eq!(escrow.listing, listing_key, AppMarketError::InvalidBatchAccounts);
+
+    // SECURITY: Validate escrow balance
+    let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+    let required_balance = transaction.platform_fee
+        .checked_add(transaction.seller_proceeds)
+        .ok_or(AppMarketError::MathOverflow)?;
+    require!(
+        escrow_info.lamports() >= required_balance + rent && escrow.amount >= required_balance,
+        AppMarketError::InsufficientEscrowBalance
+    );
+    let (insurance_cut, _) = split_insurance_cut(transaction.platform_fee, insurance_fee_bps)?;
+    require!(
+        insurance_cut == 0 || has_insurance_vault,
+        AppMarketError::InsuranceVaultRequired
+    );
+
+    let buyer_profile = load_batch_profile(buyer_profile_info, transaction.buyer, program_id)?;
+    let seller_profile = load_batch_profile(seller_profile_info, transaction.seller, program_id)?;
+
+    Ok(Some(DueRelease {
+        listing,
+        transaction,
+        escrow,
+        buyer_profile,
+        seller_profile,
+    }))
+}
+
+/// Release a validated release_due_transactions item and persist its accounts.
+/// Callers must finish validation first (check_due_release): this performs CPIs.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn complete_due_release<'info>(
+    item: DueRelease<'info>,
+    seller_info: &'info AccountInfo<'info>,
+    config: &mut Account<'info, MarketConfig>,
+    volume_stats: &mut Account<'info, VolumeStats>,
+    treasury: AccountInfo<'info>,
+    insurance_vault: Option<AccountInfo<'info>>,
+    system_program: AccountInfo<'info>,
+    program_id: &Pubkey,
+    actor: Pubkey,
+    now: i64,
+) -> Result<()> {
+    let DueRelease {
+        mut listing,
+        mut transaction,
+        mut escrow,
+        mut buyer_profile,
+        mut seller_profile,
+    } = item;
+    let listing_key = listing.key();
+    let escrow_info = escrow.to_account_info();
+
+    let seeds = &[
+        b"escrow",
+        listing_key.as_ref(),
+        &[escrow.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Platform fee to treasury (less the insurance fund slice)
+    pay_platform_fee(
+        escrow_info.clone(),
+        treasury,
+        insurance_vault,
+        config.insurance_fee_bps,
+        transaction.platform_fee,
+        actor,
+        system_program.clone(),
+        signer,
+    )?;
+    escrow.debit(transaction.platform_fee, EscrowChangeReason::PlatformFee, actor)?;
+
+    // Seller proceeds to seller (vesting: proceeds stay escrowed and stream out via claims)
+    if transaction.vesting_duration > 0 {
+        transaction.vesting_start = Some(now);
+
+        emit!(ProceedsVestingStarted {
+            transaction: transaction.key(),
+            seller: transaction.seller,
+            amount: transaction.seller_proceeds,
+            vesting_end: now
+                .checked_add(transaction.vesting_duration)
+                .ok_or(AppMarketError::MathOverflow)?,
+            actor,
+            timestamp: now,
+        });
+    } else {
+        // Warranty holdback stays escrowed; the rest is paid now
+        let transaction_key = transaction.key();
+        let holdback =
+            start_holdback(&mut transaction, transaction_key, listing.holdback_bps, actor, now)?;
+        let payout = transaction.seller_proceeds
+            .checked_sub(holdback)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program,
+            anchor_lang::system_program::Transfer {
+                from: escrow_info,
+                to: seller_info.clone(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, payout)?;
+        escrow.debit(payout, EscrowChangeReason::SellerPayout, actor)?;
+    }
+
+    transaction.status = TransactionStatus::Completed;
+    transaction.completed_at = Some(now);
+    set_sale_status(&mut listing, &transaction, ListingStatus::Completed, actor, now)?;
+
+    // SECURITY: Use saturating_add for stats
+    config.total_volume = config.total_volume.saturating_add(transaction.sale_price);
+    config.total_sales = config.total_sales.saturating_add(1);
+    config.record_sale_volume(transaction.sale_price, actor)?;
+    volume_stats.record(&transaction, now);
+
+    // Loyalty points for both parties, as in finalize_transaction
+    accrue_loyalty_points(
+        buyer_profile.as_mut(),
+        transaction.sale_price,
+        listing.payment_currency(),
+        config.loyalty_points_per_sol,
+        true,
+        actor,
+        now,
+    )?;
+    accrue_loyalty_points(
+        seller_profile.as_mut(),
+        transaction.sale_price,
+        listing.payment_currency(),
+        config.loyalty_points_per_sol,
+        false,
+        actor,
+        now,
+    )?;
+
+    transaction.exit(program_id)?;
+    escrow.exit(program_id)?;
+    listing.exit(program_id)?;
+    if let Some(profile) = buyer_profile {
+        profile.exit(program_id)?;
+    }
+    if let Some(profile) = seller_profile {
+        profile.exit(program_id)?;
+    }
+
+    emit!(TransactionCompleted {
+        transaction: transaction.key(),
+        seller: transaction.seller,
+        buyer: transaction.buyer,
+        amount: transaction.sale_price,
+        platform_fee: transaction.platform_fee,
+        actor,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Store a proposed dispute resolution as pending (starts the contest timelock).
+/// Shared by the admin proposal path and committee votes reaching quorum.
+pub(crate) fn stage_dispute_resolution(
+    dispute: &mut Account<Dispute>,
+    sale_price: u64,
+    platform_fee: u64,
+    resolution: DisputeResolution,
+    notes: String,
+    actor: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    // SECURITY: Validate partial refund amounts upfront
+    if let DisputeResolution::PartialRefund { buyer_amount, seller_amount }
+        | DisputeResolution::PartialRefundWithFee { buyer_amount, seller_amount, .. } = &resolution
+    {
+        require!(*buyer_amount > 0 || *seller_amount > 0, AppMarketError::InvalidRefundAmounts);
+        let total_refund = (*buyer_amount)
+            .checked_add(*seller_amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            total_refund == sale_price,
+            AppMarketError::PartialRefundMustEqualSalePrice
+        );
+        // The party charged the fee must have a share that covers it
+        split_partial_refund(&resolution, platform_fee, sale_price)?;
+
+        dispute.pending_buyer_amount = Some(*buyer_amount);
+        dispute.pending_seller_amount = Some(*seller_amount);
+    } else {
+        dispute.pending_buyer_amount = None;
+        dispute.pending_seller_amount = None;
+    }
+
+    // Store pending resolution (starts timelock)
+    dispute.pending_resolution = Some(resolution.clone());
+    dispute.pending_resolution_at = Some(timestamp);
+    dispute.contested = false;
+    dispute.sla_default = false;
+    dispute.small_claim_moderator = None;
+    dispute.status = DisputeStatus::UnderReview;
+    dispute.resolution_notes = Some(notes);
+
+    let executable_at = timestamp + dispute.resolution_timelock();
+
+    emit!(DisputeResolutionProposed {
+        dispute: dispute.key(),
+        resolution,
+        buyer_amount: dispute.pending_buyer_amount.unwrap_or(0),
+        seller_amount: dispute.pending_seller_amount.unwrap_or(0),
+        executable_at,
+        actor,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Pay a dispute ruling out of escrow, then split the dispute fee and respondent bond.
+/// Shared by execute_dispute_resolution (no appeal window) and settle_dispute_ruling.
+pub(crate) fn apply_dispute_ruling<'info>(
+    accounts: &mut ExecuteDisputeResolution<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    resolution: &DisputeResolution,
+    now: i64,
+) -> Result<()> {
+    // Staked escrow: redeem it first (its accounts lead remaining_accounts)
+    let (stake_accounts, split_accounts) =
+        take_escrow_stake_accounts(&accounts.transaction, remaining_accounts)?;
+    if let Some(stake_accounts) = stake_accounts {
+        unwind_escrow_stake(
+            stake_accounts,
+            &mut accounts.escrow,
+            &mut accounts.transaction,
+            &accounts.system_program.to_account_info(),
+            accounts.caller.key(),
+            now,
+        )?;
+    }
+
+    // Extract values needed for CPI before taking mutable references
+    let dispute_bump = accounts.dispute.bump;
+    let dispute_fee = accounts.dispute.dispute_fee;
+    let transaction_key = accounts.transaction.key();
+    let sale_price = accounts.transaction.sale_price;
+    let platform_fee = accounts.transaction.platform_fee;
+    let seller_proceeds = accounts.transaction.seller_proceeds;
+
+    // SECURITY: Validate escrow balance before any transfers
+    let escrow_balance = accounts.escrow.to_account_info().lamports();
+    let rent = Rent::get()?.minimum_balance(
+        accounts.escrow.to_account_info().data_len()
+    );
+
+    // Allow dispute resolution even with pending withdrawals — escrow stays open for cleanup
+    require!(
+        accounts.escrow.amount >= sale_price,
+        AppMarketError::InsufficientEscrowBalance
+    );
+
+    let seeds = &[
+        b"escrow",
+        accounts.listing.to_account_info().key.as_ref(),
+        &[accounts.escrow.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    match resolution {
+        DisputeResolution::FullRefund => {
+            require!(
+                escrow_balance >= sale_price + rent,
+                AppMarketError::InsufficientEscrowBalance
+            );
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: accounts.escrow.to_account_info(),
+                    to: accounts.buyer.to_account_info(),
+                },
+                signer,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, sale_price)?;
+
+            accounts.escrow.debit(
+                sale_price,
+                EscrowChangeReason::DisputeRefund,
+                accounts.caller.key(),
+            )?;
+
+            accounts.transaction.status = TransactionStatus::Refunded;
+            set_sale_status(
+                &mut accounts.listing,
+                &accounts.transaction,
+                ListingStatus::Refunded,
+                accounts.caller.key(),
+                now,
+            )?;
+        },
+        DisputeResolution::ReleaseToSeller => {
+            let required_balance = platform_fee
+                .checked_add(seller_proceeds)
+                .ok_or(AppMarketError::MathOverflow)?;
+            require!(
+                escrow_balance >= required_balance + rent,
+                AppMarketError::InsufficientEscrowBalance
+            );
+
+            // Platform fee to treasury (less the insurance fund slice)
+            pay_platform_fee(
+                accounts.escrow.to_account_info(),
+                accounts.treasury.to_account_info(),
+                accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+                accounts.config.insurance_fee_bps,
+                platform_fee,
+                accounts.caller.key(),
+                accounts.system_program.to_account_info(),
+                signer,
+            )?;
+
+            accounts.escrow.debit(
+                platform_fee,
+                EscrowChangeReason::PlatformFee,
+                accounts.caller.key(),
+            )?;
+
+            // Seller proceeds (vesting: proceeds stay escrowed and stream out via claims)
+            let vesting_duration = accounts.transaction.vesting_duration;
+            if vesting_duration > 0 {
+                accounts.transaction.vesting_start = Some(now);
+
+                emit!(ProceedsVestingStarted {
+                    transaction: transaction_key,
+                    seller: accounts.transaction.seller,
+                    amount: seller_proceeds,
+                    vesting_end: now
+                        .checked_add(vesting_duration)
+                        .ok_or(AppMarketError::MathOverflow)?,
+                    actor: accounts.caller.key(),
+                    timestamp: now,
+                });
+            } else {
+                pay_seller_proceeds(
+                    accounts.escrow.to_account_info(),
+                    accounts.payout_wallet.to_account_info(),
+                    &accounts.listing,
+                    accounts.payout_split.as_deref(),
+                    split_accounts,
+                    seller_proceeds,
+                    accounts.system_program.to_account_info(),
+                    signer,
+                )?;
+
+                accounts.escrow.debit(
+                    seller_proceeds,
+                    EscrowChangeReason::SellerPayout,
+                    accounts.caller.key(),
+                )?;
+            }
+
+            accounts.transaction.status = TransactionStatus::Completed;
+            set_sale_status(
+                &mut accounts.listing,
+                &accounts.transaction,
+                ListingStatus::Completed,
+                accounts.caller.key(),
+                now,
+            )?;
+
+            let points_per_sol = accounts.config.loyalty_points_per_sol;
+            accrue_loyalty_points(
+                accounts.buyer_profile.as_mut(),
+                sale_price,
+                accounts.listing.payment_currency(),
+                points_per_sol,
+                true,
+                accounts.caller.key(),
+                now,
+            )?;
+            accrue_loyalty_points(
+                accounts.seller_profile.as_mut(),
+                sale_price,
+                accounts.listing.payment_currency(),
+                points_per_sol,
+                false,
+                accounts.caller.key(),
+                now,
+            )?;
+        },
+        DisputeResolution::PartialRefund { buyer_amount, seller_amount }
+        | DisputeResolution::PartialRefundWithFee { buyer_amount, seller_amount, .. } => {
+            let total_refund = (*buyer_amount)
+                .checked_add(*seller_amount)
+                .ok_or(AppMarketError::MathOverflow)?;
+            require!(
+                escrow_balance >= total_refund + rent,
+                AppMarketError::InsufficientEscrowBalance
+            );
+            let (buyer_payout, seller_payout, fee) =
+                split_partial_refund(resolution, platform_fee, sale_price)?;
+
+            // Transfer to buyer
+            if buyer_payout > 0 {
+                let cpi_ctx = CpiContext::new_with_signer(
+                    accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: accounts.escrow.to_account_info(),
+                        to: accounts.buyer.to_account_info(),
+                    },
+                    signer,
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, buyer_payout)?;
+
+                accounts.escrow.debit(
+                    buyer_payout,
+                    EscrowChangeReason::DisputeRefund,
+                    accounts.caller.key(),
+                )?;
+            }
+
+            // Platform fee on the seller's share, when the ruling charges one
+            if fee > 0 {
+                pay_platform_fee(
+                    accounts.escrow.to_account_info(),
+                    accounts.treasury.to_account_info(),
+                    accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+                    accounts.config.insurance_fee_bps,
+                    fee,
+                    accounts.caller.key(),
+                    accounts.system_program.to_account_info(),
+                    signer,
+                )?;
+
+                accounts.escrow.debit(
+                    fee,
+                    EscrowChangeReason::PlatformFee,
+                    accounts.caller.key(),
+                )?;
+            }
+
+            // Transfer to seller
+            if seller_payout > 0 {
+                pay_seller_proceeds(
+                    accounts.escrow.to_account_info(),
+                    accounts.payout_wallet.to_account_info(),
+                    &accounts.listing,
+                    accounts.payout_split.as_deref(),
+                    split_accounts,
+                    seller_payout,
+                    accounts.system_program.to_account_info(),
+                    signer,
+                )?;
+
+                accounts.escrow.debit(
+                    seller_payout,
+                    EscrowChangeReason::SellerPayout,
+                    accounts.caller.key(),
+                )?;
+            }
+
+            accounts.transaction.status = TransactionStatus::Completed;
+            set_sale_status(
+                &mut accounts.listing,
+                &accounts.transaction,
+                ListingStatus::Completed,
+                accounts.caller.key(),
+                now,
+            )?;
+
+            // Points accrue only on the portion of the sale that actually went through
+            let points_per_sol = accounts.config.loyalty_points_per_sol;
+            accrue_loyalty_points(
+                accounts.buyer_profile.as_mut(),
+                *seller_amount,
+                accounts.listing.payment_currency(),
+                points_per_sol,
+                true,
+                accounts.caller.key(),
+                now,
+            )?;
+            accrue_loyalty_points(
+                accounts.seller_profile.as_mut(),
+                *seller_amount,
+                accounts.listing.payment_currency(),
+                points_per_sol,
+                false,
+                accounts.caller.key(),
+                now,
+            )?;
+        },
+    }
+
+    // SECURITY: Distribute dispute fee based on resolution outcome
+    let dispute_bump_arr = [dispute_bump];
+    let dispute_seeds = &[
+        b"dispute",
+        transaction_key.as_ref(),
+        &dispute_bump_arr,
+    ];
+    let dispute_signer = &[&dispute_seeds[..]];
+
+    let initiator_is_buyer = accounts.transaction.is_buyer_signer(&accounts.dispute.initiator);
+    let (fee_to_buyer, fee_to_seller, fee_to_treasury) = split_dispute_fee(
+        &accounts.dispute.fee_policy,
+        resolution,
+        initiator_is_buyer,
+        dispute_fee,
+        sale_price,
+    )?;
+
+    let fee_transfers = [
+        (accounts.buyer.to_account_info(), fee_to_buyer),
+        (accounts.seller.to_account_info(), fee_to_seller),
+        (accounts.treasury.to_account_info(), fee_to_treasury),
+    ];
+    for (destination, amount) in fee_transfers {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: accounts.dispute.to_account_info(),
+                to: destination,
+            },
+            dispute_signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+    }
+
+    emit!(DisputeFeeDistributed {
+        dispute: accounts.dispute.key(),
+        policy: accounts.dispute.fee_policy.clone(),
+        to_buyer: fee_to_buyer,
+        to_seller: fee_to_seller,
+        to_treasury: fee_to_treasury,
+        actor: accounts.caller.key(),
+        timestamp: now,
+    });
+
+    // Respondent bond: returned in proportion to what the respondent was awarded,
+    // the rest to the treasury (the initiator fee's RefundPrevailingInitiator rule)
+    let respondent_bond = accounts.dispute.respondent_bond;
+    if respondent_bond > 0 {
+        let (bond_to_buyer, bond_to_seller, bond_to_treasury) = split_dispute_fee(
+            &DisputeFeePolicy::RefundPrevailingInitiator,
+            resolution,
+            !initiator_is_buyer,
+            respondent_bond,
+            sale_price,
+        )?;
+        let bond_transfers = [
+            (accounts.buyer.to_account_info(), bond_to_buyer),
+            (accounts.seller.to_account_info(), bond_to_seller),
+            (accounts.treasury.to_account_info(), bond_to_treasury),
+        ];
+        for (destination, amount) in bond_transfers {
+            if amount == 0 {
+                continue;
+            }
+            accounts.dispute.sub_lamports(amount)?;
+            destination.add_lamports(amount)?;
+        }
+        accounts.dispute.respondent_bond = 0;
+
+        emit!(RespondentBondDistributed {
+            dispute: accounts.dispute.key(),
+            to_buyer: bond_to_buyer,
+            to_seller: bond_to_seller,
+            to_treasury: bond_to_treasury,
+            actor: accounts.caller.key(),
+            timestamp: now,
+        });
+    }
+
+    Ok(())
+}
+
+
+/// Split a dispute fee into (buyer, seller, treasury) shares per the locked policy.
+pub(crate) fn split_dispute_fee(
+    policy: &DisputeFeePolicy,
+    resolution: &DisputeResolution,
+    initiator_is_buyer: bool,
+    dispute_fee: u64,
+    sale_price: u64,
+) -> Result<(u64, u64, u64)> {
+    match policy {
+        DisputeFeePolicy::RefundOnFullRefund => match resolution {
+            // Buyer wins - refund dispute fee to buyer
+            DisputeResolution::FullRefund => Ok((dispute_fee, 0, 0)),
+            // Seller wins or compromise - send dispute fee to treasury
+            _ => Ok((0, 0, dispute_fee)),
+        },
+        DisputeFeePolicy::RefundPrevailingInitiator => {
+            // Share of the sale price awarded to the initiator
+            let initiator_award = match resolution {
+                DisputeResolution::FullRefund => {
+                    if initiator_is_buyer { sale_price } else { 0 }
+                },
+                DisputeResolution::ReleaseToSeller => {
+                    if initiator_is_buyer { 0 } else { sale_price }
+                },
+                DisputeResolution::PartialRefund { buyer_amount, seller_amount }
+                | DisputeResolution::PartialRefundWithFee { buyer_amount, seller_amount, .. } => {
+                    if initiator_is_buyer { *buyer_amount } else { *seller_amount }
+                },
+            };
+
+            let refund = if sale_price == 0 {
+                0
+            } else {
+                // u128 intermediate: fee * award can exceed u64
+                let refund = (dispute_fee as u128)
+                    .checked_mul(initiator_award as u128)
+                    .ok_or(AppMarketError::MathOverflow)?
+                    .checked_div(sale_price as u128)
+                    .ok_or(AppMarketError::MathOverflow)?;
+                u64::try_from(refund).map_err(|_| AppMarketError::MathOverflow)?
+            };
+            let to_treasury = dispute_fee
+                .checked_sub(refund)
+                .ok_or(AppMarketError::MathOverflow)?;
+
+            if initiator_is_buyer {
+                Ok((refund, 0, to_treasury))
+            } else {
+                Ok((0, refund, to_treasury))
+            }
+        },
+    }
+}
+
+/// Partial-refund payouts: (to buyer, to seller, platform fee). The fee is the sale's
+/// platform fee scaled to the seller's share (the refunded part returns its fee), taken out
+/// of the share named by the ruling; plain PartialRefund rulings waive it.
+pub(crate) fn split_partial_refund(
+    resolution: &DisputeResolution,
+    platform_fee: u64,
+    sale_price: u64,
+) -> Result<(u64, u64, u64)> {
+    let (buyer_amount, seller_amount, disposition) = match resolution {
+        DisputeResolution::PartialRefund { buyer_amount, seller_amount } => {
+            (*buyer_amount, *seller_amount, FeeDisposition::Waived)
+        },
+        DisputeResolution::PartialRefundWithFee { buyer_amount, seller_amount, fee_disposition } => {
+            (*buyer_amount, *seller_amount, *fee_disposition)
+        },
+        _ => return Err(AppMarketError::InvalidRefundAmounts.into()),
+    };
+    if disposition == FeeDisposition::Waived || sale_price == 0 {
+        return Ok((buyer_amount, seller_amount, 0));
+    }
+
+    // u128 intermediate: fee * share can exceed u64
+    let fee = (platform_fee as u128)
+        .checked_mul(seller_amount as u128)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(sale_price as u128)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let fee = u64::try_from(fee).map_err(|_| AppMarketError::MathOverflow)?;
+
+    match disposition {
+        FeeDisposition::ChargedToSeller => Ok((
+            buyer_amount,
+            seller_amount.checked_sub(fee).ok_or(AppMarketError::InvalidRefundAmounts)?,
+            fee,
+        )),
+        FeeDisposition::ChargedToBuyer => Ok((
+            buyer_amount.checked_sub(fee).ok_or(AppMarketError::InvalidRefundAmounts)?,
+            seller_amount,
+            fee,
+        )),
+        FeeDisposition::Waived => Ok((buyer_amount, seller_amount, 0)),
+    }
+}
+
+/// Record (or switch) an arbitrator's ballot and return everyone backing that outcome.
+/// Other arbitrators' ballots are never touched, so a dissenter cannot reset the tally.
+pub(crate) fn cast_ballot(
+    votes: &mut Vec<ResolutionVote>,
+    voter: Pubkey,
+    resolution: &DisputeResolution,
+) -> Result<Vec<Pubkey>> {
+    match votes.iter_mut().find(|vote| vote.arbitrator == voter) {
+        Some(ballot) => {
+            require!(ballot.resolution != *resolution, AppMarketError::AlreadyVoted);
+            ballot.resolution = resolution.clone();
+        },
+        None => votes.push(ResolutionVote { arbitrator: voter, resolution: resolution.clone() }),
+    }
+
+    Ok(votes
+        .iter()
+        .filter(|vote| vote.resolution == *resolution)
+        .map(|vote| vote.arbitrator)
+        .collect())
+}
+
+/// Validate arbitrator committee membership and quorum.
+pub(crate) fn validate_arbitrator_set(arbitrators: &[Pubkey], quorum: u8) -> Result<()> {
+    require!(
+        !arbitrators.is_empty() && arbitrators.len() <= MAX_ARBITRATORS,
+        AppMarketError::InvalidArbitratorSet
+    );
+    for (i, arbitrator) in arbitrators.iter().enumerate() {
+        require!(
+            !arbitrators[..i].contains(arbitrator),
+            AppMarketError::InvalidArbitratorSet
+        );
+    }
+    require!(
+        quorum > 0 && (quorum as usize) <= arbitrators.len(),
+        AppMarketError::InvalidQuorum
+    );
+    Ok(())
+}
+
+/// Current signed-order nonce of a seller (0 until their OrderNonce account exists).
+pub(crate) fn current_order_nonce(order_nonce: &AccountInfo, program_id: &Pubkey) -> Result<u64> {
+    if order_nonce.data_is_empty() {
+        return Ok(0);
+    }
+    require!(order_nonce.owner == program_id, AppMarketError::InvalidSignedOrder);
+    Ok(OrderNonce::try_deserialize(&mut &order_nonce.try_borrow_data()?[..])?.nonce)
+}
+
+/// Check that the instruction just before the current one is an ed25519 program
+/// verification of `signer` over exactly `message`, with all data inline.
+pub(crate) fn verify_ed25519_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    use solana_instructions_sysvar as ix_sysvar;
+    use solana_sdk_ids::ed25519_program;
+
+    let current = ix_sysvar::load_current_index_checked(instructions)?;
+    require!(current > 0, AppMarketError::MissingSignatureVerification);
+    let ix = ix_sysvar::load_instruction_at_checked(current as usize - 1, instructions)?;
+    require!(
+        ix.program_id == ed25519_program::ID,
+        AppMarketError::MissingSignatureVerification
+    );
+
+    // Layout: [count u8, padding u8, then per signature 7 x u16 offsets]
+    let data = &ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        AppMarketError::InvalidSignatureVerification
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+
+    // SECURITY: Signature, key and message must live in the verify instruction itself
+    // (u16::MAX), not be pulled from another instruction an attacker controls
+    for index_offset in [4, 8, 14] {
+        require!(
+            read_u16(index_offset) == u16::MAX,
+            AppMarketError::InvalidSignatureVerification
+        );
+    }
+
+    let pubkey_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    let verified_pubkey = data
+        .get(pubkey_offset..pubkey_offset + 32)
+        .ok_or(AppMarketError::InvalidSignatureVerification)?;
+    let verified_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(AppMarketError::InvalidSignatureVerification)?;
+
+    require!(
+        verified_pubkey == signer.as_ref() && verified_message == message,
+        AppMarketError::InvalidSignatureVerification
+    );
+    Ok(())
+}
+
+/// The configured stake pool account, owned by the SPL stake pool program
+pub(crate) fn check_stake_pool(
+    config: &MarketConfig,
+    stake_pool: &AccountInfo,
+    pool_mint: &Pubkey,
+) -> Result<()> {
+    require!(
+        config.escrow_stake_pool == Some(stake_pool.key()),
+        AppMarketError::EscrowYieldDisabled
+    );
+    require_keys_eq!(
+        *stake_pool.owner,
+        SPL_STAKE_POOL_PROGRAM_ID,
+        AppMarketError::InvalidStakePool
+    );
+    let data = stake_pool.try_borrow_data()?;
+    let mint = data
+        .get(STAKE_POOL_POOL_MINT_OFFSET..STAKE_POOL_POOL_MINT_OFFSET + 32)
+        .ok_or(AppMarketError::InvalidStakePool)?;
+    require!(mint == pool_mint.as_ref(), AppMarketError::InvalidStakePool);
+    Ok(())
+}
+
+/// SPL stake pool DepositSol: `lamports` from `from` (a system-owned PDA signing with
+/// `signer_seeds`) into pool tokens minted to `pool_tokens_to`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn stake_pool_deposit_sol<'info>(
+    stake_pool: &AccountInfo<'info>,
+    withdraw_authority: &AccountInfo<'info>,
+    reserve_stake: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    pool_tokens_to: &AccountInfo<'info>,
+    manager_fee_account: &AccountInfo<'info>,
+    pool_mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    lamports: u64,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let mut data = vec![14u8]; // StakePoolInstruction::DepositSol
+    data.extend_from_slice(&lamports.to_le_bytes());
+    let ix = Instruction {
+        program_id: SPL_STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(stake_pool.key(), false),
+            AccountMeta::new_readonly(withdraw_authority.key(), false),
+            AccountMeta::new(reserve_stake.key(), false),
+            AccountMeta::new(from.key(), true),
+            AccountMeta::new(pool_tokens_to.key(), false),
+            AccountMeta::new(manager_fee_account.key(), false),
+            // Referral fees come back to the escrow's own token account
+            AccountMeta::new(pool_tokens_to.key(), false),
+            AccountMeta::new(pool_mint.key(), false),
+            AccountMeta::new_readonly(system_program.key(), false),
+            AccountMeta::new_readonly(token_program.key(), false),
+        ],
+        data,
+    };
+    anchor_lang::solana_program::program::invoke_signed(
+        &ix,
+        &[
+            stake_pool.clone(),
+            withdraw_authority.clone(),
+            reserve_stake.clone(),
+            from.clone(),
+            pool_tokens_to.clone(),
+            manager_fee_account.clone(),
+            pool_mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
+
+/// SPL stake pool WithdrawSol: burn `pool_tokens` from `pool_tokens_from` (owned by
+/// `authority`, signing with `signer_seeds`) for lamports paid to `to`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn stake_pool_withdraw_sol<'info>(
+    stake_pool: &AccountInfo<'info>,
+    withdraw_authority: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    pool_tokens_from: &AccountInfo<'info>,
+    reserve_stake: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    manager_fee_account: &AccountInfo<'info>,
+    pool_mint: &AccountInfo<'info>,
+    sysvars: [&AccountInfo<'info>; 3], // clock, stake history, stake program
+    token_program: &AccountInfo<'info>,
+    pool_tokens: u64,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+
+    let [clock, stake_history, stake_program] = sysvars;
+    let mut data = vec![16u8]; // StakePoolInstruction::WithdrawSol
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+    let ix = Instruction {
+        program_id: SPL_STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(stake_pool.key(), false),
+            AccountMeta::new_readonly(withdraw_authority.key(), false),
+            AccountMeta::new_readonly(authority.key(), true),
+            AccountMeta::new(pool_tokens_from.key(), false),
+            AccountMeta::new(reserve_stake.key(), false),
+            AccountMeta::new(to.key(), false),
+            AccountMeta::new(manager_fee_account.key(), false),
+            AccountMeta::new(pool_mint.key(), false),
+            AccountMeta::new_readonly(clock.key(), false),
+            AccountMeta::new_readonly(stake_history.key(), false),
+            AccountMeta::new_readonly(stake_program.key(), false),
+            AccountMeta::new_readonly(token_program.key(), false),
+        ],
+        data,
+    };
+    anchor_lang::solana_program::program::invoke_signed(
+        &ix,
+        &[
+            stake_pool.clone(),
+            withdraw_authority.clone(),
+            authority.clone(),
+            pool_tokens_from.clone(),
+            reserve_stake.clone(),
+            to.clone(),
+            manager_fee_account.clone(),
+            pool_mint.clone(),
+            clock.clone(),
+            stake_history.clone(),
+            stake_program.clone(),
+            token_program.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
+
+/// Split a stake redemption into (back to the escrow, buyer yield, protocol yield, shortfall).
+/// Redeeming less than the principal returns all of it to the escrow and no yield is paid.
+pub(crate) fn split_stake_redemption(
+    principal: u64,
+    redeemed: u64,
+    buyer_share_bps: u16,
+) -> Result<(u64, u64, u64, u64)> {
+    if redeemed < principal {
+        return Ok((redeemed, 0, 0, principal - redeemed));
+    }
+    let yield_amount = redeemed - principal;
+    let buyer_yield = yield_amount
+        .checked_mul(buyer_share_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    Ok((principal, buyer_yield, yield_amount - buyer_yield, 0))
+}
+
+/// Charge a stake shortfall to the sale it was staked from: a refund returns that much less
+/// to the buyer, and a release takes it out of the platform fee first (the protocol shares
+/// in the yield) and only then the seller's proceeds. Buyer and seller both signed
+/// stake_escrow.
+/// Returns the new (sale_price, platform_fee, seller_proceeds).
+pub(crate) fn absorb_stake_shortfall(
+    sale_price: u64,
+    platform_fee: u64,
+    seller_proceeds: u64,
+    shortfall: u64,
+) -> Result<(u64, u64, u64)> {
+    let fee_cut = shortfall.min(platform_fee);
+    Ok((
+        sale_price
+            .checked_sub(shortfall)
+            .ok_or(AppMarketError::MathOverflow)?,
+        platform_fee - fee_cut,
+        seller_proceeds
+            .checked_sub(shortfall - fee_cut)
+            .ok_or(AppMarketError::MathOverflow)?,
+    ))
+}
+
+/// Split a payout path's remaining_accounts into the stake unwind group (present while the
+/// escrow is staked, see unwind_escrow_stake) and the accounts that follow it
+pub(crate) fn take_escrow_stake_accounts<'a, 'info>(
+    transaction: &Transaction,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(Option<&'a [AccountInfo<'info>]>, &'a [AccountInfo<'info>])> {
+    if !transaction.escrow_staked {
+        return Ok((None, remaining_accounts));
+    }
+    require!(
+        remaining_accounts.len() >= ESCROW_STAKE_ACCOUNT_COUNT,
+        AppMarketError::InvalidStakePool
+    );
+    let (stake_accounts, rest) = remaining_accounts.split_at(ESCROW_STAKE_ACCOUNT_COUNT);
+    Ok((Some(stake_accounts), rest))
+}
+
+/// Unwind a staked escrow: redeem the pool tokens, return the principal to the escrow and
+/// split any yield between the buyer and the fee destination. Never fails on a shortfall:
+/// the escrow keeps what was redeemed and absorb_stake_shortfall charges the rest to the
+/// sale, so releases and refunds are never held up by the pool's share price.
+/// stake_accounts: [escrow_stake, stake_vault, pool_tokens, stake_pool, withdraw_authority,
+/// reserve_stake, manager_fee_account, pool_mint, config, buyer, treasury, rent_recipient,
+/// clock, stake_history, stake_program, stake_pool_program, token_program]
+pub(crate) fn unwind_escrow_stake<'info>(
+    stake_accounts: &[AccountInfo<'info>],
+    escrow: &mut Account<'info, Escrow>,
+    transaction: &mut Account<'info, Transaction>,
+    system_program: &AccountInfo<'info>,
+    actor: Pubkey,
+    now: i64,
+) -> Result<()> {
+    let [
+        escrow_stake_info,
+        stake_vault,
+        pool_tokens_info,
+        stake_pool,
+        withdraw_authority,
+        reserve_stake,
+        manager_fee_account,
+        pool_mint,
+        config_info,
+        buyer,
+        treasury,
+        rent_recipient,
+        clock,
+        stake_history,
+        stake_program,
+        stake_pool_program,
+        token_program,
+    ] = stake_accounts
+    else {
+        return err!(AppMarketError::InvalidStakePool);
+    };
+
+    // SECURITY: Every account is checked against the EscrowStake and its PDAs
+    require_keys_eq!(*escrow_stake_info.owner, crate::ID, AppMarketError::InvalidStakePool);
+    let escrow_stake = EscrowStake::try_deserialize(&mut &escrow_stake_info.try_borrow_data()?[..])?;
+    let escrow_stake_key = escrow_stake_info.key();
+    require_keys_eq!(escrow_stake.escrow, escrow.key(), AppMarketError::InvalidStakePool);
+    require_keys_eq!(escrow_stake.transaction, transaction.key(), AppMarketError::InvalidStakePool);
+    let vault_key = Pubkey::create_program_address(
+        &[b"stake_vault", escrow_stake_key.as_ref(), &[escrow_stake.vault_bump]],
+        &crate::ID,
+    )
+    .map_err(|_| AppMarketError::InvalidStakePool)?;
+    require_keys_eq!(stake_vault.key(), vault_key, AppMarketError::InvalidStakePool);
+    let (pool_tokens_key, _) = Pubkey::find_program_address(
+        &[b"escrow_stake_tokens", escrow_stake_key.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(pool_tokens_info.key(), pool_tokens_key, AppMarketError::InvalidStakePool);
+    require_keys_eq!(*pool_tokens_info.owner, anchor_spl::token::ID, AppMarketError::InvalidStakePool);
+    let pool_tokens = TokenAccount::try_deserialize(&mut &pool_tokens_info.try_borrow_data()?[..])?;
+    require_keys_eq!(pool_mint.key(), pool_tokens.mint, AppMarketError::InvalidStakePool);
+    require_keys_eq!(stake_pool.key(), escrow_stake.stake_pool, AppMarketError::InvalidStakePool);
+    require_keys_eq!(*config_info.owner, crate::ID, AppMarketError::InvalidStakePool);
+    let config = MarketConfig::try_deserialize(&mut &config_info.try_borrow_data()?[..])?;
+    let config_key = Pubkey::create_program_address(&[b"config", &[config.bump]], &crate::ID)
+        .map_err(|_| AppMarketError::InvalidStakePool)?;
+    require_keys_eq!(config_info.key(), config_key, AppMarketError::InvalidStakePool);
+    require_keys_eq!(buyer.key(), transaction.buyer, AppMarketError::InvalidBuyer);
+    require_keys_eq!(treasury.key(), config.fee_destination(), AppMarketError::InvalidTreasury);
+    require_keys_eq!(rent_recipient.key(), escrow_stake.rent_payer, AppMarketError::InvalidRentPayer);
+    require_keys_eq!(clock.key(), solana_sdk_ids::sysvar::clock::ID, AppMarketError::InvalidStakePool);
+    require_keys_eq!(
+        stake_history.key(),
+        solana_sdk_ids::sysvar::stake_history::ID,
+        AppMarketError::InvalidStakePool
+    );
+    require_keys_eq!(stake_program.key(), solana_sdk_ids::stake::ID, AppMarketError::InvalidStakePool);
+    require_keys_eq!(stake_pool_program.key(), SPL_STAKE_POOL_PROGRAM_ID, AppMarketError::InvalidStakePool);
+    require_keys_eq!(token_program.key(), anchor_spl::token::ID, AppMarketError::InvalidStakePool);
+
+    let principal = escrow_stake.principal;
+    let escrow_key = escrow.key();
+    let stake_seeds = &[
+        b"escrow_stake".as_ref(),
+        escrow_key.as_ref(),
+        &[escrow_stake.bump],
+    ];
+    let vault_before = stake_vault.lamports();
+    stake_pool_withdraw_sol(
+        stake_pool,
+        withdraw_authority,
+        escrow_stake_info,
+        pool_tokens_info,
+        reserve_stake,
+        stake_vault,
+        manager_fee_account,
+        pool_mint,
+        [clock, stake_history, stake_program],
+        token_program,
+        pool_tokens.amount,
+        stake_seeds,
+    )?;
+    let redeemed = stake_vault.lamports()
+        .checked_sub(vault_before)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let (to_escrow, buyer_yield, protocol_yield, shortfall) =
+        split_stake_redemption(principal, redeemed, config.escrow_yield_buyer_bps)?;
+
+    // Principal (or all that came back) to the escrow, yield out; the vault ends empty
+    let vault_seeds = &[
+        b"stake_vault".as_ref(),
+        escrow_stake_key.as_ref(),
+        &[escrow_stake.vault_bump],
+    ];
+    let signer = &[&vault_seeds[..]];
+    for (to, amount) in [
+        (escrow.to_account_info(), to_escrow),
+        (buyer.clone(), buyer_yield),
+        (treasury.clone(), protocol_yield),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_ctx = CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: stake_vault.clone(),
+                to,
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+    }
+
+    if shortfall > 0 {
+        escrow.debit(shortfall, EscrowChangeReason::StakeShortfall, actor)?;
+        let (sale_price, platform_fee, seller_proceeds) = absorb_stake_shortfall(
+            transaction.sale_price,
+            transaction.platform_fee,
+            transaction.seller_proceeds,
+            shortfall,
+        )?;
+        transaction.sale_price = sale_price;
+        transaction.platform_fee = platform_fee;
+        transaction.seller_proceeds = seller_proceeds;
+    }
+    transaction.escrow_staked = false;
+
+    // Pool tokens are burnt: close their account and the EscrowStake to the rent payer
+    anchor_spl::token::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        anchor_spl::token::CloseAccount {
+            account: pool_tokens_info.clone(),
+            destination: rent_recipient.clone(),
+            authority: escrow_stake_info.clone(),
+        },
+        &[&stake_seeds[..]],
+    ))?;
+    let stake_rent = escrow_stake_info.lamports();
+    escrow_stake_info.sub_lamports(stake_rent)?;
+    rent_recipient.add_lamports(stake_rent)?;
+    escrow_stake_info.assign(&anchor_lang::system_program::ID);
+    escrow_stake_info.resize(0)?;
+
+    emit!(EscrowUnstaked {
+        escrow: escrow_key,
+        transaction: transaction.key(),
+        escrow_stake: escrow_stake_key,
+        principal,
+        buyer_yield,
+        protocol_yield,
+        shortfall,
+        actor,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Pay the next recurring period out of the RecurringEscrow PDA: platform fee to the fee
+/// destination, the rest to the seller
+pub(crate) fn pay_recurring_period<'info>(
+    recurring: &mut Account<'info, RecurringEscrow>,
+    seller: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    actor: Pubkey,
+    now: i64,
+) -> Result<()> {
+    let amount = recurring.amount_per_period;
+    let platform_fee = amount
+        .checked_mul(recurring.platform_fee_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let seller_amount = amount
+        .checked_sub(platform_fee)
+        .ok_or(AppMarketError::MathOverflow)?;
+
+    recurring.sub_lamports(amount)?;
+    seller.add_lamports(seller_amount)?;
+    treasury.add_lamports(platform_fee)?;
+
+    let period = recurring.settled_periods;
+    settle_recurring_period(recurring);
+
+    emit!(RecurringPeriodReleased {
+        recurring_escrow: recurring.key(),
+        period,
+        seller_amount,
+        platform_fee,
+        actor,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+/// Advance past the next recurring period, completing the escrow after the last one
+pub(crate) fn settle_recurring_period(recurring: &mut RecurringEscrow) {
+    recurring.settled_periods += 1;
+    if recurring.settled_periods == recurring.total_periods {
+        recurring.status = RecurringStatus::Completed;
+    }
+}
+
+/// Split rent-to-own payments into (seller's cut, platform fee, buyer refund).
+/// The seller keeps `forfeiture_bps` of what was paid, less the platform fee on that share;
+/// completion keeps all of it (forfeiture at 100%).
+pub(crate) fn split_rent_to_own_payments(
+    total_paid: u64,
+    forfeiture_bps: u16,
+    platform_fee_bps: u16,
+) -> Result<(u64, u64, u64)> {
+    let forfeited = total_paid
+        .checked_mul(forfeiture_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let platform_fee = forfeited
+        .checked_mul(platform_fee_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    Ok((forfeited - platform_fee, platform_fee, total_paid - forfeited))
+}
+
+/// A contributor's refund from a pool whose purchase is final: their share of the
+/// returned_amount snapshot, with the last claimer sweeping the rounding dust
+pub(crate) fn pool_refund(pool: &BuyerPool, amount: u64, vault_balance: u64) -> Result<u64> {
+    let is_last = pool.settled_amount
+        .checked_add(amount)
+        .ok_or(AppMarketError::MathOverflow)?
+        == pool.total_contributed;
+    if is_last {
+        return Ok(vault_balance);
+    }
+    let returned = pool.returned_amount.unwrap_or(vault_balance);
+    let share = (returned as u128)
+        .checked_mul(amount as u128)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(pool.total_contributed as u128)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let share = u64::try_from(share).map_err(|_| AppMarketError::MathOverflow)?;
+    Ok(share.min(vault_balance))
+}
+
+/// Split a fraction buyout into (platform fee, shareholders' pot, seller's cut).
+/// The fee is taken at the listing's locked rate; the rest is shared per share, with
+/// unsold shares (and the rounding dust) going to the seller.
+pub(crate) fn split_fraction_buyout(
+    buyout_price: u64,
+    platform_fee_bps: u16,
+    shares_sold: u64,
+    total_shares: u64,
+) -> Result<(u64, u64, u64)> {
+    let fee = buyout_price
+        .checked_mul(platform_fee_bps as u64)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(BASIS_POINTS_DIVISOR)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let net = buyout_price
+        .checked_sub(fee)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let holder_pot = (net as u128)
+        .checked_mul(shares_sold as u128)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(total_shares as u128)
+        .ok_or(AppMarketError::MathOverflow)?;
+    let holder_pot = u64::try_from(holder_pot).map_err(|_| AppMarketError::MathOverflow)?;
+    Ok((fee, holder_pot, net - holder_pot))
+}
+
+/// Buyout proceeds owed for redeeming `shares` of a bought-out vault.
+/// The last shares redeemed sweep the rounding dust left in the pot.
+pub(crate) fn fraction_redemption(vault: &FractionVault, shares: u64) -> Result<u64> {
+    let redeemed_after = vault.shares_redeemed
+        .checked_add(shares)
+        .ok_or(AppMarketError::MathOverflow)?;
+    require!(redeemed_after <= vault.shares_sold, AppMarketError::FractionSharesUnavailable);
+    if redeemed_after == vault.shares_sold {
+        return vault.holder_pot
+            .checked_sub(vault.redeemed_amount)
+            .ok_or(AppMarketError::MathOverflow.into());
+    }
+    let amount = (vault.holder_pot as u128)
+        .checked_mul(shares as u128)
+        .ok_or(AppMarketError::MathOverflow)?
+        .checked_div(vault.shares_sold as u128)
+        .ok_or(AppMarketError::MathOverflow)?;
+    u64::try_from(amount).map_err(|_| AppMarketError::MathOverflow.into())
+}
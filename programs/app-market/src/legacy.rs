@@ -0,0 +1,413 @@
+//! Superseded account layouts, kept so migrate_account can read them
+
+use crate::*;
+
+// Unversioned (v0) layouts as deployed before ACCOUNT_LAYOUT_VERSION 1 (u64 fees and
+// counters, no version byte), kept only so migrate_account can read them. They must match
+// the original definitions byte for byte: accounts are recognised by their size.
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct MarketConfigV0 {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub backend_authority: Pubkey,
+    pub platform_fee_bps: u64,
+    pub dispute_fee_bps: u64,
+    pub total_volume: u64,
+    pub total_sales: u64,
+    pub paused: bool,
+    pub pending_treasury: Option<Pubkey>,
+    pub pending_treasury_at: Option<i64>,
+    pub pending_admin: Option<Pubkey>,
+    pub pending_admin_at: Option<i64>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct ListingV0 {
+    pub seller: Pubkey,
+    // "{seller}-{salt}"; the salt is the listing PDA seed
+    #[max_len(64)]
+    pub listing_id: String,
+    pub listing_type: ListingType,
+    pub starting_price: u64,
+    pub reserve_price: Option<u64>,
+    pub buy_now_price: Option<u64>,
+    pub current_bid: u64,
+    pub current_bidder: Option<Pubkey>,
+    pub created_at: i64,
+    pub auction_started: bool,
+    pub auction_start_time: Option<i64>,
+    pub end_time: i64,
+    pub status: ListingStatus,
+    pub platform_fee_bps: u64,
+    pub dispute_fee_bps: u64,
+    pub requires_github: bool,
+    #[max_len(64)]
+    pub required_github_username: String,
+    pub withdrawal_count: u64,
+    pub offer_count: u64,
+    pub last_offer_buyer: Option<Pubkey>,
+    pub consecutive_offer_count: u64,
+    pub last_bidder: Option<Pubkey>,
+    pub consecutive_bid_count: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct TransactionV0 {
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub sale_price: u64,
+    pub platform_fee: u64,
+    pub seller_proceeds: u64,
+    pub status: TransactionStatus,
+    pub transfer_deadline: i64,
+    pub created_at: i64,
+    pub seller_confirmed_transfer: bool,
+    pub seller_confirmed_at: Option<i64>,
+    pub completed_at: Option<i64>,
+    pub uploads_verified: bool,
+    pub verification_timestamp: Option<i64>,
+    #[max_len(64)]
+    pub verification_hash: String,
+    pub bump: u8,
+}
+
+// v0 DisputeResolution: PartialRefundWithFee has since grown the enum
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub enum DisputeResolutionV0 {
+    FullRefund,
+    ReleaseToSeller,
+    PartialRefund { buyer_amount: u64, seller_amount: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct DisputeV0 {
+    pub transaction: Pubkey,
+    pub initiator: Pubkey,
+    pub respondent: Pubkey,
+    #[max_len(500)]
+    pub reason: String,
+    pub status: DisputeStatus,
+    pub resolution: Option<DisputeResolutionV0>,
+    #[max_len(1000)]
+    pub resolution_notes: Option<String>,
+    pub dispute_fee: u64,
+    pub created_at: i64,
+    pub resolved_at: Option<i64>,
+    pub pending_resolution: Option<DisputeResolutionV0>,
+    pub pending_buyer_amount: Option<u64>,
+    pub pending_seller_amount: Option<u64>,
+    pub pending_resolution_at: Option<i64>,
+    pub contested: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct PendingWithdrawalV0 {
+    pub user: Pubkey,
+    pub listing: Pubkey,
+    pub amount: u64,
+    pub withdrawal_id: u64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct OfferV0 {
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub deadline: i64,
+    pub status: OfferStatus,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+// Fields added since v0 take the values initialize / create_listing / the purchase paths
+// give a fresh account with every optional feature off.
+
+impl From<MarketConfigV0> for MarketConfig {
+    fn from(old: MarketConfigV0) -> Self {
+        Self {
+            version: ACCOUNT_LAYOUT_VERSION,
+            admin: old.admin,
+            treasury: old.treasury,
+            backend_authority: old.backend_authority,
+            platform_fee_bps: old.platform_fee_bps as u16,
+            dispute_fee_bps: old.dispute_fee_bps as u16,
+            total_volume: old.total_volume,
+            total_sales: old.total_sales,
+            paused: old.paused,
+            pending_treasury: old.pending_treasury,
+            pending_treasury_at: old.pending_treasury_at,
+            pending_admin: old.pending_admin,
+            pending_admin_at: old.pending_admin_at,
+            loyalty_points_per_sol: 0,
+            loyalty_points_per_bps: 0,
+            appeal_window_seconds: 0,
+            appeal_fee_bps: 0,
+            committee_resolution: false,
+            min_arbitrator_stake: 0,
+            arbitrator_slash_bps: 0,
+            dispute_sla_seconds: DEFAULT_DISPUTE_SLA_SECONDS,
+            dispute_fee_policy: DisputeFeePolicy::RefundOnFullRefund,
+            escheat_period_seconds: 0,
+            insurance_fee_bps: 0,
+            fee_vault: None,
+            guardian: None,
+            pause_reason: String::new(),
+            paused_until: None,
+            circuit_breaker_multiple: 0,
+            circuit_breaker_tripped: false,
+            volume_day_start: 0,
+            volume_today: 0,
+            volume_slot: 0,
+            volume_this_slot: 0,
+            trailing_daily_volume: 0,
+            max_sale_price: None,
+            promotion_fee_per_day: 0,
+            total_promotion_fees: 0,
+            max_bidder_exposure: 0,
+            has_fee_schedule: false,
+            treasury_withdrawal_count: 0,
+            bump: old.bump,
+            max_anti_snipe_extensions: DEFAULT_MAX_ANTI_SNIPE_EXTENSIONS,
+            escrow_stake_pool: None,
+            escrow_yield_buyer_bps: 0,
+            min_offer_duration_seconds: DEFAULT_MIN_OFFER_DURATION_SECONDS,
+            max_offer_duration_seconds: DEFAULT_MAX_OFFER_DURATION_SECONDS,
+            small_claims_threshold: 0,
+            respondent_bonds_enabled: false,
+        }
+    }
+}
+
+impl From<ListingV0> for Listing {
+    fn from(old: ListingV0) -> Self {
+        // v0 create_listing always wrote "{seller}-{salt}"
+        let salt = old.listing_id
+            .rsplit('-')
+            .next()
+            .and_then(|salt| salt.parse().ok())
+            .unwrap_or_default();
+        Self {
+            version: ACCOUNT_LAYOUT_VERSION,
+            seller: old.seller,
+            salt,
+            listing_type: old.listing_type,
+            starting_price: old.starting_price,
+            reserve_price: old.reserve_price,
+            buy_now_price: old.buy_now_price,
+            current_bid: old.current_bid,
+            current_bidder: old.current_bidder,
+            created_at: old.created_at,
+            auction_started: old.auction_started,
+            auction_start_time: old.auction_start_time,
+            // v0 listings went live at creation
+            start_time: old.created_at,
+            paused_at: None,
+            operator: None,
+            requires_verification: false,
+            end_time: old.end_time,
+            duration_seconds: old.end_time.saturating_sub(old.created_at),
+            status: old.status,
+            platform_fee_bps: old.platform_fee_bps as u16,
+            dispute_fee_bps: old.dispute_fee_bps as u16,
+            requires_github: old.requires_github,
+            required_github_username: old.required_github_username,
+            withdrawal_count: old.withdrawal_count,
+            offer_count: old.offer_count as u32,
+            last_offer_buyer: old.last_offer_buyer,
+            consecutive_offer_count: old.consecutive_offer_count as u32,
+            last_bidder: old.last_bidder,
+            consecutive_bid_count: old.consecutive_bid_count as u32,
+            payment_mint: old.payment_mint,
+            // v0 prices were always lamports (payment_mint was only a label)
+            payment_decimals: 9,
+            deposit_bps: 0,
+            vesting_days: 0,
+            holdback_bps: 0,
+            payout_wallet: old.seller,
+            pending_payout_wallet: None,
+            pending_payout_wallet_at: None,
+            has_payout_split: false,
+            arbitrator: None,
+            relist_count: 0,
+            quantity: 1,
+            units_sold: 0,
+            has_bid_book: false,
+            bundle_assets: Vec::new(),
+            tags: Vec::new(),
+            category: Category::Other,
+            featured_until: 0,
+            bump: old.bump,
+            uses_bid_escrows: false,
+            extension_count: 0,
+            // v0 auctions had no extension cap
+            max_extensions: 0,
+            min_offer: 0,
+            auto_accept_price: None,
+            best_offer: None,
+            best_offer_amount: 0,
+            price_drop_amount: 0,
+            price_drop_interval: 0,
+            price_floor: 0,
+            accepted_currencies: Vec::new(),
+            current_bid_exposure: 0,
+        }
+    }
+}
+
+impl From<TransactionV0> for Transaction {
+    fn from(old: TransactionV0) -> Self {
+        Self {
+            version: ACCOUNT_LAYOUT_VERSION,
+            listing: old.listing,
+            seller: old.seller,
+            buyer: old.buyer,
+            // v0 buyers always paid for themselves
+            payer: old.buyer,
+            sale_price: old.sale_price,
+            platform_fee: old.platform_fee,
+            seller_proceeds: old.seller_proceeds,
+            status: old.status,
+            transfer_deadline: old.transfer_deadline,
+            created_at: old.created_at,
+            seller_confirmed_transfer: old.seller_confirmed_transfer,
+            seller_confirmed_at: old.seller_confirmed_at,
+            completed_at: old.completed_at,
+            uploads_verified: old.uploads_verified,
+            verification_timestamp: old.verification_timestamp,
+            verification_hash: old.verification_hash,
+            verification_report: None,
+            uploads_rejected_at: None,
+            rejection_code: None,
+            verification_rounds: 0,
+            deposit_amount: 0,
+            payment_deadline: None,
+            vesting_duration: 0,
+            vesting_start: None,
+            vested_claimed: 0,
+            holdback_amount: 0,
+            holdback_release_at: None,
+            warranty_disputed_at: None,
+            cancel_proposed_by: None,
+            cancel_relist: false,
+            arbitrator: None,
+            unit_index: None,
+            unit_index_seed: [0; 8],
+            bundle_confirmed: 0,
+            deliverables: Vec::new(),
+            agreement_hash: None,
+            buyer_contact_key: None,
+            seller_contact_key: None,
+            bid_exposure: 0,
+            bump: old.bump,
+            escrow_staked: false,
+            buyer_delegate: None,
+            vesting_disputed_at: None,
+        }
+    }
+}
+
+impl From<DisputeResolutionV0> for DisputeResolution {
+    fn from(old: DisputeResolutionV0) -> Self {
+        match old {
+            DisputeResolutionV0::FullRefund => DisputeResolution::FullRefund,
+            DisputeResolutionV0::ReleaseToSeller => DisputeResolution::ReleaseToSeller,
+            DisputeResolutionV0::PartialRefund { buyer_amount, seller_amount } => {
+                DisputeResolution::PartialRefund { buyer_amount, seller_amount }
+            },
+        }
+    }
+}
+
+impl From<DisputeV0> for Dispute {
+    fn from(old: DisputeV0) -> Self {
+        // v0 paid the ruling out of escrow at execution
+        let ruling_settled = old.status == DisputeStatus::Resolved;
+        Self {
+            version: ACCOUNT_LAYOUT_VERSION,
+            transaction: old.transaction,
+            initiator: old.initiator,
+            respondent: old.respondent,
+            reason: old.reason,
+            status: old.status,
+            resolution: old.resolution.map(Into::into),
+            resolution_notes: old.resolution_notes,
+            dispute_fee: old.dispute_fee,
+            created_at: old.created_at,
+            resolved_at: old.resolved_at,
+            pending_resolution: old.pending_resolution.map(Into::into),
+            pending_buyer_amount: old.pending_buyer_amount,
+            pending_seller_amount: old.pending_seller_amount,
+            pending_resolution_at: old.pending_resolution_at,
+            contested: old.contested,
+            appeal_deadline: None,
+            appealed_by: None,
+            appeal_fee: 0,
+            buyer_evidence_count: 0,
+            seller_evidence_count: 0,
+            votes: Vec::new(),
+            ruling_arbitrators: Vec::new(),
+            appeal_overturned: false,
+            sla_default: false,
+            // v0 refunded the fee to a fully refunded buyer
+            fee_policy: DisputeFeePolicy::RefundOnFullRefund,
+            bump: old.bump,
+            // No respondent bond window (response_deadline 0)
+            respondent_bond: 0,
+            respondent_bonded: false,
+            response_deadline: 0,
+            response_status: None,
+            response: String::new(),
+            response_hash: None,
+            responded_at: None,
+            category: None,
+            severity: None,
+            small_claim: false,
+            small_claim_moderator: None,
+            ruling_settled,
+        }
+    }
+}
+
+impl From<PendingWithdrawalV0> for PendingWithdrawal {
+    fn from(old: PendingWithdrawalV0) -> Self {
+        Self {
+            version: ACCOUNT_LAYOUT_VERSION,
+            user: old.user,
+            listing: old.listing,
+            amount: old.amount,
+            // v0 had no exposure tracking
+            exposure: 0,
+            withdrawal_id: old.withdrawal_id,
+            created_at: old.created_at,
+            expires_at: old.expires_at,
+            // v0 returned withdrawal rent to the refunded user
+            rent_payer: old.user,
+            bump: old.bump,
+        }
+    }
+}
+
+impl From<OfferV0> for Offer {
+    fn from(old: OfferV0) -> Self {
+        Self {
+            version: ACCOUNT_LAYOUT_VERSION,
+            listing: old.listing,
+            buyer: old.buyer,
+            amount: old.amount,
+            deadline: old.deadline,
+            status: old.status,
+            created_at: old.created_at,
+            recipient: None,
+            bump: old.bump,
+        }
+    }
+}
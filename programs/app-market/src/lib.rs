@@ -1,7 +1,33 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 declare_id!("9udUgupraga6dj92zfLec8bAdXUZsU3FGNN3Lf8XGzog");
 
+#[cfg(any(
+    all(feature = "mainnet", feature = "devnet"),
+    all(feature = "mainnet", feature = "localnet"),
+    all(feature = "devnet", feature = "localnet"),
+))]
+compile_error!("enable one cluster feature; build devnet/localnet with --no-default-features");
+
+pub mod constants;
+pub mod contexts;
+pub mod enums;
+pub mod errors;
+pub mod events;
+mod helpers;
+pub mod legacy;
+pub mod state;
+
+pub use constants::*;
+pub use contexts::*;
+pub use enums::*;
+pub use errors::*;
+pub use events::*;
+use helpers::*;
+pub use legacy::*;
+pub use state::*;
+
 /// App Market Escrow Program
 /// Handles secure escrow for marketplace transactions
 ///
@@ -18,70 +44,6 @@ declare_id!("9udUgupraga6dj92zfLec8bAdXUZsU3FGNN3Lf8XGzog");
 pub mod app_market {
     use super::*;
 
-    // ============================================
-    // CONSTANTS
-    // ============================================
-
-    /// Basis points divisor (100% = 10000 basis points)
-    pub const BASIS_POINTS_DIVISOR: u64 = 10000;
-
-    /// Platform fee: 5% (500 basis points)
-    pub const PLATFORM_FEE_BPS: u64 = 500;
-    /// APP token fee: 3% (300 basis points) - discounted rate for $APP payments
-    pub const APP_FEE_BPS: u64 = 300;
-    /// Dispute fee: 2% (200 basis points)
-    pub const DISPUTE_FEE_BPS: u64 = 200;
-
-    /// APP token mint address (mainnet)
-    pub const APP_TOKEN_MINT: Pubkey = solana_program::pubkey!("Ansto3G3SzGt6bXo3pMddiM4YkW9Yt8y7Qvwy47dBAGS");
-
-    /// Maximum platform fee: 10% (prevents accidental/malicious fee rug)
-    pub const MAX_PLATFORM_FEE_BPS: u64 = 1000;
-    /// Maximum dispute fee: 5%
-    pub const MAX_DISPUTE_FEE_BPS: u64 = 500;
-
-    /// Transfer deadline: 7 days in seconds
-    pub const TRANSFER_DEADLINE_SECONDS: i64 = 7 * 24 * 60 * 60;
-    /// Maximum auction duration: 30 days
-    pub const MAX_AUCTION_DURATION_SECONDS: i64 = 30 * 24 * 60 * 60;
-
-    /// Minimum bid increment: 5% (500 basis points)
-    pub const MIN_BID_INCREMENT_BPS: u64 = 500;
-    /// Absolute minimum bid increment: 0.1 SOL (100,000,000 lamports)
-    pub const MIN_BID_INCREMENT_LAMPORTS: u64 = 100_000_000;
-
-    /// Anti-sniping window: 15 minutes before auction end
-    pub const ANTI_SNIPE_WINDOW: i64 = 15 * 60;
-    /// Extension time when bid placed in anti-snipe window
-    pub const ANTI_SNIPE_EXTENSION: i64 = 15 * 60;
-
-    /// Admin timelock: 48 hours for sensitive operations
-    pub const ADMIN_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
-
-    /// Finalize grace period: 7 days after seller confirmation
-    pub const FINALIZE_GRACE_PERIOD: i64 = 7 * 24 * 60 * 60;
-
-    /// Maximum bids per listing (prevents DoS via bid spam)
-    pub const MAX_BIDS_PER_LISTING: u64 = 1000;
-    /// Maximum total offers per listing (prevents DoS via offer spam)
-    pub const MAX_OFFERS_PER_LISTING: u64 = 100;
-    /// Maximum consecutive offers per buyer without being outbid
-    pub const MAX_CONSECUTIVE_OFFERS: u64 = 10;
-    /// Maximum consecutive bids per bidder without being outbid
-    pub const MAX_CONSECUTIVE_BIDS: u64 = 10;
-
-    /// Transaction fee buffer (10k lamports) for balance pre-checks
-    pub const TX_FEE_BUFFER_LAMPORTS: u64 = 10_000;
-
-    /// Backend verification timeout: 30 days (fallback if backend unresponsive)
-    pub const BACKEND_TIMEOUT_SECONDS: i64 = 30 * 24 * 60 * 60;
-
-    /// Dispute resolution timelock: 48 hours for parties to contest
-    pub const DISPUTE_RESOLUTION_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
-
-    /// Expected admin pubkey (prevents initialization frontrunning)
-    pub const EXPECTED_ADMIN: Pubkey = solana_program::pubkey!("63jQ3qffMgacpUw8ebDZPuyUHf7DsfsYnQ7sk8fmFaF1");
-
     // ============================================
     // INSTRUCTIONS
     // ============================================
@@ -125,8 +87,8 @@ pub mod app_market {
         config.admin = ctx.accounts.admin.key();
         config.treasury = ctx.accounts.treasury.key();
         config.backend_authority = backend_authority;
-        config.platform_fee_bps = platform_fee_bps;
-        config.dispute_fee_bps = dispute_fee_bps;
+        config.platform_fee_bps = platform_fee_bps as u16;
+        config.dispute_fee_bps = dispute_fee_bps as u16;
         config.total_volume = 0;
         config.total_sales = 0;
         config.paused = false;
@@ -134,6 +96,55 @@ pub mod app_market {
         config.pending_treasury_at = None;
         config.pending_admin = None;
         config.pending_admin_at = None;
+        // Loyalty program starts disabled until admin configures rates
+        config.loyalty_points_per_sol = 0;
+        config.loyalty_points_per_bps = 0;
+        // Appeals disabled until admin configures a window
+        config.appeal_window_seconds = 0;
+        config.appeal_fee_bps = 0;
+        // Single-admin resolution until an arbitrator committee is configured
+        config.committee_resolution = false;
+        // Arbitrator staking disabled until configured
+        config.min_arbitrator_stake = 0;
+        config.arbitrator_slash_bps = 0;
+        config.dispute_sla_seconds = DEFAULT_DISPUTE_SLA_SECONDS;
+        config.dispute_fee_policy = DisputeFeePolicy::RefundOnFullRefund;
+        // Unclaimed withdrawals are never escheated until configured
+        config.escheat_period_seconds = 0;
+        // No insurance slice until the insurance fund is initialized
+        config.insurance_fee_bps = 0;
+        // Fees go straight to the treasury until a fee split is executed
+        config.fee_vault = None;
+        // No guardian until the admin appoints one
+        config.guardian = None;
+        config.pause_reason = String::new();
+        config.paused_until = None;
+        // Circuit breaker disabled until the admin configures a multiple
+        config.circuit_breaker_multiple = 0;
+        config.circuit_breaker_tripped = false;
+        config.volume_day_start = 0;
+        config.volume_today = 0;
+        config.volume_slot = 0;
+        config.volume_this_slot = 0;
+        config.trailing_daily_volume = 0;
+        // No price cap until the admin sets one
+        config.max_sale_price = None;
+        // Promotions disabled until the admin prices them
+        config.promotion_fee_per_day = 0;
+        config.total_promotion_fees = 0;
+        // No per-wallet bid exposure cap by default
+        config.max_bidder_exposure = 0;
+        // Flat platform fee until the admin creates a FeeSchedule
+        config.has_fee_schedule = false;
+        config.treasury_withdrawal_count = 0;
+        config.max_anti_snipe_extensions = DEFAULT_MAX_ANTI_SNIPE_EXTENSIONS;
+        config.min_offer_duration_seconds = DEFAULT_MIN_OFFER_DURATION_SECONDS;
+        config.max_offer_duration_seconds = DEFAULT_MAX_OFFER_DURATION_SECONDS;
+        // Every dispute takes the full committee process until the admin sets a threshold
+        config.small_claims_threshold = 0;
+        // Respondent bonds (and default judgments) are opt-in
+        config.respondent_bonds_enabled = false;
+        config.version = ACCOUNT_LAYOUT_VERSION;
         config.bump = ctx.bumps.config;
 
         emit!(MarketplaceInitialized {
@@ -142,6 +153,7 @@ pub mod app_market {
             backend_authority: config.backend_authority,
             platform_fee_bps,
             dispute_fee_bps,
+            actor: ctx.accounts.admin.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -166,6 +178,8 @@ pub mod app_market {
             old_treasury: config.treasury,
             new_treasury,
             executable_at: Clock::get()?.unix_timestamp + ADMIN_TIMELOCK_SECONDS,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
@@ -200,12 +214,122 @@ pub mod app_market {
 
         emit!(TreasuryChanged {
             new_treasury: config.treasury,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Queue a withdrawal from the program treasury vault (admin only, step 1 of timelock)
+    /// Fees reach the vault when config.treasury is pointed at it via propose_treasury_change;
+    /// every outbound transfer is then announced here and delayed by the admin timelock.
+    pub fn request_treasury_withdrawal(
+        ctx: Context<RequestTreasuryWithdrawal>,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        require!(amount > 0, AppMarketError::InvalidWithdrawalAmount);
+
+        let clock = Clock::get()?;
+        let config = &mut ctx.accounts.config;
+
+        let withdrawal = &mut ctx.accounts.treasury_withdrawal;
+        withdrawal.id = config.treasury_withdrawal_count;
+        withdrawal.recipient = recipient;
+        withdrawal.amount = amount;
+        withdrawal.requested_at = clock.unix_timestamp;
+        withdrawal.executable_at = clock.unix_timestamp + ADMIN_TIMELOCK_SECONDS;
+        withdrawal.rent_payer = ctx.accounts.payer.key();
+        withdrawal.version = ACCOUNT_LAYOUT_VERSION;
+        withdrawal.bump = ctx.bumps.treasury_withdrawal;
+
+        config.treasury_withdrawal_count = config.treasury_withdrawal_count
+            .checked_add(1)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        emit!(TreasuryWithdrawalRequested {
+            id: withdrawal.id,
+            treasury_withdrawal: withdrawal.key(),
+            recipient,
+            amount,
+            executable_at: withdrawal.executable_at,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a queued treasury withdrawal (admin only, step 2 of timelock, after 48 hours)
+    /// The vault keeps its rent-exempt minimum.
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+
+        let clock = Clock::get()?;
+        let withdrawal = &ctx.accounts.treasury_withdrawal;
+        require!(
+            clock.unix_timestamp >= withdrawal.executable_at,
+            AppMarketError::TimelockNotExpired
+        );
+
+        let vault_rent = Rent::get()?.minimum_balance(0);
+        let available = ctx.accounts.treasury_vault.lamports().saturating_sub(vault_rent);
+        require!(available >= withdrawal.amount, AppMarketError::InsufficientBalance);
+
+        let seeds = &[
+            b"treasury_vault".as_ref(),
+            &[ctx.bumps.treasury_vault],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.treasury_vault.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, withdrawal.amount)?;
+
+        emit!(TreasuryWithdrawn {
+            id: withdrawal.id,
+            treasury_withdrawal: ctx.accounts.treasury_withdrawal.key(),
+            recipient: withdrawal.recipient,
+            amount: withdrawal.amount,
+            remaining: available - withdrawal.amount,
+            actor: ctx.accounts.admin.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
+    /// Cancel a queued treasury withdrawal (admin only)
+    pub fn cancel_treasury_withdrawal(ctx: Context<CancelTreasuryWithdrawal>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+
+        emit!(TreasuryWithdrawalCancelled {
+            id: ctx.accounts.treasury_withdrawal.id,
+            treasury_withdrawal: ctx.accounts.treasury_withdrawal.key(),
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
     /// Propose admin change (step 1 of timelock)
     pub fn propose_admin_change(
         ctx: Context<ProposeAdminChange>,
@@ -224,6 +348,8 @@ pub mod app_market {
             old_admin: config.admin,
             new_admin,
             executable_at: Clock::get()?.unix_timestamp + ADMIN_TIMELOCK_SECONDS,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
@@ -258,6 +384,7 @@ pub mod app_market {
 
         emit!(AdminChanged {
             new_admin: config.admin,
+            actor: ctx.accounts.admin.key(),
             timestamp: clock.unix_timestamp,
         });
 
@@ -265,1101 +392,8476 @@ pub mod app_market {
     }
 
     /// Set paused state (admin only, no timelock for emergencies)
-    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    /// Pausing requires a reason and a paused_until at most 14 days out; after it,
+    /// user-exit paths (cancel/expire) treat the marketplace as unpaused.
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        paused: bool,
+        reason: String,
+        paused_until: Option<i64>,
+    ) -> Result<()> {
         require!(
             ctx.accounts.admin.key() == ctx.accounts.config.admin,
             AppMarketError::NotAdmin
         );
 
-        ctx.accounts.config.paused = paused;
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+
+        if paused {
+            require!(reason.len() <= MAX_PAUSE_REASON_LEN, AppMarketError::PauseReasonTooLong);
+            let until = paused_until.ok_or(AppMarketError::InvalidPauseDuration)?;
+            require!(
+                until > now && until <= now + MAX_PAUSE_DURATION_SECONDS,
+                AppMarketError::InvalidPauseDuration
+            );
+            config.pause_reason = reason;
+            config.paused_until = Some(until);
+        } else {
+            config.pause_reason = String::new();
+            config.paused_until = None;
+        }
+        config.paused = paused;
 
         emit!(ContractPausedEvent {
             paused,
-            timestamp: Clock::get()?.unix_timestamp,
+            reason: config.pause_reason.clone(),
+            paused_until: config.paused_until,
+            actor: ctx.accounts.admin.key(),
+            timestamp: now,
         });
 
         Ok(())
     }
 
-    /// Create a new listing with escrow initialized atomically
-    pub fn create_listing(
-        ctx: Context<CreateListing>,
-        salt: u64,
-        listing_type: ListingType,
-        starting_price: u64,
-        reserve_price: Option<u64>,
-        buy_now_price: Option<u64>,
-        duration_seconds: i64,
-        requires_github: bool,
-        required_github_username: String,
-        payment_mint: Option<Pubkey>,
-    ) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-        require!(starting_price > 0, AppMarketError::InvalidPrice);
+    /// Appoint or remove the pause-only guardian (admin only)
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
         require!(
-            duration_seconds > 0 && duration_seconds <= MAX_AUCTION_DURATION_SECONDS,
-            AppMarketError::InvalidDuration
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
 
-        // Validate listing type requirements
-        match listing_type {
-            ListingType::Auction => {
-                // Auction with reserve: starting bid must equal reserve
-                if let Some(reserve) = reserve_price {
-                    require!(
-                        starting_price == reserve,
-                        AppMarketError::StartingPriceMustEqualReserve
-                    );
-                }
-                // ENHANCEMENT: Auctions can have buy_now_price for instant purchase during bidding
-                // If someone hits buy_now during auction, they win immediately
-            },
-            ListingType::BuyNow => {
-                require!(
-                    buy_now_price.is_some(),
-                    AppMarketError::BuyNowPriceRequired
-                );
-                // Note: BuyNow can also have reserve_price for dual listing functionality
-            },
-        }
-
-        // SECURITY: Validate GitHub username format if provided
-        // Rules: 1-39 chars, alphanumeric or hyphen, cannot start/end with hyphen, no consecutive hyphens
-        if requires_github && !required_github_username.is_empty() {
-            let username = &required_github_username;
-            // Max 39 chars (GitHub's actual limit)
-            require!(
-                username.len() <= 39,
-                AppMarketError::InvalidGithubUsername
-            );
-            // Only alphanumeric or hyphen
-            require!(
-                username.chars().all(|c| c.is_alphanumeric() || c == '-'),
-                AppMarketError::InvalidGithubUsername
-            );
-            // Cannot start with hyphen
-            require!(
-                !username.starts_with('-'),
-                AppMarketError::InvalidGithubUsername
-            );
-            // Cannot end with hyphen
-            require!(
-                !username.ends_with('-'),
-                AppMarketError::InvalidGithubUsername
-            );
-            // No consecutive hyphens
-            require!(
-                !username.contains("--"),
-                AppMarketError::InvalidGithubUsername
-            );
-        }
+        ctx.accounts.config.guardian = guardian;
 
-        let listing = &mut ctx.accounts.listing;
-        let escrow = &mut ctx.accounts.escrow;
-        let clock = Clock::get()?;
+        emit!(GuardianUpdated {
+            guardian,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Initialize listing
-        listing.seller = ctx.accounts.seller.key();
-        listing.listing_id = format!("{}-{}", ctx.accounts.seller.key(), salt);
-        listing.listing_type = listing_type.clone();
-        listing.starting_price = starting_price;
-        listing.reserve_price = reserve_price;
-        listing.buy_now_price = buy_now_price;
-        listing.current_bid = 0;
-        listing.current_bidder = None;
-        listing.created_at = clock.unix_timestamp;
+        Ok(())
+    }
 
-        // SECURITY: Auction timer doesn't start until reserve bid placed
-        listing.auction_started = false;
-        listing.auction_start_time = None;
-        listing.end_time = clock.unix_timestamp + duration_seconds;
-        listing.status = ListingStatus::Active;
+    /// Pause the marketplace immediately for the maximum pause duration (guardian only)
+    /// Unpausing stays with the admin.
+    pub fn guardian_pause(ctx: Context<GuardianPause>, reason: String) -> Result<()> {
+        require!(
+            ctx.accounts.config.guardian == Some(ctx.accounts.guardian.key()),
+            AppMarketError::NotGuardian
+        );
+        require!(reason.len() <= MAX_PAUSE_REASON_LEN, AppMarketError::PauseReasonTooLong);
 
-        // SECURITY: Lock fees at listing creation time
-        // Use discounted 3% fee for APP token payments, standard 5% for others
-        // SECURITY: APP token fee discount is only valid when payment is actually
-        // made in APP tokens via SPL token transfer. The buy_now and place_bid
-        // instructions must verify the payment mint matches the actual transfer.
-        listing.platform_fee_bps = if payment_mint == Some(APP_TOKEN_MINT) {
-            APP_FEE_BPS
-        } else {
-            ctx.accounts.config.platform_fee_bps
-        };
-        listing.dispute_fee_bps = ctx.accounts.config.dispute_fee_bps;
-        listing.payment_mint = payment_mint;
+        let now = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        config.paused = true;
+        config.pause_reason = reason;
+        config.paused_until = Some(now + MAX_PAUSE_DURATION_SECONDS);
 
-        // GitHub requirements
-        listing.requires_github = requires_github;
-        listing.required_github_username = required_github_username;
+        emit!(ContractPausedEvent {
+            paused: true,
+            reason: config.pause_reason.clone(),
+            paused_until: config.paused_until,
+            actor: ctx.accounts.guardian.key(),
+            timestamp: now,
+        });
 
-        // Withdrawal counter for unique PDA seeds
-        listing.withdrawal_count = 0;
-        // Offer counter
-        listing.offer_count = 0;
-        // Consecutive offer tracking
-        listing.last_offer_buyer = None;
-        listing.consecutive_offer_count = 0;
-        // Consecutive bid tracking
-        listing.last_bidder = None;
-        listing.consecutive_bid_count = 0;
+        Ok(())
+    }
 
-        listing.bump = ctx.bumps.listing;
+    /// Update platform and dispute fees for future listings (admin only)
+    /// Existing listings keep the fees locked at creation. Single instruction with
+    /// absolute values so it can be executed as-is by a governance proposal.
+    pub fn set_platform_fees(
+        ctx: Context<SetPlatformFees>,
+        platform_fee_bps: u64,
+        dispute_fee_bps: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        require!(
+            platform_fee_bps <= MAX_PLATFORM_FEE_BPS,
+            AppMarketError::FeeTooHigh
+        );
+        require!(
+            dispute_fee_bps <= MAX_DISPUTE_FEE_BPS,
+            AppMarketError::FeeTooHigh
+        );
 
-        // Initialize escrow (seller pays rent)
-        escrow.listing = listing.key();
-        escrow.amount = 0;
-        escrow.bump = ctx.bumps.escrow;
+        let config = &mut ctx.accounts.config;
+        config.platform_fee_bps = platform_fee_bps as u16;
+        config.dispute_fee_bps = dispute_fee_bps as u16;
 
-        emit!(ListingCreated {
-            listing: listing.key(),
-            seller: listing.seller,
-            listing_id: listing.listing_id.clone(),
-            listing_type,
-            starting_price,
-            end_time: listing.end_time,
-            platform_fee_bps: listing.platform_fee_bps,
+        emit!(PlatformFeesUpdated {
+            platform_fee_bps,
+            dispute_fee_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Place a bid on a listing (uses withdrawal pattern for refunds)
-    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-
-        let listing = &mut ctx.accounts.listing;
-        let clock = Clock::get()?;
-
-        // CHECKS: All validations first
-        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+    /// Configure loyalty points accrual and redemption rates (admin only)
+    /// Setting points_per_sol to 0 disables accrual; points_per_bps to 0 disables redemption.
+    /// Only SOL-denominated sales earn points (see accrue_loyalty_points)
+    pub fn set_loyalty_config(
+        ctx: Context<SetLoyaltyConfig>,
+        points_per_sol: u64,
+        points_per_bps: u64,
+    ) -> Result<()> {
         require!(
-            listing.listing_type == ListingType::Auction,
-            AppMarketError::NotAnAuction
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
 
-        // Check auction timing
-        if listing.auction_started {
-            require!(
-                clock.unix_timestamp < listing.end_time,
-                AppMarketError::AuctionEnded
-            );
-        }
-
-        require!(ctx.accounts.bidder.key() != listing.seller, AppMarketError::SellerCannotBid);
+        let config = &mut ctx.accounts.config;
+        config.loyalty_points_per_sol = points_per_sol;
+        config.loyalty_points_per_bps = points_per_bps;
 
-        // SECURITY: Pre-check bidder has exact amount needed for everything to perform tx
-        // Need: bid amount + withdrawal PDA rent (if creating) + tx fees
-        let rent = Rent::get()?;
+        emit!(LoyaltyConfigUpdated {
+            points_per_sol,
+            points_per_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        let required_balance = if listing.current_bidder.is_some() && listing.current_bid > 0 {
-            // Need rent for withdrawal PDA creation + bid amount + tx fees
-            let withdrawal_space = 8 + PendingWithdrawal::INIT_SPACE;
-            let withdrawal_rent = rent.minimum_balance(withdrawal_space);
-            amount
-                .checked_add(withdrawal_rent)
-                .ok_or(AppMarketError::MathOverflow)?
-                .checked_add(TX_FEE_BUFFER_LAMPORTS)
-                .ok_or(AppMarketError::MathOverflow)?
-        } else {
-            // First bid - no withdrawal PDA needed, just bid + tx fees
-            amount.checked_add(TX_FEE_BUFFER_LAMPORTS).ok_or(AppMarketError::MathOverflow)?
-        };
+        Ok(())
+    }
 
+    /// Configure the dispute appeal window and appeal fee (admin only)
+    /// A window of 0 disables appeals (disputes close at execution)
+    pub fn set_appeal_config(
+        ctx: Context<SetAppealConfig>,
+        appeal_window_seconds: i64,
+        appeal_fee_bps: u64,
+    ) -> Result<()> {
         require!(
-            ctx.accounts.bidder.lamports() >= required_balance,
-            AppMarketError::InsufficientBalance
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-
+        require!(
+            (0..=MAX_APPEAL_WINDOW_SECONDS).contains(&appeal_window_seconds),
+            AppMarketError::InvalidAppealWindow
+        );
+        require!(
+            appeal_fee_bps <= MAX_DISPUTE_FEE_BPS,
+            AppMarketError::FeeTooHigh
+        );
+
+        let config = &mut ctx.accounts.config;
+        config.appeal_window_seconds = appeal_window_seconds;
+        config.appeal_fee_bps = appeal_fee_bps as u16;
+
+        emit!(AppealConfigUpdated {
+            appeal_window_seconds,
+            appeal_fee_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the caller's user profile (tracks loyalty points and fee discounts)
+    pub fn init_user_profile(ctx: Context<InitUserProfile>) -> Result<()> {
+        let profile = &mut ctx.accounts.user_profile;
+        let clock = Clock::get()?;
+
+        profile.user = ctx.accounts.user.key();
+        profile.points = 0;
+        profile.lifetime_points = 0;
+        profile.total_purchases = 0;
+        profile.total_sales = 0;
+        profile.fee_discount_bps = 0;
+        profile.locked_exposure = 0;
+        profile.contact_key = None;
+        profile.created_at = clock.unix_timestamp;
+        profile.version = ACCOUNT_LAYOUT_VERSION;
+        profile.bump = ctx.bumps.user_profile;
+
+        emit!(UserProfileCreated {
+            user: profile.user,
+            profile: profile.key(),
+            actor: ctx.accounts.user.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the sales aggregate for one payment currency (anyone; payer covers rent)
+    /// `mint` is the SPL payment mint, or Pubkey::default() for native SOL
+    pub fn init_volume_stats(ctx: Context<InitVolumeStats>, mint: Pubkey) -> Result<()> {
+        let stats = &mut ctx.accounts.volume_stats;
+        stats.mint = mint;
+        stats.total_volume = 0;
+        stats.total_sales = 0;
+        stats.total_fees = 0;
+        stats.last_sale_at = None;
+        stats.version = ACCOUNT_LAYOUT_VERSION;
+        stats.bump = ctx.bumps.volume_stats;
+
+        emit!(VolumeStatsInitialized {
+            mint,
+            volume_stats: stats.key(),
+            actor: ctx.accounts.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Publish the compiled behavioral constants on-chain (anyone; payer covers rent)
+    pub fn init_protocol_params(ctx: Context<InitProtocolParams>) -> Result<()> {
+        let clock = Clock::get()?;
+        let params = &mut ctx.accounts.protocol_params;
+        params.sync(&ctx.accounts.config, clock.unix_timestamp);
+        params.version = ACCOUNT_LAYOUT_VERSION;
+        params.bump = ctx.bumps.protocol_params;
+
+        emit!(ProtocolParamsSynced {
+            protocol_params: params.key(),
+            params_version: params.params_version,
+            actor: ctx.accounts.payer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Refresh ProtocolParams after an upgrade changed the compiled constants or the admin
+    /// changed a mirrored config limit (anyone)
+    pub fn sync_protocol_params(ctx: Context<SyncProtocolParams>) -> Result<()> {
+        let clock = Clock::get()?;
+        let params = &mut ctx.accounts.protocol_params;
+        params.sync(&ctx.accounts.config, clock.unix_timestamp);
+
+        emit!(ProtocolParamsSynced {
+            protocol_params: params.key(),
+            params_version: params.params_version,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Rewrite an account created under an older layout in the current one (anyone)
+    /// Accounts are resized to the current space; surplus rent stays until the account closes.
+    /// Only the accounts of the original (v0) deployment have an older layout. Future layout
+    /// changes bump ACCOUNT_LAYOUT_VERSION, keep the previous layout in legacy.rs and add a
+    /// branch here keyed on the stored version.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        let target = ctx.accounts.target.to_account_info();
+        let payer = ctx.accounts.payer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        let (discriminator, len) = {
+            let data = target.try_borrow_data()?;
+            require!(data.len() >= 8, AppMarketError::NothingToMigrate);
+            (data[..8].to_vec(), data.len())
+        };
+
+        // v0 accounts are told apart by size: they were allocated at 8 + INIT_SPACE
+        if discriminator == MarketConfig::DISCRIMINATOR && len == 8 + MarketConfigV0::INIT_SPACE {
+            migrate_layout::<MarketConfigV0, MarketConfig>(&target, &payer, &system_program)?;
+        } else if discriminator == Listing::DISCRIMINATOR && len == 8 + ListingV0::INIT_SPACE {
+            migrate_layout::<ListingV0, Listing>(&target, &payer, &system_program)?;
+        } else if discriminator == Transaction::DISCRIMINATOR
+            && len == 8 + TransactionV0::INIT_SPACE
+        {
+            migrate_layout::<TransactionV0, Transaction>(&target, &payer, &system_program)?;
+        } else if discriminator == Dispute::DISCRIMINATOR && len == 8 + DisputeV0::INIT_SPACE {
+            migrate_layout::<DisputeV0, Dispute>(&target, &payer, &system_program)?;
+        } else if discriminator == PendingWithdrawal::DISCRIMINATOR
+            && len == 8 + PendingWithdrawalV0::INIT_SPACE
+        {
+            migrate_layout::<PendingWithdrawalV0, PendingWithdrawal>(&target, &payer, &system_program)?;
+        } else if discriminator == Offer::DISCRIMINATOR && len == 8 + OfferV0::INIT_SPACE {
+            migrate_layout::<OfferV0, Offer>(&target, &payer, &system_program)?;
+        } else {
+            // Escrow and OfferEscrow only gained the leading version byte
+            let space = version_byte_space(&discriminator).ok_or(AppMarketError::NothingToMigrate)?;
+            require!(len == 8 + space - 1, AppMarketError::NothingToMigrate);
+            insert_version_byte(&target, &payer, &system_program, space)?;
+        }
+
+        emit!(AccountMigrated {
+            account: target.key(),
+            version: ACCOUNT_LAYOUT_VERSION,
+            actor: ctx.accounts.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Redeem loyalty points for a platform fee discount on the next listing
+    /// Platform fees are charged on the seller side, so this is the one way to spend points:
+    /// points a wallet earns as a buyer are spent when it next lists (create_listing)
+    pub fn redeem_points(ctx: Context<RedeemPoints>, points: u64) -> Result<()> {
+        let points_per_bps = ctx.accounts.config.loyalty_points_per_bps;
+        require!(points_per_bps > 0, AppMarketError::LoyaltyRedemptionDisabled);
+        require!(points > 0, AppMarketError::InvalidPointsAmount);
+
+        let profile = &mut ctx.accounts.user_profile;
+        require!(
+            profile.points >= points,
+            AppMarketError::InsufficientPoints
+        );
+
+        // Only whole basis points can be redeemed; leftover points stay on the profile
+        let discount_bps = points
+            .checked_div(points_per_bps)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(discount_bps > 0, AppMarketError::InvalidPointsAmount);
+
+        let new_discount = profile.fee_discount_bps
+            .checked_add(discount_bps)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            new_discount <= MAX_LOYALTY_DISCOUNT_BPS,
+            AppMarketError::LoyaltyDiscountTooHigh
+        );
+
+        let points_spent = discount_bps
+            .checked_mul(points_per_bps)
+            .ok_or(AppMarketError::MathOverflow)?;
+        profile.points = profile.points
+            .checked_sub(points_spent)
+            .ok_or(AppMarketError::MathOverflow)?;
+        profile.fee_discount_bps = new_discount;
+
+        emit!(LoyaltyPointsRedeemed {
+            user: profile.user,
+            user_profile: ctx.accounts.user_profile.key(),
+            points_spent,
+            discount_bps,
+            total_discount_bps: new_discount,
+            actor: ctx.accounts.user.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set (or clear) the caller's X25519 contact key for encrypted handover messaging
+    pub fn set_contact_key(ctx: Context<SetContactKey>, contact_key: Option<[u8; 32]>) -> Result<()> {
+        let profile = &mut ctx.accounts.user_profile;
+        profile.contact_key = contact_key;
+
+        emit!(ContactKeySet {
+            user: profile.user,
+            user_profile: ctx.accounts.user_profile.key(),
+            contact_key,
+            actor: ctx.accounts.user.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Copy both parties' current contact keys onto a transaction (buyer or seller).
+    /// Covers transactions created without profiles attached and key rotation mid-deal.
+    pub fn sync_contact_keys(ctx: Context<SyncContactKeys>) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let caller = ctx.accounts.caller.key();
+        require!(
+            caller == transaction.buyer || caller == transaction.seller,
+            AppMarketError::NotPartyToTransaction
+        );
+
+        apply_contact_keys(
+            transaction,
+            Some(&ctx.accounts.buyer_profile),
+            Some(&ctx.accounts.seller_profile),
+        )?;
+
+        emit!(ContactKeysSynced {
+            transaction: transaction.key(),
+            buyer_contact_key: transaction.buyer_contact_key,
+            seller_contact_key: transaction.seller_contact_key,
+            actor: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create a new listing with escrow initialized atomically
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_listing(
+        ctx: Context<CreateListing>,
+        salt: u64,
+        listing_type: ListingType,
+        starting_price: u64,
+        reserve_price: Option<u64>,
+        buy_now_price: Option<u64>,
+        duration_seconds: i64,
+        requires_github: bool,
+        required_github_username: String,
+        payment_mint: Option<Pubkey>,
+        deposit_bps: Option<u64>,
+        vesting_days: Option<u16>,
+        arbitrator: Option<Pubkey>,
+        quantity: Option<u64>,
+        holdback_bps: Option<u64>,
+        payout_wallet: Option<Pubkey>,
+        tags: Vec<String>,
+        start_time: Option<i64>,
+        draft: bool,
+        category: Category,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        // SECURITY: Seller cannot arbitrate their own sale
+        require!(
+            arbitrator != Some(ctx.accounts.seller.key()),
+            AppMarketError::InvalidArbitrator
+        );
+        validate_listing_terms(
+            &listing_type,
+            starting_price,
+            reserve_price,
+            buy_now_price,
+            duration_seconds,
+            deposit_bps,
+        )?;
+
+        // Multi-unit listings: buy_now_price is the per-unit price (fixed price only)
+        let quantity = quantity.unwrap_or(1);
+        require!(
+            quantity > 0 && quantity <= MAX_LISTING_QUANTITY,
+            AppMarketError::InvalidQuantity
+        );
+        if quantity > 1 {
+            require!(
+                listing_type == ListingType::BuyNow,
+                AppMarketError::InvalidQuantity
+            );
+        }
+
+        // SECURITY: Reject absurd prices (fat fingers, wash trades inflating volume stats)
+        let highest_price = starting_price
+            .max(reserve_price.unwrap_or(0))
+            .max(buy_now_price.unwrap_or(0));
+        check_price_cap(
+            &ctx.accounts.config,
+            ctx.accounts.price_cap_override.as_deref(),
+            highest_price,
+        )?;
+
+        // Optional earn-out: seller proceeds stream linearly after completion
+        if let Some(days) = vesting_days {
+            require!(
+                days > 0 && days <= MAX_VESTING_DAYS,
+                AppMarketError::InvalidVestingPeriod
+            );
+        }
+
+        // Optional warranty holdback: a slice of proceeds stays escrowed after completion
+        if let Some(bps) = holdback_bps {
+            require!(
+                bps > 0 && bps <= MAX_HOLDBACK_BPS && vesting_days.is_none(),
+                AppMarketError::InvalidHoldback
+            );
+        }
+
+        // Seller proceeds can go to a separate (e.g. cold) wallet
+        if let Some(wallet) = payout_wallet {
+            require!(wallet != Pubkey::default(), AppMarketError::InvalidPayoutWallet);
+        }
+
+        validate_listing_tags(&tags)?;
+
+        // SPL prices are raw token units: record the mint's decimals so minimums scale
+        let payment_decimals = match payment_mint {
+            Some(mint) => {
+                let mint_account = ctx.accounts.payment_mint_account
+                    .as_ref()
+                    .ok_or(AppMarketError::InvalidPaymentMint)?;
+                require_keys_eq!(mint_account.key(), mint, AppMarketError::InvalidPaymentMint);
+                mint_account.decimals
+            },
+            None => NATIVE_DECIMALS,
+        };
+
+        // SECURITY: Validate GitHub username format if provided
+        // Rules: 1-39 chars, alphanumeric or hyphen, cannot start/end with hyphen, no consecutive hyphens
+        if requires_github && !required_github_username.is_empty() {
+            let username = &required_github_username;
+            // Max 39 chars (GitHub's actual limit)
+            require!(
+                username.len() <= 39,
+                AppMarketError::InvalidGithubUsername
+            );
+            // Only alphanumeric or hyphen
+            require!(
+                username.chars().all(|c| c.is_alphanumeric() || c == '-'),
+                AppMarketError::InvalidGithubUsername
+            );
+            // Cannot start with hyphen
+            require!(
+                !username.starts_with('-'),
+                AppMarketError::InvalidGithubUsername
+            );
+            // Cannot end with hyphen
+            require!(
+                !username.ends_with('-'),
+                AppMarketError::InvalidGithubUsername
+            );
+            // No consecutive hyphens
+            require!(
+                !username.contains("--"),
+                AppMarketError::InvalidGithubUsername
+            );
+        }
+
+        let listing = &mut ctx.accounts.listing;
+        let escrow = &mut ctx.accounts.escrow;
+        let clock = Clock::get()?;
+
+        // Scheduled drop: sales and bids open at start_time (None = immediately)
+        let start_time = start_time.unwrap_or(clock.unix_timestamp);
+        require!(
+            start_time >= clock.unix_timestamp
+                && start_time <= clock.unix_timestamp + MAX_START_DELAY_SECONDS,
+            AppMarketError::InvalidStartTime
+        );
+
+        // Initialize listing
+        listing.seller = ctx.accounts.seller.key();
+        listing.salt = salt;
+        listing.listing_type = listing_type.clone();
+        listing.starting_price = starting_price;
+        listing.reserve_price = reserve_price;
+        listing.buy_now_price = buy_now_price;
+        listing.current_bid = 0;
+        listing.current_bidder = None;
+        listing.current_bid_exposure = 0;
+        listing.created_at = clock.unix_timestamp;
+
+        // SECURITY: Auction timer doesn't start until reserve bid placed
+        listing.auction_started = false;
+        listing.auction_start_time = None;
+        listing.extension_count = 0;
+        listing.max_extensions = ctx.accounts.config.max_anti_snipe_extensions;
+        listing.start_time = start_time;
+        listing.end_time = listing.sale_window_end(start_time, duration_seconds)?;
+        // Original duration, reused when a listing is reactivated
+        listing.duration_seconds = duration_seconds;
+        // Drafts stay invisible to buyers until activate_listing (which restarts the window)
+        listing.status = if draft { ListingStatus::Draft } else { ListingStatus::Active };
+
+        // SECURITY: Lock fees at listing creation time
+        // Use discounted 3% fee for APP token payments, standard 5% for others
+        // SECURITY: APP token fee discount is only valid when payment is actually
+        // made in APP tokens via SPL token transfer. The buy_now and place_bid
+        // instructions must verify the payment mint matches the actual transfer.
+        // Category fee tiers (FeeSchedule) apply to everything but APP token payments
+        listing.platform_fee_bps = if payment_mint == Some(APP_TOKEN_MINT) {
+            APP_FEE_BPS as u16
+        } else {
+            category_platform_fee_bps(
+                &ctx.accounts.config,
+                ctx.accounts.fee_schedule.as_deref(),
+                &category,
+            )?
+        };
+
+        // Apply (and consume) any fee discount the seller redeemed from loyalty points
+        if let Some(profile) = ctx.accounts.seller_profile.as_mut() {
+            if profile.fee_discount_bps > 0 {
+                let discount = profile.fee_discount_bps.min(listing.platform_fee_bps as u64) as u16;
+                listing.platform_fee_bps = listing.platform_fee_bps
+                    .checked_sub(discount)
+                    .ok_or(AppMarketError::MathOverflow)?;
+                profile.fee_discount_bps = 0;
+
+                emit!(LoyaltyDiscountApplied {
+                    user: profile.user,
+                    listing: listing.key(),
+                    discount_bps: discount as u64,
+                    actor: ctx.accounts.seller.key(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+        listing.dispute_fee_bps = ctx.accounts.config.dispute_fee_bps;
+        listing.payment_mint = payment_mint;
+        listing.payment_decimals = payment_decimals;
+        // 0 = full-price escrow at bid time
+        listing.deposit_bps = deposit_bps.unwrap_or(0) as u16;
+        // 0 = proceeds released in full at completion
+        listing.vesting_days = vesting_days.unwrap_or(0);
+        // 0 = no warranty holdback
+        listing.holdback_bps = holdback_bps.unwrap_or(0) as u16;
+        // Proceeds and escrow rent go here (defaults to the seller)
+        listing.payout_wallet = payout_wallet.unwrap_or(ctx.accounts.seller.key());
+        listing.pending_payout_wallet = None;
+        listing.pending_payout_wallet_at = None;
+        // Neutral arbitrator for disputes (buyer agrees by purchasing); None = platform
+        listing.arbitrator = arbitrator;
+        // 1 = single sale; more = units sold via buy_unit
+        listing.quantity = quantity;
+        listing.units_sold = 0;
+        // Discovery tags for indexers (editable until the first bid)
+        listing.tags = tags;
+        listing.category = category;
+
+        // GitHub requirements
+        listing.requires_github = requires_github;
+        listing.required_github_username = required_github_username;
+
+        // Withdrawal counter for unique PDA seeds
+        listing.withdrawal_count = 0;
+        // Offer counter
+        listing.offer_count = 0;
+        // Consecutive offer tracking
+        listing.last_offer_buyer = None;
+        listing.consecutive_offer_count = 0;
+        // Consecutive bid tracking
+        listing.last_bidder = None;
+        listing.consecutive_bid_count = 0;
+
+        listing.version = ACCOUNT_LAYOUT_VERSION;
+        listing.bump = ctx.bumps.listing;
+
+        // Initialize escrow (seller pays rent)
+        escrow.listing = listing.key();
+        escrow.amount = 0;
+        escrow.version = ACCOUNT_LAYOUT_VERSION;
+        escrow.bump = ctx.bumps.escrow;
+
+        emit!(ListingCreated {
+            listing: listing.key(),
+            seller: listing.seller,
+            listing_id: listing.listing_id(),
+            listing_type,
+            starting_price,
+            end_time: listing.end_time,
+            platform_fee_bps: listing.platform_fee_bps as u64,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create several plain listings (with their escrows) in one transaction
+    /// remaining_accounts: [listing PDA, escrow PDA] per entry, in order. Entries get the
+    /// create_listing defaults (SOL, immediate start, no extras); the seller pays all rent.
+    pub fn create_listings_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateListingsBatch<'info>>,
+        listings: Vec<BatchListingParams>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        require!(
+            !listings.is_empty() && listings.len() <= MAX_BATCH_LISTINGS,
+            AppMarketError::InvalidBatchSize
+        );
+
+        let accounts = ctx.remaining_accounts;
+        require!(
+            accounts.len() == listings.len() * 2,
+            AppMarketError::InvalidBatchAccounts
+        );
+
+        let config = &ctx.accounts.config;
+        let seller_key = ctx.accounts.seller.key();
+        let clock = Clock::get()?;
+
+        for (params, group) in listings.iter().zip(accounts.chunks_exact(2)) {
+            validate_listing_terms(
+                &params.listing_type,
+                params.starting_price,
+                params.reserve_price,
+                params.buy_now_price,
+                params.duration_seconds,
+                None,
+            )?;
+            let highest_price = params.starting_price
+                .max(params.reserve_price.unwrap_or(0))
+                .max(params.buy_now_price.unwrap_or(0));
+            check_price_cap(
+                config,
+                ctx.accounts.price_cap_override.as_deref(),
+                highest_price,
+            )?;
+
+            // SECURITY: Accounts must be this seller's listing PDA for the salt and its escrow
+            let salt_bytes = params.salt.to_le_bytes();
+            let (listing_pda, listing_bump) = Pubkey::find_program_address(
+                &[b"listing", seller_key.as_ref(), &salt_bytes],
+                ctx.program_id,
+            );
+            require_keys_eq!(group[0].key(), listing_pda, AppMarketError::InvalidBatchAccounts);
+            let (escrow_pda, escrow_bump) = Pubkey::find_program_address(
+                &[b"escrow", listing_pda.as_ref()],
+                ctx.program_id,
+            );
+            require_keys_eq!(group[1].key(), escrow_pda, AppMarketError::InvalidBatchAccounts);
+            require!(
+                group[0].data_is_empty() && group[1].data_is_empty(),
+                AppMarketError::InvalidBatchAccounts
+            );
+
+            create_pda_account(
+                ctx.accounts.seller.to_account_info(),
+                group[0].clone(),
+                ctx.accounts.system_program.to_account_info(),
+                &[b"listing", seller_key.as_ref(), &salt_bytes, &[listing_bump]],
+                8 + Listing::INIT_SPACE,
+                ctx.program_id,
+            )?;
+            group[0].try_borrow_mut_data()?[..8].copy_from_slice(Listing::DISCRIMINATOR);
+            create_pda_account(
+                ctx.accounts.seller.to_account_info(),
+                group[1].clone(),
+                ctx.accounts.system_program.to_account_info(),
+                &[b"escrow", listing_pda.as_ref(), &[escrow_bump]],
+                8 + Escrow::INIT_SPACE,
+                ctx.program_id,
+            )?;
+            group[1].try_borrow_mut_data()?[..8].copy_from_slice(Escrow::DISCRIMINATOR);
+
+            // Fresh accounts are zeroed: unset fields start at 0 / None / empty
+            let mut listing = Account::<Listing>::try_from(&group[0])?;
+            listing.seller = seller_key;
+            listing.salt = params.salt;
+            listing.listing_type = params.listing_type.clone();
+            listing.starting_price = params.starting_price;
+            listing.reserve_price = params.reserve_price;
+            listing.buy_now_price = params.buy_now_price;
+            listing.created_at = clock.unix_timestamp;
+            listing.start_time = clock.unix_timestamp;
+            listing.end_time = listing.sale_window_end(clock.unix_timestamp, params.duration_seconds)?;
+            listing.duration_seconds = params.duration_seconds;
+            listing.status = ListingStatus::Active;
+            // SECURITY: Lock fees at listing creation time
+            listing.platform_fee_bps = category_platform_fee_bps(
+                config,
+                ctx.accounts.fee_schedule.as_deref(),
+                &params.category,
+            )?;
+            listing.category = params.category.clone();
+            listing.dispute_fee_bps = config.dispute_fee_bps;
+            listing.max_extensions = config.max_anti_snipe_extensions;
+            listing.payout_wallet = seller_key;
+            listing.payment_decimals = NATIVE_DECIMALS;
+            listing.quantity = 1;
+            listing.version = ACCOUNT_LAYOUT_VERSION;
+            listing.bump = listing_bump;
+            listing.exit(ctx.program_id)?;
+
+            let mut escrow = Account::<Escrow>::try_from(&group[1])?;
+            escrow.listing = listing_pda;
+            escrow.amount = 0;
+            escrow.version = ACCOUNT_LAYOUT_VERSION;
+            escrow.bump = escrow_bump;
+            escrow.exit(ctx.program_id)?;
+
+            emit!(ListingCreated {
+                listing: listing_pda,
+                seller: seller_key,
+                listing_id: listing.listing_id(),
+                listing_type: params.listing_type.clone(),
+                starting_price: params.starting_price,
+                end_time: listing.end_time,
+                platform_fee_bps: listing.platform_fee_bps as u64,
+                actor: ctx.accounts.seller.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Place a bid on a listing (uses withdrawal pattern for refunds)
+    /// The bidder may be a PDA signing via CPI (DAO treasury, program vault); transaction
+    /// fees are paid by the outer fee payer, so only the bidder's own outflows are checked.
+    /// With `session`, the signer is a session key bidding for the session owner.
+    /// `withdrawal_bump` (bump of the pending_withdrawal PDA) skips the on-chain bump search;
+    /// any valid bump is accepted, so clients should pass the canonical one.
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64, withdrawal_bump: Option<u8>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        // CHECKS: All validations first
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+        // Scheduled drops: no bids before the listing's start time
+        require!(listing.has_started(clock.unix_timestamp), AppMarketError::ListingNotStarted);
+
+        // Check auction timing
+        if listing.auction_started {
+            require!(
+                clock.unix_timestamp < listing.end_time,
+                AppMarketError::AuctionEnded
+            );
+        }
+
+        // Session-key bids act for the session owner and draw from the session vault
+        let bidder_key = match ctx.accounts.session.as_ref() {
+            Some(session) => {
+                require!(
+                    session.session_key == ctx.accounts.bidder.key(),
+                    AppMarketError::InvalidSessionKey
+                );
+                require!(
+                    clock.unix_timestamp < session.expires_at,
+                    AppMarketError::SessionExpired
+                );
+                session.owner
+            },
+            None => ctx.accounts.bidder.key(),
+        };
+
+        require!(bidder_key != listing.seller, AppMarketError::SellerCannotBid);
+        check_buyer_attestation(
+            listing,
+            ctx.accounts.buyer_attestation.as_deref(),
+            bidder_key,
+            clock.unix_timestamp,
+        )?;
+
+        // Amount actually locked in escrow (full bid, or deposit in deposit mode)
+        let escrowed_amount = escrowed_bid_amount(listing.deposit_bps, amount)?;
+        require!(escrowed_amount > 0, AppMarketError::BidTooLow);
+
+        // Bid-escrow listings: the bidder's BidEscrow is only topped up to the new bid
+        let bid_escrow_balance = if listing.uses_bid_escrows {
+            require!(ctx.accounts.session.is_none(), AppMarketError::BidEscrowUnsupported);
+            let bid_escrow = ctx.accounts.bid_escrow
+                .as_ref()
+                .ok_or(AppMarketError::BidEscrowRequired)?;
+            if bid_escrow.data_is_empty() {
+                0
+            } else {
+                BidEscrow::try_deserialize(&mut &bid_escrow.try_borrow_data()?[..])?.amount
+            }
+        } else {
+            0
+        };
+        // New funds this bid locks
+        let locked_amount = escrowed_amount.saturating_sub(bid_escrow_balance);
+
+        // SECURITY: Pre-check bidder can fund the escrowed amount
+        // Withdrawal PDA rent comes from the payer account; tx fees from the fee payer
+        match ctx.accounts.session.as_ref() {
+            Some(session) => require!(
+                session.remaining() >= escrowed_amount,
+                AppMarketError::SessionLimitExceeded
+            ),
+            None => require!(
+                ctx.accounts.bidder.lamports() >= locked_amount,
+                AppMarketError::InsufficientBalance
+            ),
+        }
+
+        // SECURITY: Per-wallet exposure cap (bids locked across all listings)
+        let max_exposure = ctx.accounts.config.max_bidder_exposure;
+        match ctx.accounts.bidder_profile.as_ref() {
+            Some(profile) => {
+                require_keys_eq!(profile.user, bidder_key, AppMarketError::InvalidUserProfile);
+                if max_exposure > 0 {
+                    let exposure = profile.locked_exposure
+                        .checked_add(locked_amount)
+                        .ok_or(AppMarketError::MathOverflow)?;
+                    require!(exposure <= max_exposure, AppMarketError::ExposureLimitExceeded);
+                }
+            },
+            None => require!(max_exposure == 0, AppMarketError::UserProfileRequired),
+        }
+
         // SECURITY: Prevent DoS via bid spam
         require!(
-            listing.withdrawal_count < MAX_BIDS_PER_LISTING,
-            AppMarketError::MaxBidsExceeded
+            listing.withdrawal_count < MAX_BIDS_PER_LISTING,
+            AppMarketError::MaxBidsExceeded
+        );
+
+        // SECURITY: Track consecutive bids from same bidder (max 10 without being outbid)
+        if let Some(last_bidder) = listing.last_bidder {
+            if last_bidder == bidder_key {
+                // Same bidder making consecutive bids
+                require!(
+                    listing.consecutive_bid_count < MAX_CONSECUTIVE_BIDS,
+                    AppMarketError::MaxConsecutiveBidsExceeded
+                );
+            }
+            // Note: The counter will be updated in EFFECTS section below
+        }
+
+        // SECURITY: Enforce minimum bid increment to prevent spam
+        if listing.current_bid > 0 {
+            let increment = listing.current_bid
+                .checked_mul(MIN_BID_INCREMENT_BPS)
+                .ok_or(AppMarketError::MathOverflow)?
+                .checked_div(BASIS_POINTS_DIVISOR)
+                .ok_or(AppMarketError::MathOverflow)?;
+
+            let min_increment = increment.max(listing.min_bid_increment()?);
+            let min_bid = listing.current_bid
+                .checked_add(min_increment)
+                .ok_or(AppMarketError::MathOverflow)?;
+
+            require!(amount >= min_bid, AppMarketError::BidIncrementTooSmall);
+        } else {
+            require!(amount >= listing.starting_price, AppMarketError::BidTooLow);
+        }
+
+        // EFFECTS: Update state BEFORE external calls
+        let old_bid = listing.current_bid;
+        let old_bidder = listing.current_bidder;
+        let old_exposure = listing.current_bid_exposure;
+
+        // Top-N bid book (listings that opted in must always pass it)
+        if listing.has_bid_book {
+            let bid_book = ctx.accounts.bid_book
+                .as_ref()
+                .ok_or(AppMarketError::BidBookRequired)?;
+            let mut book = bid_book.load_mut()?;
+            require!(book.listing == listing.key(), AppMarketError::InvalidBidBook);
+            // First bid of a (re)activated listing starts a fresh book
+            if old_bidder.is_none() {
+                book.clear();
+            }
+            book.record_bid(bidder_key, amount);
+
+            if book.has_history != 0 {
+                let bid_history = ctx.accounts.bid_history
+                    .as_ref()
+                    .ok_or(AppMarketError::BidHistoryRequired)?;
+                let mut history = bid_history.load_mut()?;
+                require!(history.listing == listing.key(), AppMarketError::InvalidBidHistory);
+                if old_bidder.is_none() {
+                    history.clear();
+                }
+                history.record_bid(bidder_key, amount, clock.unix_timestamp);
+            }
+        }
+
+        listing.current_bid = amount;
+        listing.current_bidder = Some(bidder_key);
+        listing.current_bid_exposure = if ctx.accounts.bidder_profile.is_some() {
+            locked_amount
+        } else {
+            0
+        };
+
+        // Update consecutive bid tracking
+        if let Some(last_bidder) = listing.last_bidder {
+            if last_bidder == bidder_key {
+                // Same bidder - increment counter
+                listing.consecutive_bid_count = listing.consecutive_bid_count
+                    .checked_add(1)
+                    .ok_or(AppMarketError::MathOverflow)?;
+            } else {
+                // Different bidder - reset counter
+                listing.last_bidder = Some(bidder_key);
+                listing.consecutive_bid_count = 1;
+            }
+        } else {
+            // First bid on this listing
+            listing.last_bidder = Some(bidder_key);
+            listing.consecutive_bid_count = 1;
+        }
+
+        // Start auction timer if reserve price met (or no reserve)
+        if !listing.auction_started {
+            let reserve_met = if let Some(reserve) = listing.reserve_price {
+                amount >= reserve
+            } else {
+                true
+            };
+
+            if reserve_met {
+                listing.auction_started = true;
+                listing.auction_start_time = Some(clock.unix_timestamp);
+                listing.end_time = clock.unix_timestamp
+                    .checked_add(listing.duration_seconds)
+                    .ok_or(AppMarketError::MathOverflow)?;
+            }
+        }
+
+        // Update escrow amount tracking BEFORE transfers (bid-escrow funds stay out of it
+        // until the winning bid is committed)
+        if !listing.uses_bid_escrows {
+            ctx.accounts.escrow.credit(
+                escrowed_amount,
+                EscrowChangeReason::Bid,
+                ctx.accounts.bidder.key(),
+            )?;
+        }
+
+        // SECURITY: Anti-sniping - extend auction if bid placed near end (only if started),
+        // up to the listing's extension cap; after that end_time is hard
+        let can_extend = listing.max_extensions == 0
+            || listing.extension_count < listing.max_extensions;
+        if listing.auction_started
+            && can_extend
+            && clock.unix_timestamp > listing.end_time - ANTI_SNIPE_WINDOW
+        {
+            listing.end_time = clock.unix_timestamp
+                .checked_add(ANTI_SNIPE_EXTENSION)
+                .ok_or(AppMarketError::MathOverflow)?;
+            listing.extension_count = listing.extension_count.saturating_add(1);
+
+            if listing.extension_count == listing.max_extensions {
+                emit!(AntiSnipeCapReached {
+                    listing: listing.key(),
+                    extensions: listing.extension_count,
+                    end_time: listing.end_time,
+                    actor: ctx.accounts.bidder.key(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        if let Some(profile) = ctx.accounts.bidder_profile.as_mut() {
+            profile.locked_exposure = profile.locked_exposure
+                .checked_add(locked_amount)
+                .ok_or(AppMarketError::MathOverflow)?;
+        }
+        if let Some(session) = ctx.accounts.session.as_mut() {
+            session.spent = session.spent
+                .checked_add(escrowed_amount)
+                .ok_or(AppMarketError::MathOverflow)?;
+        }
+
+        // INTERACTIONS: External calls LAST
+        match ctx.accounts.session.as_ref() {
+            Some(session) => {
+                let vault = ctx.accounts.session_vault
+                    .as_ref()
+                    .ok_or(AppMarketError::InvalidSessionVault)?;
+                let session_key = session.key();
+                let seeds = &[
+                    b"session_vault".as_ref(),
+                    session_key.as_ref(),
+                    &[session.vault_bump],
+                ];
+                // SECURITY: Vault must be this session's PDA
+                let expected_vault = Pubkey::create_program_address(seeds, ctx.program_id)
+                    .map_err(|_| AppMarketError::InvalidSessionVault)?;
+                require_keys_eq!(vault.key(), expected_vault, AppMarketError::InvalidSessionVault);
+
+                let signer = &[&seeds[..]];
+                let cpi_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: vault.to_account_info(),
+                        to: ctx.accounts.escrow.to_account_info(),
+                    },
+                    signer,
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, escrowed_amount)?;
+            },
+            None if listing.uses_bid_escrows => {
+                let bid_escrow = ctx.accounts.bid_escrow
+                    .as_ref()
+                    .ok_or(AppMarketError::BidEscrowRequired)?;
+                let listing_key = listing.key();
+                let bump = ctx.bumps.bid_escrow.ok_or(AppMarketError::BidEscrowRequired)?;
+                if bid_escrow.data_is_empty() {
+                    create_pda_account(
+                        ctx.accounts.payer.to_account_info(),
+                        bid_escrow.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                        &[b"bid_escrow", listing_key.as_ref(), bidder_key.as_ref(), &[bump]],
+                        8 + BidEscrow::INIT_SPACE,
+                        ctx.program_id,
+                    )?;
+                }
+
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.bidder.to_account_info(),
+                        to: bid_escrow.to_account_info(),
+                    },
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, locked_amount)?;
+
+                // A new bid re-arms the escrow even if an earlier win was committed
+                let record = BidEscrow {
+                    version: ACCOUNT_LAYOUT_VERSION,
+                    listing: listing_key,
+                    bidder: bidder_key,
+                    amount: bid_escrow_balance
+                        .checked_add(locked_amount)
+                        .ok_or(AppMarketError::MathOverflow)?,
+                    committed: false,
+                    bump,
+                };
+                record.try_serialize(&mut &mut bid_escrow.try_borrow_mut_data()?[..])?;
+            },
+            None => {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.bidder.to_account_info(),
+                        to: ctx.accounts.escrow.to_account_info(),
+                    },
+                );
+                anchor_lang::system_program::transfer(cpi_ctx, escrowed_amount)?;
+            },
+        }
+
+        // SECURITY: Use withdrawal pattern for refunds (prevents DoS, only create when needed)
+        // Bid-escrow listings skip this: the outbid bidder reclaims their own BidEscrow
+        if let Some(previous_bidder) = old_bidder {
+            if old_bid > 0 && !listing.uses_bid_escrows {
+                // Refund what the previous bidder actually locked (deposit in deposit mode)
+                let old_escrowed = escrowed_bid_amount(listing.deposit_bps, old_bid)?;
+
+                // Increment withdrawal counter to prevent PDA collision
+                listing.withdrawal_count = listing.withdrawal_count
+                    .checked_add(1)
+                    .ok_or(AppMarketError::MathOverflow)?;
+
+                // Derive PDA and verify
+                let (withdrawal_pda, bump) = withdrawal_pda(
+                    &listing.key(),
+                    listing.withdrawal_count,
+                    withdrawal_bump,
+                    ctx.program_id,
+                )?;
+
+                require!(
+                    withdrawal_pda == ctx.accounts.pending_withdrawal.key(),
+                    AppMarketError::InvalidPreviousBidder
+                );
+
+                // Create the withdrawal account
+                let rent = Rent::get()?;
+                let space = 8 + PendingWithdrawal::INIT_SPACE;
+                let lamports = rent.minimum_balance(space);
+
+                anchor_lang::system_program::create_account(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::CreateAccount {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: ctx.accounts.pending_withdrawal.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                    space as u64,
+                    ctx.program_id,
+                )?;
+
+                // Initialize withdrawal data
+                let mut withdrawal_data = ctx.accounts.pending_withdrawal.try_borrow_mut_data()?;
+                let withdrawal = PendingWithdrawal {
+                    version: ACCOUNT_LAYOUT_VERSION,
+                    user: previous_bidder,
+                    listing: listing.key(),
+                    amount: old_escrowed,
+                    exposure: old_exposure,
+                    withdrawal_id: listing.withdrawal_count,
+                    created_at: clock.unix_timestamp,
+                    expires_at: clock.unix_timestamp + 3600, // 1 hour
+                    rent_payer: ctx.accounts.payer.key(),
+                    bump,
+                };
+
+                withdrawal.try_serialize(&mut &mut withdrawal_data[..])?;
+
+                emit!(WithdrawalCreated {
+                    user: previous_bidder,
+                    listing: listing.key(),
+                    pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+                    amount: old_escrowed,
+                    withdrawal_id: listing.withdrawal_count,
+                    actor: ctx.accounts.bidder.key(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        emit!(BidPlaced {
+            listing: listing.key(),
+            bidder: bidder_key,
+            amount,
+            actor: ctx.accounts.bidder.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Authorize an ephemeral session key to bid for the owner (create_session)
+    /// The owner pre-funds the session vault with max_spend; session-key bids draw from it
+    /// and outbid refunds go to the owner as usual. Expires after at most 24 hours.
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        max_spend: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let clock = Clock::get()?;
+        require!(
+            session_key != Pubkey::default() && session_key != ctx.accounts.owner.key(),
+            AppMarketError::InvalidSessionKey
+        );
+        require!(max_spend > 0, AppMarketError::InvalidPrice);
+        require!(
+            expires_at > clock.unix_timestamp
+                && expires_at <= clock.unix_timestamp + MAX_SESSION_DURATION_SECONDS,
+            AppMarketError::InvalidSessionExpiry
+        );
+
+        let session = &mut ctx.accounts.session;
+        session.owner = ctx.accounts.owner.key();
+        session.session_key = session_key;
+        session.max_spend = max_spend;
+        session.spent = 0;
+        session.expires_at = expires_at;
+        session.version = ACCOUNT_LAYOUT_VERSION;
+        session.bump = ctx.bumps.session;
+        session.vault_bump = ctx.bumps.session_vault;
+
+        // Fund the vault: spendable balance plus rent so it always stays rent-exempt
+        let rent = Rent::get()?.minimum_balance(0);
+        let funding = max_spend
+            .checked_add(rent.saturating_sub(ctx.accounts.session_vault.lamports()))
+            .ok_or(AppMarketError::MathOverflow)?;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.session_vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, funding)?;
+
+        emit!(SessionCreated {
+            session: session.key(),
+            owner: session.owner,
+            session_key,
+            max_spend,
+            expires_at,
+            actor: ctx.accounts.owner.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close a bidding session and return the unspent vault balance (owner only, any time)
+    pub fn close_session(ctx: Context<CloseSession>) -> Result<()> {
+        let session_key = ctx.accounts.session.key();
+        let refund = ctx.accounts.session_vault.lamports();
+
+        let seeds = &[
+            b"session_vault".as_ref(),
+            session_key.as_ref(),
+            &[ctx.accounts.session.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.session_vault.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, refund)?;
+
+        emit!(SessionClosed {
+            session: session_key,
+            owner: ctx.accounts.owner.key(),
+            spent: ctx.accounts.session.spent,
+            refunded: refund,
+            actor: ctx.accounts.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw funds from pending withdrawal (pull pattern)
+    pub fn withdraw_funds(ctx: Context<WithdrawFunds>) -> Result<()> {
+        let withdrawal = &ctx.accounts.pending_withdrawal;
+        let clock = Clock::get()?;
+
+        // CHECKS: Validate user
+        require!(
+            ctx.accounts.user.key() == withdrawal.user,
+            AppMarketError::NotWithdrawalOwner
+        );
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= withdrawal.amount + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // INTERACTIONS: Transfer funds
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.user.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, withdrawal.amount)?;
+
+        // Update escrow tracking
+        ctx.accounts.escrow.debit(
+            withdrawal.amount,
+            EscrowChangeReason::WithdrawalClaimed,
+            ctx.accounts.user.key(),
+        )?;
+
+        // Refunded bid escrow no longer counts toward the user's exposure
+        release_withdrawal_exposure(
+            &ctx.accounts.pending_withdrawal,
+            ctx.accounts.user_profile.as_deref_mut(),
+        )?;
+
+        emit!(WithdrawalClaimed {
+            user: withdrawal.user,
+            listing: ctx.accounts.listing.key(),
+            pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+            amount: withdrawal.amount,
+            actor: ctx.accounts.user.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Check a listing escrow's bookkeeping against its lamports (permissionless tripwire)
+    /// remaining_accounts: the listing's outstanding PendingWithdrawals, in increasing
+    /// withdrawal_id order. Emits EscrowInconsistent on any mismatch; never fails on one.
+    pub fn verify_escrow<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyEscrow<'info>>,
+    ) -> Result<()> {
+        let listing_key = ctx.accounts.listing.key();
+        let escrow = &ctx.accounts.escrow;
+        let escrow_info = escrow.to_account_info();
+
+        // Sum outstanding refunds (still counted in escrow.amount until withdrawn)
+        let mut pending_total: u64 = 0;
+        let mut last_id: Option<u64> = None;
+        for info in ctx.remaining_accounts.iter() {
+            let withdrawal = Account::<PendingWithdrawal>::try_from(info)?;
+            require_keys_eq!(withdrawal.listing, listing_key, AppMarketError::InvalidBatchAccounts);
+            // SECURITY: Strictly increasing ids, so no withdrawal is counted twice
+            if let Some(last) = last_id {
+                require!(withdrawal.withdrawal_id > last, AppMarketError::InvalidBatchAccounts);
+            }
+            last_id = Some(withdrawal.withdrawal_id);
+            pending_total = pending_total
+                .checked_add(withdrawal.amount)
+                .ok_or(AppMarketError::MathOverflow)?;
+        }
+
+        let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+        let lamports = escrow_info.lamports();
+        let expected = escrow.amount
+            .checked_add(rent)
+            .ok_or(AppMarketError::MathOverflow)?;
+        let clock = Clock::get()?;
+
+        if lamports != expected || pending_total > escrow.amount {
+            emit!(EscrowInconsistent {
+                listing: listing_key,
+                tracked_amount: escrow.amount,
+                rent,
+                lamports,
+                pending_withdrawals: pending_total,
+                actor: ctx.accounts.caller.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        } else {
+            emit!(EscrowVerified {
+                listing: listing_key,
+                tracked_amount: escrow.amount,
+                pending_withdrawals: pending_total,
+                actor: ctx.accounts.caller.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Release a settled winning bid from the buyer's exposure (permissionless, once)
+    /// Once an auction settles the bid is a purchase obligation tracked by the
+    /// Transaction, so it no longer counts toward config.max_bidder_exposure.
+    pub fn release_bid_exposure(ctx: Context<ReleaseBidExposure>) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let released = transaction.bid_exposure;
+        require!(released > 0, AppMarketError::NoBidExposure);
+
+        transaction.bid_exposure = 0;
+        let profile = &mut ctx.accounts.buyer_profile;
+        profile.locked_exposure = profile.locked_exposure.saturating_sub(released);
+
+        emit!(BidExposureReleased {
+            user: profile.user,
+            transaction: transaction.key(),
+            amount: released,
+            locked_exposure: profile.locked_exposure,
+            actor: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Expire unclaimed withdrawal (anyone can call after expiry)
+    /// Returns funds to the original user and unblocks the escrow.
+    /// This prevents auctions from stalling when outbid users don't claim.
+    pub fn expire_withdrawal(ctx: Context<ExpireWithdrawal>) -> Result<()> {
+        let withdrawal = &ctx.accounts.pending_withdrawal;
+        let clock = Clock::get()?;
+
+        // CHECKS: Withdrawal must be expired
+        require!(
+            clock.unix_timestamp > withdrawal.expires_at,
+            AppMarketError::WithdrawalNotExpired
+        );
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= withdrawal.amount + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // INTERACTIONS: Transfer funds back to the original user
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, withdrawal.amount)?;
+
+        // Update escrow tracking
+        ctx.accounts.escrow.debit(
+            withdrawal.amount,
+            EscrowChangeReason::WithdrawalExpired,
+            ctx.accounts.caller.key(),
+        )?;
+
+        // Refunded bid escrow no longer counts toward the user's exposure
+        release_withdrawal_exposure(
+            &ctx.accounts.pending_withdrawal,
+            ctx.accounts.user_profile.as_deref_mut(),
+        )?;
+
+        emit!(WithdrawalExpired {
+            user: withdrawal.user,
+            listing: ctx.accounts.listing.key(),
+            pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+            amount: withdrawal.amount,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Escheat a long-unclaimed withdrawal to the treasury (anyone can call)
+    /// Only after the configured escheat period has elapsed past the withdrawal's expiry;
+    /// until then expire_withdrawal can still return the funds to the user
+    pub fn escheat_withdrawal(ctx: Context<EscheatWithdrawal>) -> Result<()> {
+        let withdrawal = &ctx.accounts.pending_withdrawal;
+        let clock = Clock::get()?;
+
+        // CHECKS
+        let escheat_period = ctx.accounts.config.escheat_period_seconds;
+        require!(escheat_period > 0, AppMarketError::EscheatDisabled);
+        let escheatable_at = withdrawal.expires_at
+            .checked_add(escheat_period)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp > escheatable_at,
+            AppMarketError::EscheatPeriodNotElapsed
+        );
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= withdrawal.amount + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // INTERACTIONS: Sweep unclaimed funds to the treasury
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, withdrawal.amount)?;
+
+        // Update escrow tracking
+        ctx.accounts.escrow.debit(
+            withdrawal.amount,
+            EscrowChangeReason::WithdrawalEscheated,
+            ctx.accounts.caller.key(),
+        )?;
+
+        // The bid is gone either way, so it stops counting toward the user's exposure
+        release_withdrawal_exposure(
+            &ctx.accounts.pending_withdrawal,
+            ctx.accounts.user_profile.as_deref_mut(),
+        )?;
+
+        emit!(WithdrawalEscheated {
+            user: withdrawal.user,
+            listing: ctx.accounts.listing.key(),
+            pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+            withdrawal_id: withdrawal.withdrawal_id,
+            amount: withdrawal.amount,
+            destination: ctx.accounts.treasury.key(),
+            expired_at: withdrawal.expires_at,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Close escrow after all pending withdrawals are cleared
+    /// Permissionless — anyone can call once escrow.amount == 0 and transaction is terminal
+    /// Caller receives PDA rent as incentive for cleanup
+    pub fn close_escrow(ctx: Context<CloseEscrow>) -> Result<()> {
+        let status = ctx.accounts.transaction.status.clone();
+        require!(
+            status == TransactionStatus::Completed
+                || status == TransactionStatus::Refunded
+                || status == TransactionStatus::Cancelled,
+            AppMarketError::TransactionNotComplete
+        );
+
+        require!(
+            ctx.accounts.escrow.amount == 0,
+            AppMarketError::PendingWithdrawalsExist
+        );
+        // Multi-unit listings keep selling while earlier unit sales settle
+        require!(
+            ctx.accounts.listing.status != ListingStatus::Active,
+            AppMarketError::ListingStillActive
+        );
+
+        emit!(EscrowClosed {
+            listing: ctx.accounts.listing.key(),
+            actor: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Buy now (instant purchase)
+    /// `recipient` lets the payer buy on behalf of another wallet (gift / third party);
+    /// the recipient becomes the transaction buyer for confirmations, disputes, and refunds.
+    /// The buyer may be a PDA signing via CPI; the balance pre-check covers only its own outflows
+    /// `expected_price` (the buy-now price the client saw) fails the purchase if it changed.
+    /// `agreement_hash` records the off-chain purchase agreement on the Transaction.
+    /// `withdrawal_bump` (bump of the pending_withdrawal PDA) skips the on-chain bump search;
+    /// any valid bump is accepted, so clients should pass the canonical one.
+    pub fn buy_now(
+        ctx: Context<BuyNow>,
+        recipient: Option<Pubkey>,
+        expected_price: Option<u64>,
+        agreement_hash: Option<[u8; 32]>,
+        withdrawal_bump: Option<u8>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        // CHECKS
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::ListingExpired);
+        require!(listing.has_started(clock.unix_timestamp), AppMarketError::ListingNotStarted);
+        require!(listing.buy_now_price.is_some(), AppMarketError::BuyNowNotEnabled);
+        require!(ctx.accounts.buyer.key() != listing.seller, AppMarketError::SellerCannotBuy);
+        check_buyer_attestation(
+            listing,
+            ctx.accounts.buyer_attestation.as_deref(),
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+        require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
+
+        // Paid in lamports: a non-SOL listing charges its SOL price if it lists one
+        let buy_now_price = listing.buy_now_price_in(Pubkey::default(), clock.unix_timestamp)?;
+        // SECURITY: Slippage guard against price changes racing this transaction
+        if let Some(expected) = expected_price {
+            require!(buy_now_price == expected, AppMarketError::StateChanged);
+        }
+
+        // SECURITY: Re-check the cap at sale time (it may have been lowered since listing)
+        check_price_cap(
+            &ctx.accounts.config,
+            ctx.accounts.price_cap_override.as_deref(),
+            buy_now_price,
+        )?;
+
+        // Auction buy-now: must outbid the standing high bid (which ends the auction)
+        let is_auction = listing.listing_type == ListingType::Auction;
+        if is_auction {
+            require!(
+                buy_now_price > listing.current_bid,
+                AppMarketError::BuyNowBelowCurrentBid
+            );
+        }
+
+        let deal_buyer = recipient.unwrap_or(ctx.accounts.buyer.key());
+        require!(deal_buyer != Pubkey::default(), AppMarketError::InvalidRecipient);
+        require!(deal_buyer != listing.seller, AppMarketError::InvalidRecipient);
+
+        // SECURITY: Validate payment mint matches actual payment method
+        // buy_now uses SOL transfer via SystemProgram - APP token fee discount
+        // requires actual SPL token transfer which is not supported in this path
+        if listing.payment_mint == Some(APP_TOKEN_MINT) {
+            // When APP token is claimed, verify we're actually using the token transfer path
+            // and not a raw SOL transfer. Since buy_now only supports SOL transfers,
+            // listings with APP token payment mint cannot use this instruction.
+            return Err(AppMarketError::InvalidPaymentMint.into());
+        }
+
+        // SECURITY: Pre-check buyer has sufficient balance
+        // Transaction and withdrawal PDA rent come from the payer account
+        require!(
+            ctx.accounts.buyer.lamports() >= buy_now_price,
+            AppMarketError::InsufficientBalance
+        );
+
+        // EFFECTS
+        let old_bid = listing.current_bid;
+        let old_bidder = listing.current_bidder;
+        let old_exposure = listing.current_bid_exposure;
+
+        listing.current_bid = buy_now_price;
+        listing.current_bidder = Some(ctx.accounts.buyer.key());
+        listing.current_bid_exposure = 0;
+        set_listing_status(
+            listing,
+            ListingStatus::InEscrow,
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+        listing.end_time = clock.unix_timestamp;
+
+        // Update escrow tracking BEFORE transfers
+        ctx.accounts.escrow.credit(
+            buy_now_price,
+            EscrowChangeReason::Purchase,
+            ctx.accounts.buyer.key(),
+        )?;
+
+        // INTERACTIONS
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, buy_now_price)?;
+
+        // SECURITY FIX M-2: Use withdrawal_count (same as PlaceBid) for consistent PDA seeds
+        // Bid-escrow listings skip this: the displaced bidder reclaims their own BidEscrow
+        if let Some(previous_bidder) = old_bidder {
+            if old_bid > 0 && !listing.uses_bid_escrows {
+                // Refund what the previous bidder actually locked (deposit in deposit mode)
+                let old_escrowed = escrowed_bid_amount(listing.deposit_bps, old_bid)?;
+
+                // Increment withdrawal counter FIRST to prevent PDA collision (consistent with PlaceBid)
+                listing.withdrawal_count = listing.withdrawal_count
+                    .checked_add(1)
+                    .ok_or(AppMarketError::MathOverflow)?;
+
+                // Derive PDA and verify
+                let (withdrawal_pda, bump) = withdrawal_pda(
+                    &listing.key(),
+                    listing.withdrawal_count,
+                    withdrawal_bump,
+                    ctx.program_id,
+                )?;
+
+                require!(
+                    withdrawal_pda == ctx.accounts.pending_withdrawal.key(),
+                    AppMarketError::InvalidPreviousBidder
+                );
+
+                // Create the account
+                let rent = Rent::get()?;
+                let space = 8 + PendingWithdrawal::INIT_SPACE;
+                let lamports = rent.minimum_balance(space);
+
+                anchor_lang::system_program::create_account(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::CreateAccount {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: ctx.accounts.pending_withdrawal.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                    space as u64,
+                    ctx.program_id,
+                )?;
+
+                // Initialize the withdrawal data
+                let mut withdrawal_data = ctx.accounts.pending_withdrawal.try_borrow_mut_data()?;
+                let mut withdrawal = PendingWithdrawal::try_from_slice(&vec![0u8; space])?;
+                withdrawal.user = previous_bidder;
+                withdrawal.listing = listing.key();
+                withdrawal.amount = old_escrowed;
+                withdrawal.exposure = old_exposure;
+                withdrawal.withdrawal_id = listing.withdrawal_count;
+                withdrawal.created_at = clock.unix_timestamp;
+                withdrawal.expires_at = clock.unix_timestamp + 3600; // 1 hour
+                withdrawal.rent_payer = ctx.accounts.payer.key();
+                withdrawal.version = ACCOUNT_LAYOUT_VERSION;
+                withdrawal.bump = bump;
+
+                withdrawal.try_serialize(&mut &mut withdrawal_data[..])?;
+
+                emit!(WithdrawalCreated {
+                    user: previous_bidder,
+                    listing: listing.key(),
+                    pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+                    amount: old_escrowed,
+                    withdrawal_id: listing.withdrawal_count,
+                    actor: ctx.accounts.buyer.key(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        if is_auction {
+            emit!(AuctionEndedByBuyNow {
+                listing: listing.key(),
+                buyer: deal_buyer,
+                price: buy_now_price,
+                displaced_bidder: old_bidder,
+                displaced_bid: old_bid,
+                actor: ctx.accounts.buyer.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        // Create transaction record
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.listing = listing.key();
+        transaction.seller = listing.seller;
+        transaction.buyer = deal_buyer;
+        transaction.payer = ctx.accounts.buyer.key();
+        transaction.agreement_hash = agreement_hash;
+        transaction.sale_price = buy_now_price;
+
+        // SECURITY: Use LOCKED fees from listing, not current config
+        transaction.platform_fee = buy_now_price
+            .checked_mul(listing.platform_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.seller_proceeds = buy_now_price
+            .checked_sub(transaction.platform_fee)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        transaction.status = TransactionStatus::InEscrow;
+        transaction.transfer_deadline = clock.unix_timestamp
+            .checked_add(TRANSFER_DEADLINE_SECONDS)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.created_at = clock.unix_timestamp;
+        transaction.seller_confirmed_transfer = false;
+        transaction.seller_confirmed_at = None;
+        transaction.completed_at = None;
+        transaction.vesting_duration = (listing.vesting_days as i64)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.vesting_start = None;
+        transaction.vested_claimed = 0;
+        transaction.arbitrator = listing.arbitrator;
+        transaction.version = ACCOUNT_LAYOUT_VERSION;
+        transaction.bump = ctx.bumps.transaction;
+        apply_contact_keys(
+            transaction,
+            ctx.accounts.buyer_profile.as_deref(),
+            ctx.accounts.seller_profile.as_deref(),
+        )?;
+
+        emit!(SaleCompleted {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount: buy_now_price,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Buy one unit of a multi-unit listing at the per-unit price
+    /// Each unit gets its own transaction (seeded by the unit index) and settles independently;
+    /// the listing stays active until every unit is sold
+    pub fn buy_unit(ctx: Context<BuyUnit>, recipient: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        // CHECKS
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::ListingExpired);
+        require!(listing.has_started(clock.unix_timestamp), AppMarketError::ListingNotStarted);
+        require!(listing.is_multi_unit(), AppMarketError::NotMultiUnitListing);
+        require!(listing.units_sold < listing.quantity, AppMarketError::SoldOut);
+        require!(ctx.accounts.buyer.key() != listing.seller, AppMarketError::SellerCannotBuy);
+
+        let unit_price = listing.buy_now_price_in(Pubkey::default(), clock.unix_timestamp)?;
+
+        let deal_buyer = recipient.unwrap_or(ctx.accounts.buyer.key());
+        require!(deal_buyer != Pubkey::default(), AppMarketError::InvalidRecipient);
+        require!(deal_buyer != listing.seller, AppMarketError::InvalidRecipient);
+
+        // SECURITY: Same payment-path restriction as buy_now (SOL transfers only)
+        if listing.payment_mint == Some(APP_TOKEN_MINT) {
+            return Err(AppMarketError::InvalidPaymentMint.into());
+        }
+
+        require!(
+            ctx.accounts.buyer.lamports() >= unit_price,
+            AppMarketError::InsufficientBalance
+        );
+
+        // EFFECTS
+        let unit_index = listing.units_sold;
+        listing.units_sold = listing.units_sold
+            .checked_add(1)
+            .ok_or(AppMarketError::MathOverflow)?;
+        if listing.units_sold == listing.quantity {
+            set_listing_status(
+                listing,
+                ListingStatus::Sold,
+                ctx.accounts.buyer.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        // Escrow pools funds for every open unit sale
+        ctx.accounts.escrow.credit(
+            unit_price,
+            EscrowChangeReason::Purchase,
+            ctx.accounts.buyer.key(),
+        )?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.listing = listing.key();
+        transaction.seller = listing.seller;
+        transaction.buyer = deal_buyer;
+        transaction.payer = ctx.accounts.buyer.key();
+        transaction.sale_price = unit_price;
+
+        // SECURITY: Use LOCKED fees from listing, not current config
+        transaction.platform_fee = unit_price
+            .checked_mul(listing.platform_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.seller_proceeds = unit_price
+            .checked_sub(transaction.platform_fee)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        transaction.status = TransactionStatus::InEscrow;
+        transaction.transfer_deadline = clock.unix_timestamp
+            .checked_add(TRANSFER_DEADLINE_SECONDS)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.created_at = clock.unix_timestamp;
+        transaction.seller_confirmed_transfer = false;
+        transaction.seller_confirmed_at = None;
+        transaction.completed_at = None;
+        transaction.vesting_duration = (listing.vesting_days as i64)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.vesting_start = None;
+        transaction.vested_claimed = 0;
+        transaction.arbitrator = listing.arbitrator;
+        transaction.unit_index = Some(unit_index);
+        transaction.unit_index_seed = unit_index.to_le_bytes();
+        transaction.version = ACCOUNT_LAYOUT_VERSION;
+        transaction.bump = ctx.bumps.transaction;
+        apply_contact_keys(
+            transaction,
+            ctx.accounts.buyer_profile.as_deref(),
+            ctx.accounts.seller_profile.as_deref(),
+        )?;
+
+        // INTERACTIONS
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, unit_price)?;
+
+        emit!(UnitPurchased {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            unit_index,
+            units_remaining: listing.quantity.saturating_sub(listing.units_sold),
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        emit!(SaleCompleted {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount: unit_price,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Open a crowdfunding pool to buy a fixed-price listing (anyone)
+    /// Contributors fund the pool vault up to the buy-now price before the deadline; the pool
+    /// vault then becomes the transaction buyer and contributors receive share tokens.
+    /// `pool_id` is any creator-chosen id, so other wallets' pools never collide with this one.
+    pub fn create_buyer_pool(
+        ctx: Context<CreateBuyerPool>,
+        pool_id: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        // Pools buy outright: no standing bids to displace
+        require!(
+            listing.listing_type == ListingType::BuyNow,
+            AppMarketError::BuyNowNotEnabled
+        );
+        require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
+        require!(
+            listing.payment_mint != Some(APP_TOKEN_MINT),
+            AppMarketError::InvalidPaymentMint
+        );
+        require!(
+            ctx.accounts.creator.key() != listing.seller,
+            AppMarketError::SellerCannotBuy
+        );
+        require!(
+            deadline > clock.unix_timestamp && deadline <= listing.end_time,
+            AppMarketError::InvalidDeadline
+        );
+
+        // Pools raise SOL: target the listing's SOL price
+        let target_amount = listing.list_price_in(Pubkey::default())?;
+
+        let pool = &mut ctx.accounts.buyer_pool;
+        pool.listing = listing.key();
+        pool.creator = ctx.accounts.creator.key();
+        pool.pool_id = pool_id;
+        pool.share_mint = ctx.accounts.share_mint.key();
+        pool.target_amount = target_amount;
+        pool.total_contributed = 0;
+        pool.settled_amount = 0;
+        pool.deadline = deadline;
+        pool.transaction = None;
+        pool.returned_amount = None;
+        pool.version = ACCOUNT_LAYOUT_VERSION;
+        pool.bump = ctx.bumps.buyer_pool;
+        pool.vault_bump = ctx.bumps.pool_vault;
+        pool.mint_bump = ctx.bumps.share_mint;
+
+        emit!(BuyerPoolCreated {
+            pool: pool.key(),
+            listing: pool.listing,
+            creator: pool.creator,
+            target_amount,
+            deadline,
+            actor: ctx.accounts.creator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Contribute SOL to a buyer pool (one contribution per wallet, capped at the amount still needed)
+    pub fn contribute_to_pool(ctx: Context<ContributeToPool>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let pool = &mut ctx.accounts.buyer_pool;
+        let clock = Clock::get()?;
+
+        require!(pool.transaction.is_none(), AppMarketError::PoolAlreadyPurchased);
+        require!(clock.unix_timestamp < pool.deadline, AppMarketError::PoolExpired);
+        require!(
+            amount >= MIN_POOL_CONTRIBUTION_LAMPORTS,
+            AppMarketError::PoolContributionTooSmall
+        );
+        let remaining = pool.target_amount
+            .checked_sub(pool.total_contributed)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(amount <= remaining, AppMarketError::PoolContributionExceedsTarget);
+
+        // EFFECTS
+        pool.total_contributed = pool.total_contributed
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.pool = pool.key();
+        contribution.contributor = ctx.accounts.contributor.key();
+        contribution.amount = amount;
+        contribution.version = ACCOUNT_LAYOUT_VERSION;
+        contribution.bump = ctx.bumps.contribution;
+
+        // INTERACTIONS
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.contributor.to_account_info(),
+                to: ctx.accounts.pool_vault.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+        emit!(PoolContributionMade {
+            pool: pool.key(),
+            contributor: contribution.contributor,
+            amount,
+            total_contributed: pool.total_contributed,
+            actor: ctx.accounts.contributor.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Buy the listing with a fully funded pool (permissionless; caller pays transaction rent)
+    /// The pool vault is the transaction buyer, so refunds flow back to contributors; the pool
+    /// creator signs buyer-side actions for it (confirm receipt, disputes, mutual cancel)
+    pub fn execute_pool_purchase(ctx: Context<ExecutePoolPurchase>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let pool = &mut ctx.accounts.buyer_pool;
+        let clock = Clock::get()?;
+
+        // CHECKS
+        require!(pool.transaction.is_none(), AppMarketError::PoolAlreadyPurchased);
+        require!(clock.unix_timestamp < pool.deadline, AppMarketError::PoolExpired);
+        require!(
+            pool.total_contributed == pool.target_amount,
+            AppMarketError::PoolNotFunded
+        );
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::ListingExpired);
+        require!(listing.has_started(clock.unix_timestamp), AppMarketError::ListingNotStarted);
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        // SECURITY: Contributors funded a specific price
+        require!(
+            listing.list_price_in(Pubkey::default())? == pool.target_amount,
+            AppMarketError::PoolTargetMismatch
+        );
+
+        let price = pool.target_amount;
+        let vault_key = ctx.accounts.pool_vault.key();
+
+        // EFFECTS
+        listing.current_bid = price;
+        listing.current_bidder = Some(vault_key);
+        set_listing_status(
+            listing,
+            ListingStatus::InEscrow,
+            ctx.accounts.executor.key(),
+            clock.unix_timestamp,
+        )?;
+        listing.end_time = clock.unix_timestamp;
+
+        ctx.accounts.escrow.credit(
+            price,
+            EscrowChangeReason::Purchase,
+            ctx.accounts.executor.key(),
+        )?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.listing = listing.key();
+        transaction.seller = listing.seller;
+        transaction.buyer = vault_key;
+        transaction.payer = vault_key;
+        transaction.buyer_delegate = Some(pool.creator);
+        transaction.sale_price = price;
+
+        // SECURITY: Use LOCKED fees from listing, not current config
+        transaction.platform_fee = price
+            .checked_mul(listing.platform_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.seller_proceeds = price
+            .checked_sub(transaction.platform_fee)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        transaction.status = TransactionStatus::InEscrow;
+        transaction.transfer_deadline = clock.unix_timestamp
+            .checked_add(TRANSFER_DEADLINE_SECONDS)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.created_at = clock.unix_timestamp;
+        transaction.seller_confirmed_transfer = false;
+        transaction.seller_confirmed_at = None;
+        transaction.completed_at = None;
+        transaction.vesting_duration = (listing.vesting_days as i64)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.vesting_start = None;
+        transaction.vested_claimed = 0;
+        transaction.arbitrator = listing.arbitrator;
+        transaction.version = ACCOUNT_LAYOUT_VERSION;
+        transaction.bump = ctx.bumps.transaction;
+
+        pool.transaction = Some(transaction.key());
+
+        // INTERACTIONS
+        let pool_key = pool.key();
+        let vault_seeds = &[
+            b"pool_vault",
+            pool_key.as_ref(),
+            &[pool.vault_bump],
+        ];
+        let signer = &[&vault_seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.pool_vault.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, price)?;
+
+        emit!(PoolPurchaseExecuted {
+            pool: pool_key,
+            listing: listing.key(),
+            transaction: transaction.key(),
+            amount: price,
+            actor: ctx.accounts.executor.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        emit!(SaleCompleted {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount: price,
+            actor: ctx.accounts.executor.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle a pool contribution (contributor only)
+    /// Failed pool (deadline passed without a purchase): full refund.
+    /// Purchased pool, once the sale is final: share tokens if it completed, plus a
+    /// pro-rata cut of anything refunded to the pool vault (dispute or cancellation refunds)
+    pub fn claim_pool_contribution(ctx: Context<ClaimPoolContribution>) -> Result<()> {
+        let clock = Clock::get()?;
+        let pool = &mut ctx.accounts.buyer_pool;
+        let amount = ctx.accounts.contribution.amount;
+        let vault_balance = ctx.accounts.pool_vault.lamports();
+
+        // CHECKS
+        let (refund, shares) = match pool.transaction {
+            None => {
+                require!(
+                    clock.unix_timestamp >= pool.deadline,
+                    AppMarketError::PoolDeadlineNotPassed
+                );
+                (amount, 0)
+            },
+            Some(transaction_key) => {
+                let transaction = ctx.accounts.transaction
+                    .as_ref()
+                    .ok_or(AppMarketError::PoolPurchasePending)?;
+                require!(
+                    transaction.key() == transaction_key,
+                    AppMarketError::InvalidPoolTransaction
+                );
+                let completed = transaction.status == TransactionStatus::Completed;
+                require!(
+                    completed
+                        || transaction.status == TransactionStatus::Refunded
+                        || transaction.status == TransactionStatus::Cancelled,
+                    AppMarketError::PoolPurchasePending
+                );
+
+                // Snapshot refunded funds on the first claim after the sale is final
+                pool.returned_amount.get_or_insert(vault_balance);
+                let refund = pool_refund(pool, amount, vault_balance)?;
+                (refund, if completed { amount } else { 0 })
+            },
+        };
+
+        // EFFECTS
+        pool.settled_amount = pool.settled_amount
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        // INTERACTIONS
+        let listing_key = ctx.accounts.listing.key();
+        let pool_key = pool.key();
+
+        if refund > 0 {
+            let vault_seeds = &[
+                b"pool_vault",
+                pool_key.as_ref(),
+                &[pool.vault_bump],
+            ];
+            let signer = &[&vault_seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.contributor.to_account_info(),
+                },
+                signer,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, refund)?;
+        }
+
+        if shares > 0 {
+            let share_account = ctx.accounts.contributor_share_account
+                .as_ref()
+                .ok_or(AppMarketError::InvalidShareAccount)?;
+            require!(
+                share_account.mint == ctx.accounts.share_mint.key()
+                    && share_account.owner == ctx.accounts.contributor.key(),
+                AppMarketError::InvalidShareAccount
+            );
+
+            let pool_id_bytes = pool.pool_id.to_le_bytes();
+            let pool_seeds = &[
+                b"buyer_pool",
+                listing_key.as_ref(),
+                pool.creator.as_ref(),
+                &pool_id_bytes,
+                &[pool.bump],
+            ];
+            let signer = &[&pool_seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    to: share_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            );
+            token::mint_to(cpi_ctx, shares)?;
+        }
+
+        emit!(PoolContributionClaimed {
+            pool: pool_key,
+            contributor: ctx.accounts.contributor.key(),
+            contributed: amount,
+            refund,
+            shares,
+            actor: ctx.accounts.contributor.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Fill an off-chain signed sell order (buyer)
+    /// The seller signs the borsh-encoded `SignedOrder` off-chain; the buyer submits it with an
+    /// ed25519 program verify instruction placed immediately before this one. Listing, escrow and
+    /// transaction are created atomically, so orders that never sell cost no rent. The listing
+    /// PDA (seller + salt) can only be created once, which prevents replaying a filled order
+    pub fn fulfill_signed_order(
+        ctx: Context<FulfillSignedOrder>,
+        order: SignedOrder,
+        recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let clock = Clock::get()?;
+        let buyer_key = ctx.accounts.buyer.key();
+
+        // CHECKS
+        require!(order.market == *ctx.program_id, AppMarketError::InvalidSignedOrder);
+        require!(clock.unix_timestamp < order.expires_at, AppMarketError::SignedOrderExpired);
+        require!(order.price > 0, AppMarketError::InvalidPrice);
+        require!(buyer_key != order.seller, AppMarketError::SellerCannotBuy);
+        if let Some(taker) = order.buyer {
+            require!(taker == buyer_key, AppMarketError::InvalidBuyer);
+        }
+        require!(
+            order.vesting_days <= MAX_VESTING_DAYS,
+            AppMarketError::InvalidVestingPeriod
+        );
+        require!(
+            order.arbitrator != Some(order.seller),
+            AppMarketError::InvalidArbitrator
+        );
+
+        // SECURITY: Seller revocations (cancellation registry and nonce counter)
+        require!(
+            ctx.accounts.order_cancellation.data_is_empty(),
+            AppMarketError::SignedOrderCancelled
+        );
+        require!(
+            order.nonce == current_order_nonce(&ctx.accounts.order_nonce, ctx.program_id)?,
+            AppMarketError::SignedOrderCancelled
+        );
+
+        let deal_buyer = recipient.unwrap_or(buyer_key);
+        require!(deal_buyer != Pubkey::default(), AppMarketError::InvalidRecipient);
+        require!(deal_buyer != order.seller, AppMarketError::InvalidRecipient);
+
+        require!(
+            ctx.accounts.buyer.lamports() >= order.price,
+            AppMarketError::InsufficientBalance
+        );
+
+        // SECURITY: Seller authorized exactly these order terms
+        let mut message = Vec::new();
+        order.serialize(&mut message)
+            .map_err(|_| AppMarketError::InvalidSignedOrder)?;
+        verify_ed25519_signature(
+            &ctx.accounts.instructions.to_account_info(),
+            &order.seller,
+            &message,
+        )?;
+
+        // EFFECTS
+        let listing = &mut ctx.accounts.listing;
+        listing.seller = order.seller;
+        listing.salt = order.salt;
+        listing.listing_type = ListingType::BuyNow;
+        listing.starting_price = order.price;
+        listing.reserve_price = None;
+        listing.buy_now_price = Some(order.price);
+        listing.current_bid = order.price;
+        listing.current_bidder = Some(buyer_key);
+        listing.created_at = clock.unix_timestamp;
+        listing.start_time = clock.unix_timestamp;
+        listing.auction_started = false;
+        listing.auction_start_time = None;
+        listing.end_time = clock.unix_timestamp;
+        // Remaining order lifetime, reused if the sale is mutually cancelled and relisted
+        listing.duration_seconds = order.expires_at
+            .checked_sub(clock.unix_timestamp)
+            .ok_or(AppMarketError::MathOverflow)?;
+        set_listing_status(
+            listing,
+            ListingStatus::InEscrow,
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+        // SECURITY: Fees locked at fill time (no on-chain listing existed before)
+        listing.platform_fee_bps = ctx.accounts.config.platform_fee_bps;
+        listing.dispute_fee_bps = ctx.accounts.config.dispute_fee_bps;
+        listing.payment_mint = None;
+        listing.payment_decimals = NATIVE_DECIMALS;
+        listing.deposit_bps = 0;
+        listing.vesting_days = order.vesting_days;
+        listing.holdback_bps = 0;
+        listing.payout_wallet = order.seller;
+        listing.pending_payout_wallet = None;
+        listing.pending_payout_wallet_at = None;
+        listing.arbitrator = order.arbitrator;
+        listing.quantity = 1;
+        listing.units_sold = 0;
+        listing.requires_github = false;
+        listing.required_github_username = String::new();
+        listing.category = Category::Other;
+        listing.version = ACCOUNT_LAYOUT_VERSION;
+        listing.bump = ctx.bumps.listing;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.listing = listing.key();
+        escrow.amount = 0;
+        escrow.credit(order.price, EscrowChangeReason::Purchase, ctx.accounts.buyer.key())?;
+        escrow.version = ACCOUNT_LAYOUT_VERSION;
+        escrow.bump = ctx.bumps.escrow;
+
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.listing = listing.key();
+        transaction.seller = order.seller;
+        transaction.buyer = deal_buyer;
+        transaction.payer = buyer_key;
+        transaction.sale_price = order.price;
+
+        transaction.platform_fee = order.price
+            .checked_mul(listing.platform_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.seller_proceeds = order.price
+            .checked_sub(transaction.platform_fee)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        transaction.status = TransactionStatus::InEscrow;
+        transaction.transfer_deadline = clock.unix_timestamp
+            .checked_add(TRANSFER_DEADLINE_SECONDS)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.created_at = clock.unix_timestamp;
+        transaction.seller_confirmed_transfer = false;
+        transaction.seller_confirmed_at = None;
+        transaction.completed_at = None;
+        transaction.vesting_duration = (order.vesting_days as i64)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.vesting_start = None;
+        transaction.vested_claimed = 0;
+        transaction.arbitrator = order.arbitrator;
+        transaction.version = ACCOUNT_LAYOUT_VERSION;
+        transaction.bump = ctx.bumps.transaction;
+
+        // INTERACTIONS
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, order.price)?;
+
+        emit!(SignedOrderFulfilled {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            seller: order.seller,
+            buyer: deal_buyer,
+            price: order.price,
+            salt: order.salt,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        emit!(SaleCompleted {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: order.seller,
+            amount: order.price,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel one outstanding signed order by its salt (seller only)
+    /// Records a cancellation marker that fulfill_signed_order refuses to fill past
+    pub fn cancel_signed_order(ctx: Context<CancelSignedOrder>, salt: u64) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let cancellation = &mut ctx.accounts.order_cancellation;
+        cancellation.seller = ctx.accounts.seller.key();
+        cancellation.salt = salt;
+        cancellation.cancelled_at = clock.unix_timestamp;
+        cancellation.version = ACCOUNT_LAYOUT_VERSION;
+        cancellation.bump = ctx.bumps.order_cancellation;
+
+        emit!(SignedOrderCancelled {
+            seller: cancellation.seller,
+            order_cancellation: ctx.accounts.order_cancellation.key(),
+            salt,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Invalidate every outstanding signed order by bumping the seller's nonce (seller only)
+    /// The OrderNonce account is created on first use; sellers without one are at nonce 0
+    pub fn increment_nonce(ctx: Context<IncrementNonce>) -> Result<()> {
+        let clock = Clock::get()?;
+        let seller_key = ctx.accounts.seller.key();
+        let nonce_info = ctx.accounts.order_nonce.to_account_info();
+
+        let mut order_nonce = if nonce_info.data_is_empty() {
+            create_pda_account(
+                ctx.accounts.seller.to_account_info(),
+                nonce_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+                &[b"order_nonce", seller_key.as_ref(), &[ctx.bumps.order_nonce]],
+                8 + OrderNonce::INIT_SPACE,
+                ctx.program_id,
+            )?;
+            OrderNonce {
+                version: ACCOUNT_LAYOUT_VERSION,
+                seller: seller_key,
+                nonce: 0,
+                bump: ctx.bumps.order_nonce,
+            }
+        } else {
+            OrderNonce::try_deserialize(&mut &nonce_info.try_borrow_data()?[..])?
+        };
+
+        order_nonce.nonce = order_nonce.nonce
+            .checked_add(1)
+            .ok_or(AppMarketError::MathOverflow)?;
+        order_nonce.try_serialize(&mut &mut nonce_info.try_borrow_mut_data()?[..])?;
+
+        emit!(OrderNonceIncremented {
+            seller: seller_key,
+            order_nonce: ctx.accounts.order_nonce.key(),
+            nonce: order_nonce.nonce,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle auction (called after auction ends)
+    /// Optional expected_winner / expected_amount fail settlement if the high bid changed;
+    /// the seller or winner may attach the purchase agreement hash.
+    pub fn settle_auction(
+        ctx: Context<SettleAuction>,
+        expected_winner: Option<Pubkey>,
+        expected_amount: Option<u64>,
+        agreement_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        // SECURITY: Fix validation order - check bidder validity FIRST
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+
+        // Only require auction to be ended if it was started
+        if listing.auction_started {
+            require!(
+                clock.unix_timestamp >= listing.end_time,
+                AppMarketError::AuctionNotEnded
+            );
+        }
+
+        // SECURITY: Only allow seller, winner, or admin to settle - until the grace
+        // period after end_time lapses, then anyone can crank settlement for a tip
+        let is_seller = ctx.accounts.payer.key() == listing.seller;
+        let is_winner = listing.current_bidder
+            .map(|bidder| ctx.accounts.payer.key() == bidder)
+            .unwrap_or(false);
+        let is_admin = ctx.accounts.payer.key() == ctx.accounts.config.admin;
+        let is_cranker = !(is_seller || is_winner || is_admin);
+
+        if is_cranker {
+            require!(
+                listing.auction_started
+                    && clock.unix_timestamp >= listing.end_time
+                        .checked_add(SETTLEMENT_GRACE_PERIOD_SECONDS)
+                        .ok_or(AppMarketError::MathOverflow)?,
+                AppMarketError::UnauthorizedSettlement
+            );
+        }
+
+        // SECURITY: Must have bids to settle - use cancel_auction for no-bid scenarios
+        require!(
+            listing.current_bidder.is_some(),
+            AppMarketError::NoBidsToSettle
+        );
+        // SECURITY: Race guard - the client settles the result it saw, or nothing
+        if let Some(expected) = expected_winner {
+            require!(
+                listing.current_bidder == Some(expected),
+                AppMarketError::StateChanged
+            );
+        }
+        if let Some(expected) = expected_amount {
+            require!(listing.current_bid == expected, AppMarketError::StateChanged);
+        }
+        // Only a party to the deal can attach the purchase agreement
+        if agreement_hash.is_some() {
+            require!(is_seller || is_winner, AppMarketError::NotPartyToTransaction);
+        }
+
+        // SECURITY FIX M-1: Validate bidder account matches listing.current_bidder
+        // This prevents passing an arbitrary account as the bidder
+        require!(
+            ctx.accounts.bidder.key() == listing.current_bidder.unwrap(),
+            AppMarketError::InvalidBidder
+        );
+
+        // Bid-escrow auctions: pull the winning bid into the listing escrow first
+        commit_winning_bid_funds(
+            listing,
+            &mut ctx.accounts.escrow,
+            ctx.accounts.bid_escrow.as_mut(),
+            ctx.accounts.payer.key(),
+            clock.unix_timestamp,
+        )?;
+
+        // Auction successful - create transaction
+        let transaction = &mut ctx.accounts.transaction;
+        populate_auction_transaction(transaction, listing, listing.key(), clock.unix_timestamp)?;
+        set_listing_status(
+            listing,
+            sold_listing_status(transaction),
+            ctx.accounts.payer.key(),
+            clock.unix_timestamp,
+        )?;
+        transaction.agreement_hash = agreement_hash;
+        transaction.version = ACCOUNT_LAYOUT_VERSION;
+        transaction.bump = ctx.bumps.transaction;
+        apply_contact_keys(
+            transaction,
+            ctx.accounts.buyer_profile.as_deref(),
+            ctx.accounts.seller_profile.as_deref(),
+        )?;
+
+        if is_cranker {
+            let tip = pay_settlement_tip(
+                transaction,
+                &mut ctx.accounts.escrow,
+                listing.key(),
+                ctx.accounts.payer.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            )?;
+
+            emit!(SettlementCranked {
+                listing: listing.key(),
+                cranker: ctx.accounts.payer.key(),
+                tip,
+                actor: ctx.accounts.payer.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        emit!(SaleCompleted {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount: listing.current_bid,
+            actor: ctx.accounts.payer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Accept the standing high bid and end an auction early (seller or operator)
+    /// Only allowed once the reserve is met; creates the Transaction immediately
+    pub fn accept_current_bid(ctx: Context<AcceptCurrentBid>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        // CHECKS
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::AuctionEnded);
+        require!(
+            listing.auction_started && listing.current_bidder.is_some(),
+            AppMarketError::NoBidsToSettle
+        );
+        if let Some(reserve) = listing.reserve_price {
+            require!(listing.current_bid >= reserve, AppMarketError::ReserveNotMet);
+        }
+
+        // Bid-escrow auctions: pull the winning bid into the listing escrow first
+        if listing.uses_bid_escrows {
+            let escrow = ctx.accounts.escrow
+                .as_mut()
+                .ok_or(AppMarketError::BidEscrowRequired)?;
+            commit_winning_bid_funds(
+                listing,
+                escrow,
+                ctx.accounts.bid_escrow.as_mut(),
+                ctx.accounts.seller.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        // EFFECTS: End auction now and create transaction for the high bidder
+        listing.end_time = clock.unix_timestamp;
+
+        let transaction = &mut ctx.accounts.transaction;
+        populate_auction_transaction(transaction, listing, listing.key(), clock.unix_timestamp)?;
+        set_listing_status(
+            listing,
+            sold_listing_status(transaction),
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+        transaction.version = ACCOUNT_LAYOUT_VERSION;
+        transaction.bump = ctx.bumps.transaction;
+        apply_contact_keys(
+            transaction,
+            ctx.accounts.buyer_profile.as_deref(),
+            ctx.accounts.seller_profile.as_deref(),
+        )?;
+
+        emit!(CurrentBidAccepted {
+            listing: listing.key(),
+            seller: listing.seller,
+            bidder: transaction.buyer,
+            amount: listing.current_bid,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        emit!(SaleCompleted {
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount: listing.current_bid,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle every due auction in a batch (permissionless keeper crank)
+    /// remaining_accounts: groups of [listing, escrow, transaction PDA]. Auctions not yet
+    /// past end_time + grace, or already settled, are skipped so batches can be resubmitted;
+    /// items that fail validation emit a failure event and are skipped as well.
+    /// Each settlement pays the cranker the usual settlement tip.
+    pub fn settle_due_auctions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankBatch<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let accounts = ctx.remaining_accounts;
+        require!(
+            !accounts.is_empty() && accounts.chunks_exact(3).remainder().is_empty(),
+            AppMarketError::InvalidBatchAccounts
+        );
+
+        let clock = Clock::get()?;
+        let mut processed: u16 = 0;
+        for group in accounts.chunks_exact(3) {
+            // Validation phase: failures are reported per item
+            let checked = check_due_auction(
+                &group[0],
+                &group[1],
+                &group[2],
+                ctx.program_id,
+                clock.unix_timestamp,
+            );
+            let (listing, escrow, transaction_bump) = match checked {
+                Ok(Some(item)) => item,
+                Ok(None) => continue,
+                Err(err) => {
+                    emit!(AuctionSettlementResult {
+                        listing: group[0].key(),
+                        success: false,
+                        error_code: error_code_number(&err),
+                        actor: ctx.accounts.cranker.key(),
+                        timestamp: clock.unix_timestamp,
+                    });
+                    continue;
+                }
+            };
+
+            // Execution phase: errors here abort the batch (state would be inconsistent)
+            complete_auction_settlement(
+                listing,
+                escrow,
+                &group[2],
+                transaction_bump,
+                ctx.accounts.cranker.to_account_info(),
+                true,
+                ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+                clock.unix_timestamp,
+            )?;
+            processed += 1;
+
+            emit!(AuctionSettlementResult {
+                listing: group[0].key(),
+                success: true,
+                error_code: 0,
+                actor: ctx.accounts.cranker.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        emit!(BatchCranked {
+            crank: CrankKind::SettleAuctions,
+            items: (accounts.len() / 3) as u16,
+            processed,
+            actor: ctx.accounts.cranker.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Expire and refund every lapsed offer in a batch (permissionless keeper crank)
+    /// remaining_accounts: groups of [listing, offer, offer_escrow, buyer]. Refunds always go
+    /// to offer.buyer; offers still live or no longer Active are skipped.
+    pub fn expire_due_offers<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankBatch<'info>>,
+    ) -> Result<()> {
+        let accounts = ctx.remaining_accounts;
+        require!(
+            !accounts.is_empty() && accounts.chunks_exact(4).remainder().is_empty(),
+            AppMarketError::InvalidBatchAccounts
+        );
+
+        let clock = Clock::get()?;
+        let mut processed: u16 = 0;
+        for group in accounts.chunks_exact(4) {
+            if expire_due_offer(
+                &group[0],
+                &group[1],
+                &group[2],
+                &group[3],
+                ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+                ctx.accounts.cranker.key(),
+                clock.unix_timestamp,
+            )? {
+                processed += 1;
+            }
+        }
+
+        emit!(BatchCranked {
+            crank: CrankKind::ExpireOffers,
+            items: (accounts.len() / 4) as u16,
+            processed,
+            actor: ctx.accounts.cranker.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Release escrow for every transaction past its finalize grace period (keeper crank)
+    /// remaining_accounts: groups of [listing (writable), transaction, escrow, payout_wallet,
+    /// buyer_profile, seller_profile]; pass the program id for a party without a profile.
+    /// Applies exactly the finalize_transaction conditions (loyalty points included);
+    /// anything not yet releasable is skipped, as are listings with a payout split or a
+    /// staked escrow (finalize those individually) and sales in a currency other than the
+    /// volume_stats account's. Items that fail validation emit a failure event and are skipped.
+    pub fn release_due_transactions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankBatch<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let accounts = ctx.remaining_accounts;
+        require!(
+            !accounts.is_empty() && accounts.chunks_exact(6).remainder().is_empty(),
+            AppMarketError::InvalidBatchAccounts
+        );
+
+        let clock = Clock::get()?;
+        let volume_stats_mint = ctx.accounts.volume_stats.as_ref().map(|stats| stats.mint);
+        let mut processed: u16 = 0;
+        for group in accounts.chunks_exact(6) {
+            // Validation phase: failures are reported per item
+            let checked = check_due_release(
+                &group[0],
+                &group[1],
+                &group[2],
+                &group[3],
+                &group[4],
+                &group[5],
+                volume_stats_mint,
+                ctx.accounts.config.insurance_fee_bps,
+                ctx.accounts.insurance_vault.is_some(),
+                ctx.program_id,
+                clock.unix_timestamp,
+            );
+            let item = match checked {
+                Ok(Some(item)) => item,
+                Ok(None) => continue,
+                Err(err) => {
+                    emit!(TransactionReleaseResult {
+                        transaction: group[1].key(),
+                        success: false,
+                        error_code: error_code_number(&err),
+                        actor: ctx.accounts.cranker.key(),
+                        timestamp: clock.unix_timestamp,
+                    });
+                    continue;
+                }
+            };
+
+            // Execution phase: errors here abort the batch (state would be inconsistent)
+            let volume_stats = ctx.accounts.volume_stats
+                .as_mut()
+                .ok_or(AppMarketError::InvalidVolumeStats)?;
+            complete_due_release(
+                item,
+                &group[3],
+                &mut ctx.accounts.config,
+                volume_stats,
+                ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+                ctx.accounts.cranker.key(),
+                clock.unix_timestamp,
+            )?;
+            processed += 1;
+
+            emit!(TransactionReleaseResult {
+                transaction: group[1].key(),
+                success: true,
+                error_code: 0,
+                actor: ctx.accounts.cranker.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        emit!(BatchCranked {
+            crank: CrankKind::ReleaseTransactions,
+            items: (accounts.len() / 6) as u16,
+            processed,
+            actor: ctx.accounts.cranker.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle several ended auctions in one transaction
+    /// remaining_accounts: groups of [listing, escrow, transaction PDA]. Each item follows
+    /// settle_auction's rules for the signer; items that fail validation emit a failure
+    /// event and are skipped instead of aborting the whole batch.
+    pub fn settle_auctions_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankBatch<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let accounts = ctx.remaining_accounts;
+        require!(
+            !accounts.is_empty() && accounts.chunks_exact(3).remainder().is_empty(),
+            AppMarketError::InvalidBatchAccounts
+        );
+
+        let clock = Clock::get()?;
+        let caller = ctx.accounts.cranker.key();
+        let admin = ctx.accounts.config.admin;
+        let mut processed: u16 = 0;
+
+        for group in accounts.chunks_exact(3) {
+            // Validation phase: failures are reported per item
+            let checked = load_auction_batch_item(&group[0], &group[1], &group[2], ctx.program_id)
+                .and_then(|(listing, escrow, transaction_bump)| {
+                    let is_cranker = check_batch_settlement(
+                        &listing,
+                        &escrow,
+                        &group[2],
+                        caller,
+                        admin,
+                        clock.unix_timestamp,
+                    )?;
+                    Ok((listing, escrow, transaction_bump, is_cranker))
+                });
+
+            let (listing, escrow, transaction_bump, is_cranker) = match checked {
+                Ok(item) => item,
+                Err(err) => {
+                    emit!(AuctionSettlementResult {
+                        listing: group[0].key(),
+                        success: false,
+                        error_code: error_code_number(&err),
+                        actor: ctx.accounts.cranker.key(),
+                        timestamp: clock.unix_timestamp,
+                    });
+                    continue;
+                }
+            };
+
+            // Execution phase: errors here abort the batch (state would be inconsistent)
+            complete_auction_settlement(
+                listing,
+                escrow,
+                &group[2],
+                transaction_bump,
+                ctx.accounts.cranker.to_account_info(),
+                is_cranker,
+                ctx.accounts.system_program.to_account_info(),
+                ctx.program_id,
+                clock.unix_timestamp,
+            )?;
+            processed += 1;
+
+            emit!(AuctionSettlementResult {
+                listing: group[0].key(),
+                success: true,
+                error_code: 0,
+                actor: ctx.accounts.cranker.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        }
+
+        emit!(BatchCranked {
+            crank: CrankKind::SettleAuctionsBatch,
+            items: (accounts.len() / 3) as u16,
+            processed,
+            actor: ctx.accounts.cranker.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay the remainder of a deposit-mode winning bid (buyer only, within payment window)
+    /// Moves the transaction into escrow and starts the transfer period
+    pub fn complete_purchase(ctx: Context<CompletePurchase>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::Pending,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(
+            ctx.accounts.buyer.key() == transaction.buyer,
+            AppMarketError::NotBuyer
+        );
+
+        let payment_deadline = transaction.payment_deadline
+            .ok_or(AppMarketError::InvalidTransactionStatus)?;
+        require!(
+            clock.unix_timestamp <= payment_deadline,
+            AppMarketError::PaymentWindowExpired
+        );
+
+        let remainder = transaction.sale_price
+            .checked_sub(transaction.deposit_amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        // SECURITY: Pre-check buyer has sufficient balance
+        require!(
+            ctx.accounts.buyer.lamports() >= remainder,
+            AppMarketError::InsufficientBalance
+        );
+
+        // EFFECTS
+        ctx.accounts.escrow.credit(
+            remainder,
+            EscrowChangeReason::RemainderPaid,
+            ctx.accounts.buyer.key(),
+        )?;
+
+        transaction.status = TransactionStatus::InEscrow;
+        set_sale_status(
+            &mut ctx.accounts.listing,
+            transaction,
+            ListingStatus::InEscrow,
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+        transaction.transfer_deadline = clock.unix_timestamp
+            .checked_add(TRANSFER_DEADLINE_SECONDS)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        // INTERACTIONS
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, remainder)?;
+
+        emit!(RemainderPaid {
+            listing: ctx.accounts.listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            deposit_amount: transaction.deposit_amount,
+            remainder,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Forfeit a deposit-mode winner's deposit after the payment window lapses
+    /// Permissionless: deposit goes to seller (platform fee at the locked rate to treasury)
+    pub fn forfeit_deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForfeitDeposit<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.transaction.status == TransactionStatus::Pending,
+            AppMarketError::InvalidTransactionStatus
+        );
+
+        let payment_deadline = ctx.accounts.transaction.payment_deadline
+            .ok_or(AppMarketError::InvalidTransactionStatus)?;
+        require!(
+            clock.unix_timestamp > payment_deadline,
+            AppMarketError::PaymentWindowNotExpired
+        );
+
+        let deposit = ctx.accounts.transaction.deposit_amount;
+        let platform_cut = deposit
+            .checked_mul(ctx.accounts.listing.platform_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+        let seller_cut = deposit
+            .checked_sub(platform_cut)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= deposit + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+        require!(
+            ctx.accounts.escrow.amount >= deposit,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        pay_platform_fee(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+            ctx.accounts.config.insurance_fee_bps,
+            platform_cut,
+            ctx.accounts.caller.key(),
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+
+        if seller_cut > 0 {
+            pay_seller_proceeds(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.payout_wallet.to_account_info(),
+                &ctx.accounts.listing,
+                ctx.accounts.payout_split.as_deref(),
+                ctx.remaining_accounts,
+                seller_cut,
+                ctx.accounts.system_program.to_account_info(),
+                signer,
+            )?;
+        }
+
+        ctx.accounts.escrow.debit(
+            deposit,
+            EscrowChangeReason::DepositForfeited,
+            ctx.accounts.caller.key(),
+        )?;
+
+        // Sale fell through: transaction is terminal, listing ends without a sale
+        let transaction = &mut ctx.accounts.transaction;
+        transaction.status = TransactionStatus::Cancelled;
+        transaction.completed_at = Some(clock.unix_timestamp);
+        set_listing_status(
+            &mut ctx.accounts.listing,
+            ListingStatus::Ended,
+            ctx.accounts.caller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(DepositForfeited {
+            listing: ctx.accounts.listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            seller: transaction.seller,
+            deposit_amount: deposit,
+            platform_fee: platform_cut,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel auction (when no bids received, closes escrow and refunds rent)
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
+
+        let listing = &mut ctx.accounts.listing;
+
+        // Validations
+        require!(
+            listing.status == ListingStatus::Active,
+            AppMarketError::ListingNotActive
+        );
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+        require!(
+            ctx.accounts.seller.key() == listing.seller,
+            AppMarketError::NotSeller
+        );
+
+        // Can only cancel if:
+        // 1. No bids received, OR
+        // 2. Auction ended and reserve not met (auction_started = false means no valid bids)
+        require!(
+            listing.current_bidder.is_none(),
+            AppMarketError::CannotCancelWithBids
+        );
+
+        // If auction has ended, require it to be past end_time
+        if listing.auction_started {
+            require!(
+                clock.unix_timestamp >= listing.end_time,
+                AppMarketError::AuctionNotEnded
+            );
+        }
+
+        set_listing_status(
+            listing,
+            ListingStatus::Cancelled,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(AuctionCancelled {
+            listing: listing.key(),
+            reason: "Cancelled by seller - no bids received".to_string(),
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Relist an expired or cancelled listing in place (seller or operator)
+    /// Resets bidding state and timing and re-initializes the escrow; listing ID, locked
+    /// fees and the withdrawal counter are preserved so PDAs and indexers stay consistent
+    pub fn relist(ctx: Context<Relist>, duration_seconds: Option<i64>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            matches!(
+                listing.status,
+                ListingStatus::Ended | ListingStatus::Cancelled | ListingStatus::ReserveNotMet
+            ),
+            AppMarketError::CannotRelist
+        );
+
+        let duration_seconds = duration_seconds.unwrap_or(listing.duration_seconds);
+        check_listing_duration(&listing.listing_type, duration_seconds)?;
+
+        // Fresh bidding state and timing
+        set_listing_status(
+            listing,
+            ListingStatus::Active,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+        listing.current_bid = 0;
+        listing.current_bidder = None;
+        listing.current_bid_exposure = 0;
+        listing.last_bidder = None;
+        listing.consecutive_bid_count = 0;
+        listing.last_offer_buyer = None;
+        listing.consecutive_offer_count = 0;
+        listing.auction_started = false;
+        listing.auction_start_time = None;
+        listing.extension_count = 0;
+        listing.created_at = clock.unix_timestamp;
+        listing.start_time = clock.unix_timestamp;
+        listing.duration_seconds = duration_seconds;
+        listing.end_time = listing.sale_window_end(clock.unix_timestamp, duration_seconds)?;
+        listing.relist_count = listing.relist_count.saturating_add(1);
+
+        // Re-initialize escrow (closed when the listing expired or was cancelled, or by
+        // close_failed_auction_escrow once a failed auction's refunds were paid out)
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.listing = listing.key();
+        escrow.amount = 0;
+        escrow.version = ACCOUNT_LAYOUT_VERSION;
+        escrow.bump = ctx.bumps.escrow;
+
+        emit!(ListingRelisted {
+            listing: listing.key(),
+            listing_id: listing.listing_id(),
+            relist_count: listing.relist_count,
+            end_time: listing.end_time,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Reopen a sold listing whose sale ended Refunded (seller or operator)
+    /// Closes the refunded transaction so the listing can sell again, keeping the time that
+    /// was left when it sold unless a fresh `duration_seconds` window is given. The escrow is
+    /// recreated if it was already closed; any dispute must be closed first.
+    pub fn reopen_listing(ctx: Context<ReopenListing>, duration_seconds: Option<i64>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let transaction = &ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        // Sales refunded before post-sale tracking left their listing at Sold
+        require!(
+            matches!(listing.status, ListingStatus::Refunded | ListingStatus::Sold),
+            AppMarketError::CannotReopen
+        );
+        require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
+        require!(
+            transaction.status == TransactionStatus::Refunded,
+            AppMarketError::CannotReopen
+        );
+        // The dispute PDA is keyed by the transaction address, which the next sale reuses
+        require!(
+            ctx.accounts.dispute.data_is_empty(),
+            AppMarketError::DisputeNotClosed
+        );
+
+        let remaining = if listing.is_open_ended() {
+            0
+        } else {
+            listing.end_time
+                .checked_sub(transaction.created_at)
+                .ok_or(AppMarketError::MathOverflow)?
+                .max(0)
+        };
+        let duration_seconds = duration_seconds.unwrap_or(remaining);
+        check_listing_duration(&listing.listing_type, duration_seconds)?;
+
+        // Recreate the escrow if close_escrow already ran
+        let escrow_info = ctx.accounts.escrow.to_account_info();
+        if escrow_info.data_is_empty() {
+            create_pda_account(
+                ctx.accounts.seller.to_account_info(),
+                escrow_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+                &[b"escrow", listing.key().as_ref(), &[ctx.bumps.escrow]],
+                8 + Escrow::INIT_SPACE,
+                ctx.program_id,
+            )?;
+            let escrow = Escrow {
+                version: ACCOUNT_LAYOUT_VERSION,
+                listing: listing.key(),
+                amount: 0,
+                bump: ctx.bumps.escrow,
+            };
+            escrow.try_serialize(&mut &mut escrow_info.try_borrow_mut_data()?[..])?;
+        }
+
+        // Fresh bidding state and timing
+        set_listing_status(
+            listing,
+            ListingStatus::Active,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+        listing.current_bid = 0;
+        listing.current_bidder = None;
+        listing.current_bid_exposure = 0;
+        listing.last_bidder = None;
+        listing.consecutive_bid_count = 0;
+        listing.last_offer_buyer = None;
+        listing.consecutive_offer_count = 0;
+        listing.auction_started = false;
+        listing.auction_start_time = None;
+        listing.extension_count = 0;
+        listing.start_time = clock.unix_timestamp;
+        listing.end_time = listing.sale_window_end(clock.unix_timestamp, duration_seconds)?;
+
+        emit!(ListingReopened {
+            listing: listing.key(),
+            refunded_transaction: transaction.key(),
+            end_time: listing.end_time,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Attach a top-N bid book to an auction before its first bid (anyone; caller pays rent)
+    /// Once attached, every bid must update it, so the book stays authoritative
+    pub fn init_bid_book(ctx: Context<InitBidBook>) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+
+        let mut book = ctx.accounts.bid_book.load_init()?;
+        book.listing = listing.key();
+        book.version = ACCOUNT_LAYOUT_VERSION;
+        book.bump = ctx.bumps.bid_book;
+
+        listing.has_bid_book = true;
+
+        emit!(BidBookInitialized {
+            listing: listing.key(),
+            bid_book: ctx.accounts.bid_book.key(),
+            actor: ctx.accounts.payer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Keep a rolling history of recent bids next to a listing's bid book (anyone; payer covers rent)
+    pub fn init_bid_history(ctx: Context<InitBidHistory>) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        let clock = Clock::get()?;
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+
+        let mut book = ctx.accounts.bid_book.load_mut()?;
+        require!(book.listing == listing.key(), AppMarketError::InvalidBidBook);
+
+        let mut history = ctx.accounts.bid_history.load_init()?;
+        history.listing = listing.key();
+        history.version = ACCOUNT_LAYOUT_VERSION;
+        history.bump = ctx.bumps.bid_history;
+
+        // Seed the history with the current high bid so it never misses the leader
+        if let Some(bidder) = listing.current_bidder {
+            history.record_bid(bidder, listing.current_bid, clock.unix_timestamp);
+        }
+        book.has_history = 1;
+
+        emit!(BidHistoryInitialized {
+            listing: listing.key(),
+            bid_history: ctx.accounts.bid_history.key(),
+            actor: ctx.accounts.payer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Hold an auction's bids in per-bidder BidEscrow PDAs (seller or operator, before any bid)
+    /// Outbidding then moves no funds and creates no withdrawal account: outbid bidders
+    /// reclaim their own escrow. SOL auctions without deposit mode only.
+    pub fn enable_bid_escrows(ctx: Context<EnableBidEscrows>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        require!(
+            listing.payment_mint.is_none() && listing.deposit_bps == 0,
+            AppMarketError::BidEscrowUnsupported
+        );
+
+        listing.uses_bid_escrows = true;
+
+        emit!(BidEscrowsEnabled {
+            listing: listing.key(),
+            seller: listing.seller,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Move an ended bid-escrow auction's winning bid into the listing escrow (anyone)
+    /// Lets settle_due_auctions / settle_auctions_batch settle it like any other auction.
+    pub fn commit_winning_bid(ctx: Context<CommitWinningBid>) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.auction_started && clock.unix_timestamp >= listing.end_time,
+            AppMarketError::AuctionNotEnded
+        );
+        require!(listing.uses_bid_escrows, AppMarketError::BidEscrowsNotEnabled);
+
+        commit_winning_bid_funds(
+            listing,
+            &mut ctx.accounts.escrow,
+            Some(&mut ctx.accounts.bid_escrow),
+            ctx.accounts.caller.key(),
+            clock.unix_timestamp,
+        )
+    }
+
+    /// Close a BidEscrow and take its funds back (bidder only)
+    /// Allowed unless it backs the standing high bid of a live auction; a committed winner
+    /// reclaims only what is left over from earlier, higher-than-winning top-ups.
+    pub fn reclaim_bid_escrow(ctx: Context<ReclaimBidEscrow>) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        let bid_escrow = &ctx.accounts.bid_escrow;
+        let bidder = ctx.accounts.bidder.key();
+
+        require!(
+            bid_escrow.committed
+                || listing.current_bidder != Some(bidder)
+                || listing.status != ListingStatus::Active,
+            AppMarketError::BidEscrowLocked
+        );
+
+        if let Some(profile) = ctx.accounts.bidder_profile.as_mut() {
+            profile.locked_exposure = profile.locked_exposure.saturating_sub(bid_escrow.amount);
+        }
+
+        emit!(BidEscrowReclaimed {
+            listing: listing.key(),
+            bidder,
+            bid_escrow: ctx.accounts.bid_escrow.key(),
+            amount: bid_escrow.amount,
+            actor: ctx.accounts.bidder.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Lower the reserve of a stalled auction (seller or operator, before the reserve is met)
+    /// Starting price never exceeds the reserve; a standing bid that now meets it starts
+    /// the auction timer, as if it had been placed against the lower reserve
+    pub fn lower_reserve(ctx: Context<LowerReserve>, new_reserve: u64) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::AuctionEnded);
+        // SECURITY: Reserve is fixed once a bid has met it and the auction started
+        require!(!listing.auction_started, AppMarketError::AuctionAlreadyStarted);
+
+        let old_reserve = listing.reserve_price
+            .ok_or(AppMarketError::NoReservePrice)?;
+        require!(
+            new_reserve > 0 && new_reserve < old_reserve,
+            AppMarketError::InvalidReserveReduction
+        );
+
+        listing.reserve_price = Some(new_reserve);
+        listing.starting_price = listing.starting_price.min(new_reserve);
+        if listing.current_bidder.is_some() && listing.current_bid >= new_reserve {
+            listing.auction_started = true;
+            listing.auction_start_time = Some(clock.unix_timestamp);
+            listing.end_time = clock.unix_timestamp
+                .checked_add(listing.duration_seconds)
+                .ok_or(AppMarketError::MathOverflow)?;
+        }
+
+        emit!(ReserveLowered {
+            listing: listing.key(),
+            old_reserve,
+            new_reserve,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Turn a listing into a bundle of several apps (seller or operator, before any bid or sale)
+    /// Each entry identifies one asset (hash of its off-chain attestation); an empty list
+    /// reverts to a single-app listing. One escrow and transaction cover the whole bundle
+    pub fn set_bundle_assets(ctx: Context<SetBundleAssets>, assets: Vec<[u8; 32]>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            matches!(listing.status, ListingStatus::Active | ListingStatus::Draft),
+            AppMarketError::ListingNotActive
+        );
+        // SECURITY: Bundle contents are fixed once a buyer has committed funds
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        require!(listing.units_sold == 0, AppMarketError::HasUnitSales);
+        require!(
+            assets.is_empty() || (assets.len() >= 2 && assets.len() <= MAX_BUNDLE_ASSETS),
+            AppMarketError::InvalidBundle
+        );
+        require!(
+            assets.iter().all(|asset| *asset != [0u8; 32]),
+            AppMarketError::InvalidBundle
+        );
+        for (i, asset) in assets.iter().enumerate() {
+            require!(!assets[..i].contains(asset), AppMarketError::InvalidBundle);
+        }
+
+        listing.bundle_assets = assets;
+
+        emit!(BundleAssetsSet {
+            listing: listing.key(),
+            assets: listing.bundle_assets.clone(),
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Take a draft listing live (seller or operator)
+    /// The sale window starts now, or at the scheduled start_time if that is later.
+    pub fn activate_listing(ctx: Context<ActivateListing>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(listing.status == ListingStatus::Draft, AppMarketError::ListingNotDraft);
+
+        listing.start_time = listing.start_time.max(clock.unix_timestamp);
+        listing.end_time = listing.sale_window_end(listing.start_time, listing.duration_seconds)?;
+        set_listing_status(
+            listing,
+            ListingStatus::Active,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(ListingActivated {
+            listing: listing.key(),
+            seller: listing.seller,
+            start_time: listing.start_time,
+            end_time: listing.end_time,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Temporarily hide an active, bid-free listing (seller or operator)
+    pub fn pause_listing(ctx: Context<SetListingPaused>) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::ListingExpired);
+        // SECURITY: Never strand a bidder or buyer behind a paused listing
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        require!(listing.units_sold == 0, AppMarketError::HasUnitSales);
+
+        set_listing_status(
+            listing,
+            ListingStatus::Paused,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+        listing.paused_at = Some(clock.unix_timestamp);
+
+        emit!(ListingPauseToggled {
+            listing: listing.key(),
+            paused: true,
+            end_time: listing.end_time,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Put a paused listing back on sale (seller or operator)
+    /// The sale window is extended by the time spent paused.
+    pub fn resume_listing(ctx: Context<SetListingPaused>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(listing.status == ListingStatus::Paused, AppMarketError::ListingNotPaused);
+
+        let paused_at = listing.paused_at.ok_or(AppMarketError::ListingNotPaused)?;
+        let paused_for = clock.unix_timestamp.saturating_sub(paused_at);
+        if !listing.is_open_ended() {
+            listing.end_time = listing.end_time
+                .checked_add(paused_for)
+                .ok_or(AppMarketError::MathOverflow)?;
+        }
+        set_listing_status(
+            listing,
+            ListingStatus::Active,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+        listing.paused_at = None;
+
+        emit!(ListingPauseToggled {
+            listing: listing.key(),
+            paused: false,
+            end_time: listing.end_time,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Delegate day-to-day management of a listing to an operator wallet (seller only)
+    /// The operator can activate, pause, edit, relist, accept bids/offers and confirm
+    /// transfers; proceeds, payout wallets and cancellation stay with the seller.
+    /// None revokes the operator.
+    pub fn set_operator(ctx: Context<SetListingOperator>, operator: Option<Pubkey>) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+
+        require!(
+            ctx.accounts.seller.key() == listing.seller,
+            AppMarketError::NotSeller
+        );
+        if let Some(operator) = operator {
+            require!(
+                operator != Pubkey::default() && operator != listing.seller,
+                AppMarketError::InvalidOperator
+            );
+        }
+
+        listing.operator = operator;
+
+        emit!(ListingOperatorSet {
+            listing: listing.key(),
+            seller: listing.seller,
+            operator,
+            actor: ctx.accounts.seller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Require (or stop requiring) backend-attested buyers (seller or operator, before any bid)
+    pub fn set_requires_verification(
+        ctx: Context<SetRequiresVerification>,
+        requires_verification: bool,
+    ) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            matches!(listing.status, ListingStatus::Active | ListingStatus::Draft),
+            AppMarketError::ListingNotActive
+        );
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+
+        listing.requires_verification = requires_verification;
+
+        emit!(ListingVerificationRequirementSet {
+            listing: listing.key(),
+            requires_verification,
+            actor: ctx.accounts.seller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Replace a listing's discovery tags (seller or operator, before any bid or sale)
+    pub fn set_listing_tags(ctx: Context<SetListingTags>, tags: Vec<String>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            matches!(listing.status, ListingStatus::Active | ListingStatus::Draft),
+            AppMarketError::ListingNotActive
+        );
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        require!(listing.units_sold == 0, AppMarketError::HasUnitSales);
+        validate_listing_tags(&tags)?;
+
+        listing.tags = tags;
+
+        emit!(ListingTagsSet {
+            listing: listing.key(),
+            tags: listing.tags.clone(),
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Schedule automatic buy-now price drops (seller or operator, BuyNow listings only)
+    /// The price falls by `drop_amount` every `interval_seconds` counted from created_at,
+    /// stopping at `floor_price`. A `drop_amount` of 0 removes the schedule.
+    pub fn set_price_drop_schedule(
+        ctx: Context<SetPriceDropSchedule>,
+        drop_amount: u64,
+        interval_seconds: i64,
+        floor_price: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            matches!(listing.status, ListingStatus::Active | ListingStatus::Draft),
+            AppMarketError::ListingNotActive
+        );
+        require!(
+            listing.listing_type == ListingType::BuyNow,
+            AppMarketError::InvalidPriceDropSchedule
+        );
+        let initial_price = listing.buy_now_price
+            .ok_or(AppMarketError::BuyNowNotEnabled)?;
+
+        if drop_amount == 0 {
+            listing.price_drop_amount = 0;
+            listing.price_drop_interval = 0;
+            listing.price_floor = 0;
+        } else {
+            require!(
+                interval_seconds >= MIN_PRICE_DROP_INTERVAL_SECONDS
+                    && floor_price > 0
+                    && floor_price < initial_price,
+                AppMarketError::InvalidPriceDropSchedule
+            );
+            listing.price_drop_amount = drop_amount;
+            listing.price_drop_interval = interval_seconds;
+            listing.price_floor = floor_price;
+        }
+
+        emit!(PriceDropScheduleSet {
+            listing: listing.key(),
+            initial_price,
+            drop_amount: listing.price_drop_amount,
+            interval_seconds: listing.price_drop_interval,
+            floor_price: listing.price_floor,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay to feature a listing for `days` days (seller only)
+    /// Extends any running promotion; the fee goes to the treasury (or fee-split vault).
+    pub fn promote_listing(ctx: Context<PromoteListing>, days: u16) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let fee_per_day = ctx.accounts.config.promotion_fee_per_day;
+        require!(fee_per_day > 0, AppMarketError::PromotionsDisabled);
+        require!(
+            days > 0 && days <= MAX_PROMOTION_DAYS,
+            AppMarketError::InvalidPromotionDays
+        );
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.seller.key() == listing.seller,
+            AppMarketError::NotSeller
+        );
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::ListingExpired);
+
+        let fee = fee_per_day
+            .checked_mul(days as u64)
+            .ok_or(AppMarketError::MathOverflow)?;
+        let extension = (days as i64)
+            .checked_mul(SECONDS_PER_DAY)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        // EFFECTS
+        listing.featured_until = listing.featured_until
+            .max(clock.unix_timestamp)
+            .checked_add(extension)
+            .ok_or(AppMarketError::MathOverflow)?;
+        let config = &mut ctx.accounts.config;
+        config.total_promotion_fees = config.total_promotion_fees.saturating_add(fee);
+
+        // INTERACTIONS
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.seller.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+
+        emit!(ListingPromoted {
+            listing: listing.key(),
+            seller: listing.seller,
+            days,
+            fee,
+            featured_until: listing.featured_until,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Propose a new payout wallet for a listing (step 1 of timelock, seller only, pre-sale)
+    pub fn propose_payout_wallet_change(
+        ctx: Context<ChangePayoutWallet>,
+        new_payout_wallet: Pubkey,
+    ) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.seller.key() == listing.seller,
+            AppMarketError::NotSeller
+        );
+        require!(
+            listing.status == ListingStatus::Active && listing.units_sold == 0,
+            AppMarketError::PayoutWalletLocked
+        );
+        require!(
+            new_payout_wallet != Pubkey::default(),
+            AppMarketError::InvalidPayoutWallet
+        );
+
+        listing.pending_payout_wallet = Some(new_payout_wallet);
+        listing.pending_payout_wallet_at = Some(clock.unix_timestamp);
+
+        emit!(PayoutWalletChangeProposed {
+            listing: listing.key(),
+            old_payout_wallet: listing.payout_wallet,
+            new_payout_wallet,
+            executable_at: clock.unix_timestamp + PAYOUT_WALLET_TIMELOCK_SECONDS,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a payout wallet change (step 2 of timelock, after 48 hours, still pre-sale)
+    pub fn execute_payout_wallet_change(ctx: Context<ChangePayoutWallet>) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.seller.key() == listing.seller,
+            AppMarketError::NotSeller
+        );
+        // SECURITY: Proceeds destination is fixed once a sale exists
+        require!(
+            listing.status == ListingStatus::Active && listing.units_sold == 0,
+            AppMarketError::PayoutWalletLocked
+        );
+
+        let proposed_at = listing.pending_payout_wallet_at
+            .ok_or(AppMarketError::NoPendingChange)?;
+        require!(
+            clock.unix_timestamp >= proposed_at + PAYOUT_WALLET_TIMELOCK_SECONDS,
+            AppMarketError::TimelockNotExpired
+        );
+
+        listing.payout_wallet = listing.pending_payout_wallet
+            .ok_or(AppMarketError::NoPendingChange)?;
+        listing.pending_payout_wallet = None;
+        listing.pending_payout_wallet_at = None;
+
+        emit!(PayoutWalletChanged {
+            listing: listing.key(),
+            payout_wallet: listing.payout_wallet,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Split a listing's seller proceeds among up to 5 recipients (seller only, pre-sale)
+    /// Shares are in bps of each payout; whatever is left goes to the payout wallet.
+    /// Immutable once created so co-founders can rely on it.
+    pub fn create_payout_split(
+        ctx: Context<CreatePayoutSplit>,
+        recipients: Vec<SplitRecipient>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.seller.key() == listing.seller,
+            AppMarketError::NotSeller
+        );
+        require!(
+            listing.status == ListingStatus::Active && listing.units_sold == 0,
+            AppMarketError::PayoutWalletLocked
+        );
+        require!(
+            !recipients.is_empty() && recipients.len() <= MAX_SPLIT_RECIPIENTS,
+            AppMarketError::InvalidPayoutSplit
+        );
+
+        let mut total_bps: u64 = 0;
+        for (i, recipient) in recipients.iter().enumerate() {
+            require!(
+                recipient.bps > 0 && recipient.wallet != Pubkey::default(),
+                AppMarketError::InvalidPayoutSplit
+            );
+            require!(
+                recipients[..i].iter().all(|other| other.wallet != recipient.wallet),
+                AppMarketError::InvalidPayoutSplit
+            );
+            total_bps = total_bps
+                .checked_add(recipient.bps)
+                .ok_or(AppMarketError::MathOverflow)?;
+        }
+        require!(total_bps <= BASIS_POINTS_DIVISOR, AppMarketError::InvalidPayoutSplit);
+
+        let split = &mut ctx.accounts.payout_split;
+        split.listing = listing.key();
+        split.recipients = recipients;
+        split.version = ACCOUNT_LAYOUT_VERSION;
+        split.bump = ctx.bumps.payout_split;
+
+        listing.has_payout_split = true;
+
+        emit!(PayoutSplitCreated {
+            listing: listing.key(),
+            recipients: split.recipients.clone(),
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Expire listing (for buy-now listings that reached deadline)
+    pub fn expire_listing(ctx: Context<ExpireListing>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
+
+        let listing = &mut ctx.accounts.listing;
+
+        // Validations
+        require!(
+            listing.status == ListingStatus::Active,
+            AppMarketError::ListingNotActive
+        );
+        require!(
+            clock.unix_timestamp >= listing.end_time,
+            AppMarketError::ListingNotExpired
+        );
+        require!(
+            listing.current_bidder.is_none(),
+            AppMarketError::HasBids
+        );
+        // SECURITY: Escrow closes here; unit sales still holding funds must settle first
+        require!(
+            ctx.accounts.escrow.amount == 0,
+            AppMarketError::PendingWithdrawalsExist
+        );
+
+        set_listing_status(
+            listing,
+            ListingStatus::Ended,
+            ctx.accounts.caller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(ListingExpired {
+            listing: listing.key(),
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Seller checks off one bundled app as transferred (bundle listings only)
+    pub fn confirm_bundle_asset(ctx: Context<ConfirmBundleAsset>, asset_index: u8) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let listing = &ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::InEscrow,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(
+            ctx.accounts.listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            !transaction.seller_confirmed_transfer,
+            AppMarketError::AlreadyConfirmed
+        );
+
+        let asset = *listing.bundle_assets
+            .get(asset_index as usize)
+            .ok_or(AppMarketError::InvalidBundleAsset)?;
+        let bit = 1u16 << asset_index;
+        require!(
+            transaction.bundle_confirmed & bit == 0,
+            AppMarketError::AlreadyConfirmed
+        );
+
+        transaction.bundle_confirmed |= bit;
+
+        emit!(BundleAssetTransferred {
+            listing: ctx.accounts.listing.key(),
+            transaction: transaction.key(),
+            asset,
+            asset_index,
+            remaining: (listing.bundle_assets.len() as u8)
+                .saturating_sub(transaction.bundle_confirmed.count_ones() as u8),
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Record a content hash / IPFS CID of a delivered artifact (seller or operator).
+    /// Must happen before seller_confirm_transfer so verification and disputes
+    /// reference exactly what was delivered.
+    pub fn record_deliverable(ctx: Context<RecordDeliverable>, content_hash: String) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::InEscrow,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(
+            ctx.accounts.listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            !transaction.seller_confirmed_transfer,
+            AppMarketError::AlreadyConfirmed
+        );
+        require!(
+            !content_hash.is_empty() && content_hash.len() <= MAX_DELIVERABLE_LEN,
+            AppMarketError::InvalidDeliverable
+        );
+        require!(
+            transaction.deliverables.len() < MAX_DELIVERABLES,
+            AppMarketError::TooManyDeliverables
+        );
+        require!(
+            !transaction.deliverables.contains(&content_hash),
+            AppMarketError::InvalidDeliverable
+        );
+
+        transaction.deliverables.push(content_hash.clone());
+
+        emit!(DeliverableRecorded {
+            transaction: transaction.key(),
+            seller: transaction.seller,
+            content_hash,
+            index: (transaction.deliverables.len() - 1) as u8,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Seller confirms they have transferred all assets (on-chain proof)
+    pub fn seller_confirm_transfer(ctx: Context<SellerConfirmTransfer>) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // SECURITY: Verify seller is the actual signer (defense-in-depth, Signer type also checks)
+        require!(
+            ctx.accounts.seller.is_signer,
+            AppMarketError::SellerMustSign
+        );
+
+        // Validations
+        require!(
+            transaction.status == TransactionStatus::InEscrow,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(
+            ctx.accounts.listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            !transaction.seller_confirmed_transfer,
+            AppMarketError::AlreadyConfirmed
+        );
+        // Bundles: every app on the checklist must be transferred first
+        require!(
+            transaction.bundle_confirmed.count_ones() as usize
+                == ctx.accounts.listing.bundle_assets.len(),
+            AppMarketError::BundleIncomplete
+        );
+
+        transaction.seller_confirmed_transfer = true;
+        transaction.seller_confirmed_at = Some(clock.unix_timestamp);
+        // Re-confirming after a rejection starts a new verification round
+        transaction.uploads_rejected_at = None;
+        transaction.rejection_code = None;
+
+        // A re-confirmation after rejected uploads finds the listing already TransferPending
+        if ctx.accounts.listing.status != ListingStatus::TransferPending {
+            set_sale_status(
+                &mut ctx.accounts.listing,
+                transaction,
+                ListingStatus::TransferPending,
+                ctx.accounts.seller.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        emit!(SellerConfirmedTransfer {
+            transaction: transaction.key(),
+            seller: transaction.seller,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Attest that a buyer passed the backend's human/KYC check (backend authority only)
+    /// Listings with requires_verification only accept bids and purchases from attested buyers.
+    pub fn attest_buyer(ctx: Context<AttestBuyer>, buyer: Pubkey, expires_at: i64) -> Result<()> {
+        require!(
+            ctx.accounts.backend_authority.key() == ctx.accounts.config.backend_authority,
+            AppMarketError::NotBackendAuthority
+        );
+
+        let clock = Clock::get()?;
+        require!(
+            expires_at > clock.unix_timestamp
+                && expires_at <= clock.unix_timestamp + MAX_ATTESTATION_VALIDITY_SECONDS,
+            AppMarketError::InvalidAttestationExpiry
+        );
+
+        let attestation = &mut ctx.accounts.buyer_attestation;
+        attestation.buyer = buyer;
+        attestation.attested_at = clock.unix_timestamp;
+        attestation.expires_at = expires_at;
+        attestation.version = ACCOUNT_LAYOUT_VERSION;
+        attestation.bump = ctx.bumps.buyer_attestation;
+
+        emit!(BuyerAttested {
+            buyer,
+            buyer_attestation: ctx.accounts.buyer_attestation.key(),
+            expires_at,
+            actor: ctx.accounts.backend_authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a buyer attestation (backend authority only); re-attest to renew
+    pub fn revoke_buyer_attestation(ctx: Context<RevokeBuyerAttestation>) -> Result<()> {
+        require!(
+            ctx.accounts.backend_authority.key() == ctx.accounts.config.backend_authority,
+            AppMarketError::NotBackendAuthority
+        );
+
+        emit!(BuyerAttestationRevoked {
+            buyer: ctx.accounts.buyer_attestation.buyer,
+            buyer_attestation: ctx.accounts.buyer_attestation.key(),
+            actor: ctx.accounts.backend_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Backend service verifies uploads (GitHub repo, files, etc.)
+    pub fn verify_uploads(
+        ctx: Context<VerifyUploads>,
+        verification_hash: String,
+    ) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // SECURITY: Only backend authority can verify
+        require!(
+            ctx.accounts.backend_authority.key() == ctx.accounts.config.backend_authority,
+            AppMarketError::NotBackendAuthority
+        );
+
+        require!(
+            transaction.seller_confirmed_transfer,
+            AppMarketError::SellerNotConfirmed
+        );
+
+        require!(
+            !transaction.uploads_verified,
+            AppMarketError::AlreadyVerified
+        );
+
+        transaction.verification_hash = verification_hash.clone();
+        transaction.record_verification(
+            VerificationReport {
+                merkle_root: [0u8; 32],
+                item_count: 0,
+                verifier: ctx.accounts.backend_authority.key(),
+                method: VerificationMethod::LegacyHash,
+            },
+            clock.unix_timestamp,
+        );
+
+        emit!(UploadsVerified {
+            transaction: transaction.key(),
+            verification_hash,
+            actor: ctx.accounts.backend_authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Backend service verifies uploads with a typed report: merkle root over the
+    /// verified items (e.g. the recorded deliverables) and how many there were
+    pub fn verify_uploads_v2(
+        ctx: Context<VerifyUploadsV2>,
+        merkle_root: [u8; 32],
+        item_count: u16,
+    ) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // SECURITY: Only backend authority can verify
+        require!(
+            ctx.accounts.backend_authority.key() == ctx.accounts.config.backend_authority,
+            AppMarketError::NotBackendAuthority
+        );
+
+        require!(
+            transaction.seller_confirmed_transfer,
+            AppMarketError::SellerNotConfirmed
+        );
+
+        require!(
+            !transaction.uploads_verified,
+            AppMarketError::AlreadyVerified
+        );
+
+        require!(
+            item_count > 0 && merkle_root != [0u8; 32],
+            AppMarketError::InvalidVerificationReport
+        );
+
+        let report = VerificationReport {
+            merkle_root,
+            item_count,
+            verifier: ctx.accounts.backend_authority.key(),
+            method: VerificationMethod::Backend,
+        };
+        transaction.record_verification(report.clone(), clock.unix_timestamp);
+
+        let round = &mut ctx.accounts.verification_round;
+        round.transaction = transaction.key();
+        round.round = transaction.verification_rounds;
+        round.passed = true;
+        round.report = Some(report.clone());
+        round.rejection_code = None;
+        round.deliverable_count = transaction.deliverables.len() as u8;
+        round.recorded_at = clock.unix_timestamp;
+        round.version = ACCOUNT_LAYOUT_VERSION;
+        round.bump = ctx.bumps.verification_round;
+        transaction.verification_rounds = transaction.verification_rounds
+            .checked_add(1)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        emit!(VerificationReportRecorded {
+            transaction: transaction.key(),
+            report,
+            actor: ctx.accounts.backend_authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Backend service rejects uploads with a reason code (backend authority only).
+    /// Unblocks the buyer immediately: open_dispute ignores the dispute deadline and
+    /// emergency_refund ignores the transfer deadline and seller confirmation.
+    /// Each rejection is kept as a VerificationRound; the seller confirmation is reset
+    /// so the seller can fix the delivery and confirm again for another round.
+    pub fn reject_uploads(ctx: Context<RejectUploads>, reason_code: u16) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // SECURITY: Only backend authority can reject
+        require!(
+            ctx.accounts.backend_authority.key() == ctx.accounts.config.backend_authority,
+            AppMarketError::NotBackendAuthority
+        );
+
+        require!(
+            transaction.status == TransactionStatus::InEscrow,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(
+            transaction.seller_confirmed_transfer,
+            AppMarketError::SellerNotConfirmed
+        );
+        require!(
+            !transaction.uploads_verified,
+            AppMarketError::AlreadyVerified
+        );
+
+        transaction.uploads_rejected_at = Some(clock.unix_timestamp);
+        transaction.rejection_code = Some(reason_code);
+        transaction.seller_confirmed_transfer = false;
+        transaction.seller_confirmed_at = None;
+
+        let round = &mut ctx.accounts.verification_round;
+        round.transaction = transaction.key();
+        round.round = transaction.verification_rounds;
+        round.passed = false;
+        round.report = None;
+        round.rejection_code = Some(reason_code);
+        round.deliverable_count = transaction.deliverables.len() as u8;
+        round.recorded_at = clock.unix_timestamp;
+        round.version = ACCOUNT_LAYOUT_VERSION;
+        round.bump = ctx.bumps.verification_round;
+        transaction.verification_rounds = transaction.verification_rounds
+            .checked_add(1)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        emit!(UploadsRejected {
+            transaction: transaction.key(),
+            round: round.round,
+            reason_code,
+            actor: ctx.accounts.backend_authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Emergency auto-verification by buyer after backend timeout (30 days)
+    /// SECURITY: Fallback mechanism if backend is unresponsive
+    pub fn emergency_auto_verify(ctx: Context<EmergencyAutoVerify>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // SECURITY: Only buyer can trigger emergency auto-verify
+        require!(
+            ctx.accounts.buyer.key() == transaction.buyer,
+            AppMarketError::NotBuyer
+        );
+
+        require!(
+            transaction.seller_confirmed_transfer,
+            AppMarketError::SellerNotConfirmed
+        );
+
+        require!(
+            !transaction.uploads_verified,
+            AppMarketError::AlreadyVerified
+        );
+
+        // SECURITY: Must wait 30 days from seller confirmation
+        let confirmed_at = transaction.seller_confirmed_at.ok_or(AppMarketError::SellerNotConfirmed)?;
+        require!(
+            clock.unix_timestamp >= confirmed_at + BACKEND_TIMEOUT_SECONDS,
+            AppMarketError::BackendTimeoutNotExpired
+        );
+
+        // Auto-verify
+        transaction.record_verification(
+            VerificationReport {
+                merkle_root: [0u8; 32],
+                item_count: 0,
+                verifier: ctx.accounts.buyer.key(),
+                method: VerificationMethod::BuyerTimeout,
+            },
+            clock.unix_timestamp,
+        );
+
+        emit!(EmergencyVerification {
+            transaction: transaction.key(),
+            actor: ctx.accounts.buyer.key(),
+            verification_type: "buyer_timeout".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Admin emergency verification after backend timeout (30 days)
+    /// SECURITY: Admin can only intervene after same 30-day timeout as buyer
+    pub fn admin_emergency_verify(ctx: Context<AdminEmergencyVerify>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // SECURITY: Only admin can call
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+
+        require!(
+            transaction.seller_confirmed_transfer,
+            AppMarketError::SellerNotConfirmed
+        );
+
+        require!(
+            !transaction.uploads_verified,
+            AppMarketError::AlreadyVerified
+        );
+
+        // SECURITY: Admin must also wait 30 days - no special privileges
+        let confirmed_at = transaction.seller_confirmed_at.ok_or(AppMarketError::SellerNotConfirmed)?;
+        require!(
+            clock.unix_timestamp >= confirmed_at + BACKEND_TIMEOUT_SECONDS,
+            AppMarketError::BackendTimeoutNotExpired
+        );
+
+        // Admin verify
+        transaction.record_verification(
+            VerificationReport {
+                merkle_root: [0u8; 32],
+                item_count: 0,
+                verifier: ctx.accounts.admin.key(),
+                method: VerificationMethod::AdminOverride,
+            },
+            clock.unix_timestamp,
+        );
+
+        emit!(EmergencyVerification {
+            transaction: transaction.key(),
+            actor: ctx.accounts.admin.key(),
+            verification_type: "admin_override".to_string(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Finalize transaction after grace period (7 days after seller confirmation)
+    /// remaining_accounts: the stake unwind accounts while the escrow is staked
+    /// (unwind_escrow_stake), then the payout split recipients
+    pub fn finalize_transaction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeTransaction<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // SECURITY: Only seller can call finalize
+        require!(
+            ctx.accounts.seller.key() == transaction.seller,
+            AppMarketError::NotSeller
+        );
+        require!(
+            ctx.accounts.seller.is_signer,
+            AppMarketError::SellerMustSign
+        );
+
+        // Validations
+        // SECURITY: Block finalization if disputed
+        if transaction.status == TransactionStatus::Disputed {
+            return Err(AppMarketError::CannotFinalizeDisputed.into());
+        }
+
+        require!(
+            transaction.status == TransactionStatus::InEscrow,
+            AppMarketError::InvalidTransactionStatus
+        );
+
+        require!(
+            transaction.seller_confirmed_transfer,
+            AppMarketError::SellerNotConfirmed
+        );
+
+        // SECURITY: Uploads must be verified
+        require!(
+            transaction.uploads_verified,
+            AppMarketError::UploadsNotVerified
+        );
+
+        let confirmed_at = transaction.seller_confirmed_at
+            .ok_or(AppMarketError::SellerNotConfirmed)?;
+        require!(
+            clock.unix_timestamp >= confirmed_at + FINALIZE_GRACE_PERIOD,
+            AppMarketError::GracePeriodNotExpired
+        );
+
+        require!(
+            ctx.accounts.treasury.key() == ctx.accounts.config.fee_destination(),
+            AppMarketError::InvalidTreasury
+        );
+
+        // Staked escrow: redeem it first (its accounts lead remaining_accounts)
+        let (stake_accounts, split_accounts) =
+            take_escrow_stake_accounts(transaction, ctx.remaining_accounts)?;
+        if let Some(stake_accounts) = stake_accounts {
+            unwind_escrow_stake(
+                stake_accounts,
+                &mut ctx.accounts.escrow,
+                transaction,
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.seller.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+
+        let required_balance = transaction.platform_fee
+            .checked_add(transaction.seller_proceeds)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            escrow_balance >= required_balance + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // Allow finalization even with pending withdrawals — escrow stays open for cleanup
+        // The >= check ensures enough SOL exists for the sale; excess is pending withdrawal SOL
+        // that will be returned via expire_withdrawal/withdraw_funds + close_escrow
+        require!(
+            ctx.accounts.escrow.amount >= required_balance,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // Transfer funds
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Platform fee to treasury (less the insurance fund slice)
+        pay_platform_fee(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+            ctx.accounts.config.insurance_fee_bps,
+            transaction.platform_fee,
+            ctx.accounts.seller.key(),
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+
+        ctx.accounts.escrow.debit(
+            transaction.platform_fee,
+            EscrowChangeReason::PlatformFee,
+            ctx.accounts.seller.key(),
+        )?;
+
+        // Seller proceeds to seller (vesting: proceeds stay escrowed and stream out via claims)
+        if transaction.vesting_duration > 0 {
+            transaction.vesting_start = Some(clock.unix_timestamp);
+
+            emit!(ProceedsVestingStarted {
+                transaction: transaction.key(),
+                seller: transaction.seller,
+                amount: transaction.seller_proceeds,
+                vesting_end: clock.unix_timestamp
+                    .checked_add(transaction.vesting_duration)
+                    .ok_or(AppMarketError::MathOverflow)?,
+                actor: ctx.accounts.seller.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        } else {
+            // Warranty holdback stays escrowed; the rest is paid now
+            let transaction_key = transaction.key();
+            let holdback = start_holdback(
+                transaction,
+                transaction_key,
+                ctx.accounts.listing.holdback_bps,
+                ctx.accounts.seller.key(),
+                clock.unix_timestamp,
+            )?;
+            let payout = transaction.seller_proceeds
+                .checked_sub(holdback)
+                .ok_or(AppMarketError::MathOverflow)?;
+
+            pay_seller_proceeds(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.payout_wallet.to_account_info(),
+                &ctx.accounts.listing,
+                ctx.accounts.payout_split.as_deref(),
+                split_accounts,
+                payout,
+                ctx.accounts.system_program.to_account_info(),
+                signer,
+            )?;
+
+            ctx.accounts.escrow.debit(
+                payout,
+                EscrowChangeReason::SellerPayout,
+                ctx.accounts.seller.key(),
+            )?;
+        }
+
+        // Update transaction status
+        transaction.status = TransactionStatus::Completed;
+        transaction.completed_at = Some(clock.unix_timestamp);
+        set_sale_status(
+            &mut ctx.accounts.listing,
+            transaction,
+            ListingStatus::Completed,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        // SECURITY: Use saturating_add for stats
+        let config = &mut ctx.accounts.config;
+        config.total_volume = config.total_volume.saturating_add(transaction.sale_price);
+        config.total_sales = config.total_sales.saturating_add(1);
+        config.record_sale_volume(transaction.sale_price, ctx.accounts.seller.key())?;
+        ctx.accounts.volume_stats.record(transaction, clock.unix_timestamp);
+
+        // Loyalty points for both parties (profiles are optional)
+        accrue_loyalty_points(
+            ctx.accounts.buyer_profile.as_mut(),
+            transaction.sale_price,
+            ctx.accounts.listing.payment_currency(),
+            config.loyalty_points_per_sol,
+            true,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+        accrue_loyalty_points(
+            ctx.accounts.seller_profile.as_mut(),
+            transaction.sale_price,
+            ctx.accounts.listing.payment_currency(),
+            config.loyalty_points_per_sol,
+            false,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(TransactionCompleted {
+            transaction: transaction.key(),
+            seller: transaction.seller,
+            buyer: transaction.buyer,
+            amount: transaction.sale_price,
+            platform_fee: transaction.platform_fee,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Buyer confirms receipt of all assets - releases escrow
+    /// remaining_accounts: the stake unwind accounts while the escrow is staked
+    /// (unwind_escrow_stake), then the payout split recipients
+    pub fn confirm_receipt<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfirmReceipt<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        // Validations
+        require!(transaction.status == TransactionStatus::InEscrow, AppMarketError::InvalidTransactionStatus);
+        require!(transaction.is_buyer_signer(&ctx.accounts.buyer.key()), AppMarketError::NotBuyer);
+        require!(
+            ctx.accounts.treasury.key() == ctx.accounts.config.fee_destination(),
+            AppMarketError::InvalidTreasury
+        );
+        require!(
+            ctx.accounts.seller.key() == transaction.seller,
+            AppMarketError::InvalidSeller
+        );
+
+        // SECURITY: Require upload verification before buyer can confirm receipt
+        require!(
+            transaction.uploads_verified,
+            AppMarketError::UploadsNotVerified
+        );
+
+        // Staked escrow: redeem it first (its accounts lead remaining_accounts)
+        let (stake_accounts, split_accounts) =
+            take_escrow_stake_accounts(transaction, ctx.remaining_accounts)?;
+        if let Some(stake_accounts) = stake_accounts {
+            unwind_escrow_stake(
+                stake_accounts,
+                &mut ctx.accounts.escrow,
+                transaction,
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.buyer.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        // SECURITY: Validate escrow balance (4 checks)
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+
+        // Check 1: Sufficient for payment + rent
+        let required_balance = transaction.platform_fee
+            .checked_add(transaction.seller_proceeds)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            escrow_balance >= required_balance + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // Check 2: Tracked amount matches reality
+        let tracked_with_rent = ctx.accounts.escrow.amount
+            .checked_add(rent)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            escrow_balance >= tracked_with_rent,
+            AppMarketError::EscrowBalanceMismatch
+        );
+
+        // Allow confirmation even with pending withdrawals — escrow stays open for cleanup
+        require!(
+            ctx.accounts.escrow.amount >= required_balance,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // Transfer funds
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Platform fee to treasury (less the insurance fund slice)
+        pay_platform_fee(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+            ctx.accounts.config.insurance_fee_bps,
+            transaction.platform_fee,
+            ctx.accounts.buyer.key(),
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+
+        ctx.accounts.escrow.debit(
+            transaction.platform_fee,
+            EscrowChangeReason::PlatformFee,
+            ctx.accounts.buyer.key(),
+        )?;
+
+        // Seller proceeds to seller (vesting: proceeds stay escrowed and stream out via claims)
+        if transaction.vesting_duration > 0 {
+            transaction.vesting_start = Some(clock.unix_timestamp);
+
+            emit!(ProceedsVestingStarted {
+                transaction: transaction.key(),
+                seller: transaction.seller,
+                amount: transaction.seller_proceeds,
+                vesting_end: clock.unix_timestamp
+                    .checked_add(transaction.vesting_duration)
+                    .ok_or(AppMarketError::MathOverflow)?,
+                actor: ctx.accounts.buyer.key(),
+                timestamp: clock.unix_timestamp,
+            });
+        } else {
+            // Warranty holdback stays escrowed; the rest is paid now
+            let transaction_key = transaction.key();
+            let holdback = start_holdback(
+                transaction,
+                transaction_key,
+                ctx.accounts.listing.holdback_bps,
+                ctx.accounts.buyer.key(),
+                clock.unix_timestamp,
+            )?;
+            let payout = transaction.seller_proceeds
+                .checked_sub(holdback)
+                .ok_or(AppMarketError::MathOverflow)?;
+
+            pay_seller_proceeds(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.payout_wallet.to_account_info(),
+                &ctx.accounts.listing,
+                ctx.accounts.payout_split.as_deref(),
+                split_accounts,
+                payout,
+                ctx.accounts.system_program.to_account_info(),
+                signer,
+            )?;
+
+            ctx.accounts.escrow.debit(
+                payout,
+                EscrowChangeReason::SellerPayout,
+                ctx.accounts.buyer.key(),
+            )?;
+        }
+
+        // Update transaction status
+        transaction.status = TransactionStatus::Completed;
+        transaction.completed_at = Some(clock.unix_timestamp);
+        set_sale_status(
+            &mut ctx.accounts.listing,
+            transaction,
+            ListingStatus::Completed,
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+
+        // SECURITY: Use saturating_add for stats (prevents overflow blocking transactions)
+        let config = &mut ctx.accounts.config;
+        config.total_volume = config.total_volume.saturating_add(transaction.sale_price);
+        config.total_sales = config.total_sales.saturating_add(1);
+        config.record_sale_volume(transaction.sale_price, ctx.accounts.buyer.key())?;
+        ctx.accounts.volume_stats.record(transaction, clock.unix_timestamp);
+
+        // Loyalty points for both parties (profiles are optional)
+        accrue_loyalty_points(
+            ctx.accounts.buyer_profile.as_mut(),
+            transaction.sale_price,
+            ctx.accounts.listing.payment_currency(),
+            config.loyalty_points_per_sol,
+            true,
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+        accrue_loyalty_points(
+            ctx.accounts.seller_profile.as_mut(),
+            transaction.sale_price,
+            ctx.accounts.listing.payment_currency(),
+            config.loyalty_points_per_sol,
+            false,
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(TransactionCompleted {
+            transaction: transaction.key(),
+            seller: transaction.seller,
+            buyer: transaction.buyer,
+            amount: transaction.sale_price,
+            platform_fee: transaction.platform_fee,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim the vested portion of streamed seller proceeds (seller only)
+    /// Proceeds vest linearly from completion over the transaction's vesting duration;
+    /// a vesting dispute stops the clock (open_vesting_dispute)
+    pub fn claim_vested_proceeds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimVestedProceeds<'info>>,
+    ) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::Completed,
+            AppMarketError::TransactionNotComplete
+        );
+        require!(
+            ctx.accounts.seller.key() == transaction.seller,
+            AppMarketError::NotSeller
+        );
+
+        // A resolved vesting dispute settles everything, leaving nothing to claim
+        let vested = transaction.vested_proceeds(clock.unix_timestamp)?;
+        let claimable = vested.saturating_sub(transaction.vested_claimed);
+        require!(claimable > 0, AppMarketError::NothingToClaim);
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= claimable + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+        require!(
+            ctx.accounts.escrow.amount >= claimable,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // EFFECTS
+        transaction.vested_claimed = vested;
+        ctx.accounts.escrow.debit(
+            claimable,
+            EscrowChangeReason::VestingRelease,
+            ctx.accounts.seller.key(),
+        )?;
+
+        // INTERACTIONS
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        pay_seller_proceeds(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.payout_wallet.to_account_info(),
+            &ctx.accounts.listing,
+            ctx.accounts.payout_split.as_deref(),
+            ctx.remaining_accounts,
+            claimable,
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+
+        emit!(VestedProceedsClaimed {
+            transaction: transaction.key(),
+            seller: transaction.seller,
+            amount: claimable,
+            total_claimed: transaction.vested_claimed,
+            remaining: transaction.seller_proceeds.saturating_sub(transaction.vested_claimed),
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim warranty-held proceeds once the warranty period ends without a dispute (seller only)
+    pub fn claim_holdback<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimHoldback<'info>>,
+    ) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::Completed,
+            AppMarketError::TransactionNotComplete
+        );
+        require!(
+            ctx.accounts.seller.key() == transaction.seller,
+            AppMarketError::NotSeller
+        );
+        let amount = transaction.holdback_amount;
+        require!(amount > 0, AppMarketError::NothingToClaim);
+        let release_at = transaction.holdback_release_at
+            .ok_or(AppMarketError::NothingToClaim)?;
+        require!(
+            clock.unix_timestamp >= release_at,
+            AppMarketError::WarrantyPeriodActive
+        );
+        require!(
+            transaction.warranty_disputed_at.is_none(),
+            AppMarketError::WarrantyDisputed
+        );
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= amount + rent && ctx.accounts.escrow.amount >= amount,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // EFFECTS
+        transaction.holdback_amount = 0;
+        ctx.accounts.escrow.debit(
+            amount,
+            EscrowChangeReason::HoldbackRelease,
+            ctx.accounts.seller.key(),
+        )?;
+
+        // INTERACTIONS
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        pay_seller_proceeds(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.payout_wallet.to_account_info(),
+            &ctx.accounts.listing,
+            ctx.accounts.payout_split.as_deref(),
+            ctx.remaining_accounts,
+            amount,
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+
+        emit!(HoldbackClaimed {
+            transaction: transaction.key(),
+            seller: transaction.seller,
+            amount,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Open a warranty dispute over held-back proceeds during the warranty period (buyer only)
+    /// Limited to the holdback: the rest of the sale is final. Freezes the holdback until
+    /// the admin (or the transaction's arbitrator) splits it.
+    pub fn open_warranty_dispute(ctx: Context<OpenWarrantyDispute>, reason: String) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        require!(reason.len() <= 500, AppMarketError::DisputeReasonTooLong);
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::Completed,
+            AppMarketError::TransactionNotComplete
+        );
+        require!(
+            ctx.accounts.buyer.key() == transaction.buyer,
+            AppMarketError::NotBuyer
+        );
+        require!(transaction.holdback_amount > 0, AppMarketError::NoHoldback);
+        let release_at = transaction.holdback_release_at
+            .ok_or(AppMarketError::NoHoldback)?;
+        require!(
+            clock.unix_timestamp < release_at,
+            AppMarketError::WarrantyPeriodExpired
+        );
+        require!(
+            transaction.warranty_disputed_at.is_none(),
+            AppMarketError::WarrantyDisputed
+        );
+
+        transaction.warranty_disputed_at = Some(clock.unix_timestamp);
+
+        emit!(WarrantyDisputeOpened {
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            holdback_amount: transaction.holdback_amount,
+            reason,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Split a disputed holdback between buyer and seller (admin, or the transaction's arbitrator)
+    pub fn resolve_warranty_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveWarrantyDispute<'info>>,
+        buyer_amount: u64,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == ctx.accounts.config.admin
+                || Some(authority) == ctx.accounts.transaction.arbitrator,
+            AppMarketError::NotAdmin
+        );
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.warranty_disputed_at.is_some(),
+            AppMarketError::NoWarrantyDispute
+        );
+        let holdback = transaction.holdback_amount;
+        require!(holdback > 0, AppMarketError::NoHoldback);
+        require!(buyer_amount <= holdback, AppMarketError::InvalidRefundAmounts);
+        let seller_amount = holdback
+            .checked_sub(buyer_amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= holdback + rent && ctx.accounts.escrow.amount >= holdback,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // EFFECTS
+        transaction.holdback_amount = 0;
+        ctx.accounts.escrow.debit(
+            holdback,
+            EscrowChangeReason::WarrantyResolution,
+            ctx.accounts.authority.key(),
+        )?;
+
+        // INTERACTIONS
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        if buyer_amount > 0 {
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.buyer.to_account_info(),
+                },
+                signer,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, buyer_amount)?;
+        }
+
+        if seller_amount > 0 {
+            pay_seller_proceeds(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.payout_wallet.to_account_info(),
+                &ctx.accounts.listing,
+                ctx.accounts.payout_split.as_deref(),
+                ctx.remaining_accounts,
+                seller_amount,
+                ctx.accounts.system_program.to_account_info(),
+                signer,
+            )?;
+        }
+
+        emit!(WarrantyDisputeResolved {
+            transaction: transaction.key(),
+            buyer_amount,
+            seller_amount,
+            actor: authority,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Dispute a streamed sale during its earn-out (buyer only)
+    /// Vesting stops: the seller can still claim what vested before the dispute, and the
+    /// unvested remainder is frozen in escrow until the admin (or the transaction's
+    /// arbitrator) splits it.
+    pub fn open_vesting_dispute(ctx: Context<OpenVestingDispute>, reason: String) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        require!(reason.len() <= 500, AppMarketError::DisputeReasonTooLong);
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::Completed,
+            AppMarketError::TransactionNotComplete
+        );
+        require!(
+            transaction.is_buyer_signer(&ctx.accounts.buyer.key()),
+            AppMarketError::NotBuyer
+        );
+        let vesting_start = transaction.vesting_start
+            .ok_or(AppMarketError::NoVestingSchedule)?;
+        let vesting_end = vesting_start
+            .checked_add(transaction.vesting_duration)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp < vesting_end,
+            AppMarketError::VestingPeriodEnded
+        );
+        require!(
+            transaction.vesting_disputed_at.is_none(),
+            AppMarketError::VestingDisputed
+        );
+
+        let frozen_amount = transaction.seller_proceeds
+            .checked_sub(transaction.vested_proceeds(clock.unix_timestamp)?)
+            .ok_or(AppMarketError::MathOverflow)?;
+        transaction.vesting_disputed_at = Some(clock.unix_timestamp);
+
+        emit!(VestingDisputeOpened {
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            frozen_amount,
+            reason,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Settle a vesting dispute (admin, or the transaction's arbitrator)
+    /// Claws back up to the frozen unvested remainder to the buyer; everything else still
+    /// escrowed for the seller (including vested but unclaimed proceeds) is paid out now
+    pub fn resolve_vesting_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveVestingDispute<'info>>,
+        buyer_amount: u64,
+    ) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == ctx.accounts.config.admin
+                || Some(authority) == ctx.accounts.transaction.arbitrator,
+            AppMarketError::NotAdmin
+        );
+
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        let disputed_at = transaction.vesting_disputed_at
+            .ok_or(AppMarketError::NoVestingDispute)?;
+        let outstanding = transaction.seller_proceeds
+            .checked_sub(transaction.vested_claimed)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(outstanding > 0, AppMarketError::NoVestingDispute);
+        let frozen = transaction.seller_proceeds
+            .checked_sub(transaction.vested_proceeds(disputed_at)?)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(buyer_amount <= frozen, AppMarketError::InvalidRefundAmounts);
+        let seller_amount = outstanding
+            .checked_sub(buyer_amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= outstanding + rent && ctx.accounts.escrow.amount >= outstanding,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // EFFECTS
+        transaction.vested_claimed = transaction.seller_proceeds;
+        ctx.accounts.escrow.debit(
+            outstanding,
+            EscrowChangeReason::VestingDisputeResolution,
+            authority,
+        )?;
+
+        // INTERACTIONS
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        if buyer_amount > 0 {
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.buyer.to_account_info(),
+                },
+                signer,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, buyer_amount)?;
+        }
+
+        if seller_amount > 0 {
+            pay_seller_proceeds(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.payout_wallet.to_account_info(),
+                &ctx.accounts.listing,
+                ctx.accounts.payout_split.as_deref(),
+                ctx.remaining_accounts,
+                seller_amount,
+                ctx.accounts.system_program.to_account_info(),
+                signer,
+            )?;
+        }
+
+        emit!(VestingDisputeResolved {
+            transaction: transaction.key(),
+            buyer_amount,
+            seller_amount,
+            actor: authority,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Make an offer on a listing
+    /// The buyer may be a PDA signing via CPI (offer and escrow rent are paid at account init)
+    /// An offer at or above the listing's auto_accept_price (with no standing bid) is
+    /// accepted on the spot: pass listing_escrow and the listing's transaction PDA.
+    pub fn make_offer(
+        ctx: Context<MakeOffer>,
+        amount: u64,
+        deadline: i64,
+        offer_seed: u64,
+        recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        // Validations
+        require!(
+            listing.status == ListingStatus::Active,
+            AppMarketError::ListingNotActive
+        );
+        require!(listing.has_started(clock.unix_timestamp), AppMarketError::ListingNotStarted);
+        // SECURITY: Pre-check buyer has sufficient balance
+        require!(
+            ctx.accounts.buyer.lamports() >= amount,
+            AppMarketError::InsufficientBalance
+        );
+        let buyer_key = ctx.accounts.buyer.key();
+        register_offer(
+            &ctx.accounts.config,
+            listing,
+            buyer_key,
+            amount,
+            deadline,
+            offer_seed,
+            recipient,
+            clock.unix_timestamp,
+        )?;
+
+        // Initialize offer
+        let offer = &mut ctx.accounts.offer;
+        offer.listing = listing.key();
+        offer.buyer = ctx.accounts.buyer.key();
+        offer.amount = amount;
+        offer.deadline = deadline;
+        offer.status = OfferStatus::Active;
+        offer.created_at = clock.unix_timestamp;
+        offer.recipient = recipient;
+        offer.version = ACCOUNT_LAYOUT_VERSION;
+        offer.bump = ctx.bumps.offer;
+
+        // Initialize escrow for offer
+        let offer_escrow = &mut ctx.accounts.offer_escrow;
+        offer_escrow.offer = offer.key();
+        offer_escrow.amount = amount;
+        offer_escrow.version = ACCOUNT_LAYOUT_VERSION;
+        offer_escrow.bump = ctx.bumps.offer_escrow;
+
+        // Auto-accept only without a standing bid (no outbid refund to arrange here)
+        let auto_accept = listing.current_bidder.is_none()
+            && listing.auto_accept_price.is_some_and(|price| amount >= price);
+        if !auto_accept {
+            // Transfer funds to escrow
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.offer_escrow.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        }
+
+        emit!(OfferCreated {
+            offer: offer.key(),
+            listing: listing.key(),
+            buyer: ctx.accounts.buyer.key(),
+            amount,
+            deadline,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        if !auto_accept {
+            if listing.note_offer(offer.key(), amount) {
+                emit_best_offer_changed(listing, buyer_key, clock.unix_timestamp);
+            }
+            return Ok(());
+        }
+
+        let listing_escrow = ctx.accounts.listing_escrow
+            .as_mut()
+            .ok_or(AppMarketError::AutoAcceptAccountsRequired)?;
+        let transaction_info = ctx.accounts.transaction
+            .as_ref()
+            .ok_or(AppMarketError::AutoAcceptAccountsRequired)?
+            .to_account_info();
+        let listing_key = listing.key();
+        let (transaction_pda, transaction_bump) = Pubkey::find_program_address(
+            &[b"transaction", listing_key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            transaction_info.key(),
+            transaction_pda,
+            AppMarketError::AutoAcceptAccountsRequired
+        );
+
+        // Funds go straight to the listing escrow; the unused offer escrow is closed below
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: listing_escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        listing_escrow.credit(amount, EscrowChangeReason::Purchase, buyer_key)?;
+
+        offer.status = OfferStatus::Accepted;
+        set_listing_status(
+            listing,
+            ListingStatus::InEscrow,
+            buyer_key,
+            clock.unix_timestamp,
+        )?;
+        listing.current_bid = amount;
+        listing.current_bidder = Some(buyer_key);
+
+        // Reset consecutive offer tracking since listing is now sold
+        listing.last_offer_buyer = None;
+        listing.consecutive_offer_count = 0;
+        if listing.clear_best_offer() {
+            emit_best_offer_changed(listing, buyer_key, clock.unix_timestamp);
+        }
+
+        create_pda_account(
+            ctx.accounts.buyer.to_account_info(),
+            transaction_info.clone(),
+            ctx.accounts.system_program.to_account_info(),
+            &[b"transaction", listing_key.as_ref(), &[transaction_bump]],
+            8 + Transaction::INIT_SPACE,
+            ctx.program_id,
+        )?;
+        let mut transaction_data = transaction_info.try_borrow_mut_data()?;
+        transaction_data[..8].copy_from_slice(Transaction::DISCRIMINATOR);
+
+        // Fresh accounts are zeroed: unset fields start at 0 / None / empty
+        let mut transaction = Transaction::try_deserialize(&mut &transaction_data[..])?;
+        populate_offer_transaction(
+            &mut transaction,
+            listing,
+            listing_key,
+            offer,
+            None,
+            clock.unix_timestamp,
+        )?;
+        transaction.bump = transaction_bump;
+        transaction.try_serialize(&mut &mut transaction_data[..])?;
+        drop(transaction_data);
+
+        emit!(OfferAccepted {
+            offer: offer.key(),
+            listing: listing_key,
+            transaction: transaction_pda,
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount,
+            actor: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
+
+        ctx.accounts.offer_escrow.amount = 0;
+        ctx.accounts.offer_escrow.close(ctx.accounts.buyer.to_account_info())?;
+
+        Ok(())
+    }
+
+    /// Make an offer on a listing that expired unsold (Ended with no bidder)
+    /// Funds sit in the offer escrow as usual; accept_offer_on_expired revives the listing
+    /// straight into a sale. Auto-accept does not apply.
+    pub fn make_offer_on_expired(
+        ctx: Context<MakeOffer>,
+        amount: u64,
+        deadline: i64,
+        offer_seed: u64,
+        recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+
+        require!(
+            listing.status == ListingStatus::Ended && listing.current_bidder.is_none(),
+            AppMarketError::ListingNotExpired
+        );
+        // SECURITY: Pre-check buyer has sufficient balance
+        require!(
+            ctx.accounts.buyer.lamports() >= amount,
+            AppMarketError::InsufficientBalance
+        );
+        let buyer_key = ctx.accounts.buyer.key();
+        register_offer(
+            &ctx.accounts.config,
+            listing,
+            buyer_key,
+            amount,
+            deadline,
+            offer_seed,
+            recipient,
+            clock.unix_timestamp,
+        )?;
+
+        let offer = &mut ctx.accounts.offer;
+        offer.listing = listing.key();
+        offer.buyer = buyer_key;
+        offer.amount = amount;
+        offer.deadline = deadline;
+        offer.status = OfferStatus::Active;
+        offer.created_at = clock.unix_timestamp;
+        offer.recipient = recipient;
+        offer.version = ACCOUNT_LAYOUT_VERSION;
+        offer.bump = ctx.bumps.offer;
+
+        let offer_escrow = &mut ctx.accounts.offer_escrow;
+        offer_escrow.offer = offer.key();
+        offer_escrow.amount = amount;
+        offer_escrow.version = ACCOUNT_LAYOUT_VERSION;
+        offer_escrow.bump = ctx.bumps.offer_escrow;
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.offer_escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+        emit!(OfferCreated {
+            offer: offer.key(),
+            listing: listing.key(),
+            buyer: buyer_key,
+            amount,
+            deadline,
+            actor: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
+        if listing.note_offer(offer.key(), amount) {
+            emit_best_offer_changed(listing, buyer_key, clock.unix_timestamp);
+        }
+
+        Ok(())
+    }
+
+    /// Set a listing's offer floor and auto-accept price (seller or operator)
+    /// `auto_accept_price` of None leaves every offer for the seller to accept.
+    pub fn set_offer_thresholds(
+        ctx: Context<SetOfferThresholds>,
+        min_offer: u64,
+        auto_accept_price: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            matches!(listing.status, ListingStatus::Active | ListingStatus::Draft),
+            AppMarketError::ListingNotActive
+        );
+        if let Some(price) = auto_accept_price {
+            require!(
+                price > 0 && price >= min_offer,
+                AppMarketError::InvalidOfferThresholds
+            );
+        }
+
+        listing.min_offer = min_offer;
+        listing.auto_accept_price = auto_accept_price;
+
+        emit!(OfferThresholdsSet {
+            listing: listing.key(),
+            min_offer,
+            auto_accept_price,
+            actor: ctx.accounts.seller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel offer and get refund
+    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+        let offer = &mut ctx.accounts.offer;
+        let clock = Clock::get()?;
+
+        // SECURITY: Verify offer belongs to this listing
+        require!(
+            offer.listing == ctx.accounts.listing.key(),
+            AppMarketError::InvalidOffer
+        );
+
+        // Validations
+        require!(
+            ctx.accounts.buyer.key() == offer.buyer,
+            AppMarketError::NotOfferOwner
+        );
+        require!(
+            offer.status == OfferStatus::Active,
+            AppMarketError::OfferNotActive
+        );
+
+        // Update offer status
+        offer.status = OfferStatus::Cancelled;
+
+        // Update consecutive offer tracking when buyer cancels
+        let listing = &mut ctx.accounts.listing;
+        if let Some(last_buyer) = listing.last_offer_buyer {
+            if last_buyer == ctx.accounts.buyer.key() && listing.consecutive_offer_count > 0 {
+                // Decrement the consecutive count since this buyer cancelled
+                listing.consecutive_offer_count = listing.consecutive_offer_count.saturating_sub(1);
+            }
+        }
+        if listing.drop_offer(offer.key()) {
+            emit_best_offer_changed(listing, ctx.accounts.buyer.key(), clock.unix_timestamp);
+        }
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.offer_escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.offer_escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= offer.amount + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // Refund buyer (escrow will be closed, rent returned to buyer)
+        let seeds = &[
+            b"offer_escrow",
+            offer.to_account_info().key.as_ref(),
+            &[ctx.accounts.offer_escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.offer_escrow.to_account_info(),
+                to: ctx.accounts.buyer.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+
+        emit!(OfferCancelled {
+            offer: offer.key(),
+            listing: ctx.accounts.listing.key(),
+            buyer: offer.buyer,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim expired offer refund
+    /// Expire an offer after deadline (anyone can call, refund goes to buyer)
+    pub fn expire_offer(ctx: Context<ExpireOffer>) -> Result<()> {
+        let offer = &mut ctx.accounts.offer;
+        let clock = Clock::get()?;
+
+        // SECURITY: Verify offer belongs to this listing
+        require!(
+            offer.listing == ctx.accounts.listing.key(),
+            AppMarketError::InvalidOffer
+        );
+
+        // Validations
+        require!(
+            offer.status == OfferStatus::Active,
+            AppMarketError::OfferNotActive
+        );
+        require!(
+            clock.unix_timestamp > offer.deadline,
+            AppMarketError::OfferNotExpired
+        );
+        // SECURITY: Only offer owner (buyer) can expire their own offer
+        require!(
+            ctx.accounts.caller.key() == offer.buyer,
+            AppMarketError::NotOfferOwner
+        );
+
+        // Update offer status
+        offer.status = OfferStatus::Expired;
+
+        // Update consecutive offer tracking when offer expires
+        let listing = &mut ctx.accounts.listing;
+        if let Some(last_buyer) = listing.last_offer_buyer {
+            if last_buyer == offer.buyer && listing.consecutive_offer_count > 0 {
+                // Decrement the consecutive count since this offer expired
+                listing.consecutive_offer_count = listing.consecutive_offer_count.saturating_sub(1);
+            }
+        }
+        if listing.drop_offer(offer.key()) {
+            emit_best_offer_changed(listing, ctx.accounts.caller.key(), clock.unix_timestamp);
+        }
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.offer_escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.offer_escrow.to_account_info().data_len()
+        );
+        require!(
+            escrow_balance >= offer.amount + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        // Refund buyer
+        let seeds = &[
+            b"offer_escrow",
+            offer.to_account_info().key.as_ref(),
+            &[ctx.accounts.offer_escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.offer_escrow.to_account_info(),
+                to: ctx.accounts.buyer.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+
+        emit!(OfferExpired {
+            offer: offer.key(),
+            listing: ctx.accounts.listing.key(),
+            buyer: offer.buyer,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Accept offer (seller or operator)
+    /// `expected_amount` / `expected_buyer` (the offer the seller reviewed) fail the
+    /// acceptance if the offer account no longer matches. `agreement_hash` records the
+    /// off-chain purchase agreement on the Transaction. `withdrawal_bump` (bump of the
+    /// pending_withdrawal PDA) skips the on-chain bump search; any valid bump is accepted,
+    /// so clients should pass the canonical one.
+    pub fn accept_offer(
+        ctx: Context<AcceptOffer>,
+        expected_amount: Option<u64>,
+        expected_buyer: Option<Pubkey>,
+        agreement_hash: Option<[u8; 32]>,
+        withdrawal_bump: Option<u8>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let offer = &mut ctx.accounts.offer;
+        let clock = Clock::get()?;
+
+        // Validations
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            listing.status == ListingStatus::Active,
+            AppMarketError::ListingNotActive
+        );
+        require!(
+            offer.status == OfferStatus::Active,
+            AppMarketError::OfferNotActive
+        );
+        require!(
+            clock.unix_timestamp <= offer.deadline,
+            AppMarketError::OfferExpired
+        );
+        // SECURITY: Expectation guards (state must match what the client built against)
+        if let Some(expected) = expected_amount {
+            require!(offer.amount == expected, AppMarketError::StateChanged);
+        }
+        if let Some(expected) = expected_buyer {
+            require_keys_eq!(offer.buyer, expected, AppMarketError::StateChanged);
+        }
+
+        // SECURITY: Store old values before updating
+        let old_bid = listing.current_bid;
+        let old_bidder = listing.current_bidder;
+        let old_exposure = listing.current_bid_exposure;
+
+        // Update statuses
+        offer.status = OfferStatus::Accepted;
+        set_listing_status(
+            listing,
+            ListingStatus::InEscrow,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+        listing.current_bid = offer.amount;
+        listing.current_bidder = Some(offer.buyer);
+        listing.current_bid_exposure = 0;
+
+        // Reset consecutive offer tracking since listing is now sold
+        listing.last_offer_buyer = None;
+        listing.consecutive_offer_count = 0;
+        if listing.clear_best_offer() {
+            emit_best_offer_changed(listing, ctx.accounts.seller.key(), clock.unix_timestamp);
+        }
+
+        // Transfer funds from offer escrow to listing escrow
+        let offer_escrow_balance = ctx.accounts.offer_escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.offer_escrow.to_account_info().data_len()
+        );
+        require!(
+            offer_escrow_balance >= offer.amount + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        let seeds = &[
+            b"offer_escrow",
+            offer.to_account_info().key.as_ref(),
+            &[ctx.accounts.offer_escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.offer_escrow.to_account_info(),
+                to: ctx.accounts.listing_escrow.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+
+        // Update listing escrow tracking
+        ctx.accounts.listing_escrow.credit(
+            offer.amount,
+            EscrowChangeReason::Purchase,
+            ctx.accounts.seller.key(),
+        )?;
+
+        // SECURITY FIX M-3: Only create withdrawal account when there's a previous bidder
+        // (prevents unnecessary account creation and rent waste)
+        // Bid-escrow listings skip this: the displaced bidder reclaims their own BidEscrow
+        if let Some(previous_bidder) = old_bidder {
+            if previous_bidder != offer.buyer && old_bid > 0 && !listing.uses_bid_escrows {
+                // Refund what the previous bidder actually locked (deposit in deposit mode)
+                let old_escrowed = escrowed_bid_amount(listing.deposit_bps, old_bid)?;
+
+                // Increment withdrawal counter to prevent PDA collision
+                listing.withdrawal_count = listing.withdrawal_count
+                    .checked_add(1)
+                    .ok_or(AppMarketError::MathOverflow)?;
+
+                // Derive PDA and verify
+                let (withdrawal_pda, bump) = withdrawal_pda(
+                    &listing.key(),
+                    listing.withdrawal_count,
+                    withdrawal_bump,
+                    ctx.program_id,
+                )?;
+
+                require!(
+                    withdrawal_pda == ctx.accounts.pending_withdrawal.key(),
+                    AppMarketError::InvalidPreviousBidder
+                );
+
+                // Create the withdrawal account
+                let rent = Rent::get()?;
+                let space = 8 + PendingWithdrawal::INIT_SPACE;
+                let lamports = rent.minimum_balance(space);
+
+                anchor_lang::system_program::create_account(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::CreateAccount {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: ctx.accounts.pending_withdrawal.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                    space as u64,
+                    ctx.program_id,
+                )?;
+
+                // Initialize withdrawal data
+                let mut withdrawal_data = ctx.accounts.pending_withdrawal.try_borrow_mut_data()?;
+                let withdrawal = PendingWithdrawal {
+                    version: ACCOUNT_LAYOUT_VERSION,
+                    user: previous_bidder,
+                    listing: listing.key(),
+                    amount: old_escrowed,
+                    exposure: old_exposure,
+                    withdrawal_id: listing.withdrawal_count,
+                    created_at: clock.unix_timestamp,
+                    expires_at: clock.unix_timestamp + 3600, // 1 hour
+                    rent_payer: ctx.accounts.payer.key(),
+                    bump,
+                };
+
+                withdrawal.try_serialize(&mut &mut withdrawal_data[..])?;
+
+                emit!(WithdrawalCreated {
+                    user: previous_bidder,
+                    listing: listing.key(),
+                    pending_withdrawal: ctx.accounts.pending_withdrawal.key(),
+                    amount: old_escrowed,
+                    withdrawal_id: listing.withdrawal_count,
+                    actor: ctx.accounts.seller.key(),
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+        }
+
+        // Create transaction record
+        let transaction = &mut ctx.accounts.transaction;
+        populate_offer_transaction(
+            transaction,
+            listing,
+            listing.key(),
+            offer,
+            agreement_hash,
+            clock.unix_timestamp,
+        )?;
+        transaction.bump = ctx.bumps.transaction;
+        apply_contact_keys(
+            transaction,
+            ctx.accounts.buyer_profile.as_deref(),
+            ctx.accounts.seller_profile.as_deref(),
+        )?;
+
+        emit!(OfferAccepted {
+            offer: offer.key(),
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount: offer.amount,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Accept an offer on an expired listing (seller or operator)
+    /// Reactivates the listing, recreates its escrow (closed by expire_listing) and opens
+    /// the sale in one step. Guards match accept_offer; an expired listing has no bidder,
+    /// so there is no outbid refund.
+    pub fn accept_offer_on_expired(
+        ctx: Context<AcceptExpiredOffer>,
+        expected_amount: Option<u64>,
+        expected_buyer: Option<Pubkey>,
+        agreement_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let listing = &mut ctx.accounts.listing;
+        let offer = &mut ctx.accounts.offer;
+        let clock = Clock::get()?;
+        let seller_key = ctx.accounts.seller.key();
+
+        require!(listing.can_manage(seller_key), AppMarketError::NotSeller);
+        require!(
+            listing.status == ListingStatus::Ended && listing.current_bidder.is_none(),
+            AppMarketError::ListingNotExpired
+        );
+        require!(
+            offer.status == OfferStatus::Active,
+            AppMarketError::OfferNotActive
+        );
+        require!(
+            clock.unix_timestamp <= offer.deadline,
+            AppMarketError::OfferExpired
+        );
+        // SECURITY: Expectation guards (state must match what the client built against)
+        if let Some(expected) = expected_amount {
+            require!(offer.amount == expected, AppMarketError::StateChanged);
+        }
+        if let Some(expected) = expected_buyer {
+            require_keys_eq!(offer.buyer, expected, AppMarketError::StateChanged);
+        }
+
+        // Revive, then sell: indexers see Ended -> Active -> InEscrow
+        set_listing_status(listing, ListingStatus::Active, seller_key, clock.unix_timestamp)?;
+        set_listing_status(listing, ListingStatus::InEscrow, seller_key, clock.unix_timestamp)?;
+        offer.status = OfferStatus::Accepted;
+        listing.current_bid = offer.amount;
+        listing.current_bidder = Some(offer.buyer);
+        listing.current_bid_exposure = 0;
+        listing.last_offer_buyer = None;
+        listing.consecutive_offer_count = 0;
+        if listing.clear_best_offer() {
+            emit_best_offer_changed(listing, seller_key, clock.unix_timestamp);
+        }
+
+        // Fresh escrow (the previous one closed when the listing expired)
+        let listing_escrow = &mut ctx.accounts.listing_escrow;
+        listing_escrow.listing = listing.key();
+        listing_escrow.amount = 0;
+        listing_escrow.version = ACCOUNT_LAYOUT_VERSION;
+        listing_escrow.bump = ctx.bumps.listing_escrow;
+
+        // Transfer funds from offer escrow to listing escrow
+        let offer_escrow_balance = ctx.accounts.offer_escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.offer_escrow.to_account_info().data_len()
+        );
+        require!(
+            offer_escrow_balance >= offer.amount + rent,
+            AppMarketError::InsufficientEscrowBalance
+        );
+
+        let seeds = &[
+            b"offer_escrow",
+            offer.to_account_info().key.as_ref(),
+            &[ctx.accounts.offer_escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.offer_escrow.to_account_info(),
+                to: ctx.accounts.listing_escrow.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+        ctx.accounts.listing_escrow.credit(
+            offer.amount,
+            EscrowChangeReason::Purchase,
+            seller_key,
+        )?;
+
+        let transaction = &mut ctx.accounts.transaction;
+        populate_offer_transaction(
+            transaction,
+            listing,
+            listing.key(),
+            offer,
+            agreement_hash,
+            clock.unix_timestamp,
+        )?;
+        transaction.bump = ctx.bumps.transaction;
+        apply_contact_keys(
+            transaction,
+            ctx.accounts.buyer_profile.as_deref(),
+            ctx.accounts.seller_profile.as_deref(),
+        )?;
+
+        emit!(OfferAccepted {
+            offer: offer.key(),
+            listing: listing.key(),
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            payer: transaction.payer,
+            seller: listing.seller,
+            amount: offer.amount,
+            actor: seller_key,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Open a dispute, recording its typed category and severity for committee triage
+    pub fn open_dispute(
+        ctx: Context<OpenDispute>,
+        reason: String,
+        category: DisputeCategory,
+        severity: DisputeSeverity,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let clock = Clock::get()?;
+
+        // Validations
+        require!(ctx.accounts.transaction.status == TransactionStatus::InEscrow, AppMarketError::InvalidTransactionStatus);
+        require!(
+            ctx.accounts.transaction.is_buyer_signer(&ctx.accounts.initiator.key()) ||
+            ctx.accounts.initiator.key() == ctx.accounts.transaction.seller,
+            AppMarketError::NotPartyToTransaction
+        );
+        require!(
+            ctx.accounts.treasury.key() == ctx.accounts.config.fee_destination(),
+            AppMarketError::InvalidTreasury
+        );
+
+        // SECURITY: Dispute deadline - must open within 7 days of seller confirmation
+        // After deadline expires, buyer can no longer dispute and seller can finalize
+        // (unless the backend rejected the uploads)
+        if let (Some(confirmed_at), None) = (
+            ctx.accounts.transaction.seller_confirmed_at,
+            ctx.accounts.transaction.uploads_rejected_at,
+        ) {
+            require!(
+                clock.unix_timestamp <= confirmed_at + FINALIZE_GRACE_PERIOD,
+                AppMarketError::DisputeDeadlineExpired
+            );
+        }
+
+        // SECURITY: Pre-check initiator has sufficient balance for dispute fee
+        // Use the locked dispute fee from listing creation time, not the live config
+        // which could be changed by admin after the transaction was created
+        let dispute_fee = ctx.accounts.transaction.sale_price
+            .checked_mul(ctx.accounts.listing.dispute_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        require!(
+            ctx.accounts.initiator.lamports() >= dispute_fee,
+            AppMarketError::InsufficientBalance
+        );
+
+        // SECURITY: Hold dispute fee in Dispute PDA (refunded to buyer if they win)
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.initiator.to_account_info(),
+                to: ctx.accounts.dispute.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, dispute_fee)?;
+
+        // Now take mutable references after CPI call
+        let transaction = &mut ctx.accounts.transaction;
+        let dispute = &mut ctx.accounts.dispute;
+
+        // Update transaction status
+        transaction.status = TransactionStatus::Disputed;
+        set_sale_status(
+            &mut ctx.accounts.listing,
+            transaction,
+            ListingStatus::Disputed,
+            ctx.accounts.initiator.key(),
+            clock.unix_timestamp,
+        )?;
+
+        // Create dispute record
+        dispute.transaction = transaction.key();
+        dispute.initiator = ctx.accounts.initiator.key();
+        dispute.respondent = if transaction.is_buyer_signer(&ctx.accounts.initiator.key()) {
+            transaction.seller
+        } else {
+            transaction.buyer_signer()
+        };
+        dispute.reason = reason.clone();
+        dispute.category = Some(category);
+        dispute.severity = Some(severity);
+        // Locked at open time so a later threshold change cannot move a live dispute
+        dispute.small_claim = ctx.accounts.config.is_small_claim(transaction.sale_price);
+        dispute.status = DisputeStatus::Open;
+        dispute.created_at = clock.unix_timestamp;
+        dispute.dispute_fee = dispute_fee;
+        // SECURITY: Lock fee routing policy at open time, like the fee itself
+        dispute.fee_policy = ctx.accounts.config.dispute_fee_policy.clone();
+        dispute.respondent_bond = 0;
+        dispute.respondent_bonded = false;
+        // Locked at open time: no window (and no default judgment) while bonds are off
+        dispute.response_deadline = if ctx.accounts.config.respondent_bonds_enabled {
+            clock.unix_timestamp
+                .checked_add(RESPONDENT_BOND_WINDOW_SECONDS)
+                .ok_or(AppMarketError::MathOverflow)?
+        } else {
+            0
+        };
+        dispute.version = ACCOUNT_LAYOUT_VERSION;
+        dispute.bump = ctx.bumps.dispute;
+
+        emit!(DisputeOpened {
+            dispute: dispute.key(),
+            transaction: transaction.key(),
+            initiator: dispute.initiator,
+            category,
+            severity,
+            reason,
+            actor: ctx.accounts.initiator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Submit evidence for an active dispute (buyer or seller)
+    /// Creates an Evidence PDA recording a content hash and URI as an on-chain audit trail
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        content_hash: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(uri.len() <= MAX_EVIDENCE_URI_LEN, AppMarketError::EvidenceUriTooLong);
+
+        let submitter = ctx.accounts.submitter.key();
+        let is_buyer = ctx.accounts.transaction.is_buyer_signer(&submitter);
+        require!(
+            is_buyer || submitter == ctx.accounts.transaction.seller,
+            AppMarketError::NotPartyToTransaction
+        );
+
+        let dispute = &mut ctx.accounts.dispute;
+        require!(
+            dispute.status == DisputeStatus::Open
+                || dispute.status == DisputeStatus::UnderReview
+                || dispute.status == DisputeStatus::Appealed,
+            AppMarketError::DisputeNotOpen
+        );
+
+        // SECURITY: Cap per-party submissions (also drives the PDA index)
+        let index = if is_buyer {
+            dispute.buyer_evidence_count
+        } else {
+            dispute.seller_evidence_count
+        };
+        require!(index < MAX_EVIDENCE_PER_PARTY, AppMarketError::TooManyEvidenceSubmissions);
+
+        if is_buyer {
+            dispute.buyer_evidence_count = index + 1;
+        } else {
+            dispute.seller_evidence_count = index + 1;
+        }
+
+        let evidence = &mut ctx.accounts.evidence;
+        evidence.dispute = dispute.key();
+        evidence.party = submitter;
+        evidence.index = index;
+        evidence.content_hash = content_hash;
+        evidence.uri = uri.clone();
+        evidence.submitted_at = clock.unix_timestamp;
+        evidence.version = ACCOUNT_LAYOUT_VERSION;
+        evidence.bump = ctx.bumps.evidence;
+
+        emit!(EvidenceSubmitted {
+            dispute: dispute.key(),
+            evidence: evidence.key(),
+            party: submitter,
+            index,
+            content_hash,
+            uri,
+            actor: ctx.accounts.submitter.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Resolve dispute (admin only)
+    /// Propose dispute resolution (starts 48hr timelock)
+    /// SECURITY: Resolution is not executed immediately - parties can contest
+    pub fn propose_dispute_resolution(
+        ctx: Context<ProposeDisputeResolution>,
+        resolution: DisputeResolution,
+        notes: String,
+    ) -> Result<()> {
+        let transaction = &ctx.accounts.transaction;
+        let dispute = &mut ctx.accounts.dispute;
+        let clock = Clock::get()?;
+
+        // Validations
+        // SECURITY: A designated per-transaction arbitrator replaces the global admin/committee
+        if let Some(arbitrator) = transaction.designated_arbitrator() {
+            require!(ctx.accounts.admin.key() == arbitrator, AppMarketError::NotArbitrator);
+        } else {
+            require!(ctx.accounts.admin.key() == ctx.accounts.config.admin, AppMarketError::NotAdmin);
+            // SECURITY: Once a committee is configured, proposals require an arbitrator quorum
+            require!(
+                !ctx.accounts.config.committee_resolution,
+                AppMarketError::CommitteeResolutionRequired
+            );
+        }
+        require!(
+            dispute.status == DisputeStatus::Open
+                || dispute.status == DisputeStatus::UnderReview
+                || dispute.status == DisputeStatus::Appealed,
+            AppMarketError::DisputeNotOpen
+        );
+
+        // Single-key rulings have no staked panel to slash
+        if dispute.appealed_by.is_none() {
+            dispute.ruling_arbitrators.clear();
+        }
+
+        stage_dispute_resolution(
+            dispute,
+            transaction.sale_price,
+            transaction.platform_fee,
+            resolution,
+            notes,
+            ctx.accounts.admin.key(),
+            clock.unix_timestamp,
+        )
+    }
+
+    /// Create the arbitrator committee (admin only)
+    /// Once created, dispute resolutions are proposed by committee vote instead of the admin key
+    pub fn initialize_arbitrator_set(
+        ctx: Context<InitializeArbitratorSet>,
+        arbitrators: Vec<Pubkey>,
+        quorum: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        validate_arbitrator_set(&arbitrators, quorum)?;
+
+        let arbitrator_set = &mut ctx.accounts.arbitrator_set;
+        arbitrator_set.arbitrators = arbitrators.clone();
+        arbitrator_set.quorum = quorum;
+        arbitrator_set.version = ACCOUNT_LAYOUT_VERSION;
+        arbitrator_set.bump = ctx.bumps.arbitrator_set;
+
+        ctx.accounts.config.committee_resolution = true;
+
+        emit!(ArbitratorSetUpdated {
+            arbitrators,
+            quorum,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Replace committee members and/or quorum (admin only)
+    /// Votes already cast by removed arbitrators stop counting toward quorum
+    pub fn update_arbitrator_set(
+        ctx: Context<UpdateArbitratorSet>,
+        arbitrators: Vec<Pubkey>,
+        quorum: u8,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        validate_arbitrator_set(&arbitrators, quorum)?;
+
+        let arbitrator_set = &mut ctx.accounts.arbitrator_set;
+        arbitrator_set.arbitrators = arbitrators.clone();
+        arbitrator_set.quorum = quorum;
+
+        emit!(ArbitratorSetUpdated {
+            arbitrators,
+            quorum,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Vote for a dispute resolution (arbitrators only)
+    /// Ballots are tallied per outcome; when `quorum` arbitrators back the same outcome it
+    /// becomes the pending resolution and the usual timelock/contest flow applies. An
+    /// arbitrator may switch their own ballot, never anyone else's. Voting closes while a
+    /// resolution is pending (withdraw_dispute_resolution reopens it).
+    pub fn vote_resolution(
+        ctx: Context<VoteResolution>,
+        resolution: DisputeResolution,
+        notes: String,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let arbitrator_set = &ctx.accounts.arbitrator_set;
+        let voter = ctx.accounts.arbitrator.key();
+
+        require!(
+            arbitrator_set.arbitrators.contains(&voter),
+            AppMarketError::NotArbitrator
+        );
+        // Deals with their own designated arbitrator are not committee business
+        require!(
+            ctx.accounts.transaction.designated_arbitrator().is_none(),
+            AppMarketError::ArbitratorAssigned
+        );
+
+        // SECURITY: Arbitrators must have at least the minimum APP stake (excluding unstaking)
+        let min_stake = ctx.accounts.config.min_arbitrator_stake;
+        if min_stake > 0 {
+            let stake = ctx.accounts.arbitrator_stake.as_ref()
+                .ok_or(AppMarketError::InsufficientArbitratorStake)?;
+            require!(
+                stake.active_amount() >= min_stake,
+                AppMarketError::InsufficientArbitratorStake
+            );
+        }
+
+        let dispute = &mut ctx.accounts.dispute;
+        // SECURITY: A staged resolution can only be contested or withdrawn, never re-staged
+        // by a late vote (which would also erase a contest)
+        require!(
+            dispute.status != DisputeStatus::UnderReview,
+            AppMarketError::ResolutionAlreadyPending
+        );
+        require!(
+            dispute.status == DisputeStatus::Open || dispute.status == DisputeStatus::Appealed,
+            AppMarketError::DisputeNotOpen
+        );
+
+        // Only ballots from current committee members count toward quorum
+        dispute.votes.retain(|vote| arbitrator_set.arbitrators.contains(&vote.arbitrator));
+        let backers = cast_ballot(&mut dispute.votes, voter, &resolution)?;
+        let vote_count = backers.len();
+
+        emit!(ResolutionVoteCast {
+            dispute: dispute.key(),
+            arbitrator: voter,
+            resolution: resolution.clone(),
+            votes: vote_count as u8,
+            quorum: arbitrator_set.quorum,
+            actor: ctx.accounts.arbitrator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        if vote_count >= arbitrator_set.quorum as usize {
+            // Record the staked panel behind the original ruling (slashable if overturned)
+            if dispute.appealed_by.is_none() {
+                dispute.ruling_arbitrators = if min_stake > 0 { backers } else { Vec::new() };
+            }
+            dispute.votes.clear();
+            stage_dispute_resolution(
+                dispute,
+                ctx.accounts.transaction.sale_price,
+                ctx.accounts.transaction.platform_fee,
+                resolution,
+                notes,
+                ctx.accounts.arbitrator.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Contest dispute resolution (within 48hr window)
+    /// SECURITY: Either party can contest - emits event for admin review
+    pub fn contest_dispute_resolution(ctx: Context<ContestDisputeResolution>) -> Result<()> {
+        let transaction = &ctx.accounts.transaction;
+        let dispute = &mut ctx.accounts.dispute;
+        let clock = Clock::get()?;
+
+        // Must be buyer or seller
+        let caller = ctx.accounts.caller.key();
+        require!(
+            transaction.is_buyer_signer(&caller) || caller == transaction.seller,
+            AppMarketError::NotPartyToTransaction
+        );
+
+        // Must have pending resolution
+        require!(
+            dispute.pending_resolution.is_some(),
+            AppMarketError::NoPendingResolution
+        );
+
+        // Must be within timelock window (longer for appeals)
+        let proposed_at = dispute.pending_resolution_at.unwrap();
+        require!(
+            clock.unix_timestamp < proposed_at + dispute.resolution_timelock(),
+            AppMarketError::TimelockNotExpired
+        );
+
+        // Cannot contest twice
+        require!(
+            !dispute.contested,
+            AppMarketError::AlreadyContested
+        );
+
+        // SLA defaults follow a fixed rule - nothing to contest
+        require!(!dispute.sla_default, AppMarketError::SlaDefaultNotContestable);
+
+        dispute.contested = true;
+
+        emit!(DisputeContested {
+            dispute: dispute.key(),
+            actor: caller,
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw a pending dispute resolution (admin or designated arbitrator)
+    /// Clears the proposal (e.g. after it was contested) so a new one can be made
+    pub fn withdraw_dispute_resolution(ctx: Context<WithdrawDisputeResolution>) -> Result<()> {
+        let clock = Clock::get()?;
+        let transaction = &ctx.accounts.transaction;
+        let dispute = &mut ctx.accounts.dispute;
+
+        let authority = transaction.designated_arbitrator()
+            .unwrap_or(ctx.accounts.config.admin);
+        require!(ctx.accounts.admin.key() == authority, AppMarketError::NotAdmin);
+
+        require!(
+            dispute.status == DisputeStatus::UnderReview,
+            AppMarketError::DisputeNotOpen
+        );
+        let resolution = dispute.pending_resolution.clone()
+            .ok_or(AppMarketError::NoPendingResolution)?;
+
+        dispute.pending_resolution = None;
+        dispute.pending_buyer_amount = None;
+        dispute.pending_seller_amount = None;
+        dispute.pending_resolution_at = None;
+        dispute.contested = false;
+        dispute.sla_default = false;
+        // Appeals return to the Appealed state, first-instance disputes to Open
+        dispute.status = if dispute.appealed_by.is_some() {
+            DisputeStatus::Appealed
+        } else {
+            DisputeStatus::Open
+        };
+
+        emit!(DisputeResolutionWithdrawn {
+            dispute: dispute.key(),
+            resolution,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim the default dispute ruling after the SLA period (initiator only)
+    /// Applies when a dispute stays Open with no proposed resolution: full refund to the
+    /// buyer if the seller never confirmed transfer, otherwise release to the seller.
+    /// The initiator can then execute it immediately via execute_dispute_resolution.
+    pub fn claim_dispute_default(ctx: Context<ClaimDisputeDefault>) -> Result<()> {
+        let clock = Clock::get()?;
+        let transaction = &ctx.accounts.transaction;
+        let dispute = &mut ctx.accounts.dispute;
+
+        require!(
+            ctx.accounts.initiator.key() == dispute.initiator,
+            AppMarketError::NotDisputeInitiator
+        );
+        require!(
+            dispute.status == DisputeStatus::Open && dispute.pending_resolution.is_none(),
+            AppMarketError::DisputeNotOpen
+        );
+        require!(
+            clock.unix_timestamp >= dispute.created_at + ctx.accounts.config.dispute_sla_seconds,
+            AppMarketError::DisputeSlaNotExpired
+        );
+
+        let resolution = if transaction.seller_confirmed_transfer {
+            DisputeResolution::ReleaseToSeller
+        } else {
+            DisputeResolution::FullRefund
+        };
+
+        stage_dispute_resolution(
+            dispute,
+            transaction.sale_price,
+            transaction.platform_fee,
+            resolution.clone(),
+            "Default resolution: dispute SLA expired".to_string(),
+            ctx.accounts.initiator.key(),
+            clock.unix_timestamp,
+        )?;
+        dispute.sla_default = true;
+
+        emit!(DisputeSlaDefaultClaimed {
+            dispute: dispute.key(),
+            transaction: transaction.key(),
+            initiator: dispute.initiator,
+            resolution,
+            actor: ctx.accounts.initiator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Execute dispute resolution (after 48hr timelock, 12h for small claims)
+    /// SECURITY: If contested, admin must re-propose new resolution
+    /// A small-claim ruling can also be executed by the moderator who proposed it
+    /// Pays out immediately only when no appeal window is configured (see settle_dispute_ruling)
+    /// remaining_accounts: the stake unwind accounts while the escrow is staked
+    /// (unwind_escrow_stake), then the payout split recipients
+    pub fn execute_dispute_resolution<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteDisputeResolution<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let sla_default = ctx.accounts.dispute.sla_default;
+        let caller = ctx.accounts.caller.key();
+
+        // SECURITY: Only admin can resolve disputes (initiator may execute an SLA default,
+        // the proposing moderator their own small-claim ruling)
+        require!(
+            caller == ctx.accounts.config.admin
+                || (sla_default && caller == ctx.accounts.dispute.initiator)
+                || (ctx.accounts.dispute.small_claim
+                    && ctx.accounts.dispute.small_claim_moderator == Some(caller)),
+            AppMarketError::Unauthorized
+        );
+
+        // Must have pending resolution
+        require!(
+            ctx.accounts.dispute.pending_resolution.is_some(),
+            AppMarketError::NoPendingResolution
+        );
+
+        // Cannot execute if contested
+        require!(
+            !ctx.accounts.dispute.contested,
+            AppMarketError::AlreadyContested
+        );
+
+        // SECURITY: Appeals never move escrow funds again - use execute_appeal_resolution
+        require!(
+            ctx.accounts.dispute.appealed_by.is_none(),
+            AppMarketError::DisputeUnderAppeal
+        );
+
+        // Timelock must have expired (SLA defaults already waited out the SLA period)
+        let proposed_at = ctx.accounts.dispute.pending_resolution_at.unwrap();
+        require!(
+            sla_default || clock.unix_timestamp >= proposed_at + ctx.accounts.dispute.resolution_timelock(),
+            AppMarketError::DisputeTimelockNotExpired
+        );
+
+        require!(
+            ctx.accounts.treasury.key() == ctx.accounts.config.fee_destination(),
+            AppMarketError::InvalidTreasury
+        );
+        require!(
+            ctx.accounts.buyer.key() == ctx.accounts.transaction.buyer,
+            AppMarketError::InvalidBuyer
+        );
+        require!(
+            ctx.accounts.seller.key() == ctx.accounts.transaction.seller,
+            AppMarketError::InvalidSeller
+        );
+
+        let resolution = ctx.accounts.dispute.pending_resolution.clone().unwrap();
+        let transaction_key = ctx.accounts.transaction.key();
+
+        // With an appeal window the escrow stays put until settle_dispute_ruling pays the
+        // final ruling, so a successful appeal can still change who gets the money
+        let appeal_window = ctx.accounts.config.appeal_window_seconds;
+        if appeal_window == 0 {
+            apply_dispute_ruling(
+                ctx.accounts,
+                ctx.remaining_accounts,
+                &resolution,
+                clock.unix_timestamp,
+            )?;
+            ctx.accounts.dispute.ruling_settled = true;
+        }
+
+        // Update dispute
+        let resolution_notes = ctx.accounts.dispute.resolution_notes.clone();
+        ctx.accounts.dispute.status = DisputeStatus::Resolved;
+        ctx.accounts.dispute.resolution = Some(resolution.clone());
+        ctx.accounts.dispute.resolved_at = Some(clock.unix_timestamp);
+        ctx.accounts.dispute.pending_resolution = None;
+        ctx.accounts.dispute.pending_resolution_at = None;
+
+        emit!(DisputeResolved {
+            dispute: ctx.accounts.dispute.key(),
+            transaction: transaction_key,
+            resolution,
+            notes: resolution_notes.unwrap_or_default(),
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        // Keep the dispute open for the appeal window; otherwise close it now
+        if appeal_window > 0 {
+            ctx.accounts.dispute.appeal_deadline = Some(
+                clock.unix_timestamp
+                    .checked_add(appeal_window)
+                    .ok_or(AppMarketError::MathOverflow)?
+            );
+        } else {
+            ctx.accounts.dispute.close(ctx.accounts.caller.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Appeal an executed dispute resolution (buyer or seller, within the appeal window)
+    /// Escrows an appeal fee and re-opens the dispute for a fresh, longer-timelocked proposal.
+    /// The escrow is still held, so settle_dispute_ruling pays whichever ruling stands.
+    pub fn appeal_dispute(ctx: Context<AppealDispute>, reason: String) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        require!(reason.len() <= 500, AppMarketError::AppealReasonTooLong);
+
+        let clock = Clock::get()?;
+
+        let appellant = ctx.accounts.appellant.key();
+        require!(
+            ctx.accounts.transaction.is_buyer_signer(&appellant)
+                || appellant == ctx.accounts.transaction.seller,
+            AppMarketError::NotPartyToTransaction
+        );
+        require!(
+            ctx.accounts.dispute.status == DisputeStatus::Resolved,
+            AppMarketError::DisputeNotResolved
+        );
+        require!(
+            ctx.accounts.dispute.appealed_by.is_none(),
+            AppMarketError::AlreadyAppealed
+        );
+
+        let appeal_deadline = ctx.accounts.dispute.appeal_deadline
+            .ok_or(AppMarketError::AppealWindowExpired)?;
+        require!(
+            clock.unix_timestamp <= appeal_deadline,
+            AppMarketError::AppealWindowExpired
+        );
+
+        let appeal_fee = ctx.accounts.transaction.sale_price
+            .checked_mul(ctx.accounts.config.appeal_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        require!(
+            ctx.accounts.appellant.lamports() >= appeal_fee,
+            AppMarketError::InsufficientBalance
+        );
+
+        // SECURITY: Hold appeal fee in Dispute PDA (refunded if the appeal succeeds)
+        if appeal_fee > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.appellant.to_account_info(),
+                    to: ctx.accounts.dispute.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, appeal_fee)?;
+        }
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.status = DisputeStatus::Appealed;
+        dispute.appealed_by = Some(appellant);
+        dispute.appeal_fee = appeal_fee;
+        dispute.appeal_deadline = None;
+        dispute.contested = false;
+
+        emit!(DisputeAppealed {
+            dispute: dispute.key(),
+            transaction: dispute.transaction,
+            appellant,
+            original_resolution: dispute.resolution.clone(),
+            appeal_fee,
+            reason,
+            actor: ctx.accounts.appellant.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Execute the ruling on an appeal (admin only, after the appeal timelock)
+    /// Overturned: appeal fee refunded to the appellant. Upheld: appeal fee to treasury.
+    /// The new ruling replaces the original and is paid out by settle_dispute_ruling.
+    pub fn execute_appeal_resolution(ctx: Context<ExecuteAppealResolution>) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.caller.key() == ctx.accounts.config.admin,
+            AppMarketError::Unauthorized
+        );
+
+        let appellant = ctx.accounts.dispute.appealed_by
+            .ok_or(AppMarketError::NotUnderAppeal)?;
+        require!(
+            ctx.accounts.appellant.key() == appellant,
+            AppMarketError::InvalidAppellant
+        );
+        require!(
+            ctx.accounts.dispute.status == DisputeStatus::UnderReview,
+            AppMarketError::NoPendingResolution
+        );
+        require!(
+            !ctx.accounts.dispute.contested,
+            AppMarketError::AlreadyContested
+        );
+
+        let new_resolution = ctx.accounts.dispute.pending_resolution.clone()
+            .ok_or(AppMarketError::NoPendingResolution)?;
+        let proposed_at = ctx.accounts.dispute.pending_resolution_at
+            .ok_or(AppMarketError::NoPendingResolution)?;
+        require!(
+            clock.unix_timestamp >= proposed_at + APPEAL_RESOLUTION_TIMELOCK_SECONDS,
+            AppMarketError::DisputeTimelockNotExpired
         );
 
-        // SECURITY: Track consecutive bids from same bidder (max 10 without being outbid)
-        let bidder_key = ctx.accounts.bidder.key();
-        if let Some(last_bidder) = listing.last_bidder {
-            if last_bidder == bidder_key {
-                // Same bidder making consecutive bids
-                require!(
-                    listing.consecutive_bid_count < MAX_CONSECUTIVE_BIDS,
-                    AppMarketError::MaxConsecutiveBidsExceeded
-                );
-            }
-            // Note: The counter will be updated in EFFECTS section below
-        }
+        let original_resolution = ctx.accounts.dispute.resolution.clone();
+        let overturned = original_resolution.as_ref() != Some(&new_resolution);
+        let appeal_fee = ctx.accounts.dispute.appeal_fee;
 
-        // SECURITY: Reject bids below reserve (if auction hasn't started)
-        if !listing.auction_started {
-            if let Some(reserve) = listing.reserve_price {
-                require!(amount >= reserve, AppMarketError::BidBelowReserve);
-            }
+        // Route appeal fee from the Dispute PDA
+        if appeal_fee > 0 {
+            let transaction_key = ctx.accounts.transaction.key();
+            let dispute_bump_arr = [ctx.accounts.dispute.bump];
+            let dispute_seeds = &[
+                b"dispute",
+                transaction_key.as_ref(),
+                &dispute_bump_arr,
+            ];
+            let dispute_signer = &[&dispute_seeds[..]];
+
+            let destination = if overturned {
+                ctx.accounts.appellant.to_account_info()
+            } else {
+                ctx.accounts.treasury.to_account_info()
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.dispute.to_account_info(),
+                    to: destination,
+                },
+                dispute_signer,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, appeal_fee)?;
         }
 
-        // SECURITY: Enforce minimum bid increment to prevent spam
-        if listing.current_bid > 0 {
-            let increment = listing.current_bid
-                .checked_mul(MIN_BID_INCREMENT_BPS)
-                .ok_or(AppMarketError::MathOverflow)?
-                .checked_div(BASIS_POINTS_DIVISOR)
-                .ok_or(AppMarketError::MathOverflow)?;
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolution = Some(new_resolution.clone());
+        dispute.resolved_at = Some(clock.unix_timestamp);
+        dispute.pending_resolution = None;
+        dispute.pending_resolution_at = None;
+        dispute.appeal_fee = 0;
+        // Original panel becomes slashable via slash_arbitrator
+        dispute.appeal_overturned = overturned;
+
+        emit!(AppealResolved {
+            dispute: dispute.key(),
+            transaction: dispute.transaction,
+            appellant,
+            original_resolution,
+            final_resolution: new_resolution,
+            overturned,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-            let min_increment = increment.max(MIN_BID_INCREMENT_LAMPORTS);
-            let min_bid = listing.current_bid
-                .checked_add(min_increment)
-                .ok_or(AppMarketError::MathOverflow)?;
+        Ok(())
+    }
 
-            require!(amount >= min_bid, AppMarketError::BidIncrementTooSmall);
-        } else {
-            require!(amount >= listing.starting_price, AppMarketError::BidTooLow);
-        }
+    /// Set how dispute fees are routed at execution (admin only)
+    /// Applies to disputes opened after the change
+    pub fn set_dispute_fee_policy(
+        ctx: Context<SetDisputeFeePolicy>,
+        policy: DisputeFeePolicy,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
 
-        // EFFECTS: Update state BEFORE external calls
-        let old_bid = listing.current_bid;
-        let old_bidder = listing.current_bidder;
+        ctx.accounts.config.dispute_fee_policy = policy.clone();
 
-        listing.current_bid = amount;
-        listing.current_bidder = Some(ctx.accounts.bidder.key());
+        emit!(DisputeFeePolicyUpdated {
+            policy,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Update consecutive bid tracking
-        if let Some(last_bidder) = listing.last_bidder {
-            if last_bidder == bidder_key {
-                // Same bidder - increment counter
-                listing.consecutive_bid_count = listing.consecutive_bid_count
-                    .checked_add(1)
-                    .ok_or(AppMarketError::MathOverflow)?;
-            } else {
-                // Different bidder - reset counter
-                listing.last_bidder = Some(bidder_key);
-                listing.consecutive_bid_count = 1;
-            }
-        } else {
-            // First bid on this listing
-            listing.last_bidder = Some(bidder_key);
-            listing.consecutive_bid_count = 1;
+        Ok(())
+    }
+
+    /// Configure the dispute SLA period after which initiators can claim the default ruling
+    pub fn set_dispute_sla(ctx: Context<SetDisputeSla>, dispute_sla_seconds: i64) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        require!(
+            (MIN_DISPUTE_SLA_SECONDS..=MAX_DISPUTE_SLA_SECONDS).contains(&dispute_sla_seconds),
+            AppMarketError::InvalidDisputeSla
+        );
+
+        ctx.accounts.config.dispute_sla_seconds = dispute_sla_seconds;
+
+        emit!(DisputeSlaUpdated {
+            dispute_sla_seconds,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the maximum listing/sale price (admin only); None removes the cap
+    pub fn set_max_sale_price(ctx: Context<SetMaxSalePrice>, max_sale_price: Option<u64>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        require!(max_sale_price != Some(0), AppMarketError::InvalidPriceCap);
+
+        ctx.accounts.config.max_sale_price = max_sale_price;
+
+        emit!(MaxSalePriceUpdated {
+            max_sale_price,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the per-category fee schedule (admin only). Once it exists, create_listing
+    /// must pass it and locks the category's rate instead of config.platform_fee_bps.
+    /// `default_fee_bps` applies to categories without their own rate (None = config rate).
+    pub fn init_fee_schedule(
+        ctx: Context<InitFeeSchedule>,
+        default_fee_bps: Option<u64>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        if let Some(fee_bps) = default_fee_bps {
+            require!(fee_bps <= MAX_PLATFORM_FEE_BPS, AppMarketError::FeeTooHigh);
         }
 
-        // Start auction timer if reserve price met (or no reserve)
-        if !listing.auction_started {
-            let reserve_met = if let Some(reserve) = listing.reserve_price {
-                amount >= reserve
-            } else {
-                true
-            };
+        let schedule = &mut ctx.accounts.fee_schedule;
+        schedule.default_fee_bps = default_fee_bps;
+        schedule.category_fees = Vec::new();
+        schedule.version = ACCOUNT_LAYOUT_VERSION;
+        schedule.bump = ctx.bumps.fee_schedule;
+        ctx.accounts.config.has_fee_schedule = true;
+
+        emit!(FeeScheduleUpdated {
+            category: None,
+            fee_bps: default_fee_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-            if reserve_met {
-                listing.auction_started = true;
-                listing.auction_start_time = Some(clock.unix_timestamp);
-                listing.end_time = clock.unix_timestamp
-                    .checked_add(listing.end_time - listing.created_at)
-                    .ok_or(AppMarketError::MathOverflow)?;
-            }
+        Ok(())
+    }
+
+    /// Set (or clear with None) the fee schedule's default rate (admin only)
+    pub fn set_fee_schedule_default(
+        ctx: Context<SetFeeSchedule>,
+        default_fee_bps: Option<u64>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        if let Some(fee_bps) = default_fee_bps {
+            require!(fee_bps <= MAX_PLATFORM_FEE_BPS, AppMarketError::FeeTooHigh);
         }
 
-        // Update escrow amount tracking BEFORE transfers
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_add(amount)
-            .ok_or(AppMarketError::MathOverflow)?;
+        ctx.accounts.fee_schedule.default_fee_bps = default_fee_bps;
 
-        // SECURITY: Anti-sniping - extend auction if bid placed near end (only if started)
-        if listing.auction_started && clock.unix_timestamp > listing.end_time - ANTI_SNIPE_WINDOW {
-            listing.end_time = clock.unix_timestamp
-                .checked_add(ANTI_SNIPE_EXTENSION)
-                .ok_or(AppMarketError::MathOverflow)?;
+        emit!(FeeScheduleUpdated {
+            category: None,
+            fee_bps: default_fee_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set (or clear with None) one category's platform fee rate (admin only)
+    /// Only affects listings created afterwards; existing listings keep their locked fee.
+    pub fn set_category_fee(
+        ctx: Context<SetFeeSchedule>,
+        category: Category,
+        fee_bps: Option<u64>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+
+        let schedule = &mut ctx.accounts.fee_schedule;
+        schedule.category_fees.retain(|entry| entry.category != category);
+        if let Some(fee_bps) = fee_bps {
+            require!(fee_bps <= MAX_PLATFORM_FEE_BPS, AppMarketError::FeeTooHigh);
+            schedule.category_fees.push(CategoryFee {
+                category: category.clone(),
+                fee_bps,
+            });
         }
 
-        // INTERACTIONS: External calls LAST
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.bidder.to_account_info(),
-                to: ctx.accounts.escrow.to_account_info(),
-            },
+        emit!(FeeScheduleUpdated {
+            category: Some(category),
+            fee_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Cap the escrow one wallet can lock in open bids (admin only); 0 removes the cap
+    /// While a cap is set, bidders must pass their UserProfile to place_bid.
+    pub fn set_max_bidder_exposure(
+        ctx: Context<SetMaxBidderExposure>,
+        max_bidder_exposure: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
 
-        // SECURITY: Use withdrawal pattern for refunds (prevents DoS, only create when needed)
-        if let Some(previous_bidder) = old_bidder {
-            if old_bid > 0 {
-                // Increment withdrawal counter to prevent PDA collision
-                listing.withdrawal_count = listing.withdrawal_count
-                    .checked_add(1)
-                    .ok_or(AppMarketError::MathOverflow)?;
+        ctx.accounts.config.max_bidder_exposure = max_bidder_exposure;
 
-                // Derive PDA and verify
-                let listing_key = listing.key();
-                let withdrawal_count_bytes = listing.withdrawal_count.to_le_bytes();
-                let withdrawal_seeds = &[
-                    b"withdrawal",
-                    listing_key.as_ref(),
-                    &withdrawal_count_bytes,
-                ];
-                let (withdrawal_pda, bump) = Pubkey::find_program_address(
-                    withdrawal_seeds,
-                    ctx.program_id
-                );
+        emit!(MaxBidderExposureUpdated {
+            max_bidder_exposure,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-                require!(
-                    withdrawal_pda == ctx.accounts.pending_withdrawal.key(),
-                    AppMarketError::InvalidPreviousBidder
-                );
+        Ok(())
+    }
 
-                // Create the withdrawal account
-                let rent = Rent::get()?;
-                let space = 8 + PendingWithdrawal::INIT_SPACE;
-                let lamports = rent.minimum_balance(space);
+    /// Cap anti-snipe extensions for auctions created from now on (admin only); 0 removes
+    /// the cap. Existing listings keep the cap locked at their creation.
+    pub fn set_max_anti_snipe_extensions(
+        ctx: Context<SetMaxAntiSnipeExtensions>,
+        max_anti_snipe_extensions: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
 
-                anchor_lang::system_program::create_account(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::CreateAccount {
-                            from: ctx.accounts.bidder.to_account_info(),
-                            to: ctx.accounts.pending_withdrawal.to_account_info(),
-                        },
-                    ),
-                    lamports,
-                    space as u64,
-                    ctx.program_id,
-                )?;
+        ctx.accounts.config.max_anti_snipe_extensions = max_anti_snipe_extensions;
 
-                // Initialize withdrawal data
-                let mut withdrawal_data = ctx.accounts.pending_withdrawal.try_borrow_mut_data()?;
-                let withdrawal = PendingWithdrawal {
-                    user: previous_bidder,
-                    listing: listing.key(),
-                    amount: old_bid,
-                    withdrawal_id: listing.withdrawal_count,
-                    created_at: clock.unix_timestamp,
-                    expires_at: clock.unix_timestamp + 3600, // 1 hour
-                    bump,
-                };
+        emit!(MaxAntiSnipeExtensionsUpdated {
+            max_anti_snipe_extensions,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-                withdrawal.try_serialize(&mut &mut withdrawal_data[..])?;
+        Ok(())
+    }
 
-                emit!(WithdrawalCreated {
-                    user: previous_bidder,
-                    listing: listing.key(),
-                    amount: old_bid,
-                    withdrawal_id: listing.withdrawal_count,
-                    timestamp: clock.unix_timestamp,
-                });
-            }
-        }
+    /// Set the daily price of featured placement (admin only); 0 disables promotions
+    pub fn set_promotion_fee(ctx: Context<SetPromotionFee>, fee_per_day: u64) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
 
-        emit!(BidPlaced {
-            listing: listing.key(),
-            bidder: ctx.accounts.bidder.key(),
-            amount,
-            timestamp: clock.unix_timestamp,
+        ctx.accounts.config.promotion_fee_per_day = fee_per_day;
+
+        emit!(PromotionFeeUpdated {
+            fee_per_day,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Withdraw funds from pending withdrawal (pull pattern)
-    pub fn withdraw_funds(ctx: Context<WithdrawFunds>) -> Result<()> {
-        let withdrawal = &ctx.accounts.pending_withdrawal;
+    /// Approve a seller to list above the global price cap, up to max_price (admin only)
+    pub fn approve_price_cap_override(
+        ctx: Context<ApprovePriceCapOverride>,
+        seller: Pubkey,
+        max_price: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        require!(max_price > 0, AppMarketError::InvalidPriceCap);
+
         let clock = Clock::get()?;
+        let price_override = &mut ctx.accounts.price_cap_override;
+        price_override.seller = seller;
+        price_override.max_price = max_price;
+        price_override.approved_at = clock.unix_timestamp;
+        price_override.rent_payer = ctx.accounts.payer.key();
+        price_override.version = ACCOUNT_LAYOUT_VERSION;
+        price_override.bump = ctx.bumps.price_cap_override;
+
+        emit!(PriceCapOverrideApproved {
+            seller,
+            price_cap_override: ctx.accounts.price_cap_override.key(),
+            max_price,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-        // CHECKS: Validate user
+        Ok(())
+    }
+
+    /// Revoke a seller's price cap override (admin only); rent returns to whoever paid it
+    pub fn revoke_price_cap_override(ctx: Context<RevokePriceCapOverride>) -> Result<()> {
         require!(
-            ctx.accounts.user.key() == withdrawal.user,
-            AppMarketError::NotWithdrawalOwner
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
 
-        // SECURITY: Validate escrow balance
-        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
-        let rent = Rent::get()?.minimum_balance(
-            ctx.accounts.escrow.to_account_info().data_len()
+        emit!(PriceCapOverrideRevoked {
+            seller: ctx.accounts.price_cap_override.seller,
+            price_cap_override: ctx.accounts.price_cap_override.key(),
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Configure the volume circuit breaker (admin only); 0 disables it
+    pub fn set_circuit_breaker(ctx: Context<SetCircuitBreaker>, multiple: u64) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
         require!(
-            escrow_balance >= withdrawal.amount + rent,
-            AppMarketError::InsufficientEscrowBalance
+            multiple == 0 || multiple >= MIN_CIRCUIT_BREAKER_MULTIPLE,
+            AppMarketError::InvalidCircuitBreaker
         );
 
-        // INTERACTIONS: Transfer funds
-        let seeds = &[
-            b"escrow",
-            ctx.accounts.listing.to_account_info().key.as_ref(),
-            &[ctx.accounts.escrow.bump],
-        ];
-        let signer = &[&seeds[..]];
+        ctx.accounts.config.circuit_breaker_multiple = multiple;
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.user.to_account_info(),
-            },
-            signer,
+        emit!(CircuitBreakerUpdated {
+            multiple,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Resume after reviewing a tripped circuit breaker (admin only)
+    /// Today's window restarts so the same spike does not immediately re-trip it.
+    pub fn reset_circuit_breaker(ctx: Context<SetCircuitBreaker>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-        anchor_lang::system_program::transfer(cpi_ctx, withdrawal.amount)?;
 
-        // Update escrow tracking
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_sub(withdrawal.amount)
-            .ok_or(AppMarketError::MathOverflow)?;
+        let config = &mut ctx.accounts.config;
+        require!(config.circuit_breaker_tripped, AppMarketError::CircuitBreakerNotTripped);
 
-        emit!(WithdrawalClaimed {
-            user: withdrawal.user,
-            listing: ctx.accounts.listing.key(),
-            amount: withdrawal.amount,
-            timestamp: clock.unix_timestamp,
+        config.circuit_breaker_tripped = false;
+        config.volume_today = 0;
+        config.volume_this_slot = 0;
+
+        emit!(CircuitBreakerReset {
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Expire unclaimed withdrawal (anyone can call after expiry)
-    /// Returns funds to the original user and unblocks the escrow.
-    /// This prevents auctions from stalling when outbid users don't claim.
-    pub fn expire_withdrawal(ctx: Context<ExpireWithdrawal>) -> Result<()> {
-        let withdrawal = &ctx.accounts.pending_withdrawal;
-        let clock = Clock::get()?;
-
-        // CHECKS: Withdrawal must be expired
+    /// Configure how long after expiry unclaimed withdrawals can be escheated (admin only)
+    /// 0 disables escheatment
+    pub fn set_escheat_period(ctx: Context<SetEscheatPeriod>, escheat_period_seconds: i64) -> Result<()> {
         require!(
-            clock.unix_timestamp > withdrawal.expires_at,
-            AppMarketError::WithdrawalNotExpired
-        );
-
-        // SECURITY: Validate escrow balance
-        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
-        let rent = Rent::get()?.minimum_balance(
-            ctx.accounts.escrow.to_account_info().data_len()
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
         require!(
-            escrow_balance >= withdrawal.amount + rent,
-            AppMarketError::InsufficientEscrowBalance
+            escheat_period_seconds == 0 || escheat_period_seconds >= MIN_ESCHEAT_PERIOD_SECONDS,
+            AppMarketError::InvalidEscheatPeriod
         );
 
-        // INTERACTIONS: Transfer funds back to the original user
-        let seeds = &[
-            b"escrow",
-            ctx.accounts.listing.to_account_info().key.as_ref(),
-            &[ctx.accounts.escrow.bump],
-        ];
-        let signer = &[&seeds[..]];
+        ctx.accounts.config.escheat_period_seconds = escheat_period_seconds;
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.recipient.to_account_info(),
-            },
-            signer,
+        emit!(EscheatPeriodUpdated {
+            escheat_period_seconds,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the insurance fund and start diverting a slice of platform fees into it (admin only)
+    /// The vault is seeded with its rent-exempt minimum so small fee slices can always land.
+    pub fn initialize_insurance_fund(
+        ctx: Context<InitializeInsuranceFund>,
+        insurance_fee_bps: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-        anchor_lang::system_program::transfer(cpi_ctx, withdrawal.amount)?;
+        require!(
+            insurance_fee_bps <= MAX_INSURANCE_FEE_BPS,
+            AppMarketError::InsuranceFeeTooHigh
+        );
+
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.claims_filed = 0;
+        fund.claims_paid = 0;
+        fund.total_paid = 0;
+        fund.version = ACCOUNT_LAYOUT_VERSION;
+        fund.bump = ctx.bumps.insurance_fund;
+        fund.vault_bump = ctx.bumps.insurance_vault;
+
+        let vault_rent = Rent::get()?.minimum_balance(0);
+        let vault_balance = ctx.accounts.insurance_vault.lamports();
+        if vault_balance < vault_rent {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.insurance_vault.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, vault_rent - vault_balance)?;
+        }
 
-        // Update escrow tracking
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_sub(withdrawal.amount)
-            .ok_or(AppMarketError::MathOverflow)?;
+        ctx.accounts.config.insurance_fee_bps = insurance_fee_bps as u16;
 
-        emit!(WithdrawalExpired {
-            user: withdrawal.user,
-            listing: ctx.accounts.listing.key(),
-            amount: withdrawal.amount,
-            expired_by: ctx.accounts.caller.key(),
-            timestamp: clock.unix_timestamp,
+        emit!(InsuranceFeeUpdated {
+            insurance_fee_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Close escrow after all pending withdrawals are cleared
-    /// Permissionless — anyone can call once escrow.amount == 0 and transaction is terminal
-    /// Caller receives PDA rent as incentive for cleanup
-    pub fn close_escrow(ctx: Context<CloseEscrow>) -> Result<()> {
-        let status = ctx.accounts.transaction.status.clone();
+    /// Update the share of platform fees diverted to the insurance fund (admin only)
+    pub fn set_insurance_fee_bps(ctx: Context<SetInsuranceFeeBps>, insurance_fee_bps: u64) -> Result<()> {
         require!(
-            status == TransactionStatus::Completed || status == TransactionStatus::Refunded,
-            AppMarketError::TransactionNotComplete
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-
         require!(
-            ctx.accounts.escrow.amount == 0,
-            AppMarketError::PendingWithdrawalsExist
+            insurance_fee_bps <= MAX_INSURANCE_FEE_BPS,
+            AppMarketError::InsuranceFeeTooHigh
         );
 
-        emit!(EscrowClosed {
-            listing: ctx.accounts.listing.key(),
-            closed_by: ctx.accounts.caller.key(),
+        ctx.accounts.config.insurance_fee_bps = insurance_fee_bps as u16;
+
+        emit!(InsuranceFeeUpdated {
+            insurance_fee_bps,
+            actor: ctx.accounts.admin.key(),
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Buy now (instant purchase)
-    pub fn buy_now(ctx: Context<BuyNow>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
+    /// File an insurance claim against a completed purchase (buyer only)
+    /// Covers seller fraud that surfaces after escrow has released (e.g. a clawed-back
+    /// app store account). One claim per transaction; payout is decided by the admin.
+    pub fn file_claim(ctx: Context<FileClaim>, amount: u64, reason: String) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        require!(reason.len() <= 500, AppMarketError::ClaimReasonTooLong);
 
-        let listing = &mut ctx.accounts.listing;
         let clock = Clock::get()?;
+        let transaction = &ctx.accounts.transaction;
 
-        // CHECKS
-        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
-        require!(clock.unix_timestamp < listing.end_time, AppMarketError::ListingExpired);
-        require!(listing.buy_now_price.is_some(), AppMarketError::BuyNowNotEnabled);
-        require!(ctx.accounts.buyer.key() != listing.seller, AppMarketError::SellerCannotBuy);
-
-        let buy_now_price = listing.buy_now_price
-            .ok_or(AppMarketError::BuyNowNotEnabled)?;
+        require!(
+            ctx.accounts.claimant.key() == transaction.buyer,
+            AppMarketError::NotBuyer
+        );
+        require!(
+            transaction.status == TransactionStatus::Completed,
+            AppMarketError::InvalidTransactionStatus
+        );
+        let completed_at = transaction.completed_at
+            .ok_or(AppMarketError::InvalidTransactionStatus)?;
+        let claim_deadline = completed_at
+            .checked_add(INSURANCE_CLAIM_WINDOW_SECONDS)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp <= claim_deadline,
+            AppMarketError::ClaimWindowExpired
+        );
+        require!(
+            amount > 0 && amount <= transaction.sale_price,
+            AppMarketError::InvalidClaimAmount
+        );
+
+        let claim = &mut ctx.accounts.insurance_claim;
+        claim.transaction = transaction.key();
+        claim.claimant = ctx.accounts.claimant.key();
+        claim.amount = amount;
+        claim.reason = reason;
+        claim.status = ClaimStatus::Pending;
+        claim.filed_at = clock.unix_timestamp;
+        claim.resolved_at = None;
+        claim.payout = 0;
+        claim.version = ACCOUNT_LAYOUT_VERSION;
+        claim.bump = ctx.bumps.insurance_claim;
+
+        ctx.accounts.insurance_fund.claims_filed = ctx.accounts.insurance_fund.claims_filed
+            .saturating_add(1);
+
+        emit!(InsuranceClaimFiled {
+            claim: claim.key(),
+            transaction: claim.transaction,
+            claimant: claim.claimant,
+            amount,
+            actor: ctx.accounts.claimant.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-        // SECURITY: Validate payment mint matches actual payment method
-        // buy_now uses SOL transfer via SystemProgram - APP token fee discount
-        // requires actual SPL token transfer which is not supported in this path
-        if listing.payment_mint == Some(APP_TOKEN_MINT) {
-            // When APP token is claimed, verify we're actually using the token transfer path
-            // and not a raw SOL transfer. Since buy_now only supports SOL transfers,
-            // listings with APP token payment mint cannot use this instruction.
-            return Err(AppMarketError::InvalidPaymentMint.into());
-        }
+        Ok(())
+    }
 
-        // SECURITY: Pre-check buyer has sufficient balance
+    /// Approve a pending insurance claim and pay it from the insurance vault (admin only)
+    /// The payout may be less than the amount claimed; the vault always stays rent-exempt.
+    pub fn approve_claim(ctx: Context<ResolveClaim>, payout: u64) -> Result<()> {
         require!(
-            ctx.accounts.buyer.lamports() >= buy_now_price,
-            AppMarketError::InsufficientBalance
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        require!(
+            ctx.accounts.insurance_claim.status == ClaimStatus::Pending,
+            AppMarketError::ClaimAlreadyResolved
+        );
+        require!(
+            payout > 0 && payout <= ctx.accounts.insurance_claim.amount,
+            AppMarketError::InvalidClaimAmount
         );
 
-        // EFFECTS
-        let old_bid = listing.current_bid;
-        let old_bidder = listing.current_bidder;
+        // SECURITY: Vault must cover the payout and remain rent-exempt
+        let vault_rent = Rent::get()?.minimum_balance(0);
+        let available = ctx.accounts.insurance_vault.lamports().saturating_sub(vault_rent);
+        require!(payout <= available, AppMarketError::InsufficientInsuranceFunds);
 
-        listing.current_bid = buy_now_price;
-        listing.current_bidder = Some(ctx.accounts.buyer.key());
-        listing.status = ListingStatus::Sold;
-        listing.end_time = clock.unix_timestamp;
+        let clock = Clock::get()?;
 
-        // Update escrow tracking BEFORE transfers
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_add(buy_now_price)
-            .ok_or(AppMarketError::MathOverflow)?;
+        // EFFECTS
+        let claim = &mut ctx.accounts.insurance_claim;
+        claim.status = ClaimStatus::Approved;
+        claim.resolved_at = Some(clock.unix_timestamp);
+        claim.payout = payout;
+
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.claims_paid = fund.claims_paid.saturating_add(1);
+        fund.total_paid = fund.total_paid.saturating_add(payout);
 
         // INTERACTIONS
-        let cpi_ctx = CpiContext::new(
+        let seeds = &[
+            b"insurance_vault".as_ref(),
+            &[fund.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.escrow.to_account_info(),
+                from: ctx.accounts.insurance_vault.to_account_info(),
+                to: ctx.accounts.claimant.to_account_info(),
             },
+            signer,
         );
-        anchor_lang::system_program::transfer(cpi_ctx, buy_now_price)?;
+        anchor_lang::system_program::transfer(cpi_ctx, payout)?;
 
-        // SECURITY FIX M-2: Use withdrawal_count (same as PlaceBid) for consistent PDA seeds
-        if let Some(previous_bidder) = old_bidder {
-            if old_bid > 0 {
-                // Increment withdrawal counter FIRST to prevent PDA collision (consistent with PlaceBid)
-                listing.withdrawal_count = listing.withdrawal_count
-                    .checked_add(1)
-                    .ok_or(AppMarketError::MathOverflow)?;
+        emit!(InsuranceClaimResolved {
+            claim: ctx.accounts.insurance_claim.key(),
+            transaction: ctx.accounts.insurance_claim.transaction,
+            claimant: ctx.accounts.insurance_claim.claimant,
+            approved: true,
+            payout,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-                // Derive PDA using withdrawal_count (consistent with PlaceBid and WithdrawFunds)
-                let listing_key = listing.key();
-                let withdrawal_count_bytes = listing.withdrawal_count.to_le_bytes();
-                let withdrawal_seeds = &[
-                    b"withdrawal",
-                    listing_key.as_ref(),
-                    &withdrawal_count_bytes,
-                ];
-                let (withdrawal_pda, bump) = Pubkey::find_program_address(
-                    withdrawal_seeds,
-                    ctx.program_id
-                );
+        Ok(())
+    }
 
-                require!(
-                    withdrawal_pda == ctx.accounts.pending_withdrawal.key(),
-                    AppMarketError::InvalidPreviousBidder
-                );
+    /// Reject a pending insurance claim (admin only)
+    pub fn reject_claim(ctx: Context<ResolveClaim>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        require!(
+            ctx.accounts.insurance_claim.status == ClaimStatus::Pending,
+            AppMarketError::ClaimAlreadyResolved
+        );
 
-                // Create the account
-                let rent = Rent::get()?;
-                let space = 8 + PendingWithdrawal::INIT_SPACE;
-                let lamports = rent.minimum_balance(space);
+        let clock = Clock::get()?;
 
-                anchor_lang::system_program::create_account(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::CreateAccount {
-                            from: ctx.accounts.buyer.to_account_info(),
-                            to: ctx.accounts.pending_withdrawal.to_account_info(),
-                        },
-                    ),
-                    lamports,
-                    space as u64,
-                    ctx.program_id,
-                )?;
+        let claim = &mut ctx.accounts.insurance_claim;
+        claim.status = ClaimStatus::Rejected;
+        claim.resolved_at = Some(clock.unix_timestamp);
+
+        emit!(InsuranceClaimResolved {
+            claim: claim.key(),
+            transaction: claim.transaction,
+            claimant: claim.claimant,
+            approved: false,
+            payout: 0,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-                // Initialize the withdrawal data
-                let mut withdrawal_data = ctx.accounts.pending_withdrawal.try_borrow_mut_data()?;
-                let mut withdrawal = PendingWithdrawal::try_from_slice(&vec![0u8; space])?;
-                withdrawal.user = previous_bidder;
-                withdrawal.listing = listing.key();
-                withdrawal.amount = old_bid;
-                withdrawal.withdrawal_id = listing.withdrawal_count;
-                withdrawal.created_at = clock.unix_timestamp;
-                withdrawal.expires_at = clock.unix_timestamp + 3600; // 1 hour
-                withdrawal.bump = bump;
+        Ok(())
+    }
 
-                withdrawal.try_serialize(&mut &mut withdrawal_data[..])?;
+    /// Create the fee split and propose its first recipients (admin only)
+    /// Like every later change, the split only takes effect via execute_fee_split_change
+    /// after the admin timelock. The vault is seeded with its rent-exempt minimum.
+    pub fn initialize_fee_split(
+        ctx: Context<InitializeFeeSplit>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        validate_fee_split(&recipients)?;
 
-                emit!(WithdrawalCreated {
-                    user: previous_bidder,
-                    listing: listing.key(),
-                    amount: old_bid,
-                    withdrawal_id: listing.withdrawal_count,
-                    timestamp: clock.unix_timestamp,
-                });
-            }
+        let clock = Clock::get()?;
+
+        let vault_rent = Rent::get()?.minimum_balance(0);
+        let vault_balance = ctx.accounts.fee_vault.lamports();
+        if vault_balance < vault_rent {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, vault_rent - vault_balance)?;
         }
 
-        // Create transaction record
-        let transaction = &mut ctx.accounts.transaction;
-        transaction.listing = listing.key();
-        transaction.seller = listing.seller;
-        transaction.buyer = ctx.accounts.buyer.key();
-        transaction.sale_price = buy_now_price;
+        let split = &mut ctx.accounts.fee_split;
+        split.recipients = Vec::new();
+        split.pending_recipients = Some(recipients.clone());
+        split.pending_at = Some(clock.unix_timestamp);
+        split.version = ACCOUNT_LAYOUT_VERSION;
+        split.bump = ctx.bumps.fee_split;
+        split.vault_bump = ctx.bumps.fee_vault;
+
+        emit!(FeeSplitProposed {
+            recipients,
+            executable_at: clock.unix_timestamp + ADMIN_TIMELOCK_SECONDS,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Propose new fee split recipients (step 1 of timelock, admin only)
+    /// An empty list proposes disabling the split (fees go back to the treasury).
+    pub fn propose_fee_split_change(
+        ctx: Context<ProposeFeeSplitChange>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
+        if !recipients.is_empty() {
+            validate_fee_split(&recipients)?;
+        }
 
-        // SECURITY: Use LOCKED fees from listing, not current config
-        transaction.platform_fee = buy_now_price
-            .checked_mul(listing.platform_fee_bps)
-            .ok_or(AppMarketError::MathOverflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
-            .ok_or(AppMarketError::MathOverflow)?;
-        transaction.seller_proceeds = buy_now_price
-            .checked_sub(transaction.platform_fee)
-            .ok_or(AppMarketError::MathOverflow)?;
+        let clock = Clock::get()?;
 
-        transaction.status = TransactionStatus::InEscrow;
-        transaction.transfer_deadline = clock.unix_timestamp
-            .checked_add(TRANSFER_DEADLINE_SECONDS)
-            .ok_or(AppMarketError::MathOverflow)?;
-        transaction.created_at = clock.unix_timestamp;
-        transaction.seller_confirmed_transfer = false;
-        transaction.seller_confirmed_at = None;
-        transaction.completed_at = None;
-        transaction.bump = ctx.bumps.transaction;
+        let split = &mut ctx.accounts.fee_split;
+        split.pending_recipients = Some(recipients.clone());
+        split.pending_at = Some(clock.unix_timestamp);
 
-        emit!(SaleCompleted {
-            listing: listing.key(),
-            transaction: transaction.key(),
-            buyer: ctx.accounts.buyer.key(),
-            seller: listing.seller,
-            amount: buy_now_price,
+        emit!(FeeSplitProposed {
+            recipients,
+            executable_at: clock.unix_timestamp + ADMIN_TIMELOCK_SECONDS,
+            actor: ctx.accounts.admin.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Settle auction (called after auction ends)
-    pub fn settle_auction(ctx: Context<SettleAuction>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
+    /// Execute a fee split change (step 2 of timelock, after 48 hours, admin only)
+    /// Fees already in the vault should be distributed first: they are paid out
+    /// under whichever split is current when distribute_fees runs.
+    pub fn execute_fee_split_change(ctx: Context<ExecuteFeeSplitChange>) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
 
-        let listing = &mut ctx.accounts.listing;
         let clock = Clock::get()?;
+        let split = &mut ctx.accounts.fee_split;
 
-        // SECURITY: Fix validation order - check bidder validity FIRST
-        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        let proposed_at = split.pending_at
+            .ok_or(AppMarketError::NoPendingChange)?;
         require!(
-            listing.listing_type == ListingType::Auction,
-            AppMarketError::NotAnAuction
+            clock.unix_timestamp >= proposed_at + ADMIN_TIMELOCK_SECONDS,
+            AppMarketError::TimelockNotExpired
         );
 
-        // Only require auction to be ended if it was started
-        if listing.auction_started {
-            require!(
-                clock.unix_timestamp >= listing.end_time,
-                AppMarketError::AuctionNotEnded
+        split.recipients = split.pending_recipients
+            .take()
+            .ok_or(AppMarketError::NoPendingChange)?;
+        split.pending_at = None;
+
+        ctx.accounts.config.fee_vault = if split.recipients.is_empty() {
+            None
+        } else {
+            Some(ctx.accounts.fee_vault.key())
+        };
+
+        emit!(FeeSplitChanged {
+            recipients: split.recipients.clone(),
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Pay out the fee-split vault to its recipients by bps (anyone can call)
+    /// remaining_accounts: the recipient wallets, in split order.
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+    ) -> Result<()> {
+        let split = &ctx.accounts.fee_split;
+        require!(!split.recipients.is_empty(), AppMarketError::FeeSplitInactive);
+        require!(
+            ctx.remaining_accounts.len() >= split.recipients.len(),
+            AppMarketError::InvalidSplitRecipient
+        );
+
+        // Vault keeps its rent-exempt minimum
+        let vault_rent = Rent::get()?.minimum_balance(0);
+        let amount = ctx.accounts.fee_vault.lamports().saturating_sub(vault_rent);
+        require!(amount > 0, AppMarketError::NothingToClaim);
+
+        let seeds = &[
+            b"fee_vault".as_ref(),
+            &[split.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Shares total 100%; rounding dust stays in the vault for the next distribution
+        let mut distributed: u64 = 0;
+        for (recipient, recipient_info) in split.recipients.iter().zip(ctx.remaining_accounts) {
+            require_keys_eq!(
+                recipient_info.key(),
+                recipient.wallet,
+                AppMarketError::InvalidSplitRecipient
+            );
+            let share = amount
+                .checked_mul(recipient.bps)
+                .ok_or(AppMarketError::MathOverflow)?
+                .checked_div(BASIS_POINTS_DIVISOR)
+                .ok_or(AppMarketError::MathOverflow)?;
+            if share == 0 {
+                continue;
+            }
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    to: recipient_info.clone(),
+                },
+                signer,
             );
+            anchor_lang::system_program::transfer(cpi_ctx, share)?;
+            distributed = distributed
+                .checked_add(share)
+                .ok_or(AppMarketError::MathOverflow)?;
         }
 
-        // SECURITY: Only allow seller, winner, or admin to settle
-        let is_seller = ctx.accounts.payer.key() == listing.seller;
-        let is_winner = listing.current_bidder
-            .map(|bidder| ctx.accounts.payer.key() == bidder)
-            .unwrap_or(false);
-        let is_admin = ctx.accounts.payer.key() == ctx.accounts.config.admin;
+        emit!(FeesDistributed {
+            amount: distributed,
+            actor: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
 
+    /// Create the APP staker rewards pool (admin only)
+    /// The rewards vault earns a configurable share of fees by being a fee split recipient;
+    /// SOL landing there is shared pro rata among APP stakers.
+    pub fn initialize_rewards_pool(ctx: Context<InitializeRewardsPool>) -> Result<()> {
         require!(
-            is_seller || is_winner || is_admin,
-            AppMarketError::UnauthorizedSettlement
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
 
-        // SECURITY: Must have bids to settle - use cancel_auction for no-bid scenarios
-        require!(
-            listing.current_bidder.is_some(),
-            AppMarketError::NoBidsToSettle
+        let vault_rent = Rent::get()?.minimum_balance(0);
+        let vault_balance = ctx.accounts.rewards_vault.lamports();
+        if vault_balance < vault_rent {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.rewards_vault.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, vault_rent - vault_balance)?;
+        }
+
+        let pool = &mut ctx.accounts.rewards_pool;
+        pool.total_staked = 0;
+        pool.reward_per_share = 0;
+        // Anything already in the vault goes to the first stakers
+        pool.accounted_balance = 0;
+        pool.total_claimed = 0;
+        pool.version = ACCOUNT_LAYOUT_VERSION;
+        pool.bump = ctx.bumps.rewards_pool;
+        pool.vault_bump = ctx.bumps.rewards_vault;
+        pool.stake_vault_bump = ctx.bumps.rewards_stake_vault;
+
+        emit!(RewardsPoolInitialized {
+            rewards_vault: ctx.accounts.rewards_vault.key(),
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Create the caller's rewards stake account
+    pub fn init_reward_stake(ctx: Context<InitRewardStake>) -> Result<()> {
+        let stake = &mut ctx.accounts.reward_stake;
+        stake.owner = ctx.accounts.owner.key();
+        stake.amount = 0;
+        stake.reward_debt = 0;
+        stake.pending_rewards = 0;
+        stake.version = ACCOUNT_LAYOUT_VERSION;
+        stake.bump = ctx.bumps.reward_stake;
+
+        Ok(())
+    }
+
+    /// Stake APP tokens to earn a share of protocol fees
+    pub fn stake_for_rewards(ctx: Context<StakeForRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        require!(amount > 0, AppMarketError::InvalidStakeAmount);
+
+        // Fees that arrived before this stake belong to existing stakers
+        let available = rewards_available(&ctx.accounts.rewards_vault.to_account_info())?;
+        let pool = &mut ctx.accounts.rewards_pool;
+        pool.sync(available)?;
+
+        let stake = &mut ctx.accounts.reward_stake;
+        stake.settle(pool.reward_per_share)?;
+        stake.amount = stake.amount
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+        stake.reset_debt(pool.reward_per_share)?;
+        pool.total_staked = pool.total_staked
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                to: ctx.accounts.rewards_stake_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
         );
+        token::transfer(cpi_ctx, amount)?;
 
-        // SECURITY FIX M-1: Validate bidder account matches listing.current_bidder
-        // This prevents passing an arbitrary account as the bidder
+        emit!(RewardsStakeChanged {
+            owner: stake.owner,
+            reward_stake: stake.key(),
+            amount,
+            staked: true,
+            total_staked: stake.amount,
+            actor: ctx.accounts.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw staked APP tokens (rewards earned so far stay claimable)
+    pub fn unstake_from_rewards(ctx: Context<UnstakeFromRewards>, amount: u64) -> Result<()> {
         require!(
-            ctx.accounts.bidder.key() == listing.current_bidder.unwrap(),
-            AppMarketError::InvalidBidder
+            amount > 0 && amount <= ctx.accounts.reward_stake.amount,
+            AppMarketError::InvalidStakeAmount
         );
 
-        // Auction successful - create transaction
-        listing.status = ListingStatus::Sold;
-
-        let transaction = &mut ctx.accounts.transaction;
-        transaction.listing = listing.key();
-        transaction.seller = listing.seller;
-        transaction.buyer = listing.current_bidder
-            .ok_or(AppMarketError::NoBidsToSettle)?;
-        transaction.sale_price = listing.current_bid;
+        // EFFECTS: Settle rewards at the pre-unstake balance
+        let available = rewards_available(&ctx.accounts.rewards_vault.to_account_info())?;
+        let pool = &mut ctx.accounts.rewards_pool;
+        pool.sync(available)?;
 
-        // SECURITY: Use LOCKED fees from listing, not current config
-        transaction.platform_fee = listing.current_bid
-            .checked_mul(listing.platform_fee_bps)
-            .ok_or(AppMarketError::MathOverflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
+        let stake = &mut ctx.accounts.reward_stake;
+        stake.settle(pool.reward_per_share)?;
+        stake.amount = stake.amount
+            .checked_sub(amount)
             .ok_or(AppMarketError::MathOverflow)?;
-        transaction.seller_proceeds = listing.current_bid
-            .checked_sub(transaction.platform_fee)
+        stake.reset_debt(pool.reward_per_share)?;
+        pool.total_staked = pool.total_staked
+            .checked_sub(amount)
             .ok_or(AppMarketError::MathOverflow)?;
 
-        transaction.status = TransactionStatus::InEscrow;
-        transaction.transfer_deadline = clock.unix_timestamp
-            .checked_add(TRANSFER_DEADLINE_SECONDS)
-            .ok_or(AppMarketError::MathOverflow)?;
-        transaction.created_at = clock.unix_timestamp;
-        transaction.seller_confirmed_transfer = false;
-        transaction.seller_confirmed_at = None;
-        transaction.completed_at = None;
-        transaction.bump = ctx.bumps.transaction;
+        // INTERACTIONS: Return tokens from the stake vault
+        let pool_seeds = &[
+            b"rewards_pool".as_ref(),
+            &[ctx.accounts.rewards_pool.bump],
+        ];
+        let signer = &[&pool_seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.rewards_stake_vault.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.rewards_pool.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(cpi_ctx, amount)?;
 
-        emit!(SaleCompleted {
-            listing: listing.key(),
-            transaction: transaction.key(),
-            buyer: transaction.buyer,
-            seller: listing.seller,
-            amount: listing.current_bid,
-            timestamp: clock.unix_timestamp,
+        emit!(RewardsStakeChanged {
+            owner: ctx.accounts.reward_stake.owner,
+            reward_stake: ctx.accounts.reward_stake.key(),
+            amount,
+            staked: false,
+            total_staked: ctx.accounts.reward_stake.amount,
+            actor: ctx.accounts.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Cancel auction (when no bids received, closes escrow and refunds rent)
-    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::PlatformPaused);
+    /// Claim the caller's accrued share of protocol fees from the rewards vault
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        let available = rewards_available(&ctx.accounts.rewards_vault.to_account_info())?;
+        let pool = &mut ctx.accounts.rewards_pool;
+        pool.sync(available)?;
 
-        let listing = &mut ctx.accounts.listing;
-        let clock = Clock::get()?;
+        let stake = &mut ctx.accounts.reward_stake;
+        stake.settle(pool.reward_per_share)?;
+        stake.reset_debt(pool.reward_per_share)?;
 
-        // Validations
-        require!(
-            listing.status == ListingStatus::Active,
-            AppMarketError::ListingNotActive
-        );
-        require!(
-            listing.listing_type == ListingType::Auction,
-            AppMarketError::NotAnAuction
+        let amount = stake.pending_rewards;
+        require!(amount > 0, AppMarketError::NothingToClaim);
+
+        // EFFECTS
+        stake.pending_rewards = 0;
+        pool.accounted_balance = pool.accounted_balance
+            .checked_sub(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+        pool.total_claimed = pool.total_claimed.saturating_add(amount);
+
+        // INTERACTIONS
+        let vault_seeds = &[
+            b"rewards_vault".as_ref(),
+            &[pool.vault_bump],
+        ];
+        let signer = &[&vault_seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.rewards_vault.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            signer,
         );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+        emit!(RewardsClaimed {
+            owner: stake.owner,
+            reward_stake: ctx.accounts.reward_stake.key(),
+            amount,
+            actor: ctx.accounts.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Configure arbitrator staking (admin only)
+    /// A minimum stake of 0 disables the staking requirement and slashing
+    pub fn set_arbitrator_staking(
+        ctx: Context<SetArbitratorStaking>,
+        min_arbitrator_stake: u64,
+        arbitrator_slash_bps: u64,
+    ) -> Result<()> {
         require!(
-            ctx.accounts.seller.key() == listing.seller,
-            AppMarketError::NotSeller
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-
-        // Can only cancel if:
-        // 1. No bids received, OR
-        // 2. Auction ended and reserve not met (auction_started = false means no valid bids)
         require!(
-            listing.current_bidder.is_none(),
-            AppMarketError::CannotCancelWithBids
+            arbitrator_slash_bps <= BASIS_POINTS_DIVISOR,
+            AppMarketError::InvalidSlashBps
         );
 
-        // If auction has ended, require it to be past end_time
-        if listing.auction_started {
-            require!(
-                clock.unix_timestamp >= listing.end_time,
-                AppMarketError::AuctionNotEnded
-            );
-        }
-
-        listing.status = ListingStatus::Cancelled;
+        let config = &mut ctx.accounts.config;
+        config.min_arbitrator_stake = min_arbitrator_stake;
+        config.arbitrator_slash_bps = arbitrator_slash_bps as u16;
 
-        emit!(AuctionCancelled {
-            listing: listing.key(),
-            reason: "Cancelled by seller - no bids received".to_string(),
+        emit!(ArbitratorStakingUpdated {
+            min_arbitrator_stake,
+            arbitrator_slash_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Expire listing (for buy-now listings that reached deadline)
-    pub fn expire_listing(ctx: Context<ExpireListing>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::PlatformPaused);
+    /// Create the caller's arbitrator stake account and APP stake vault
+    pub fn init_arbitrator_stake(ctx: Context<InitArbitratorStake>) -> Result<()> {
+        let stake = &mut ctx.accounts.arbitrator_stake;
+        stake.arbitrator = ctx.accounts.arbitrator.key();
+        stake.amount = 0;
+        stake.unstake_amount = 0;
+        stake.unstake_requested_at = None;
+        stake.total_slashed = 0;
+        stake.version = ACCOUNT_LAYOUT_VERSION;
+        stake.bump = ctx.bumps.arbitrator_stake;
+        stake.vault_bump = ctx.bumps.stake_vault;
 
-        let listing = &mut ctx.accounts.listing;
-        let clock = Clock::get()?;
+        Ok(())
+    }
 
-        // Validations
-        require!(
-            listing.status == ListingStatus::Active,
-            AppMarketError::ListingNotActive
-        );
-        require!(
-            clock.unix_timestamp >= listing.end_time,
-            AppMarketError::ListingNotExpired
-        );
-        require!(
-            listing.current_bidder.is_none(),
-            AppMarketError::HasBids
+    /// Stake APP tokens to become eligible for committee voting
+    pub fn stake_arbitrator(ctx: Context<StakeArbitrator>, amount: u64) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        require!(amount > 0, AppMarketError::InvalidStakeAmount);
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.arbitrator_token_account.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.arbitrator.to_account_info(),
+            },
         );
+        token::transfer(cpi_ctx, amount)?;
 
-        listing.status = ListingStatus::Ended;
+        let stake = &mut ctx.accounts.arbitrator_stake;
+        stake.amount = stake.amount
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-        emit!(ListingExpired {
-            listing: listing.key(),
-            timestamp: clock.unix_timestamp,
+        emit!(ArbitratorStaked {
+            arbitrator: stake.arbitrator,
+            arbitrator_stake: stake.key(),
+            amount,
+            total_staked: stake.amount,
+            actor: ctx.accounts.arbitrator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Seller confirms they have transferred all assets (on-chain proof)
-    pub fn seller_confirm_transfer(ctx: Context<SellerConfirmTransfer>) -> Result<()> {
-        let transaction = &mut ctx.accounts.transaction;
+    /// Start the unstake cooldown for part of the stake
+    /// The requested amount stops counting toward eligibility immediately
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
         let clock = Clock::get()?;
+        let stake = &mut ctx.accounts.arbitrator_stake;
 
-        // SECURITY: Verify seller is the actual signer (defense-in-depth, Signer type also checks)
         require!(
-            ctx.accounts.seller.is_signer,
-            AppMarketError::SellerMustSign
+            amount > 0 && amount <= stake.amount,
+            AppMarketError::InvalidStakeAmount
         );
 
-        // Validations
-        require!(
-            transaction.status == TransactionStatus::InEscrow,
-            AppMarketError::InvalidTransactionStatus
-        );
-        require!(
-            ctx.accounts.seller.key() == transaction.seller,
-            AppMarketError::NotSeller
-        );
+        // Re-requesting replaces the previous request and restarts the cooldown
+        stake.unstake_amount = amount;
+        stake.unstake_requested_at = Some(clock.unix_timestamp);
+
+        emit!(UnstakeRequested {
+            arbitrator: stake.arbitrator,
+            arbitrator_stake: ctx.accounts.arbitrator_stake.key(),
+            amount,
+            available_at: clock.unix_timestamp + ARBITRATOR_UNSTAKE_COOLDOWN_SECONDS,
+            actor: ctx.accounts.arbitrator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the requested stake after the cooldown
+    pub fn unstake_arbitrator(ctx: Context<UnstakeArbitrator>) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let requested_at = ctx.accounts.arbitrator_stake.unstake_requested_at
+            .ok_or(AppMarketError::NoUnstakeRequested)?;
         require!(
-            !transaction.seller_confirmed_transfer,
-            AppMarketError::AlreadyConfirmed
+            clock.unix_timestamp >= requested_at + ARBITRATOR_UNSTAKE_COOLDOWN_SECONDS,
+            AppMarketError::UnstakeCooldownActive
         );
 
-        transaction.seller_confirmed_transfer = true;
-        transaction.seller_confirmed_at = Some(clock.unix_timestamp);
+        // Slashing during the cooldown may have reduced the stake below the request
+        let amount = ctx.accounts.arbitrator_stake.unstake_amount
+            .min(ctx.accounts.arbitrator_stake.amount);
 
-        emit!(SellerConfirmedTransfer {
-            transaction: transaction.key(),
-            seller: transaction.seller,
+        // EFFECTS: Update state before transfer
+        let stake = &mut ctx.accounts.arbitrator_stake;
+        stake.amount = stake.amount
+            .checked_sub(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+        stake.unstake_amount = 0;
+        stake.unstake_requested_at = None;
+
+        // INTERACTIONS: Return tokens from the stake vault
+        if amount > 0 {
+            let arbitrator_key = ctx.accounts.arbitrator.key();
+            let stake_seeds = &[
+                b"arbitrator_stake",
+                arbitrator_key.as_ref(),
+                &[ctx.accounts.arbitrator_stake.bump],
+            ];
+            let signer = &[&stake_seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.arbitrator_token_account.to_account_info(),
+                    authority: ctx.accounts.arbitrator_stake.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(cpi_ctx, amount)?;
+        }
+
+        emit!(ArbitratorUnstaked {
+            arbitrator: ctx.accounts.arbitrator_stake.arbitrator,
+            arbitrator_stake: ctx.accounts.arbitrator_stake.key(),
+            amount,
+            remaining_stake: ctx.accounts.arbitrator_stake.amount,
+            actor: ctx.accounts.arbitrator.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Backend service verifies uploads (GitHub repo, files, etc.)
-    pub fn verify_uploads(
-        ctx: Context<VerifyUploads>,
-        verification_hash: String,
-    ) -> Result<()> {
-        let transaction = &mut ctx.accounts.transaction;
+    /// Slash an arbitrator whose ruling was overturned on appeal (permissionless)
+    /// A share of their stake goes to the appellant (the wronged party)
+    pub fn slash_arbitrator(ctx: Context<SlashArbitrator>) -> Result<()> {
         let clock = Clock::get()?;
+        let arbitrator = ctx.accounts.arbitrator_stake.arbitrator;
 
-        // SECURITY: Only backend authority can verify
         require!(
-            ctx.accounts.backend_authority.key() == ctx.accounts.config.backend_authority,
-            AppMarketError::NotBackendAuthority
+            ctx.accounts.dispute.status == DisputeStatus::Resolved
+                && ctx.accounts.dispute.appeal_overturned,
+            AppMarketError::RulingNotOverturned
         );
-
         require!(
-            transaction.seller_confirmed_transfer,
-            AppMarketError::SellerNotConfirmed
+            ctx.accounts.dispute.ruling_arbitrators.contains(&arbitrator),
+            AppMarketError::NotArbitrator
         );
 
-        require!(
-            !transaction.uploads_verified,
-            AppMarketError::AlreadyVerified
-        );
+        let slash_amount = ctx.accounts.arbitrator_stake.amount
+            .checked_mul(ctx.accounts.config.arbitrator_slash_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-        transaction.uploads_verified = true;
-        transaction.verification_timestamp = Some(clock.unix_timestamp);
-        transaction.verification_hash = verification_hash.clone();
+        // EFFECTS: Each arbitrator can only be slashed once per dispute
+        ctx.accounts.dispute.ruling_arbitrators.retain(|a| *a != arbitrator);
+        let stake = &mut ctx.accounts.arbitrator_stake;
+        stake.amount = stake.amount
+            .checked_sub(slash_amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+        stake.total_slashed = stake.total_slashed
+            .checked_add(slash_amount)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-        emit!(UploadsVerified {
-            transaction: transaction.key(),
-            verification_hash,
+        // INTERACTIONS: Pay the wronged party from the stake vault
+        if slash_amount > 0 {
+            let stake_seeds = &[
+                b"arbitrator_stake",
+                arbitrator.as_ref(),
+                &[ctx.accounts.arbitrator_stake.bump],
+            ];
+            let signer = &[&stake_seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.stake_vault.to_account_info(),
+                    to: ctx.accounts.appellant_token_account.to_account_info(),
+                    authority: ctx.accounts.arbitrator_stake.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(cpi_ctx, slash_amount)?;
+        }
+
+        emit!(ArbitratorSlashed {
+            arbitrator,
+            arbitrator_stake: ctx.accounts.arbitrator_stake.key(),
+            dispute: ctx.accounts.dispute.key(),
+            recipient: ctx.accounts.appellant_token_account.owner,
+            amount: slash_amount,
+            remaining_stake: ctx.accounts.arbitrator_stake.amount,
+            actor: ctx.accounts.caller.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Emergency auto-verification by buyer after backend timeout (30 days)
-    /// SECURITY: Fallback mechanism if backend is unresponsive
-    pub fn emergency_auto_verify(ctx: Context<EmergencyAutoVerify>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-
-        let transaction = &mut ctx.accounts.transaction;
+    /// Close a resolved dispute once it can no longer be appealed (permissionless)
+    /// Rent returns to the dispute initiator who paid for the account
+    pub fn close_dispute(ctx: Context<CloseDispute>) -> Result<()> {
         let clock = Clock::get()?;
-
-        // SECURITY: Only buyer can trigger emergency auto-verify
-        require!(
-            ctx.accounts.buyer.key() == transaction.buyer,
-            AppMarketError::NotBuyer
-        );
+        let dispute = &ctx.accounts.dispute;
 
         require!(
-            transaction.seller_confirmed_transfer,
-            AppMarketError::SellerNotConfirmed
+            dispute.status == DisputeStatus::Resolved,
+            AppMarketError::DisputeNotResolved
         );
 
-        require!(
-            !transaction.uploads_verified,
-            AppMarketError::AlreadyVerified
-        );
+        // Either the appeal has been ruled on, or the appeal window lapsed unused
+        let appeal_finished = dispute.appealed_by.is_some()
+            || dispute.appeal_deadline
+                .map(|deadline| clock.unix_timestamp > deadline)
+                .unwrap_or(true);
+        require!(appeal_finished, AppMarketError::AppealWindowOpen);
+        require!(dispute.ruling_settled, AppMarketError::RulingNotSettled);
 
-        // SECURITY: Must wait 30 days from seller confirmation
-        let confirmed_at = transaction.seller_confirmed_at.ok_or(AppMarketError::SellerNotConfirmed)?;
+        // Slashes owed to the wronged party must be processed first
         require!(
-            clock.unix_timestamp >= confirmed_at + BACKEND_TIMEOUT_SECONDS,
-            AppMarketError::BackendTimeoutNotExpired
+            !dispute.appeal_overturned || dispute.ruling_arbitrators.is_empty(),
+            AppMarketError::SlashPending
         );
 
-        // Auto-verify
-        transaction.uploads_verified = true;
-        transaction.verification_timestamp = Some(clock.unix_timestamp);
-        transaction.verification_hash = "EMERGENCY_BUYER_TIMEOUT".to_string();
-
-        emit!(EmergencyVerification {
-            transaction: transaction.key(),
-            verified_by: ctx.accounts.buyer.key(),
-            verification_type: "buyer_timeout".to_string(),
+        emit!(DisputeClosed {
+            dispute: dispute.key(),
+            transaction: dispute.transaction,
+            actor: ctx.accounts.caller.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Admin emergency verification after backend timeout (30 days)
-    /// SECURITY: Admin can only intervene after same 30-day timeout as buyer
-    pub fn admin_emergency_verify(ctx: Context<AdminEmergencyVerify>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-
+    /// Emergency refund after transfer deadline passes (ONLY if seller never confirmed transfer)
+    /// remaining_accounts: the stake unwind accounts while the escrow is staked
+    pub fn emergency_refund<'info>(
+        ctx: Context<'_, '_, 'info, 'info, EmergencyRefund<'info>>,
+    ) -> Result<()> {
         let transaction = &mut ctx.accounts.transaction;
         let clock = Clock::get()?;
 
-        // SECURITY: Only admin can call
+        // Validations
         require!(
-            ctx.accounts.admin.key() == ctx.accounts.config.admin,
-            AppMarketError::NotAdmin
+            transaction.status == TransactionStatus::InEscrow,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(
+            ctx.accounts.buyer.key() == transaction.buyer,
+            AppMarketError::NotBuyer
         );
+        // Backend-rejected uploads skip the deadline and confirmation checks
+        if transaction.uploads_rejected_at.is_none() {
+            require!(
+                clock.unix_timestamp > transaction.transfer_deadline,
+                AppMarketError::DeadlineNotPassed
+            );
 
+            // SECURITY: If seller confirmed transfer, buyer MUST open dispute
+            if transaction.seller_confirmed_transfer {
+                return Err(AppMarketError::MustOpenDispute.into());
+            }
+        }
+
+        // Staked escrow: redeem it first (its accounts lead remaining_accounts)
+        let (stake_accounts, _) =
+            take_escrow_stake_accounts(transaction, ctx.remaining_accounts)?;
+        if let Some(stake_accounts) = stake_accounts {
+            unwind_escrow_stake(
+                stake_accounts,
+                &mut ctx.accounts.escrow,
+                transaction,
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.buyer.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
+        // SECURITY: Validate escrow balance
+        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
+        let rent = Rent::get()?.minimum_balance(
+            ctx.accounts.escrow.to_account_info().data_len()
+        );
         require!(
-            transaction.seller_confirmed_transfer,
-            AppMarketError::SellerNotConfirmed
+            escrow_balance >= transaction.sale_price + rent,
+            AppMarketError::InsufficientEscrowBalance
         );
 
+        // Validate tracked amount
+        let tracked_with_rent = ctx.accounts.escrow.amount
+            .checked_add(rent)
+            .ok_or(AppMarketError::MathOverflow)?;
         require!(
-            !transaction.uploads_verified,
-            AppMarketError::AlreadyVerified
+            escrow_balance >= tracked_with_rent,
+            AppMarketError::EscrowBalanceMismatch
         );
 
-        // SECURITY: Admin must also wait 30 days - no special privileges
-        let confirmed_at = transaction.seller_confirmed_at.ok_or(AppMarketError::SellerNotConfirmed)?;
+        // Allow refund even with pending withdrawals — escrow stays open for cleanup
         require!(
-            clock.unix_timestamp >= confirmed_at + BACKEND_TIMEOUT_SECONDS,
-            AppMarketError::BackendTimeoutNotExpired
+            ctx.accounts.escrow.amount >= transaction.sale_price,
+            AppMarketError::InsufficientEscrowBalance
         );
 
-        // Admin verify
-        transaction.uploads_verified = true;
-        transaction.verification_timestamp = Some(clock.unix_timestamp);
-        transaction.verification_hash = "EMERGENCY_ADMIN_OVERRIDE".to_string();
+        // Refund full amount to buyer
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
 
-        emit!(EmergencyVerification {
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.buyer.to_account_info(),
+            },
+            signer,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, transaction.sale_price)?;
+
+        ctx.accounts.escrow.debit(
+            transaction.sale_price,
+            EscrowChangeReason::EmergencyRefund,
+            ctx.accounts.buyer.key(),
+        )?;
+
+        transaction.status = TransactionStatus::Refunded;
+        transaction.completed_at = Some(clock.unix_timestamp);
+        set_sale_status(
+            &mut ctx.accounts.listing,
+            transaction,
+            ListingStatus::Refunded,
+            ctx.accounts.buyer.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(TransactionCompleted {
             transaction: transaction.key(),
-            verified_by: ctx.accounts.admin.key(),
-            verification_type: "admin_override".to_string(),
+            seller: transaction.seller,
+            buyer: transaction.buyer,
+            amount: 0,
+            platform_fee: 0,
+            actor: ctx.accounts.buyer.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Finalize transaction after grace period (7 days after seller confirmation)
-    pub fn finalize_transaction(ctx: Context<FinalizeTransaction>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-
+    /// Refund the buyer once uploads were rejected and the seller did not re-deliver
+    /// within the cure period. Permissionless: no admin or backend involvement needed.
+    /// remaining_accounts: the stake unwind accounts while the escrow is staked
+    pub fn refund_on_failed_verification<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundOnFailedVerification<'info>>,
+    ) -> Result<()> {
         let transaction = &mut ctx.accounts.transaction;
         let clock = Clock::get()?;
 
-        // SECURITY: Only seller can call finalize
-        require!(
-            ctx.accounts.seller.key() == transaction.seller,
-            AppMarketError::NotSeller
-        );
-        require!(
-            ctx.accounts.seller.is_signer,
-            AppMarketError::SellerMustSign
-        );
-
-        // Validations
-        // SECURITY: Block finalization if disputed
-        if transaction.status == TransactionStatus::Disputed {
-            return Err(AppMarketError::CannotFinalizeDisputed.into());
-        }
-
         require!(
             transaction.status == TransactionStatus::InEscrow,
             AppMarketError::InvalidTransactionStatus
         );
-
-        require!(
-            transaction.seller_confirmed_transfer,
-            AppMarketError::SellerNotConfirmed
-        );
-
-        // SECURITY: Uploads must be verified
         require!(
-            transaction.uploads_verified,
-            AppMarketError::UploadsNotVerified
+            !transaction.uploads_verified,
+            AppMarketError::AlreadyVerified
         );
-
-        let confirmed_at = transaction.seller_confirmed_at
-            .ok_or(AppMarketError::SellerNotConfirmed)?;
+        // Re-confirming after a rejection clears it, so the rejection still stands here
+        let rejected_at = transaction.uploads_rejected_at
+            .ok_or(AppMarketError::UploadsNotRejected)?;
         require!(
-            clock.unix_timestamp >= confirmed_at + FINALIZE_GRACE_PERIOD,
-            AppMarketError::GracePeriodNotExpired
+            clock.unix_timestamp >= rejected_at + VERIFICATION_CURE_PERIOD_SECONDS,
+            AppMarketError::DeadlineNotPassed
         );
 
-        require!(
-            ctx.accounts.treasury.key() == ctx.accounts.config.treasury,
-            AppMarketError::InvalidTreasury
-        );
+        // Staked escrow: redeem it first (its accounts lead remaining_accounts)
+        let (stake_accounts, _) =
+            take_escrow_stake_accounts(transaction, ctx.remaining_accounts)?;
+        if let Some(stake_accounts) = stake_accounts {
+            unwind_escrow_stake(
+                stake_accounts,
+                &mut ctx.accounts.escrow,
+                transaction,
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.caller.key(),
+                clock.unix_timestamp,
+            )?;
+        }
 
         // SECURITY: Validate escrow balance
         let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
         let rent = Rent::get()?.minimum_balance(
             ctx.accounts.escrow.to_account_info().data_len()
         );
-
-        let required_balance = transaction.platform_fee
-            .checked_add(transaction.seller_proceeds)
+        let tracked_with_rent = ctx.accounts.escrow.amount
+            .checked_add(rent)
             .ok_or(AppMarketError::MathOverflow)?;
         require!(
-            escrow_balance >= required_balance + rent,
-            AppMarketError::InsufficientEscrowBalance
+            escrow_balance >= tracked_with_rent,
+            AppMarketError::EscrowBalanceMismatch
         );
-
-        // Allow finalization even with pending withdrawals — escrow stays open for cleanup
-        // The >= check ensures enough SOL exists for the sale; excess is pending withdrawal SOL
-        // that will be returned via expire_withdrawal/withdraw_funds + close_escrow
         require!(
-            ctx.accounts.escrow.amount >= required_balance,
+            ctx.accounts.escrow.amount >= transaction.sale_price,
             AppMarketError::InsufficientEscrowBalance
         );
 
-        // Transfer funds
         let seeds = &[
             b"escrow",
             ctx.accounts.listing.to_account_info().key.as_ref(),
@@ -1367,113 +8869,200 @@ pub mod app_market {
         ];
         let signer = &[&seeds[..]];
 
-        // Platform fee to treasury
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
                 from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.treasury.to_account_info(),
+                to: ctx.accounts.buyer.to_account_info(),
             },
             signer,
         );
-        anchor_lang::system_program::transfer(cpi_ctx, transaction.platform_fee)?;
+        anchor_lang::system_program::transfer(cpi_ctx, transaction.sale_price)?;
 
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_sub(transaction.platform_fee)
-            .ok_or(AppMarketError::MathOverflow)?;
+        ctx.accounts.escrow.debit(
+            transaction.sale_price,
+            EscrowChangeReason::VerificationRefund,
+            ctx.accounts.caller.key(),
+        )?;
 
-        // Seller proceeds to seller
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.seller.to_account_info(),
-            },
-            signer,
+        transaction.status = TransactionStatus::Refunded;
+        transaction.completed_at = Some(clock.unix_timestamp);
+        set_sale_status(
+            &mut ctx.accounts.listing,
+            transaction,
+            ListingStatus::Refunded,
+            ctx.accounts.caller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(VerificationFailureRefunded {
+            transaction: transaction.key(),
+            buyer: transaction.buyer,
+            amount: transaction.sale_price,
+            rejection_code: transaction.rejection_code,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Assign buyer rights on a transaction to another wallet (both wallets sign)
+    /// Every later buyer check, refund, and payout uses the new buyer
+    pub fn assign_buyer(ctx: Context<AssignBuyer>) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::InEscrow
+                || transaction.status == TransactionStatus::Pending,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(
+            ctx.accounts.buyer.key() == transaction.buyer,
+            AppMarketError::NotBuyer
+        );
+
+        let new_buyer = ctx.accounts.new_buyer.key();
+        require!(new_buyer != transaction.buyer, AppMarketError::InvalidBuyer);
+        require!(new_buyer != transaction.seller, AppMarketError::SellerCannotBuy);
+
+        let previous_buyer = transaction.buyer;
+        transaction.buyer = new_buyer;
+
+        // A cancellation proposed by the outgoing buyer no longer speaks for the new one
+        if transaction.cancel_proposed_by == Some(previous_buyer) {
+            transaction.cancel_proposed_by = None;
+            transaction.cancel_relist = false;
+        }
+
+        emit!(BuyerAssigned {
+            transaction: transaction.key(),
+            previous_buyer,
+            new_buyer,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Propose mutually cancelling an in-escrow transaction (buyer or seller)
+    /// The counterparty must call accept_mutual_cancel for it to take effect
+    pub fn propose_mutual_cancel(
+        ctx: Context<ProposeMutualCancel>,
+        relist: bool,
+    ) -> Result<()> {
+        let transaction = &mut ctx.accounts.transaction;
+        let clock = Clock::get()?;
+
+        require!(
+            transaction.status == TransactionStatus::InEscrow
+                || transaction.status == TransactionStatus::Pending,
+            AppMarketError::InvalidTransactionStatus
+        );
+
+        let proposer = ctx.accounts.proposer.key();
+        require!(
+            transaction.is_buyer_signer(&proposer) || proposer == transaction.seller,
+            AppMarketError::NotPartyToTransaction
         );
-        anchor_lang::system_program::transfer(cpi_ctx, transaction.seller_proceeds)?;
 
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_sub(transaction.seller_proceeds)
-            .ok_or(AppMarketError::MathOverflow)?;
-
-        // Update transaction status
-        transaction.status = TransactionStatus::Completed;
-        transaction.completed_at = Some(clock.unix_timestamp);
+        // Unit sales can't relist: the listing keeps selling its other units
+        require!(
+            !(relist && transaction.unit_index.is_some()),
+            AppMarketError::MultiUnitListing
+        );
 
-        // SECURITY: Use saturating_add for stats
-        let config = &mut ctx.accounts.config;
-        config.total_volume = config.total_volume.saturating_add(transaction.sale_price);
-        config.total_sales = config.total_sales.saturating_add(1);
+        transaction.cancel_proposed_by = Some(proposer);
+        transaction.cancel_relist = relist;
 
-        emit!(TransactionCompleted {
+        emit!(MutualCancelProposed {
             transaction: transaction.key(),
-            seller: transaction.seller,
-            buyer: transaction.buyer,
-            amount: transaction.sale_price,
-            platform_fee: transaction.platform_fee,
+            actor: proposer,
+            relist,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Buyer confirms receipt of all assets - releases escrow
-    pub fn confirm_receipt(ctx: Context<ConfirmReceipt>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-
-        let transaction = &mut ctx.accounts.transaction;
+    /// Accept a mutual cancellation proposed by the counterparty
+    /// Refunds the buyer; if relisting was agreed the listing goes back to Active
+    /// and the transaction account is closed so a new sale can be recorded
+    /// remaining_accounts: the stake unwind accounts while the escrow is staked
+    pub fn accept_mutual_cancel<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AcceptMutualCancel<'info>>,
+    ) -> Result<()> {
         let clock = Clock::get()?;
 
-        // Validations
-        require!(transaction.status == TransactionStatus::InEscrow, AppMarketError::InvalidTransactionStatus);
-        require!(ctx.accounts.buyer.key() == transaction.buyer, AppMarketError::NotBuyer);
-        require!(
-            ctx.accounts.treasury.key() == ctx.accounts.config.treasury,
-            AppMarketError::InvalidTreasury
-        );
+        let status = ctx.accounts.transaction.status.clone();
         require!(
-            ctx.accounts.seller.key() == transaction.seller,
-            AppMarketError::InvalidSeller
+            status == TransactionStatus::InEscrow || status == TransactionStatus::Pending,
+            AppMarketError::InvalidTransactionStatus
         );
 
-        // SECURITY: Require upload verification before buyer can confirm receipt
+        let proposed_by = ctx.accounts.transaction.cancel_proposed_by
+            .ok_or(AppMarketError::NoCancelProposal)?;
+
+        // SECURITY: Acceptor must be the other party to the transaction
+        let acceptor = ctx.accounts.acceptor.key();
+        let buyer = ctx.accounts.transaction.buyer;
+        let seller = ctx.accounts.transaction.seller;
+        let transaction = &ctx.accounts.transaction;
+        let acceptor_is_buyer = transaction.is_buyer_signer(&acceptor);
         require!(
-            transaction.uploads_verified,
-            AppMarketError::UploadsNotVerified
+            acceptor_is_buyer || acceptor == seller,
+            AppMarketError::NotPartyToTransaction
         );
+        require!(
+            acceptor_is_buyer != transaction.is_buyer_signer(&proposed_by),
+            AppMarketError::CannotAcceptOwnProposal
+        );
+
+        // Staked escrow: redeem it first (its accounts lead remaining_accounts)
+        let (stake_accounts, _) =
+            take_escrow_stake_accounts(&ctx.accounts.transaction, ctx.remaining_accounts)?;
+        if let Some(stake_accounts) = stake_accounts {
+            unwind_escrow_stake(
+                stake_accounts,
+                &mut ctx.accounts.escrow,
+                &mut ctx.accounts.transaction,
+                &ctx.accounts.system_program.to_account_info(),
+                acceptor,
+                clock.unix_timestamp,
+            )?;
+        }
 
-        // SECURITY: Validate escrow balance (4 checks)
+        // Deposit-mode transactions awaiting payment only hold the deposit
+        let refund_amount = if status == TransactionStatus::Pending {
+            ctx.accounts.transaction.deposit_amount
+        } else {
+            ctx.accounts.transaction.sale_price
+        };
+
+        // SECURITY: Validate escrow balance
         let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
         let rent = Rent::get()?.minimum_balance(
             ctx.accounts.escrow.to_account_info().data_len()
         );
-
-        // Check 1: Sufficient for payment + rent
-        let required_balance = transaction.platform_fee
-            .checked_add(transaction.seller_proceeds)
-            .ok_or(AppMarketError::MathOverflow)?;
         require!(
-            escrow_balance >= required_balance + rent,
+            escrow_balance >= refund_amount + rent,
             AppMarketError::InsufficientEscrowBalance
         );
-
-        // Check 2: Tracked amount matches reality
-        let tracked_with_rent = ctx.accounts.escrow.amount
-            .checked_add(rent)
-            .ok_or(AppMarketError::MathOverflow)?;
-        require!(
-            escrow_balance >= tracked_with_rent,
-            AppMarketError::EscrowBalanceMismatch
-        );
-
-        // Allow confirmation even with pending withdrawals — escrow stays open for cleanup
         require!(
-            ctx.accounts.escrow.amount >= required_balance,
+            ctx.accounts.escrow.amount >= refund_amount,
             AppMarketError::InsufficientEscrowBalance
         );
 
-        // Transfer funds
+        // EFFECTS
+        ctx.accounts.escrow.debit(
+            refund_amount,
+            EscrowChangeReason::MutualCancel,
+            ctx.accounts.acceptor.key(),
+        )?;
+
+        // INTERACTIONS: Refund buyer
         let seeds = &[
             b"escrow",
             ctx.accounts.listing.to_account_info().key.as_ref(),
@@ -1481,2420 +9070,2808 @@ pub mod app_market {
         ];
         let signer = &[&seeds[..]];
 
-        // Platform fee to treasury
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.treasury.to_account_info(),
-            },
-            signer,
-        );
-        anchor_lang::system_program::transfer(cpi_ctx, transaction.platform_fee)?;
-
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_sub(transaction.platform_fee)
-            .ok_or(AppMarketError::MathOverflow)?;
-
-        // Seller proceeds to seller
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
                 from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.seller.to_account_info(),
+                to: ctx.accounts.buyer.to_account_info(),
             },
             signer,
         );
-        anchor_lang::system_program::transfer(cpi_ctx, transaction.seller_proceeds)?;
+        anchor_lang::system_program::transfer(cpi_ctx, refund_amount)?;
 
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_sub(transaction.seller_proceeds)
-            .ok_or(AppMarketError::MathOverflow)?;
-
-        // Update transaction status
-        transaction.status = TransactionStatus::Completed;
-        transaction.completed_at = Some(clock.unix_timestamp);
+        let relist = ctx.accounts.transaction.cancel_relist;
+        let transaction_key = ctx.accounts.transaction.key();
+        let listing = &mut ctx.accounts.listing;
 
-        // SECURITY: Use saturating_add for stats (prevents overflow blocking transactions)
-        let config = &mut ctx.accounts.config;
-        config.total_volume = config.total_volume.saturating_add(transaction.sale_price);
-        config.total_sales = config.total_sales.saturating_add(1);
+        if relist {
+            // Reactivate the listing with a fresh window of its original duration
+            set_listing_status(
+                listing,
+                ListingStatus::Active,
+                ctx.accounts.acceptor.key(),
+                clock.unix_timestamp,
+            )?;
+            listing.current_bid = 0;
+            listing.current_bidder = None;
+            listing.current_bid_exposure = 0;
+            listing.last_bidder = None;
+            listing.consecutive_bid_count = 0;
+            listing.auction_started = false;
+            listing.auction_start_time = None;
+            listing.extension_count = 0;
+            listing.created_at = clock.unix_timestamp;
+            listing.start_time = clock.unix_timestamp;
+            listing.end_time = listing.sale_window_end(clock.unix_timestamp, listing.duration_seconds)?;
+
+            // Close the transaction so the next sale can initialize a fresh one
+            ctx.accounts.transaction.close(ctx.accounts.buyer.to_account_info())?;
+        } else {
+            // A cancelled unit sale leaves the rest of a multi-unit listing untouched
+            set_sale_status(
+                listing,
+                &ctx.accounts.transaction,
+                ListingStatus::Cancelled,
+                ctx.accounts.acceptor.key(),
+                clock.unix_timestamp,
+            )?;
+
+            // Transaction stays open (terminal) so close_escrow can verify state later
+            let transaction = &mut ctx.accounts.transaction;
+            transaction.status = TransactionStatus::Cancelled;
+            transaction.completed_at = Some(clock.unix_timestamp);
+            transaction.cancel_proposed_by = None;
+        }
 
-        emit!(TransactionCompleted {
-            transaction: transaction.key(),
-            seller: transaction.seller,
-            buyer: transaction.buyer,
-            amount: transaction.sale_price,
-            platform_fee: transaction.platform_fee,
+        emit!(MutualCancelCompleted {
+            listing: listing.key(),
+            transaction: transaction_key,
+            buyer,
+            seller,
+            refund_amount,
+            relisted: relist,
+            actor: ctx.accounts.acceptor.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Make an offer on a listing
-    pub fn make_offer(
-        ctx: Context<MakeOffer>,
-        amount: u64,
-        deadline: i64,
-        offer_seed: u64,
-    ) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-
+    /// Cancel listing (seller only, before any bids; drafts and paused listings included)
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
         let listing = &mut ctx.accounts.listing;
         let clock = Clock::get()?;
 
         // Validations
         require!(
-            listing.status == ListingStatus::Active,
+            matches!(
+                listing.status,
+                ListingStatus::Active | ListingStatus::Draft | ListingStatus::Paused
+            ),
             AppMarketError::ListingNotActive
         );
-        require!(amount > 0, AppMarketError::InvalidPrice);
-        require!(
-            deadline > clock.unix_timestamp,
-            AppMarketError::InvalidDeadline
-        );
+        require!(ctx.accounts.seller.key() == listing.seller, AppMarketError::NotSeller);
+
+        // SECURITY: Prevent cancellation if auction has started (has bids)
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        // SECURITY: Escrow closes here; unit sales still holding funds must settle first
         require!(
-            ctx.accounts.buyer.key() != listing.seller,
-            AppMarketError::SellerCannotOffer
+            ctx.accounts.escrow.amount == 0,
+            AppMarketError::PendingWithdrawalsExist
         );
 
-        // SECURITY: Pre-check buyer has sufficient balance
+        set_listing_status(
+            listing,
+            ListingStatus::Cancelled,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(AuctionCancelled {
+            listing: listing.key(),
+            reason: "Cancelled by seller".to_string(),
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Configure escrow yield (admin only): the SPL stake pool buyers may stake escrowed
+    /// SOL into (None disables new stakes) and the buyer's share of the yield in basis points
+    pub fn set_escrow_yield(
+        ctx: Context<SetEscrowYield>,
+        stake_pool: Option<Pubkey>,
+        buyer_share_bps: u16,
+    ) -> Result<()> {
         require!(
-            ctx.accounts.buyer.lamports() >= amount,
-            AppMarketError::InsufficientBalance
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-
-        // SECURITY: Prevent DoS via total offer spam
         require!(
-            listing.offer_count < MAX_OFFERS_PER_LISTING,
-            AppMarketError::MaxOffersExceeded
+            buyer_share_bps as u64 <= BASIS_POINTS_DIVISOR,
+            AppMarketError::InvalidYieldShare
         );
 
-        // SECURITY: Check consecutive offers from same buyer (max 10 if no one else is outbidding)
-        let buyer_key = ctx.accounts.buyer.key();
-        if let Some(last_buyer) = listing.last_offer_buyer {
-            if last_buyer == buyer_key {
-                // Same buyer making consecutive offers
-                require!(
-                    listing.consecutive_offer_count < MAX_CONSECUTIVE_OFFERS,
-                    AppMarketError::MaxConsecutiveOffersExceeded
-                );
-                // Increment consecutive counter
-                listing.consecutive_offer_count = listing.consecutive_offer_count
-                    .checked_add(1)
-                    .ok_or(AppMarketError::MathOverflow)?;
-            } else {
-                // Different buyer - reset consecutive counter
-                listing.last_offer_buyer = Some(buyer_key);
-                listing.consecutive_offer_count = 1;
-            }
-        } else {
-            // First offer on this listing
-            listing.last_offer_buyer = Some(buyer_key);
-            listing.consecutive_offer_count = 1;
-        }
+        let config = &mut ctx.accounts.config;
+        config.escrow_stake_pool = stake_pool;
+        config.escrow_yield_buyer_bps = buyer_share_bps;
 
-        // SECURITY: Validate offer_seed matches current counter (prevents arbitrary seeds)
-        require!(
-            offer_seed == listing.offer_count,
-            AppMarketError::InvalidOfferSeed
-        );
+        emit!(EscrowYieldConfigured {
+            stake_pool,
+            buyer_share_bps,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Increment total offer counter
-        listing.offer_count = listing.offer_count
-            .checked_add(1)
-            .ok_or(AppMarketError::MathOverflow)?;
+        Ok(())
+    }
 
-        // Initialize offer
-        let offer = &mut ctx.accounts.offer;
-        offer.listing = listing.key();
-        offer.buyer = ctx.accounts.buyer.key();
-        offer.amount = amount;
-        offer.deadline = deadline;
-        offer.status = OfferStatus::Active;
-        offer.created_at = clock.unix_timestamp;
-        offer.bump = ctx.bumps.offer;
+    /// Stake a sale's escrowed SOL into the configured stake pool (buyer and seller, opt-in)
+    /// The whole Escrow.amount moves into pool tokens; Escrow.amount still records it, and
+    /// releases and refunds unwind the stake first (remaining_accounts, unwind_escrow_stake).
+    /// Both parties carry the risk, so both sign: a redemption short of the principal comes
+    /// off a refund, or off the platform fee and then the seller's proceeds on release.
+    /// Single-sale SOL escrows only (multi-unit escrows are shared between sales).
+    pub fn stake_escrow(ctx: Context<StakeEscrow>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+        check_stake_pool(
+            &ctx.accounts.config,
+            &ctx.accounts.stake_pool,
+            &ctx.accounts.pool_mint.key(),
+        )?;
+
+        let listing = &ctx.accounts.listing;
+        let transaction = &ctx.accounts.transaction;
+        let clock = Clock::get()?;
 
-        // Initialize escrow for offer
-        let offer_escrow = &mut ctx.accounts.offer_escrow;
-        offer_escrow.offer = offer.key();
-        offer_escrow.amount = amount;
-        offer_escrow.bump = ctx.bumps.offer_escrow;
+        require!(
+            ctx.accounts.buyer.key() == transaction.buyer,
+            AppMarketError::NotBuyer
+        );
+        require!(
+            transaction.status == TransactionStatus::InEscrow,
+            AppMarketError::InvalidTransactionStatus
+        );
+        require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
+        require!(listing.payment_mint.is_none(), AppMarketError::InvalidPaymentMint);
 
-        // Transfer funds to escrow
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.offer_escrow.to_account_info(),
-            },
+        // SECURITY: Validate escrow balance before moving the principal out
+        let principal = ctx.accounts.escrow.amount;
+        require!(principal > 0, AppMarketError::InsufficientEscrowBalance);
+        let escrow_info = ctx.accounts.escrow.to_account_info();
+        let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+        require!(
+            escrow_info.lamports() >= principal + rent,
+            AppMarketError::InsufficientEscrowBalance
         );
-        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
 
-        emit!(OfferCreated {
-            offer: offer.key(),
-            listing: listing.key(),
-            buyer: ctx.accounts.buyer.key(),
-            amount,
-            deadline,
+        ctx.accounts.escrow.sub_lamports(principal)?;
+        ctx.accounts.stake_vault.add_lamports(principal)?;
+
+        let escrow_stake_key = ctx.accounts.escrow_stake.key();
+        let vault_seeds = &[
+            b"stake_vault".as_ref(),
+            escrow_stake_key.as_ref(),
+            &[ctx.bumps.stake_vault],
+        ];
+        stake_pool_deposit_sol(
+            &ctx.accounts.stake_pool.to_account_info(),
+            &ctx.accounts.withdraw_authority.to_account_info(),
+            &ctx.accounts.reserve_stake.to_account_info(),
+            &ctx.accounts.stake_vault.to_account_info(),
+            &ctx.accounts.pool_tokens.to_account_info(),
+            &ctx.accounts.manager_fee_account.to_account_info(),
+            &ctx.accounts.pool_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            principal,
+            vault_seeds,
+        )?;
+        ctx.accounts.pool_tokens.reload()?;
+        let pool_tokens = ctx.accounts.pool_tokens.amount;
+
+        let escrow_stake = &mut ctx.accounts.escrow_stake;
+        escrow_stake.version = ACCOUNT_LAYOUT_VERSION;
+        escrow_stake.escrow = ctx.accounts.escrow.key();
+        escrow_stake.transaction = transaction.key();
+        escrow_stake.stake_pool = ctx.accounts.stake_pool.key();
+        escrow_stake.principal = principal;
+        escrow_stake.pool_tokens = pool_tokens;
+        escrow_stake.rent_payer = ctx.accounts.buyer.key();
+        escrow_stake.staked_at = clock.unix_timestamp;
+        escrow_stake.bump = ctx.bumps.escrow_stake;
+        escrow_stake.vault_bump = ctx.bumps.stake_vault;
+        ctx.accounts.transaction.escrow_staked = true;
+
+        emit!(EscrowStaked {
+            escrow: escrow_stake.escrow,
+            transaction: escrow_stake.transaction,
+            escrow_stake: escrow_stake_key,
+            stake_pool: escrow_stake.stake_pool,
+            principal,
+            pool_tokens,
+            actor: ctx.accounts.buyer.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Cancel offer and get refund
-    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
-        let offer = &mut ctx.accounts.offer;
+    /// Unwind a staked escrow early (anyone can call)
+    /// Releases and refunds unwind it themselves; see unwind_escrow_stake for the yield
+    /// split and how a shortfall is charged to the sale.
+    pub fn unstake_escrow(ctx: Context<UnstakeEscrow>) -> Result<()> {
+        let clock = Clock::get()?;
+        let stake_accounts = [
+            ctx.accounts.escrow_stake.to_account_info(),
+            ctx.accounts.stake_vault.to_account_info(),
+            ctx.accounts.pool_tokens.to_account_info(),
+            ctx.accounts.stake_pool.to_account_info(),
+            ctx.accounts.withdraw_authority.to_account_info(),
+            ctx.accounts.reserve_stake.to_account_info(),
+            ctx.accounts.manager_fee_account.to_account_info(),
+            ctx.accounts.pool_mint.to_account_info(),
+            ctx.accounts.config.to_account_info(),
+            ctx.accounts.buyer.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.rent_recipient.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+            ctx.accounts.stake_pool_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ];
+        unwind_escrow_stake(
+            &stake_accounts,
+            &mut ctx.accounts.escrow,
+            &mut ctx.accounts.transaction,
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.caller.key(),
+            clock.unix_timestamp,
+        )
+    }
+
+    /// Set up revenue-share payments on a sale (buyer and seller both sign)
+    /// `total_periods` payments of `amount_per_period`, one per `period_seconds` starting
+    /// now. The buyer funds periods ahead with fund_recurring_period; each releases to the
+    /// seller once it has run. The listing's locked platform fee applies to every release.
+    pub fn create_recurring_escrow(
+        ctx: Context<CreateRecurringEscrow>,
+        amount_per_period: u64,
+        period_seconds: i64,
+        total_periods: u16,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
+
+        let transaction = &ctx.accounts.transaction;
         let clock = Clock::get()?;
 
-        // SECURITY: Verify offer belongs to this listing
         require!(
-            offer.listing == ctx.accounts.listing.key(),
-            AppMarketError::InvalidOffer
+            ctx.accounts.buyer.key() == transaction.buyer,
+            AppMarketError::NotBuyer
         );
-
-        // Validations
         require!(
-            ctx.accounts.buyer.key() == offer.buyer,
-            AppMarketError::NotOfferOwner
+            ctx.accounts.seller.key() == transaction.seller,
+            AppMarketError::NotSeller
         );
         require!(
-            offer.status == OfferStatus::Active,
-            AppMarketError::OfferNotActive
+            !matches!(
+                transaction.status,
+                TransactionStatus::Pending | TransactionStatus::Refunded | TransactionStatus::Cancelled
+            ),
+            AppMarketError::InvalidTransactionStatus
         );
+        require!(
+            amount_per_period > 0
+                && (MIN_RECURRING_PERIOD_SECONDS..=MAX_RECURRING_PERIOD_SECONDS).contains(&period_seconds)
+                && (1..=MAX_RECURRING_PERIODS).contains(&total_periods),
+            AppMarketError::InvalidRecurringTerms
+        );
+
+        let recurring = &mut ctx.accounts.recurring_escrow;
+        recurring.version = ACCOUNT_LAYOUT_VERSION;
+        recurring.transaction = transaction.key();
+        recurring.listing = ctx.accounts.listing.key();
+        recurring.buyer = transaction.buyer;
+        recurring.seller = transaction.seller;
+        recurring.arbitrator = transaction.designated_arbitrator();
+        recurring.amount_per_period = amount_per_period;
+        recurring.period_seconds = period_seconds;
+        recurring.total_periods = total_periods;
+        recurring.funded_periods = 0;
+        recurring.settled_periods = 0;
+        recurring.platform_fee_bps = ctx.accounts.listing.platform_fee_bps;
+        recurring.start_time = clock.unix_timestamp;
+        recurring.status = RecurringStatus::Active;
+        recurring.disputed_period = None;
+        recurring.rent_payer = ctx.accounts.payer.key();
+        recurring.bump = ctx.bumps.recurring_escrow;
+
+        emit!(RecurringEscrowCreated {
+            recurring_escrow: recurring.key(),
+            transaction: recurring.transaction,
+            buyer: recurring.buyer,
+            seller: recurring.seller,
+            amount_per_period,
+            period_seconds,
+            total_periods,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-        // Update offer status
-        offer.status = OfferStatus::Cancelled;
+        Ok(())
+    }
 
-        // Update consecutive offer tracking when buyer cancels
-        let listing = &mut ctx.accounts.listing;
-        if let Some(last_buyer) = listing.last_offer_buyer {
-            if last_buyer == ctx.accounts.buyer.key() && listing.consecutive_offer_count > 0 {
-                // Decrement the consecutive count since this buyer cancelled
-                listing.consecutive_offer_count = listing.consecutive_offer_count.saturating_sub(1);
-            }
-        }
+    /// Pay in the next unfunded recurring period (buyer only; periods may be funded early)
+    pub fn fund_recurring_period(ctx: Context<FundRecurringPeriod>) -> Result<()> {
+        let recurring = &ctx.accounts.recurring_escrow;
+        let clock = Clock::get()?;
 
-        // SECURITY: Validate escrow balance
-        let escrow_balance = ctx.accounts.offer_escrow.to_account_info().lamports();
-        let rent = Rent::get()?.minimum_balance(
-            ctx.accounts.offer_escrow.to_account_info().data_len()
+        require!(
+            recurring.status == RecurringStatus::Active,
+            AppMarketError::RecurringEscrowNotActive
         );
         require!(
-            escrow_balance >= offer.amount + rent,
-            AppMarketError::InsufficientEscrowBalance
+            recurring.funded_periods < recurring.total_periods,
+            AppMarketError::AllPeriodsFunded
         );
 
-        // Refund buyer (escrow will be closed, rent returned to buyer)
-        let seeds = &[
-            b"offer_escrow",
-            offer.to_account_info().key.as_ref(),
-            &[ctx.accounts.offer_escrow.bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        let cpi_ctx = CpiContext::new_with_signer(
+        let amount = recurring.amount_per_period;
+        let cpi_ctx = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
-                from: ctx.accounts.offer_escrow.to_account_info(),
-                to: ctx.accounts.buyer.to_account_info(),
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.recurring_escrow.to_account_info(),
             },
-            signer,
         );
-        anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
 
-        emit!(OfferCancelled {
-            offer: offer.key(),
-            listing: ctx.accounts.listing.key(),
-            buyer: offer.buyer,
+        let recurring = &mut ctx.accounts.recurring_escrow;
+        let period = recurring.funded_periods;
+        recurring.funded_periods += 1;
+
+        emit!(RecurringPeriodFunded {
+            recurring_escrow: recurring.key(),
+            period,
+            amount,
+            actor: ctx.accounts.buyer.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Claim expired offer refund
-    /// Expire an offer after deadline (anyone can call, refund goes to buyer)
-    pub fn expire_offer(ctx: Context<ExpireOffer>) -> Result<()> {
-        let offer = &mut ctx.accounts.offer;
+    /// Release the next recurring period to the seller once it has run (anyone can call)
+    pub fn release_recurring_period(ctx: Context<ReleaseRecurringPeriod>) -> Result<()> {
         let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
+
+        let recurring = &mut ctx.accounts.recurring_escrow;
+        let period = recurring.settled_periods;
 
-        // SECURITY: Verify offer belongs to this listing
         require!(
-            offer.listing == ctx.accounts.listing.key(),
-            AppMarketError::InvalidOffer
+            recurring.status != RecurringStatus::Completed,
+            AppMarketError::RecurringEscrowNotActive
         );
-
-        // Validations
         require!(
-            offer.status == OfferStatus::Active,
-            AppMarketError::OfferNotActive
+            period < recurring.funded_periods,
+            AppMarketError::RecurringPeriodNotFunded
         );
         require!(
-            clock.unix_timestamp > offer.deadline,
-            AppMarketError::OfferNotExpired
+            clock.unix_timestamp >= recurring.due_at(period)?,
+            AppMarketError::RecurringPeriodNotDue
         );
-        // SECURITY: Only offer owner (buyer) can expire their own offer
         require!(
-            ctx.accounts.caller.key() == offer.buyer,
-            AppMarketError::NotOfferOwner
+            recurring.disputed_period.is_none(),
+            AppMarketError::RecurringPeriodDisputed
         );
 
-        // Update offer status
-        offer.status = OfferStatus::Expired;
+        pay_recurring_period(
+            recurring,
+            &ctx.accounts.seller,
+            &ctx.accounts.treasury,
+            ctx.accounts.caller.key(),
+            clock.unix_timestamp,
+        )
+    }
 
-        // Update consecutive offer tracking when offer expires
-        let listing = &mut ctx.accounts.listing;
-        if let Some(last_buyer) = listing.last_offer_buyer {
-            if last_buyer == offer.buyer && listing.consecutive_offer_count > 0 {
-                // Decrement the consecutive count since this offer expired
-                listing.consecutive_offer_count = listing.consecutive_offer_count.saturating_sub(1);
-            }
-        }
+    /// Waive the next recurring period (seller only); refunds it if already funded
+    pub fn skip_recurring_period(ctx: Context<SkipRecurringPeriod>) -> Result<()> {
+        let recurring = &mut ctx.accounts.recurring_escrow;
+        let clock = Clock::get()?;
+        let period = recurring.settled_periods;
 
-        // SECURITY: Validate escrow balance
-        let escrow_balance = ctx.accounts.offer_escrow.to_account_info().lamports();
-        let rent = Rent::get()?.minimum_balance(
-            ctx.accounts.offer_escrow.to_account_info().data_len()
+        require!(
+            recurring.status != RecurringStatus::Completed,
+            AppMarketError::RecurringEscrowNotActive
         );
         require!(
-            escrow_balance >= offer.amount + rent,
-            AppMarketError::InsufficientEscrowBalance
+            recurring.disputed_period.is_none(),
+            AppMarketError::RecurringPeriodDisputed
         );
 
-        // Refund buyer
-        let seeds = &[
-            b"offer_escrow",
-            offer.to_account_info().key.as_ref(),
-            &[ctx.accounts.offer_escrow.bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.offer_escrow.to_account_info(),
-                to: ctx.accounts.buyer.to_account_info(),
-            },
-            signer,
-        );
-        anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+        let refunded = if period < recurring.funded_periods {
+            recurring.sub_lamports(recurring.amount_per_period)?;
+            ctx.accounts.buyer.add_lamports(recurring.amount_per_period)?;
+            recurring.amount_per_period
+        } else {
+            // Waived before funding: the buyer never owes it
+            recurring.funded_periods += 1;
+            0
+        };
+        settle_recurring_period(recurring);
 
-        emit!(OfferExpired {
-            offer: offer.key(),
-            listing: ctx.accounts.listing.key(),
-            buyer: offer.buyer,
+        emit!(RecurringPeriodSkipped {
+            recurring_escrow: recurring.key(),
+            period,
+            refunded,
+            actor: ctx.accounts.seller.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Accept offer (seller only)
-    pub fn accept_offer(ctx: Context<AcceptOffer>) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::ContractPaused);
-
-        let listing = &mut ctx.accounts.listing;
-        let offer = &mut ctx.accounts.offer;
-        let clock = Clock::get()?;
+    /// Hold the next funded recurring period back from release (buyer or seller)
+    /// Only that period is frozen; resolve_recurring_dispute settles it either way.
+    pub fn dispute_recurring_period(
+        ctx: Context<DisputeRecurringPeriod>,
+        reason: String,
+    ) -> Result<()> {
+        let recurring = &mut ctx.accounts.recurring_escrow;
+        let party = ctx.accounts.party.key();
+        let period = recurring.settled_periods;
 
-        // Validations
         require!(
-            ctx.accounts.seller.key() == listing.seller,
-            AppMarketError::NotSeller
+            party == recurring.buyer || party == recurring.seller,
+            AppMarketError::NotPartyToTransaction
         );
+        require!(reason.len() <= 500, AppMarketError::DisputeReasonTooLong);
         require!(
-            listing.status == ListingStatus::Active,
-            AppMarketError::ListingNotActive
+            recurring.status != RecurringStatus::Completed,
+            AppMarketError::RecurringEscrowNotActive
         );
         require!(
-            offer.status == OfferStatus::Active,
-            AppMarketError::OfferNotActive
+            recurring.disputed_period.is_none(),
+            AppMarketError::RecurringPeriodDisputed
         );
         require!(
-            clock.unix_timestamp <= offer.deadline,
-            AppMarketError::OfferExpired
+            period < recurring.funded_periods,
+            AppMarketError::RecurringPeriodNotFunded
         );
 
-        // SECURITY: Store old values before updating
-        let old_bid = listing.current_bid;
-        let old_bidder = listing.current_bidder;
+        recurring.disputed_period = Some(period);
 
-        // Update statuses
-        offer.status = OfferStatus::Accepted;
-        listing.status = ListingStatus::Sold;
-        listing.current_bid = offer.amount;
-        listing.current_bidder = Some(offer.buyer);
+        emit!(RecurringPeriodDisputed {
+            recurring_escrow: recurring.key(),
+            period,
+            reason,
+            actor: party,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Reset consecutive offer tracking since listing is now sold
-        listing.last_offer_buyer = None;
-        listing.consecutive_offer_count = 0;
+        Ok(())
+    }
 
-        // Transfer funds from offer escrow to listing escrow
-        let offer_escrow_balance = ctx.accounts.offer_escrow.to_account_info().lamports();
-        let rent = Rent::get()?.minimum_balance(
-            ctx.accounts.offer_escrow.to_account_info().data_len()
+    /// Settle a disputed recurring period (admin, or the sale's designated arbitrator):
+    /// release it to the seller or refund it to the buyer
+    pub fn resolve_recurring_dispute(
+        ctx: Context<ResolveRecurringDispute>,
+        release_to_seller: bool,
+    ) -> Result<()> {
+        let recurring = &mut ctx.accounts.recurring_escrow;
+        let clock = Clock::get()?;
+
+        if let Some(arbitrator) = recurring.arbitrator {
+            require!(ctx.accounts.admin.key() == arbitrator, AppMarketError::NotArbitrator);
+        } else {
+            require!(ctx.accounts.admin.key() == ctx.accounts.config.admin, AppMarketError::NotAdmin);
+        }
+        let period = recurring.disputed_period
+            .take()
+            .ok_or(AppMarketError::NoRecurringDispute)?;
+
+        if release_to_seller {
+            pay_recurring_period(
+                recurring,
+                &ctx.accounts.seller,
+                &ctx.accounts.treasury,
+                ctx.accounts.admin.key(),
+                clock.unix_timestamp,
+            )?;
+        } else {
+            recurring.sub_lamports(recurring.amount_per_period)?;
+            ctx.accounts.buyer.add_lamports(recurring.amount_per_period)?;
+            settle_recurring_period(recurring);
+        }
+
+        emit!(RecurringDisputeResolved {
+            recurring_escrow: recurring.key(),
+            period,
+            released_to_seller: release_to_seller,
+            actor: ctx.accounts.admin.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// End a recurring escrow early (buyer or seller)
+    /// Periods already due (and any disputed one) still settle as usual; funded periods
+    /// that have not started running are refunded to the buyer.
+    pub fn terminate_recurring_escrow(ctx: Context<TerminateRecurringEscrow>) -> Result<()> {
+        let recurring = &mut ctx.accounts.recurring_escrow;
+        let party = ctx.accounts.party.key();
+        let clock = Clock::get()?;
+
+        require!(
+            party == recurring.buyer || party == recurring.seller,
+            AppMarketError::NotPartyToTransaction
         );
         require!(
-            offer_escrow_balance >= offer.amount + rent,
-            AppMarketError::InsufficientEscrowBalance
+            recurring.status == RecurringStatus::Active,
+            AppMarketError::RecurringEscrowNotActive
         );
 
-        let seeds = &[
-            b"offer_escrow",
-            offer.to_account_info().key.as_ref(),
-            &[ctx.accounts.offer_escrow.bump],
-        ];
-        let signer = &[&seeds[..]];
+        let disputed = recurring.disputed_period.map_or(0, |period| period + 1);
+        let total_periods = recurring.periods_due(clock.unix_timestamp)
+            .max(recurring.settled_periods)
+            .max(disputed);
+        let refund_periods = recurring.funded_periods.saturating_sub(total_periods);
+        let refunded = recurring.amount_per_period
+            .checked_mul(refund_periods as u64)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.offer_escrow.to_account_info(),
-                to: ctx.accounts.listing_escrow.to_account_info(),
-            },
-            signer,
-        );
-        anchor_lang::system_program::transfer(cpi_ctx, offer.amount)?;
+        recurring.funded_periods -= refund_periods;
+        recurring.total_periods = total_periods;
+        recurring.status = if recurring.settled_periods == total_periods {
+            RecurringStatus::Completed
+        } else {
+            RecurringStatus::Terminated
+        };
+        if refunded > 0 {
+            recurring.sub_lamports(refunded)?;
+            ctx.accounts.buyer.add_lamports(refunded)?;
+        }
 
-        // Update listing escrow tracking
-        ctx.accounts.listing_escrow.amount = ctx.accounts.listing_escrow.amount
-            .checked_add(offer.amount)
-            .ok_or(AppMarketError::MathOverflow)?;
+        emit!(RecurringEscrowTerminated {
+            recurring_escrow: recurring.key(),
+            total_periods,
+            refunded,
+            actor: party,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // SECURITY FIX M-3: Only create withdrawal account when there's a previous bidder
-        // (prevents unnecessary account creation and rent waste)
-        if let Some(previous_bidder) = old_bidder {
-            if previous_bidder != offer.buyer && old_bid > 0 {
-                // Increment withdrawal counter to prevent PDA collision
-                listing.withdrawal_count = listing.withdrawal_count
-                    .checked_add(1)
-                    .ok_or(AppMarketError::MathOverflow)?;
+        Ok(())
+    }
 
-                // Derive PDA and verify
-                let listing_key = listing.key();
-                let withdrawal_count_bytes = listing.withdrawal_count.to_le_bytes();
-                let withdrawal_seeds = &[
-                    b"withdrawal",
-                    listing_key.as_ref(),
-                    &withdrawal_count_bytes,
-                ];
-                let (withdrawal_pda, bump) = Pubkey::find_program_address(
-                    withdrawal_seeds,
-                    ctx.program_id
-                );
+    /// Close a recurring escrow once every period has settled (anyone can call)
+    pub fn close_recurring_escrow(ctx: Context<CloseRecurringEscrow>) -> Result<()> {
+        require!(
+            ctx.accounts.recurring_escrow.status == RecurringStatus::Completed,
+            AppMarketError::RecurringPeriodsOutstanding
+        );
 
-                require!(
-                    withdrawal_pda == ctx.accounts.pending_withdrawal.key(),
-                    AppMarketError::InvalidPreviousBidder
-                );
+        emit!(RecurringEscrowClosed {
+            recurring_escrow: ctx.accounts.recurring_escrow.key(),
+            actor: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-                // Create the withdrawal account
-                let rent = Rent::get()?;
-                let space = 8 + PendingWithdrawal::INIT_SPACE;
-                let lamports = rent.minimum_balance(space);
+        Ok(())
+    }
 
-                anchor_lang::system_program::create_account(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::CreateAccount {
-                            from: ctx.accounts.seller.to_account_info(),
-                            to: ctx.accounts.pending_withdrawal.to_account_info(),
-                        },
-                    ),
-                    lamports,
-                    space as u64,
-                    ctx.program_id,
-                )?;
+    /// Offer a listing rent-to-own (seller or operator, before any bid)
+    /// A buyer pays `upfront_amount` to take operational control, then `installments`
+    /// payments of `installment_amount` one `period_seconds` apart. On default the seller
+    /// keeps `forfeiture_bps` of what was paid and the buyer gets the rest back.
+    /// The seller must hold the listing's AppDeed (issue_app_deed); it moves with the sale.
+    #[allow(clippy::too_many_arguments)]
+    pub fn offer_rent_to_own(
+        ctx: Context<OfferRentToOwn>,
+        upfront_amount: u64,
+        installment_amount: u64,
+        installments: u16,
+        period_seconds: i64,
+        forfeiture_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-                // Initialize withdrawal data
-                let mut withdrawal_data = ctx.accounts.pending_withdrawal.try_borrow_mut_data()?;
-                let withdrawal = PendingWithdrawal {
-                    user: previous_bidder,
-                    listing: listing.key(),
-                    amount: old_bid,
-                    withdrawal_id: listing.withdrawal_count,
-                    created_at: clock.unix_timestamp,
-                    expires_at: clock.unix_timestamp + 3600, // 1 hour
-                    bump,
-                };
+        let listing = &ctx.accounts.listing;
+        let clock = Clock::get()?;
 
-                withdrawal.try_serialize(&mut &mut withdrawal_data[..])?;
+        require!(
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
+        );
+        require!(
+            listing.status == ListingStatus::Active,
+            AppMarketError::ListingNotActive
+        );
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
+        require!(listing.payment_mint.is_none(), AppMarketError::InvalidPaymentMint);
+        require!(
+            ctx.accounts.app_deed.holder == listing.seller,
+            AppMarketError::NotDeedHolder
+        );
+        require!(
+            upfront_amount > 0
+                && installment_amount > 0
+                && (1..=MAX_RECURRING_PERIODS).contains(&installments)
+                && (MIN_RECURRING_PERIOD_SECONDS..=MAX_RECURRING_PERIOD_SECONDS).contains(&period_seconds)
+                && forfeiture_bps as u64 <= BASIS_POINTS_DIVISOR,
+            AppMarketError::InvalidRentToOwnTerms
+        );
+        // SECURITY: The full price must fit in u64 and under the price cap
+        let total_price = installment_amount
+            .checked_mul(installments as u64)
+            .and_then(|installments_total| installments_total.checked_add(upfront_amount))
+            .ok_or(AppMarketError::MathOverflow)?;
+        check_price_cap(&ctx.accounts.config, None, total_price)?;
+
+        let rent_to_own = &mut ctx.accounts.rent_to_own;
+        rent_to_own.version = ACCOUNT_LAYOUT_VERSION;
+        rent_to_own.listing = listing.key();
+        rent_to_own.seller = listing.seller;
+        rent_to_own.buyer = None;
+        rent_to_own.upfront_amount = upfront_amount;
+        rent_to_own.installment_amount = installment_amount;
+        rent_to_own.installments = installments;
+        rent_to_own.period_seconds = period_seconds;
+        rent_to_own.forfeiture_bps = forfeiture_bps;
+        rent_to_own.installments_paid = 0;
+        rent_to_own.total_paid = 0;
+        rent_to_own.start_time = 0;
+        rent_to_own.status = RentToOwnStatus::Offered;
+        rent_to_own.deed = ctx.accounts.app_deed.key();
+        rent_to_own.rent_payer = ctx.accounts.payer.key();
+        rent_to_own.bump = ctx.bumps.rent_to_own;
+
+        emit!(RentToOwnOffered {
+            rent_to_own: rent_to_own.key(),
+            listing: listing.key(),
+            upfront_amount,
+            installment_amount,
+            installments,
+            period_seconds,
+            forfeiture_bps,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-                emit!(WithdrawalCreated {
-                    user: previous_bidder,
-                    listing: listing.key(),
-                    amount: old_bid,
-                    withdrawal_id: listing.withdrawal_count,
-                    timestamp: clock.unix_timestamp,
-                });
-            }
-        }
+        Ok(())
+    }
 
-        // Create transaction record
-        let transaction = &mut ctx.accounts.transaction;
-        transaction.listing = listing.key();
-        transaction.seller = listing.seller;
-        transaction.buyer = offer.buyer;
-        transaction.sale_price = offer.amount;
+    /// Take a listing rent-to-own: pay the upfront amount and take operational control
+    /// The listing leaves the market (InEscrow) and the deed moves into the rent-to-own PDA.
+    pub fn start_rent_to_own(ctx: Context<StartRentToOwn>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-        // SECURITY: Use LOCKED fees from listing
-        transaction.platform_fee = offer.amount
-            .checked_mul(listing.platform_fee_bps)
-            .ok_or(AppMarketError::MathOverflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
-            .ok_or(AppMarketError::MathOverflow)?;
-        transaction.seller_proceeds = offer.amount
-            .checked_sub(transaction.platform_fee)
-            .ok_or(AppMarketError::MathOverflow)?;
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
+        let buyer_key = ctx.accounts.buyer.key();
 
-        transaction.status = TransactionStatus::InEscrow;
-        transaction.transfer_deadline = clock.unix_timestamp
-            .checked_add(TRANSFER_DEADLINE_SECONDS)
-            .ok_or(AppMarketError::MathOverflow)?;
-        transaction.created_at = clock.unix_timestamp;
-        transaction.seller_confirmed_transfer = false;
-        transaction.seller_confirmed_at = None;
-        transaction.completed_at = None;
-        transaction.bump = ctx.bumps.transaction;
+        require!(
+            ctx.accounts.rent_to_own.status == RentToOwnStatus::Offered,
+            AppMarketError::RentToOwnNotOffered
+        );
+        require!(
+            listing.status == ListingStatus::Active,
+            AppMarketError::ListingNotActive
+        );
+        require!(clock.unix_timestamp < listing.end_time, AppMarketError::ListingExpired);
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        require!(buyer_key != listing.seller, AppMarketError::SellerCannotBuy);
+        require!(
+            ctx.accounts.app_deed.holder == listing.seller,
+            AppMarketError::NotDeedHolder
+        );
+
+        let upfront_amount = ctx.accounts.rent_to_own.upfront_amount;
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.rent_to_own.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, upfront_amount)?;
+
+        set_listing_status(
+            listing,
+            ListingStatus::InEscrow,
+            buyer_key,
+            clock.unix_timestamp,
+        )?;
+        listing.current_bidder = Some(buyer_key);
+        if listing.clear_best_offer() {
+            emit_best_offer_changed(listing, buyer_key, clock.unix_timestamp);
+        }
 
-        emit!(OfferAccepted {
-            offer: offer.key(),
+        let rent_to_own = &mut ctx.accounts.rent_to_own;
+        rent_to_own.buyer = Some(buyer_key);
+        rent_to_own.total_paid = upfront_amount;
+        rent_to_own.start_time = clock.unix_timestamp;
+        rent_to_own.status = RentToOwnStatus::Active;
+        ctx.accounts.app_deed.holder = rent_to_own.key();
+
+        emit!(AppDeedTransferred {
+            deed: rent_to_own.deed,
             listing: listing.key(),
-            transaction: transaction.key(),
-            buyer: offer.buyer,
-            seller: listing.seller,
-            amount: offer.amount,
+            from: listing.seller,
+            to: rent_to_own.key(),
+            actor: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
+
+        emit!(RentToOwnStarted {
+            rent_to_own: rent_to_own.key(),
+            listing: listing.key(),
+            buyer: buyer_key,
+            upfront_amount,
+            actor: buyer_key,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Open a dispute
-    pub fn open_dispute(
-        ctx: Context<OpenDispute>,
-        reason: String,
-    ) -> Result<()> {
-        require!(!ctx.accounts.config.paused, AppMarketError::PlatformPaused);
-
+    /// Pay the next rent-to-own installment (buyer only; installments may be paid early)
+    /// The final installment transfers the deed to the buyer and pays the seller.
+    /// Like default_rent_to_own, payments only stop when exits are paused: a trading pause
+    /// must not leave a buyer unable to pay while the installment goes overdue.
+    pub fn pay_rent_to_own_installment(ctx: Context<PayRentToOwnInstallment>) -> Result<()> {
         let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
 
-        // Validations
-        require!(ctx.accounts.transaction.status == TransactionStatus::InEscrow, AppMarketError::InvalidTransactionStatus);
-        require!(
-            ctx.accounts.initiator.key() == ctx.accounts.transaction.buyer ||
-            ctx.accounts.initiator.key() == ctx.accounts.transaction.seller,
-            AppMarketError::NotPartyToTransaction
-        );
-        require!(
-            ctx.accounts.treasury.key() == ctx.accounts.config.treasury,
-            AppMarketError::InvalidTreasury
-        );
-
-        // SECURITY: Dispute deadline - must open within 7 days of seller confirmation
-        // After deadline expires, buyer can no longer dispute and seller can finalize
-        if let Some(confirmed_at) = ctx.accounts.transaction.seller_confirmed_at {
-            require!(
-                clock.unix_timestamp <= confirmed_at + FINALIZE_GRACE_PERIOD,
-                AppMarketError::DisputeDeadlineExpired
-            );
-        }
-
-        // SECURITY: Pre-check initiator has sufficient balance for dispute fee
-        // Use the locked dispute fee from listing creation time, not the live config
-        // which could be changed by admin after the transaction was created
-        let dispute_fee = ctx.accounts.transaction.sale_price
-            .checked_mul(ctx.accounts.listing.dispute_fee_bps)
-            .ok_or(AppMarketError::MathOverflow)?
-            .checked_div(BASIS_POINTS_DIVISOR)
-            .ok_or(AppMarketError::MathOverflow)?;
+        let buyer_key = ctx.accounts.buyer.key();
+        let rent_to_own = &ctx.accounts.rent_to_own;
 
         require!(
-            ctx.accounts.initiator.lamports() >= dispute_fee,
-            AppMarketError::InsufficientBalance
+            rent_to_own.status == RentToOwnStatus::Active,
+            AppMarketError::RentToOwnNotActive
         );
+        require!(rent_to_own.buyer == Some(buyer_key), AppMarketError::NotBuyer);
 
-        // SECURITY: Hold dispute fee in Dispute PDA (refunded to buyer if they win)
+        let amount = rent_to_own.installment_amount;
         let cpi_ctx = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             anchor_lang::system_program::Transfer {
-                from: ctx.accounts.initiator.to_account_info(),
-                to: ctx.accounts.dispute.to_account_info(),
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.rent_to_own.to_account_info(),
             },
         );
-        anchor_lang::system_program::transfer(cpi_ctx, dispute_fee)?;
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
 
-        // Now take mutable references after CPI call
-        let transaction = &mut ctx.accounts.transaction;
-        let dispute = &mut ctx.accounts.dispute;
+        let rent_to_own = &mut ctx.accounts.rent_to_own;
+        let installment = rent_to_own.installments_paid;
+        rent_to_own.installments_paid += 1;
+        rent_to_own.total_paid = rent_to_own.total_paid
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-        // Update transaction status
-        transaction.status = TransactionStatus::Disputed;
+        emit!(RentToOwnInstallmentPaid {
+            rent_to_own: rent_to_own.key(),
+            installment,
+            amount,
+            actor: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
 
-        // Create dispute record
-        dispute.transaction = transaction.key();
-        dispute.initiator = ctx.accounts.initiator.key();
-        dispute.respondent = if ctx.accounts.initiator.key() == transaction.buyer {
-            transaction.seller
-        } else {
-            transaction.buyer
-        };
-        dispute.reason = reason.clone();
-        dispute.status = DisputeStatus::Open;
-        dispute.created_at = clock.unix_timestamp;
-        dispute.dispute_fee = dispute_fee;
-        dispute.bump = ctx.bumps.dispute;
+        if rent_to_own.installments_paid < rent_to_own.installments {
+            return Ok(());
+        }
 
-        emit!(DisputeOpened {
-            dispute: dispute.key(),
-            transaction: transaction.key(),
-            initiator: dispute.initiator,
-            reason,
+        // Paid in full: deed to the buyer, payments (less the locked platform fee) to the seller
+        let listing = &mut ctx.accounts.listing;
+        let total_paid = rent_to_own.total_paid;
+        let (seller_amount, platform_fee, _) = split_rent_to_own_payments(
+            total_paid,
+            BASIS_POINTS_DIVISOR as u16,
+            listing.platform_fee_bps,
+        )?;
+
+        rent_to_own.sub_lamports(total_paid)?;
+        ctx.accounts.payout_wallet.add_lamports(seller_amount)?;
+        ctx.accounts.treasury.add_lamports(platform_fee)?;
+        rent_to_own.status = RentToOwnStatus::Completed;
+        ctx.accounts.app_deed.holder = buyer_key;
+
+        emit!(AppDeedTransferred {
+            deed: rent_to_own.deed,
+            listing: listing.key(),
+            from: rent_to_own.key(),
+            to: buyer_key,
+            actor: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
+
+        set_listing_status(
+            listing,
+            ListingStatus::Completed,
+            buyer_key,
+            clock.unix_timestamp,
+        )?;
+        listing.current_bid = total_paid;
+
+        emit!(RentToOwnCompleted {
+            rent_to_own: rent_to_own.key(),
+            listing: listing.key(),
+            buyer: buyer_key,
+            seller_amount,
+            platform_fee,
+            actor: buyer_key,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Resolve dispute (admin only)
-    /// Propose dispute resolution (starts 48hr timelock)
-    /// SECURITY: Resolution is not executed immediately - parties can contest
-    pub fn propose_dispute_resolution(
-        ctx: Context<ProposeDisputeResolution>,
-        resolution: DisputeResolution,
-        notes: String,
-    ) -> Result<()> {
-        let transaction = &ctx.accounts.transaction;
-        let dispute = &mut ctx.accounts.dispute;
+    /// Call a rent-to-own default: anyone once an installment is past its grace period,
+    /// or the buyer walking away at any time. The deed returns to the seller, who keeps
+    /// forfeiture_bps of the payments (less the platform fee on that share); the buyer is
+    /// refunded the rest and the listing goes back on the market (Active) for the seller,
+    /// with a fresh sale window of its original duration.
+    pub fn default_rent_to_own(ctx: Context<DefaultRentToOwn>) -> Result<()> {
         let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
 
-        // Validations
-        require!(ctx.accounts.admin.key() == ctx.accounts.config.admin, AppMarketError::NotAdmin);
-        require!(dispute.status == DisputeStatus::Open || dispute.status == DisputeStatus::UnderReview, AppMarketError::DisputeNotOpen);
-
-        // SECURITY: Validate partial refund amounts upfront
-        if let DisputeResolution::PartialRefund { buyer_amount, seller_amount } = &resolution {
-            require!(*buyer_amount > 0 || *seller_amount > 0, AppMarketError::InvalidRefundAmounts);
-            let total_refund = (*buyer_amount)
-                .checked_add(*seller_amount)
+        let rent_to_own = &mut ctx.accounts.rent_to_own;
+        let caller = ctx.accounts.caller.key();
+
+        require!(
+            rent_to_own.status == RentToOwnStatus::Active,
+            AppMarketError::RentToOwnNotActive
+        );
+        let buyer = rent_to_own.buyer.ok_or(AppMarketError::RentToOwnNotActive)?;
+        require_keys_eq!(ctx.accounts.buyer.key(), buyer, AppMarketError::InvalidBuyer);
+        if caller != buyer {
+            let overdue_at = rent_to_own.due_at(rent_to_own.installments_paid)?
+                .checked_add(RENT_TO_OWN_GRACE_SECONDS)
                 .ok_or(AppMarketError::MathOverflow)?;
             require!(
-                total_refund == transaction.sale_price,
-                AppMarketError::PartialRefundMustEqualSalePrice
+                clock.unix_timestamp > overdue_at,
+                AppMarketError::InstallmentNotOverdue
             );
-
-            dispute.pending_buyer_amount = Some(*buyer_amount);
-            dispute.pending_seller_amount = Some(*seller_amount);
-        } else {
-            dispute.pending_buyer_amount = None;
-            dispute.pending_seller_amount = None;
         }
 
-        // Store pending resolution (starts 48hr timelock)
-        dispute.pending_resolution = Some(resolution.clone());
-        dispute.pending_resolution_at = Some(clock.unix_timestamp);
-        dispute.contested = false;
-        dispute.status = DisputeStatus::UnderReview;
-        dispute.resolution_notes = Some(notes.clone());
+        let listing = &mut ctx.accounts.listing;
+        let total_paid = rent_to_own.total_paid;
+        let (seller_amount, platform_fee, buyer_refund) = split_rent_to_own_payments(
+            total_paid,
+            rent_to_own.forfeiture_bps,
+            listing.platform_fee_bps,
+        )?;
+
+        rent_to_own.sub_lamports(total_paid)?;
+        ctx.accounts.payout_wallet.add_lamports(seller_amount)?;
+        ctx.accounts.treasury.add_lamports(platform_fee)?;
+        ctx.accounts.buyer.add_lamports(buyer_refund)?;
+        rent_to_own.status = RentToOwnStatus::Defaulted;
+        ctx.accounts.app_deed.holder = listing.seller;
+
+        set_listing_status(
+            listing,
+            ListingStatus::Active,
+            caller,
+            clock.unix_timestamp,
+        )?;
+        listing.current_bidder = None;
+        listing.current_bid_exposure = 0;
+        // The original window has usually run out during the installments
+        listing.start_time = clock.unix_timestamp;
+        listing.end_time = listing.sale_window_end(clock.unix_timestamp, listing.duration_seconds)?;
 
-        let executable_at = clock.unix_timestamp + DISPUTE_RESOLUTION_TIMELOCK_SECONDS;
+        emit!(AppDeedTransferred {
+            deed: rent_to_own.deed,
+            listing: listing.key(),
+            from: rent_to_own.key(),
+            to: listing.seller,
+            actor: caller,
+            timestamp: clock.unix_timestamp,
+        });
 
-        emit!(DisputeResolutionProposed {
-            dispute: dispute.key(),
-            resolution,
-            buyer_amount: dispute.pending_buyer_amount.unwrap_or(0),
-            seller_amount: dispute.pending_seller_amount.unwrap_or(0),
-            executable_at,
+        emit!(RentToOwnDefaulted {
+            rent_to_own: rent_to_own.key(),
+            listing: listing.key(),
+            buyer,
+            seller_amount,
+            platform_fee,
+            buyer_refund,
+            actor: caller,
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Contest dispute resolution (within 48hr window)
-    /// SECURITY: Either party can contest - emits event for admin review
-    pub fn contest_dispute_resolution(ctx: Context<ContestDisputeResolution>) -> Result<()> {
-        let transaction = &ctx.accounts.transaction;
-        let dispute = &mut ctx.accounts.dispute;
-        let clock = Clock::get()?;
+    /// Close a rent-to-own account: anyone once it completed or defaulted, or the seller
+    /// withdrawing terms no buyer has taken
+    pub fn close_rent_to_own(ctx: Context<CloseRentToOwn>) -> Result<()> {
+        let rent_to_own = &ctx.accounts.rent_to_own;
+        match rent_to_own.status {
+            RentToOwnStatus::Completed | RentToOwnStatus::Defaulted => {},
+            RentToOwnStatus::Offered => require!(
+                ctx.accounts.caller.key() == rent_to_own.seller,
+                AppMarketError::NotSeller
+            ),
+            RentToOwnStatus::Active => return Err(AppMarketError::RentToOwnOpen.into()),
+        }
 
-        // Must be buyer or seller
-        let caller = ctx.accounts.caller.key();
-        require!(
-            caller == transaction.buyer || caller == transaction.seller,
-            AppMarketError::NotPartyToTransaction
-        );
+        emit!(RentToOwnClosed {
+            rent_to_own: rent_to_own.key(),
+            listing: rent_to_own.listing,
+            actor: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-        // Must have pending resolution
-        require!(
-            dispute.pending_resolution.is_some(),
-            AppMarketError::NoPendingChange
-        );
+        Ok(())
+    }
 
-        // Must be within timelock window
-        let proposed_at = dispute.pending_resolution_at.unwrap();
-        require!(
-            clock.unix_timestamp < proposed_at + DISPUTE_RESOLUTION_TIMELOCK_SECONDS,
-            AppMarketError::TimelockNotExpired
-        );
+    /// Stop taking offers on an open-offers listing (seller only, no sale in progress)
+    /// The listing ends rather than cancels, so standing offers can still be accepted
+    /// through accept_offer_on_expired and new ones made through make_offer_on_expired.
+    pub fn close_open_listing(ctx: Context<CloseOpenListing>) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
 
-        // Cannot contest twice
+        require!(listing.is_open_ended(), AppMarketError::NotOpenOffersListing);
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(ctx.accounts.seller.key() == listing.seller, AppMarketError::NotSeller);
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
         require!(
-            !dispute.contested,
-            AppMarketError::AlreadyContested
+            ctx.accounts.escrow.amount == 0,
+            AppMarketError::PendingWithdrawalsExist
         );
 
-        dispute.contested = true;
-
-        emit!(DisputeContested {
-            dispute: dispute.key(),
-            contested_by: caller,
+        listing.end_time = clock.unix_timestamp;
+        set_listing_status(
+            listing,
+            ListingStatus::Ended,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(OpenListingClosed {
+            listing: listing.key(),
+            actor: ctx.accounts.seller.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Execute dispute resolution (after 48hr timelock)
-    /// SECURITY: If contested, admin must re-propose new resolution
-    pub fn execute_dispute_resolution(ctx: Context<ExecuteDisputeResolution>) -> Result<()> {
-        let clock = Clock::get()?;
-
-        // SECURITY: Only admin can resolve disputes
-        require!(
-            ctx.accounts.caller.key() == ctx.accounts.config.admin,
-            AppMarketError::Unauthorized
-        );
+    /// Price the listing's buy-now in other currencies too (seller or operator, before any sale)
+    /// Each entry is a fixed price in raw units of its mint; purchases paid in that currency
+    /// charge it instead of buy_now_price. Auctions keep one currency so bids stay comparable.
+    /// An empty list goes back to payment_mint only.
+    pub fn set_accepted_currencies(
+        ctx: Context<SetAcceptedCurrencies>,
+        currencies: Vec<CurrencyPrice>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-        // Must have pending resolution
-        require!(
-            ctx.accounts.dispute.pending_resolution.is_some(),
-            AppMarketError::NoPendingChange
-        );
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
 
-        // Cannot execute if contested
         require!(
-            !ctx.accounts.dispute.contested,
-            AppMarketError::AlreadyContested
+            listing.can_manage(ctx.accounts.seller.key()),
+            AppMarketError::NotSeller
         );
-
-        // Timelock must have expired
-        let proposed_at = ctx.accounts.dispute.pending_resolution_at.unwrap();
         require!(
-            clock.unix_timestamp >= proposed_at + DISPUTE_RESOLUTION_TIMELOCK_SECONDS,
-            AppMarketError::DisputeTimelockNotExpired
+            matches!(listing.status, ListingStatus::Active | ListingStatus::Draft),
+            AppMarketError::ListingNotActive
         );
+        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+        if !currencies.is_empty() {
+            require!(
+                listing.listing_type != ListingType::Auction,
+                AppMarketError::InvalidAcceptedCurrencies
+            );
+            require!(listing.buy_now_price.is_some(), AppMarketError::BuyNowNotEnabled);
+        }
+        validate_accepted_currencies(listing, &currencies)?;
+
+        // SECURITY: The SOL price is what purchases actually charge, so it is capped too
+        if let Some(sol) = currencies.iter().find(|c| c.mint == Pubkey::default()) {
+            check_price_cap(
+                &ctx.accounts.config,
+                ctx.accounts.price_cap_override.as_deref(),
+                sol.price,
+            )?;
+        }
 
-        require!(
-            ctx.accounts.treasury.key() == ctx.accounts.config.treasury,
-            AppMarketError::InvalidTreasury
-        );
-        require!(
-            ctx.accounts.buyer.key() == ctx.accounts.transaction.buyer,
-            AppMarketError::InvalidBuyer
-        );
-        require!(
-            ctx.accounts.seller.key() == ctx.accounts.transaction.seller,
-            AppMarketError::InvalidSeller
-        );
+        listing.accepted_currencies = currencies.clone();
 
-        let resolution = ctx.accounts.dispute.pending_resolution.clone().unwrap();
+        emit!(AcceptedCurrenciesSet {
+            listing: listing.key(),
+            currencies,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-        // Extract values needed for CPI before taking mutable references
-        let dispute_bump = ctx.accounts.dispute.bump;
-        let dispute_fee = ctx.accounts.dispute.dispute_fee;
-        let transaction_key = ctx.accounts.transaction.key();
-        let sale_price = ctx.accounts.transaction.sale_price;
-        let platform_fee = ctx.accounts.transaction.platform_fee;
-        let seller_proceeds = ctx.accounts.transaction.seller_proceeds;
+        Ok(())
+    }
 
-        // SECURITY: Validate escrow balance before any transfers
-        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
-        let rent = Rent::get()?.minimum_balance(
-            ctx.accounts.escrow.to_account_info().data_len()
+    /// Bound offer lifetimes (admin only): make_offer deadlines must fall between
+    /// `min_seconds` and `max_seconds` from now. Existing offers keep their deadlines.
+    pub fn set_offer_duration_bounds(
+        ctx: Context<SetOfferDurationBounds>,
+        min_seconds: i64,
+        max_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
-
-        // Allow dispute resolution even with pending withdrawals — escrow stays open for cleanup
         require!(
-            ctx.accounts.escrow.amount >= sale_price,
-            AppMarketError::InsufficientEscrowBalance
+            min_seconds > 0 && min_seconds <= max_seconds && max_seconds <= MAX_OFFER_DURATION_SECONDS,
+            AppMarketError::InvalidOfferDurationBounds
         );
 
-        let seeds = &[
-            b"escrow",
-            ctx.accounts.listing.to_account_info().key.as_ref(),
-            &[ctx.accounts.escrow.bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        match &resolution {
-            DisputeResolution::FullRefund => {
-                require!(
-                    escrow_balance >= sale_price + rent,
-                    AppMarketError::InsufficientEscrowBalance
-                );
+        let config = &mut ctx.accounts.config;
+        config.min_offer_duration_seconds = min_seconds;
+        config.max_offer_duration_seconds = max_seconds;
 
-                let cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.buyer.to_account_info(),
-                    },
-                    signer,
-                );
-                anchor_lang::system_program::transfer(cpi_ctx, sale_price)?;
+        emit!(OfferDurationBoundsUpdated {
+            min_seconds,
+            max_seconds,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-                ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-                    .checked_sub(sale_price)
-                    .ok_or(AppMarketError::MathOverflow)?;
+        Ok(())
+    }
 
-                ctx.accounts.transaction.status = TransactionStatus::Refunded;
-            },
-            DisputeResolution::ReleaseToSeller => {
-                let required_balance = platform_fee
-                    .checked_add(seller_proceeds)
-                    .ok_or(AppMarketError::MathOverflow)?;
-                require!(
-                    escrow_balance >= required_balance + rent,
-                    AppMarketError::InsufficientEscrowBalance
-                );
+    /// Settle an auction that ended with its high bid below the reserve (anyone)
+    /// The listing moves to ReserveNotMet and the high bidder's escrowed bid becomes a
+    /// PendingWithdrawal (bid-escrow listings: they reclaim their BidEscrow). The caller
+    /// funds the withdrawal rent and gets it back when the refund is paid out.
+    pub fn settle_failed_auction(ctx: Context<SettleFailedAuction>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
 
-                // Platform fee to treasury
-                let cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.treasury.to_account_info(),
-                    },
-                    signer,
-                );
-                anchor_lang::system_program::transfer(cpi_ctx, platform_fee)?;
+        let listing = &mut ctx.accounts.listing;
 
-                ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-                    .checked_sub(platform_fee)
-                    .ok_or(AppMarketError::MathOverflow)?;
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(
+            listing.listing_type == ListingType::Auction,
+            AppMarketError::NotAnAuction
+        );
+        require!(
+            clock.unix_timestamp >= listing.end_time,
+            AppMarketError::AuctionNotEnded
+        );
+        let bidder = listing.current_bidder.ok_or(AppMarketError::NoBidsToSettle)?;
+        let reserve = listing.reserve_price.ok_or(AppMarketError::NoReservePrice)?;
+        require!(listing.current_bid < reserve, AppMarketError::ReserveWasMet);
 
-                // Seller proceeds
-                let cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.escrow.to_account_info(),
-                        to: ctx.accounts.seller.to_account_info(),
-                    },
-                    signer,
-                );
-                anchor_lang::system_program::transfer(cpi_ctx, seller_proceeds)?;
+        let refund = if listing.uses_bid_escrows {
+            0
+        } else {
+            escrowed_bid_amount(listing.deposit_bps, listing.current_bid)?
+        };
 
-                ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-                    .checked_sub(seller_proceeds)
+        // EFFECTS
+        let high_bid = listing.current_bid;
+        set_listing_status(
+            listing,
+            ListingStatus::ReserveNotMet,
+            ctx.accounts.caller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        // SECURITY: Withdrawal pattern; the refund stays in the escrow until it is claimed
+        match ctx.accounts.pending_withdrawal.as_mut() {
+            Some(withdrawal) => {
+                require!(refund > 0, AppMarketError::InvalidWithdrawalAmount);
+                listing.withdrawal_count = listing.withdrawal_count
+                    .checked_add(1)
                     .ok_or(AppMarketError::MathOverflow)?;
 
-                ctx.accounts.transaction.status = TransactionStatus::Completed;
+                withdrawal.version = ACCOUNT_LAYOUT_VERSION;
+                withdrawal.user = bidder;
+                withdrawal.listing = listing.key();
+                withdrawal.amount = refund;
+                // Released from the bidder's profile when the refund is paid out
+                withdrawal.exposure = listing.current_bid_exposure;
+                withdrawal.withdrawal_id = listing.withdrawal_count;
+                withdrawal.created_at = clock.unix_timestamp;
+                withdrawal.expires_at = clock.unix_timestamp + 3600; // 1 hour
+                withdrawal.rent_payer = ctx.accounts.caller.key();
+                withdrawal.bump = ctx.bumps.pending_withdrawal
+                    .ok_or(AppMarketError::WithdrawalAccountRequired)?;
+
+                emit!(WithdrawalCreated {
+                    user: bidder,
+                    listing: listing.key(),
+                    pending_withdrawal: withdrawal.key(),
+                    amount: refund,
+                    withdrawal_id: listing.withdrawal_count,
+                    actor: ctx.accounts.caller.key(),
+                    timestamp: clock.unix_timestamp,
+                });
             },
-            DisputeResolution::PartialRefund { buyer_amount, seller_amount } => {
-                let total_refund = (*buyer_amount)
-                    .checked_add(*seller_amount)
-                    .ok_or(AppMarketError::MathOverflow)?;
-                require!(
-                    escrow_balance >= total_refund + rent,
-                    AppMarketError::InsufficientEscrowBalance
-                );
+            None => require!(refund == 0, AppMarketError::WithdrawalAccountRequired),
+        }
+        listing.current_bid_exposure = 0;
 
-                // Transfer to buyer
-                if *buyer_amount > 0 {
-                    let cpi_ctx = CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.escrow.to_account_info(),
-                            to: ctx.accounts.buyer.to_account_info(),
-                        },
-                        signer,
-                    );
-                    anchor_lang::system_program::transfer(cpi_ctx, *buyer_amount)?;
+        emit!(AuctionReserveNotMet {
+            listing: listing.key(),
+            bidder,
+            high_bid,
+            reserve_price: reserve,
+            refund,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-                    ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-                        .checked_sub(*buyer_amount)
-                        .ok_or(AppMarketError::MathOverflow)?;
-                }
+        Ok(())
+    }
 
-                // Transfer to seller
-                if *seller_amount > 0 {
-                    let cpi_ctx = CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.escrow.to_account_info(),
-                            to: ctx.accounts.seller.to_account_info(),
-                        },
-                        signer,
-                    );
-                    anchor_lang::system_program::transfer(cpi_ctx, *seller_amount)?;
+    /// Close the escrow of an auction that ended below its reserve (anyone)
+    /// Once every refund, the high bidder's included, has been withdrawn, expired or
+    /// escheated, the rent returns to the seller and relist can create a fresh escrow.
+    pub fn close_failed_auction_escrow(ctx: Context<CloseFailedAuctionEscrow>) -> Result<()> {
+        require!(
+            ctx.accounts.listing.status == ListingStatus::ReserveNotMet,
+            AppMarketError::AuctionNotFailed
+        );
+        require!(
+            ctx.accounts.escrow.amount == 0,
+            AppMarketError::PendingWithdrawalsExist
+        );
 
-                    ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-                        .checked_sub(*seller_amount)
-                        .ok_or(AppMarketError::MathOverflow)?;
-                }
+        emit!(EscrowClosed {
+            listing: ctx.accounts.listing.key(),
+            actor: ctx.accounts.caller.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-                ctx.accounts.transaction.status = TransactionStatus::Completed;
-            },
-        }
+        Ok(())
+    }
 
-        // SECURITY: Distribute dispute fee based on resolution outcome
-        let dispute_bump_arr = [dispute_bump];
-        let dispute_seeds = &[
-            b"dispute",
-            transaction_key.as_ref(),
-            &dispute_bump_arr,
-        ];
-        let dispute_signer = &[&dispute_seeds[..]];
+    /// Post the respondent bond (dispute respondent, within the response window)
+    /// Only for disputes opened while respondent bonds were enabled. Matches the initiator's
+    /// dispute fee and is held with it in the Dispute PDA; on execution the respondent gets
+    /// back the share they were awarded. Respondents who never bond let the initiator
+    /// claim a default judgment once the window closes.
+    pub fn post_respondent_bond(ctx: Context<PostRespondentBond>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-        match &resolution {
-            DisputeResolution::FullRefund => {
-                // Buyer wins - refund dispute fee to buyer
-                let cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.dispute.to_account_info(),
-                        to: ctx.accounts.buyer.to_account_info(),
-                    },
-                    dispute_signer,
-                );
-                anchor_lang::system_program::transfer(cpi_ctx, dispute_fee)?;
-            },
-            DisputeResolution::ReleaseToSeller | DisputeResolution::PartialRefund { .. } => {
-                // Seller wins or compromise - send dispute fee to treasury
-                let cpi_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.dispute.to_account_info(),
-                        to: ctx.accounts.treasury.to_account_info(),
-                    },
-                    dispute_signer,
-                );
-                anchor_lang::system_program::transfer(cpi_ctx, dispute_fee)?;
+        let clock = Clock::get()?;
+        let dispute = &ctx.accounts.dispute;
+
+        require!(
+            ctx.accounts.respondent.key() == dispute.respondent,
+            AppMarketError::NotDisputeRespondent
+        );
+        require!(
+            dispute.status == DisputeStatus::Open && dispute.pending_resolution.is_none(),
+            AppMarketError::DisputeNotOpen
+        );
+        require!(dispute.response_deadline > 0, AppMarketError::RespondentBondsDisabled);
+        require!(!dispute.respondent_bonded, AppMarketError::RespondentBondPosted);
+        require!(
+            clock.unix_timestamp <= dispute.response_deadline,
+            AppMarketError::ResponseWindowClosed
+        );
+
+        let amount = dispute.dispute_fee;
+        require!(
+            ctx.accounts.respondent.lamports() >= amount,
+            AppMarketError::InsufficientBalance
+        );
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.respondent.to_account_info(),
+                to: ctx.accounts.dispute.to_account_info(),
             },
-        }
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
 
-        // Update dispute
-        let resolution_notes = ctx.accounts.dispute.resolution_notes.clone();
-        ctx.accounts.dispute.status = DisputeStatus::Resolved;
-        ctx.accounts.dispute.resolution = Some(resolution.clone());
-        ctx.accounts.dispute.resolved_at = Some(clock.unix_timestamp);
-        ctx.accounts.dispute.pending_resolution = None;
-        ctx.accounts.dispute.pending_resolution_at = None;
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.respondent_bond = amount;
+        dispute.respondent_bonded = true;
 
-        emit!(DisputeResolved {
-            dispute: ctx.accounts.dispute.key(),
-            transaction: transaction_key,
-            resolution,
-            notes: resolution_notes.unwrap_or_default(),
+        emit!(RespondentBondPosted {
+            dispute: dispute.key(),
+            respondent: dispute.respondent,
+            amount,
+            actor: ctx.accounts.respondent.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Emergency refund after transfer deadline passes (ONLY if seller never confirmed transfer)
-    pub fn emergency_refund(ctx: Context<EmergencyRefund>) -> Result<()> {
-        let transaction = &mut ctx.accounts.transaction;
+    /// Claim default judgment once the response window closed without a respondent bond
+    /// (initiator only). Stages the ruling the initiator asked for (full refund for a buyer,
+    /// release for a seller) as a pending resolution: it takes the normal contest timelock,
+    /// the respondent can still contest it, and the admin executes it.
+    pub fn claim_default_judgment(ctx: Context<ClaimDisputeDefault>) -> Result<()> {
         let clock = Clock::get()?;
+        let transaction = &ctx.accounts.transaction;
+        let dispute = &mut ctx.accounts.dispute;
 
-        // Validations
         require!(
-            transaction.status == TransactionStatus::InEscrow,
-            AppMarketError::InvalidTransactionStatus
+            ctx.accounts.initiator.key() == dispute.initiator,
+            AppMarketError::NotDisputeInitiator
         );
         require!(
-            ctx.accounts.buyer.key() == transaction.buyer,
-            AppMarketError::NotBuyer
+            dispute.status == DisputeStatus::Open && dispute.pending_resolution.is_none(),
+            AppMarketError::DisputeNotOpen
         );
+        require!(!dispute.respondent_bonded, AppMarketError::RespondentBondPosted);
+        // No deadline: opened while bonds were off (or before they existed)
         require!(
-            clock.unix_timestamp > transaction.transfer_deadline,
-            AppMarketError::DeadlineNotPassed
+            dispute.response_deadline > 0 && clock.unix_timestamp > dispute.response_deadline,
+            AppMarketError::ResponseWindowOpen
         );
 
-        // SECURITY: If seller confirmed transfer, buyer MUST open dispute
-        if transaction.seller_confirmed_transfer {
-            return Err(AppMarketError::MustOpenDispute.into());
-        }
+        let resolution = if transaction.is_buyer_signer(&dispute.initiator) {
+            DisputeResolution::FullRefund
+        } else {
+            DisputeResolution::ReleaseToSeller
+        };
 
-        // SECURITY: Validate escrow balance
-        let escrow_balance = ctx.accounts.escrow.to_account_info().lamports();
-        let rent = Rent::get()?.minimum_balance(
-            ctx.accounts.escrow.to_account_info().data_len()
+        stage_dispute_resolution(
+            dispute,
+            transaction.sale_price,
+            transaction.platform_fee,
+            resolution.clone(),
+            "Default judgment: respondent posted no bond".to_string(),
+            ctx.accounts.initiator.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(DefaultJudgmentClaimed {
+            dispute: dispute.key(),
+            transaction: transaction.key(),
+            initiator: dispute.initiator,
+            resolution,
+            actor: ctx.accounts.initiator.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Record the respondent's position on a dispute (respondent only, once, before a
+    /// resolution is proposed): acknowledged or contested, a statement of up to 500
+    /// characters and optionally the hash of a longer document kept off-chain
+    pub fn respond_to_dispute(
+        ctx: Context<RespondToDispute>,
+        status: DisputeResponseStatus,
+        response: String,
+        response_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let dispute = &mut ctx.accounts.dispute;
+
+        require!(
+            ctx.accounts.respondent.key() == dispute.respondent,
+            AppMarketError::NotDisputeRespondent
         );
         require!(
-            escrow_balance >= transaction.sale_price + rent,
-            AppMarketError::InsufficientEscrowBalance
+            dispute.status == DisputeStatus::Open && dispute.pending_resolution.is_none(),
+            AppMarketError::DisputeNotOpen
         );
+        require!(dispute.response_status.is_none(), AppMarketError::DisputeAlreadyAnswered);
+        require!(response.len() <= 500, AppMarketError::DisputeResponseTooLong);
 
-        // Validate tracked amount
-        let tracked_with_rent = ctx.accounts.escrow.amount
-            .checked_add(rent)
-            .ok_or(AppMarketError::MathOverflow)?;
+        dispute.response_status = Some(status);
+        dispute.response = response.clone();
+        dispute.response_hash = response_hash;
+        dispute.responded_at = Some(clock.unix_timestamp);
+
+        emit!(DisputeResponded {
+            dispute: dispute.key(),
+            respondent: dispute.respondent,
+            status,
+            response,
+            response_hash,
+            actor: ctx.accounts.respondent.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the sale price below which disputes take the small-claims fast track (admin only)
+    /// 0 disables it. Applies to disputes opened afterwards.
+    pub fn set_small_claims_threshold(
+        ctx: Context<SetSmallClaimsThreshold>,
+        threshold: u64,
+    ) -> Result<()> {
         require!(
-            escrow_balance >= tracked_with_rent,
-            AppMarketError::EscrowBalanceMismatch
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
         );
 
-        // Allow refund even with pending withdrawals — escrow stays open for cleanup
+        ctx.accounts.config.small_claims_threshold = threshold;
+
+        emit!(SmallClaimsThresholdUpdated {
+            threshold,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Propose a small-claim resolution as a single moderator (any committee arbitrator)
+    /// Skips the committee quorum for disputes opened below the small-claims threshold;
+    /// the proposal gets the shorter small-claim contest window, after which the moderator
+    /// can execute it themselves. Appeals still go to the full committee.
+    pub fn propose_small_claim_resolution(
+        ctx: Context<ProposeSmallClaimResolution>,
+        resolution: DisputeResolution,
+        notes: String,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let moderator = ctx.accounts.arbitrator.key();
+
         require!(
-            ctx.accounts.escrow.amount >= transaction.sale_price,
-            AppMarketError::InsufficientEscrowBalance
+            ctx.accounts.arbitrator_set.arbitrators.contains(&moderator),
+            AppMarketError::NotArbitrator
+        );
+        // Deals with their own designated arbitrator are not committee business
+        require!(
+            ctx.accounts.transaction.designated_arbitrator().is_none(),
+            AppMarketError::ArbitratorAssigned
         );
 
-        // Refund full amount to buyer
-        let seeds = &[
-            b"escrow",
-            ctx.accounts.listing.to_account_info().key.as_ref(),
-            &[ctx.accounts.escrow.bump],
-        ];
-        let signer = &[&seeds[..]];
+        // SECURITY: Moderators must hold the same minimum APP stake as voting arbitrators
+        let min_stake = ctx.accounts.config.min_arbitrator_stake;
+        if min_stake > 0 {
+            let stake = ctx.accounts.arbitrator_stake.as_ref()
+                .ok_or(AppMarketError::InsufficientArbitratorStake)?;
+            require!(
+                stake.active_amount() >= min_stake,
+                AppMarketError::InsufficientArbitratorStake
+            );
+        }
 
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            anchor_lang::system_program::Transfer {
-                from: ctx.accounts.escrow.to_account_info(),
-                to: ctx.accounts.buyer.to_account_info(),
-            },
-            signer,
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.small_claim, AppMarketError::NotSmallClaim);
+        require!(dispute.appealed_by.is_none(), AppMarketError::DisputeUnderAppeal);
+        // Re-staging would erase a contest; the pending ruling must be withdrawn first
+        require!(
+            dispute.status != DisputeStatus::UnderReview,
+            AppMarketError::ResolutionAlreadyPending
         );
-        anchor_lang::system_program::transfer(cpi_ctx, transaction.sale_price)?;
+        require!(dispute.status == DisputeStatus::Open, AppMarketError::DisputeNotOpen);
 
-        ctx.accounts.escrow.amount = ctx.accounts.escrow.amount
-            .checked_sub(transaction.sale_price)
-            .ok_or(AppMarketError::MathOverflow)?;
+        // The moderator alone is the staked panel behind this ruling
+        dispute.ruling_arbitrators = if min_stake > 0 { vec![moderator] } else { Vec::new() };
+        dispute.votes.clear();
 
-        transaction.status = TransactionStatus::Refunded;
-        transaction.completed_at = Some(clock.unix_timestamp);
+        stage_dispute_resolution(
+            dispute,
+            ctx.accounts.transaction.sale_price,
+            ctx.accounts.transaction.platform_fee,
+            resolution,
+            notes,
+            moderator,
+            clock.unix_timestamp,
+        )?;
+        dispute.small_claim_moderator = Some(moderator);
 
-        emit!(TransactionCompleted {
-            transaction: transaction.key(),
-            seller: transaction.seller,
-            buyer: transaction.buyer,
-            amount: 0,
-            platform_fee: 0,
+        Ok(())
+    }
+
+    /// Pay out the final dispute ruling once it can no longer be appealed (permissionless)
+    /// Runs after the appeal window lapses unused, or after execute_appeal_resolution
+    /// remaining_accounts: as for execute_dispute_resolution
+    pub fn settle_dispute_ruling<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteDisputeResolution<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            ctx.accounts.dispute.status == DisputeStatus::Resolved,
+            AppMarketError::DisputeNotResolved
+        );
+        require!(
+            !ctx.accounts.dispute.ruling_settled,
+            AppMarketError::RulingAlreadySettled
+        );
+
+        // Either the appeal has been ruled on, or the appeal window lapsed unused
+        let appealed = ctx.accounts.dispute.appealed_by.is_some();
+        let appeal_finished = appealed
+            || ctx.accounts.dispute.appeal_deadline
+                .map(|deadline| clock.unix_timestamp > deadline)
+                .unwrap_or(true);
+        require!(appeal_finished, AppMarketError::AppealWindowOpen);
+
+        let resolution = ctx.accounts.dispute.resolution.clone()
+            .ok_or(AppMarketError::NoPendingResolution)?;
+
+        apply_dispute_ruling(
+            ctx.accounts,
+            ctx.remaining_accounts,
+            &resolution,
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts.dispute.ruling_settled = true;
+
+        emit!(DisputeRulingSettled {
+            dispute: ctx.accounts.dispute.key(),
+            transaction: ctx.accounts.transaction.key(),
+            resolution,
+            appealed,
+            actor: ctx.accounts.caller.key(),
             timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
 
-    /// Cancel listing (seller only, before any bids)
-    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
-        let listing = &mut ctx.accounts.listing;
-
-        // Validations
-        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
-        require!(ctx.accounts.seller.key() == listing.seller, AppMarketError::NotSeller);
-
-        // SECURITY: Prevent cancellation if auction has started (has bids)
-        require!(listing.current_bidder.is_none(), AppMarketError::HasBids);
+    /// Close a pool that never bought once every contribution has been refunded (permissionless)
+    /// Rent returns to the creator, who may open a new pool for the listing
+    pub fn close_buyer_pool(ctx: Context<CloseBuyerPool>) -> Result<()> {
+        let clock = Clock::get()?;
+        let pool = &ctx.accounts.buyer_pool;
 
-        listing.status = ListingStatus::Cancelled;
+        require!(pool.transaction.is_none(), AppMarketError::PoolAlreadyPurchased);
+        require!(
+            clock.unix_timestamp >= pool.deadline,
+            AppMarketError::PoolDeadlineNotPassed
+        );
+        require!(
+            pool.settled_amount == pool.total_contributed,
+            AppMarketError::PoolContributionsOutstanding
+        );
 
-        emit!(AuctionCancelled {
-            listing: listing.key(),
-            reason: "Cancelled by seller".to_string(),
+        emit!(BuyerPoolClosed {
+            pool: pool.key(),
+            listing: pool.listing,
+            creator: pool.creator,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
         });
 
         Ok(())
     }
-}
 
-// ============================================
-// ACCOUNTS
-// ============================================
+    /// Issue the on-chain deed for a listed app (seller)
+    /// Required before fractionalizing or offering rent-to-own; claim_app_deed moves it to
+    /// the buyer once a sale completes
+    pub fn issue_app_deed(ctx: Context<IssueAppDeed>) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = admin,
-        space = 8 + MarketConfig::INIT_SPACE,
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, MarketConfig>,
+        let listing = &ctx.accounts.listing;
+        let clock = Clock::get()?;
 
-    /// CHECK: Treasury wallet to receive fees
-    pub treasury: AccountInfo<'info>,
+        require!(
+            matches!(
+                listing.status,
+                ListingStatus::Draft | ListingStatus::Active | ListingStatus::Paused
+            ),
+            AppMarketError::ListingNotActive
+        );
+        require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
+        let deed = &mut ctx.accounts.app_deed;
+        deed.listing = listing.key();
+        deed.holder = listing.seller;
+        deed.issued_at = clock.unix_timestamp;
+        deed.version = ACCOUNT_LAYOUT_VERSION;
+        deed.bump = ctx.bumps.app_deed;
 
-    pub system_program: Program<'info, System>,
-}
+        emit!(AppDeedIssued {
+            deed: deed.key(),
+            listing: deed.listing,
+            holder: deed.holder,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct ProposeTreasuryChange<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-    pub admin: Signer<'info>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ExecuteTreasuryChange<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-    pub admin: Signer<'info>,
-}
+    /// Move a seller-held deed to the buyer of the listing's completed sale (permissionless)
+    pub fn claim_app_deed(ctx: Context<ClaimAppDeed>) -> Result<()> {
+        let transaction = &ctx.accounts.transaction;
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-pub struct ProposeAdminChange<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-    pub admin: Signer<'info>,
-}
+        require!(
+            transaction.status == TransactionStatus::Completed,
+            AppMarketError::InvalidTransactionStatus
+        );
 
-#[derive(Accounts)]
-pub struct ExecuteAdminChange<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-    pub admin: Signer<'info>,
-}
+        let deed = &mut ctx.accounts.app_deed;
+        require!(deed.holder == transaction.seller, AppMarketError::NotDeedHolder);
+        deed.holder = transaction.buyer;
 
-#[derive(Accounts)]
-#[instruction(salt: u64)]
-pub struct CreateListing<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    #[account(
-        init,
-        payer = seller,
-        space = 8 + Listing::INIT_SPACE,
-        seeds = [b"listing", seller.key().as_ref(), &salt.to_le_bytes()],
-        bump
-    )]
-    pub listing: Account<'info, Listing>,
-
-    // SECURITY: Initialize escrow atomically with listing (seller pays rent)
-    #[account(
-        init,
-        payer = seller,
-        space = 8 + Escrow::INIT_SPACE,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    #[account(mut)]
-    pub seller: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        emit!(AppDeedTransferred {
+            deed: deed.key(),
+            listing: deed.listing,
+            from: transaction.seller,
+            to: transaction.buyer,
+            actor: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-#[instruction(amount: u64)]
-pub struct PlaceBid<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
+    /// Split a listed app into share tokens (seller)
+    /// The deed moves into the fraction vault and the listing leaves the market; shares sell
+    /// at share_price through the listing escrow, and anyone can buy the whole app back out
+    /// of the vault for buyout_price (at least the value of every share)
+    pub fn fractionalize_app(
+        ctx: Context<FractionalizeApp>,
+        total_shares: u64,
+        share_price: u64,
+        buyout_price: u64,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-    // SECURITY: Escrow must already exist (no init_if_needed race condition)
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
+        let listing = &mut ctx.accounts.listing;
+        let clock = Clock::get()?;
 
-    // SECURITY: Pending withdrawal for previous bidder (only created when needed)
-    /// CHECK: Only created if there's a previous bidder to refund
-    #[account(mut)]
-    pub pending_withdrawal: UncheckedAccount<'info>,
+        // CHECKS
+        require!(listing.status == ListingStatus::Active, AppMarketError::ListingNotActive);
+        require!(!listing.is_multi_unit(), AppMarketError::MultiUnitListing);
+        // Fractions are priced and bought out in SOL
+        require!(
+            listing.payment_currency() == Pubkey::default(),
+            AppMarketError::InvalidPaymentMint
+        );
+        require!(
+            listing.current_bidder.is_none() && ctx.accounts.escrow.amount == 0,
+            AppMarketError::HasBids
+        );
+        require!(
+            ctx.accounts.app_deed.holder == listing.seller,
+            AppMarketError::NotDeedHolder
+        );
+        require!(
+            (MIN_FRACTION_SHARES..=MAX_FRACTION_SHARES).contains(&total_shares) && share_price > 0,
+            AppMarketError::InvalidFractionTerms
+        );
+        let full_value = total_shares
+            .checked_mul(share_price)
+            .ok_or(AppMarketError::InvalidFractionTerms)?;
+        require!(buyout_price >= full_value, AppMarketError::InvalidFractionTerms);
 
-    #[account(mut)]
-    pub bidder: Signer<'info>,
+        // EFFECTS
+        let vault_key = ctx.accounts.fraction_vault.key();
+        let vault = &mut ctx.accounts.fraction_vault;
+        vault.listing = listing.key();
+        vault.deed = ctx.accounts.app_deed.key();
+        vault.seller = listing.seller;
+        vault.share_mint = ctx.accounts.share_mint.key();
+        vault.total_shares = total_shares;
+        vault.shares_sold = 0;
+        vault.share_price = share_price;
+        vault.buyout_price = buyout_price;
+        vault.bought_out_by = None;
+        vault.holder_pot = 0;
+        vault.shares_redeemed = 0;
+        vault.redeemed_amount = 0;
+        vault.version = ACCOUNT_LAYOUT_VERSION;
+        vault.bump = ctx.bumps.fraction_vault;
+        vault.mint_bump = ctx.bumps.share_mint;
+
+        ctx.accounts.app_deed.holder = vault_key;
+
+        set_listing_status(
+            listing,
+            ListingStatus::InEscrow,
+            ctx.accounts.seller.key(),
+            clock.unix_timestamp,
+        )?;
+
+        emit!(AppDeedTransferred {
+            deed: vault.deed,
+            listing: vault.listing,
+            from: vault.seller,
+            to: vault_key,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-    pub system_program: Program<'info, System>,
-}
+        emit!(AppFractionalized {
+            vault: vault_key,
+            listing: vault.listing,
+            deed: vault.deed,
+            share_mint: vault.share_mint,
+            total_shares,
+            share_price,
+            buyout_price,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct WithdrawFunds<'info> {
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    // SECURITY: Close withdrawal account and return rent to user
-    // Uses withdrawal_id from PendingWithdrawal struct (not seeds - we look it up)
-    #[account(
-        mut,
-        close = user,
-        seeds = [
-            b"withdrawal",
-            listing.key().as_ref(),
-            &pending_withdrawal.withdrawal_id.to_le_bytes()
-        ],
-        bump = pending_withdrawal.bump,
-        constraint = pending_withdrawal.user == user.key() @ AppMarketError::NotWithdrawalOwner
-    )]
-    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
-
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ExpireWithdrawal<'info> {
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    // Close the expired withdrawal account, return rent to the original user (not caller)
-    #[account(
-        mut,
-        close = recipient,
-        seeds = [
-            b"withdrawal",
-            listing.key().as_ref(),
-            &pending_withdrawal.withdrawal_id.to_le_bytes()
-        ],
-        bump = pending_withdrawal.bump,
-    )]
-    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
-
-    /// The original user who was outbid — funds + PDA rent go back to them
-    /// CHECK: Validated against pending_withdrawal.user
-    #[account(
-        mut,
-        constraint = recipient.key() == pending_withdrawal.user @ AppMarketError::NotWithdrawalOwner
-    )]
-    pub recipient: AccountInfo<'info>,
-
-    /// Anyone can call this after expiry (permissionless cleanup)
-    #[account(mut)]
-    pub caller: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Buy shares of a fractionalized app: payment is held in the listing escrow for the
+    /// seller (claim_fraction_proceeds) and the shares are minted to the buyer
+    pub fn buy_fractions(ctx: Context<BuyFractions>, shares: u64) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-#[derive(Accounts)]
-pub struct CloseEscrow<'info> {
-    #[account(
-        constraint = listing.seller == seller.key() @ AppMarketError::InvalidSeller
-    )]
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump,
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    // Close escrow — rent returns to the seller (who originally created the listing)
-    #[account(
-        mut,
-        close = seller,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump,
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    /// CHECK: Seller receives escrow rent — validated against listing.seller
-    #[account(mut)]
-    pub seller: AccountInfo<'info>,
-
-    /// Anyone can call this (permissionless cleanup)
-    pub caller: Signer<'info>,
-}
+        let vault = &mut ctx.accounts.fraction_vault;
+        let clock = Clock::get()?;
 
-#[derive(Accounts)]
-pub struct BuyNow<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
-
-    // SECURITY: Escrow must already exist
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    #[account(
-        init,
-        payer = buyer,
-        space = 8 + Transaction::INIT_SPACE,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    // SECURITY: Pending withdrawal for previous bidder (only initialized if previous bidder exists)
-    /// CHECK: Only used if listing.current_bidder exists, manually initialized in instruction
-    #[account(mut)]
-    pub pending_withdrawal: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        // CHECKS
+        require!(vault.bought_out_by.is_none(), AppMarketError::FractionsBoughtOut);
+        require!(
+            ctx.accounts.buyer.key() != vault.seller,
+            AppMarketError::SellerCannotBuy
+        );
+        let shares_sold = vault.shares_sold
+            .checked_add(shares)
+            .ok_or(AppMarketError::MathOverflow)?;
+        require!(
+            shares > 0 && shares_sold <= vault.total_shares,
+            AppMarketError::FractionSharesUnavailable
+        );
+        let amount = shares
+            .checked_mul(vault.share_price)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-#[derive(Accounts)]
-pub struct SettleAuction<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    #[account(
-        init,
-        payer = payer,
-        space = 8 + Transaction::INIT_SPACE,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    /// CHECK: Current bidder (validated in instruction)
-    #[account(mut)]
-    pub bidder: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        // EFFECTS
+        vault.shares_sold = shares_sold;
+        ctx.accounts.escrow.credit(
+            amount,
+            EscrowChangeReason::Purchase,
+            ctx.accounts.buyer.key(),
+        )?;
 
-#[derive(Accounts)]
-pub struct CancelAuction<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
+        // INTERACTIONS
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
 
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
+        let listing_key = ctx.accounts.listing.key();
+        let vault_seeds = &[
+            b"fraction_vault",
+            listing_key.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&vault_seeds[..]];
 
-    // SECURITY: Close escrow and refund rent to seller when auction cancelled (no bids)
-    #[account(
-        mut,
-        close = seller,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.buyer_share_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(cpi_ctx, shares)?;
 
-    #[account(mut)]
-    pub seller: Signer<'info>,
+        emit!(FractionsPurchased {
+            vault: vault.key(),
+            listing: listing_key,
+            buyer: ctx.accounts.buyer.key(),
+            shares,
+            amount,
+            shares_sold,
+            actor: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-    pub system_program: Program<'info, System>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ExpireListing<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
-
-    // SECURITY: Close escrow when listing expires without bids
-    #[account(
-        mut,
-        close = seller,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump,
-        constraint = listing.seller == seller.key() @ AppMarketError::NotSeller
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    /// CHECK: Seller receives rent
-    #[account(mut)]
-    pub seller: AccountInfo<'info>,
-}
+    /// Pay out escrowed fraction sales to the seller (platform fee at the locked rate)
+    pub fn claim_fraction_proceeds<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimFractionProceeds<'info>>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
 
-#[derive(Accounts)]
-pub struct SellerConfirmTransfer<'info> {
-    #[account(
-        mut,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
+        let amount = ctx.accounts.escrow.amount;
+        require!(amount > 0, AppMarketError::InsufficientEscrowBalance);
 
-    pub listing: Account<'info, Listing>,
+        let platform_fee = amount
+            .checked_mul(ctx.accounts.listing.platform_fee_bps as u64)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(BASIS_POINTS_DIVISOR)
+            .ok_or(AppMarketError::MathOverflow)?;
+        let seller_amount = amount
+            .checked_sub(platform_fee)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-    pub seller: Signer<'info>,
-}
+        let seeds = &[
+            b"escrow",
+            ctx.accounts.listing.to_account_info().key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let signer = &[&seeds[..]];
 
-#[derive(Accounts)]
-pub struct VerifyUploads<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
+        pay_platform_fee(
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+            ctx.accounts.config.insurance_fee_bps,
+            platform_fee,
+            ctx.accounts.seller.key(),
+            ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+
+        ctx.accounts.escrow.debit(
+            platform_fee,
+            EscrowChangeReason::PlatformFee,
+            ctx.accounts.seller.key(),
+        )?;
+
+        if seller_amount > 0 {
+            pay_seller_proceeds(
+                ctx.accounts.escrow.to_account_info(),
+                ctx.accounts.payout_wallet.to_account_info(),
+                &ctx.accounts.listing,
+                ctx.accounts.payout_split.as_deref(),
+                ctx.remaining_accounts,
+                seller_amount,
+                ctx.accounts.system_program.to_account_info(),
+                signer,
+            )?;
+        }
 
-    #[account(mut)]
-    pub transaction: Account<'info, Transaction>,
+        ctx.accounts.escrow.debit(
+            seller_amount,
+            EscrowChangeReason::SellerPayout,
+            ctx.accounts.seller.key(),
+        )?;
 
-    /// Backend authority that verifies uploads
-    pub backend_authority: Signer<'info>,
-}
+        emit!(FractionProceedsClaimed {
+            vault: ctx.accounts.fraction_vault.key(),
+            listing: ctx.accounts.listing.key(),
+            seller: ctx.accounts.seller.key(),
+            amount,
+            platform_fee,
+            actor: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct EmergencyAutoVerify<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub transaction: Account<'info, Transaction>,
+    /// Buy a fractionalized app outright for the vault's buyout price and take its deed
+    /// The platform fee is charged at the locked rate; holders of sold shares redeem their
+    /// cut from the vault (redeem_fraction_shares) and the unsold shares' cut goes to the seller
+    pub fn buyout_fractions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyoutFractions<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.config.require_active()?;
 
-    /// Buyer who triggers emergency verification
-    pub buyer: Signer<'info>,
-}
+        let clock = Clock::get()?;
+        let buyer_key = ctx.accounts.buyer.key();
+        let vault_key = ctx.accounts.fraction_vault.key();
 
-#[derive(Accounts)]
-pub struct AdminEmergencyVerify<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
+        // CHECKS
+        require!(
+            ctx.accounts.fraction_vault.bought_out_by.is_none(),
+            AppMarketError::FractionsBoughtOut
+        );
+        require!(
+            ctx.accounts.app_deed.holder == vault_key,
+            AppMarketError::NotDeedHolder
+        );
 
-    #[account(mut)]
-    pub transaction: Account<'info, Transaction>,
+        let buyout_price = ctx.accounts.fraction_vault.buyout_price;
+        let (platform_fee, holder_pot, seller_amount) = split_fraction_buyout(
+            buyout_price,
+            ctx.accounts.listing.platform_fee_bps,
+            ctx.accounts.fraction_vault.shares_sold,
+            ctx.accounts.fraction_vault.total_shares,
+        )?;
 
-    /// Admin who triggers emergency verification
-    pub admin: Signer<'info>,
-}
+        // EFFECTS
+        let vault = &mut ctx.accounts.fraction_vault;
+        vault.bought_out_by = Some(buyer_key);
+        vault.holder_pot = holder_pot;
+        ctx.accounts.app_deed.holder = buyer_key;
+
+        set_listing_status(
+            &mut ctx.accounts.listing,
+            ListingStatus::Completed,
+            buyer_key,
+            clock.unix_timestamp,
+        )?;
+
+        // INTERACTIONS: paid straight from the buyer's wallet
+        pay_platform_fee(
+            ctx.accounts.buyer.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.insurance_vault.as_ref().map(|vault| vault.to_account_info()),
+            ctx.accounts.config.insurance_fee_bps,
+            platform_fee,
+            buyer_key,
+            ctx.accounts.system_program.to_account_info(),
+            &[],
+        )?;
+
+        if seller_amount > 0 {
+            pay_seller_proceeds(
+                ctx.accounts.buyer.to_account_info(),
+                ctx.accounts.payout_wallet.to_account_info(),
+                &ctx.accounts.listing,
+                ctx.accounts.payout_split.as_deref(),
+                ctx.remaining_accounts,
+                seller_amount,
+                ctx.accounts.system_program.to_account_info(),
+                &[],
+            )?;
+        }
 
-#[derive(Accounts)]
-pub struct FinalizeTransaction<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        mut,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    /// CHECK: Seller to receive funds and escrow rent (validated via transaction.seller)
-    #[account(
-        mut,
-        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
-    )]
-    pub seller: AccountInfo<'info>,
-
-    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    /// CHECK: Treasury to receive fees - SECURITY: validated against config
-    #[account(
-        mut,
-        constraint = treasury.key() == config.treasury @ AppMarketError::InvalidTreasury
-    )]
-    pub treasury: AccountInfo<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        if holder_pot > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.fraction_vault.to_account_info(),
+                },
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, holder_pot)?;
+        }
 
-#[derive(Accounts)]
-pub struct ConfirmReceipt<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        mut,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-
-    /// CHECK: Seller to receive funds and escrow rent (validated via transaction.seller)
-    #[account(
-        mut,
-        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
-    )]
-    pub seller: AccountInfo<'info>,
-
-    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    /// CHECK: Treasury to receive fees - SECURITY: validated against config
-    #[account(
-        mut,
-        constraint = treasury.key() == config.treasury @ AppMarketError::InvalidTreasury
-    )]
-    pub treasury: AccountInfo<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        emit!(AppDeedTransferred {
+            deed: ctx.accounts.app_deed.key(),
+            listing: ctx.accounts.listing.key(),
+            from: vault_key,
+            to: buyer_key,
+            actor: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-#[instruction(amount: u64, deadline: i64, offer_seed: u64)]
-pub struct MakeOffer<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    pub listing: Account<'info, Listing>,
-
-    // SECURITY: Use deterministic offer_seed instead of Clock::get() to prevent consensus issues
-    #[account(
-        init,
-        payer = buyer,
-        space = 8 + Offer::INIT_SPACE,
-        seeds = [
-            b"offer",
-            listing.key().as_ref(),
-            buyer.key().as_ref(),
-            &offer_seed.to_le_bytes()
-        ],
-        bump
-    )]
-    pub offer: Account<'info, Offer>,
-
-    #[account(
-        init,
-        payer = buyer,
-        space = 8 + OfferEscrow::INIT_SPACE,
-        seeds = [b"offer_escrow", offer.key().as_ref()],
-        bump
-    )]
-    pub offer_escrow: Account<'info, OfferEscrow>,
-
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        emit!(FractionsBoughtOut {
+            vault: vault_key,
+            listing: ctx.accounts.listing.key(),
+            buyer: buyer_key,
+            buyout_price,
+            platform_fee,
+            holder_pot,
+            seller_amount,
+            actor: buyer_key,
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct CancelOffer<'info> {
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
+        Ok(())
+    }
 
-    #[account(mut)]
-    pub offer: Account<'info, Offer>,
+    /// Burn all of the holder's shares of a bought-out app for their cut of the buyout
+    pub fn redeem_fraction_shares(ctx: Context<RedeemFractionShares>) -> Result<()> {
+        let clock = Clock::get()?;
+        ctx.accounts.config.require_exits_open(clock.unix_timestamp)?;
 
-    // SECURITY: Close escrow and return rent to buyer
-    #[account(
-        mut,
-        close = buyer,
-        seeds = [b"offer_escrow", offer.key().as_ref()],
-        bump = offer_escrow.bump
-    )]
-    pub offer_escrow: Account<'info, OfferEscrow>,
+        let shares = ctx.accounts.holder_share_account.amount;
 
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+        // CHECKS
+        require!(
+            ctx.accounts.fraction_vault.bought_out_by.is_some(),
+            AppMarketError::FractionsNotBoughtOut
+        );
+        require!(shares > 0, AppMarketError::FractionSharesUnavailable);
+        let amount = fraction_redemption(&ctx.accounts.fraction_vault, shares)?;
 
-    pub system_program: Program<'info, System>,
-}
+        // EFFECTS
+        let vault = &mut ctx.accounts.fraction_vault;
+        vault.shares_redeemed = vault.shares_redeemed
+            .checked_add(shares)
+            .ok_or(AppMarketError::MathOverflow)?;
+        vault.redeemed_amount = vault.redeemed_amount
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
 
-#[derive(Accounts)]
-pub struct ExpireOffer<'info> {
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
-
-    #[account(mut)]
-    pub offer: Account<'info, Offer>,
-
-    // SECURITY: Close escrow and return rent to buyer
-    #[account(
-        mut,
-        close = buyer,
-        seeds = [b"offer_escrow", offer.key().as_ref()],
-        bump = offer_escrow.bump
-    )]
-    pub offer_escrow: Account<'info, OfferEscrow>,
-
-    /// Buyer receives refund (from offer.buyer, not caller)
-    #[account(
-        mut,
-        constraint = buyer.key() == offer.buyer @ AppMarketError::InvalidBuyer
-    )]
-    pub buyer: SystemAccount<'info>,
-
-    /// Caller pays gas (can be anyone)
-    #[account(mut)]
-    pub caller: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        // INTERACTIONS
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.holder_share_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        );
+        token::burn(cpi_ctx, shares)?;
 
-#[derive(Accounts)]
-pub struct AcceptOffer<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        mut,
-        constraint = offer.listing == listing.key() @ AppMarketError::InvalidOffer
-    )]
-    pub offer: Account<'info, Offer>,
-
-    // Transfer funds from offer escrow to listing escrow
-    #[account(
-        mut,
-        close = buyer,
-        seeds = [b"offer_escrow", offer.key().as_ref()],
-        bump = offer_escrow.bump,
-        constraint = offer.buyer == buyer.key() @ AppMarketError::InvalidBuyer
-    )]
-    pub offer_escrow: Account<'info, OfferEscrow>,
-
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = listing_escrow.bump
-    )]
-    pub listing_escrow: Account<'info, Escrow>,
-
-    #[account(
-        init,
-        payer = seller,
-        space = 8 + Transaction::INIT_SPACE,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    // SECURITY FIX M-3: Pending withdrawal only created when needed (previous bidder exists)
-    /// CHECK: Only created if listing.current_bidder exists and has a non-zero bid
-    #[account(mut)]
-    pub pending_withdrawal: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub seller: Signer<'info>,
-
-    /// CHECK: Buyer - rent recipient for offer escrow
-    #[account(mut)]
-    pub buyer: AccountInfo<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        if amount > 0 {
+            ctx.accounts.fraction_vault.sub_lamports(amount)?;
+            ctx.accounts.holder.add_lamports(amount)?;
+        }
 
-#[derive(Accounts)]
-pub struct OpenDispute<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    #[account(
-        mut,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        init,
-        payer = initiator,
-        space = 8 + Dispute::INIT_SPACE,
-        seeds = [b"dispute", transaction.key().as_ref()],
-        bump
-    )]
-    pub dispute: Account<'info, Dispute>,
-
-    #[account(mut)]
-    pub initiator: Signer<'info>,
-
-    /// CHECK: Treasury to receive dispute fees - SECURITY: validated against config
-    #[account(
-        mut,
-        constraint = treasury.key() == config.treasury @ AppMarketError::InvalidTreasury
-    )]
-    pub treasury: AccountInfo<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        emit!(FractionSharesRedeemed {
+            vault: ctx.accounts.fraction_vault.key(),
+            holder: ctx.accounts.holder.key(),
+            shares,
+            amount,
+            actor: ctx.accounts.holder.key(),
+            timestamp: clock.unix_timestamp,
+        });
 
-#[derive(Accounts)]
-pub struct ProposeDisputeResolution<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
+        Ok(())
+    }
 
-    pub listing: Account<'info, Listing>,
+    /// Enable or disable respondent bonds and default judgments (admin only)
+    /// Applies to disputes opened afterwards; disabled by default.
+    pub fn set_respondent_bonds(ctx: Context<SetRespondentBonds>, enabled: bool) -> Result<()> {
+        require!(
+            ctx.accounts.admin.key() == ctx.accounts.config.admin,
+            AppMarketError::NotAdmin
+        );
 
-    #[account(
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
+        ctx.accounts.config.respondent_bonds_enabled = enabled;
 
-    #[account(
-        mut,
-        seeds = [b"dispute", transaction.key().as_ref()],
-        bump = dispute.bump
-    )]
-    pub dispute: Account<'info, Dispute>,
+        emit!(RespondentBondsUpdated {
+            enabled,
+            actor: ctx.accounts.admin.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
-    pub admin: Signer<'info>,
+        Ok(())
+    }
 }
 
-#[derive(Accounts)]
-pub struct ContestDisputeResolution<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    pub listing: Account<'info, Listing>,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::instruction::AccountMeta;
+    use solana_sysvar::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+    // A multisig vault PDA (e.g. Squads) signs via CPI: it must not be asked to be writable
+    // or to pay rent, so admin flows take it read-only and a separate payer funds rent.
+    fn assert_pda_admin_compatible(metas: &[AccountMeta], admin: Pubkey) {
+        let admin_meta = metas
+            .iter()
+            .find(|meta| meta.pubkey == admin)
+            .expect("admin account missing");
+        assert!(admin_meta.is_signer, "admin must sign");
+        assert!(!admin_meta.is_writable, "admin must be read-only");
+    }
 
-    #[account(
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
+    #[test]
+    fn timelocked_admin_flows_take_a_read_only_admin() {
+        let config = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let fee_split = Pubkey::new_unique();
+        let fee_vault = Pubkey::new_unique();
+
+        assert_pda_admin_compatible(
+            &accounts::ProposeTreasuryChange { config, admin }.to_account_metas(None),
+            admin,
+        );
+        assert_pda_admin_compatible(
+            &accounts::ExecuteTreasuryChange { config, admin }.to_account_metas(None),
+            admin,
+        );
+        assert_pda_admin_compatible(
+            &accounts::ProposeAdminChange { config, admin }.to_account_metas(None),
+            admin,
+        );
+        assert_pda_admin_compatible(
+            &accounts::ExecuteAdminChange { config, admin }.to_account_metas(None),
+            admin,
+        );
+        assert_pda_admin_compatible(
+            &accounts::ProposeFeeSplitChange { config, fee_split, admin }.to_account_metas(None),
+            admin,
+        );
+        assert_pda_admin_compatible(
+            &accounts::ExecuteFeeSplitChange { config, fee_split, fee_vault, admin }
+                .to_account_metas(None),
+            admin,
+        );
+
+        let treasury_withdrawal = Pubkey::new_unique();
+        let rent_payer = Pubkey::new_unique();
+        assert_pda_admin_compatible(
+            &accounts::WithdrawTreasury {
+                config,
+                treasury_withdrawal,
+                treasury_vault: Pubkey::new_unique(),
+                recipient: Pubkey::new_unique(),
+                rent_payer,
+                admin,
+                system_program: System::id(),
+            }
+            .to_account_metas(None),
+            admin,
+        );
+        assert_pda_admin_compatible(
+            &accounts::CancelTreasuryWithdrawal { config, treasury_withdrawal, rent_payer, admin }
+                .to_account_metas(None),
+            admin,
+        );
+    }
 
-    #[account(
-        mut,
-        seeds = [b"dispute", transaction.key().as_ref()],
-        bump = dispute.bump
-    )]
-    pub dispute: Account<'info, Dispute>,
+    #[test]
+    fn account_creating_admin_flows_take_a_separate_payer() {
+        let config = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        let flows = [
+            accounts::Initialize {
+                config,
+                treasury: Pubkey::new_unique(),
+                admin,
+                payer,
+                system_program: System::id(),
+            }
+            .to_account_metas(None),
+            accounts::InitializeFeeSplit {
+                config,
+                fee_split: Pubkey::new_unique(),
+                fee_vault: Pubkey::new_unique(),
+                admin,
+                payer,
+                system_program: System::id(),
+            }
+            .to_account_metas(None),
+            accounts::RequestTreasuryWithdrawal {
+                config,
+                treasury_withdrawal: Pubkey::new_unique(),
+                admin,
+                payer,
+                system_program: System::id(),
+            }
+            .to_account_metas(None),
+            accounts::InitFeeSchedule {
+                config,
+                fee_schedule: Pubkey::new_unique(),
+                admin,
+                payer,
+                system_program: System::id(),
+            }
+            .to_account_metas(None),
+        ];
 
-    /// Buyer or seller contesting the resolution
-    pub caller: Signer<'info>,
-}
+        for metas in flows.iter() {
+            assert_pda_admin_compatible(metas, admin);
+            let payer_meta = metas.iter().find(|meta| meta.pubkey == payer).unwrap();
+            assert!(payer_meta.is_signer && payer_meta.is_writable);
+        }
+    }
+    // Error codes of the originally deployed program, which clients already match on
+    const DEPLOYED_ERROR_CODES: [(AppMarketError, &str, u32); 77] = [
+        (AppMarketError::InvalidPrice, "InvalidPrice", 6000),
+        (AppMarketError::InvalidDuration, "InvalidDuration", 6001),
+        (AppMarketError::ListingNotActive, "ListingNotActive", 6002),
+        (AppMarketError::AuctionEnded, "AuctionEnded", 6003),
+        (AppMarketError::AuctionNotEnded, "AuctionNotEnded", 6004),
+        (AppMarketError::ListingExpired, "ListingExpired", 6005),
+        (AppMarketError::ListingNotExpired, "ListingNotExpired", 6006),
+        (AppMarketError::BidTooLow, "BidTooLow", 6007),
+        (AppMarketError::SellerCannotBid, "SellerCannotBid", 6008),
+        (AppMarketError::SellerCannotBuy, "SellerCannotBuy", 6009),
+        (AppMarketError::SellerCannotOffer, "SellerCannotOffer", 6010),
+        (AppMarketError::BuyNowNotEnabled, "BuyNowNotEnabled", 6011),
+        (AppMarketError::InvalidTransactionStatus, "InvalidTransactionStatus", 6012),
+        (AppMarketError::NotBuyer, "NotBuyer", 6013),
+        (AppMarketError::NotSeller, "NotSeller", 6014),
+        (AppMarketError::NotAdmin, "NotAdmin", 6015),
+        (AppMarketError::NotPartyToTransaction, "NotPartyToTransaction", 6016),
+        (AppMarketError::DisputeNotOpen, "DisputeNotOpen", 6017),
+        (AppMarketError::HasBids, "HasBids", 6018),
+        (AppMarketError::MathOverflow, "MathOverflow", 6019),
+        (AppMarketError::InvalidPreviousBidder, "InvalidPreviousBidder", 6020),
+        (AppMarketError::InvalidTreasury, "InvalidTreasury", 6021),
+        (AppMarketError::InvalidSeller, "InvalidSeller", 6022),
+        (AppMarketError::InvalidBuyer, "InvalidBuyer", 6023),
+        (AppMarketError::InvalidBidder, "InvalidBidder", 6024),
+        (AppMarketError::InsufficientEscrowBalance, "InsufficientEscrowBalance", 6025),
+        (AppMarketError::EscrowBalanceMismatch, "EscrowBalanceMismatch", 6026),
+        (AppMarketError::InsufficientBalance, "InsufficientBalance", 6027),
+        (AppMarketError::DeadlineNotPassed, "DeadlineNotPassed", 6028),
+        (AppMarketError::InvalidRefundAmounts, "InvalidRefundAmounts", 6029),
+        (AppMarketError::UnauthorizedSettlement, "UnauthorizedSettlement", 6030),
+        (AppMarketError::BidIncrementTooSmall, "BidIncrementTooSmall", 6031),
+        (AppMarketError::ContractPaused, "ContractPaused", 6032),
+        (AppMarketError::FeeTooHigh, "FeeTooHigh", 6033),
+        (AppMarketError::NoPendingChange, "NoPendingChange", 6034),
+        (AppMarketError::TimelockNotExpired, "TimelockNotExpired", 6035),
+        (AppMarketError::MustOpenDispute, "MustOpenDispute", 6036),
+        (AppMarketError::AlreadyConfirmed, "AlreadyConfirmed", 6037),
+        (AppMarketError::NotWithdrawalOwner, "NotWithdrawalOwner", 6038),
+        (AppMarketError::NotOfferOwner, "NotOfferOwner", 6039),
+        (AppMarketError::OfferNotActive, "OfferNotActive", 6040),
+        (AppMarketError::OfferExpired, "OfferExpired", 6041),
+        (AppMarketError::OfferNotExpired, "OfferNotExpired", 6042),
+        (AppMarketError::InvalidDeadline, "InvalidDeadline", 6043),
+        (AppMarketError::NotAnAuction, "NotAnAuction", 6044),
+        (AppMarketError::SellerNotConfirmed, "SellerNotConfirmed", 6045),
+        (AppMarketError::GracePeriodNotExpired, "GracePeriodNotExpired", 6046),
+        (AppMarketError::StartingPriceMustEqualReserve, "StartingPriceMustEqualReserve", 6047),
+        (AppMarketError::BuyNowPriceRequired, "BuyNowPriceRequired", 6048),
+        (AppMarketError::UploadsNotVerified, "UploadsNotVerified", 6049),
+        (AppMarketError::AlreadyVerified, "AlreadyVerified", 6050),
+        (AppMarketError::NotBackendAuthority, "NotBackendAuthority", 6051),
+        (AppMarketError::BidBelowReserve, "BidBelowReserve", 6052),
+        (AppMarketError::CannotFinalizeDisputed, "CannotFinalizeDisputed", 6053),
+        (AppMarketError::SellerMustSign, "SellerMustSign", 6054),
+        (AppMarketError::NoBidsToSettle, "NoBidsToSettle", 6055),
+        (AppMarketError::CannotCancelWithBids, "CannotCancelWithBids", 6056),
+        (AppMarketError::PendingWithdrawalsExist, "PendingWithdrawalsExist", 6057),
+        (AppMarketError::TransactionNotComplete, "TransactionNotComplete", 6058),
+        (AppMarketError::InvalidGithubUsername, "InvalidGithubUsername", 6059),
+        (AppMarketError::DisputeDeadlineExpired, "DisputeDeadlineExpired", 6060),
+        (AppMarketError::MaxBidsExceeded, "MaxBidsExceeded", 6061),
+        (AppMarketError::MaxOffersExceeded, "MaxOffersExceeded", 6062),
+        (AppMarketError::MaxConsecutiveOffersExceeded, "MaxConsecutiveOffersExceeded", 6063),
+        (AppMarketError::MaxConsecutiveBidsExceeded, "MaxConsecutiveBidsExceeded", 6064),
+        (AppMarketError::BackendTimeoutNotExpired, "BackendTimeoutNotExpired", 6065),
+        (AppMarketError::NotExpectedAdmin, "NotExpectedAdmin", 6066),
+        (AppMarketError::PartialRefundMustEqualSalePrice, "PartialRefundMustEqualSalePrice", 6067),
+        (AppMarketError::DisputeTimelockNotExpired, "DisputeTimelockNotExpired", 6068),
+        (AppMarketError::AlreadyContested, "AlreadyContested", 6069),
+        (AppMarketError::InvalidOfferSeed, "InvalidOfferSeed", 6070),
+        (AppMarketError::InvalidWithdrawalId, "InvalidWithdrawalId", 6071),
+        (AppMarketError::InvalidPaymentMint, "InvalidPaymentMint", 6072),
+        (AppMarketError::InvalidOffer, "InvalidOffer", 6073),
+        (AppMarketError::Unauthorized, "Unauthorized", 6074),
+        (AppMarketError::PlatformPaused, "PlatformPaused", 6075),
+        (AppMarketError::WithdrawalNotExpired, "WithdrawalNotExpired", 6076),
+    ];
+
+    // Error codes are client ABI: the deployed variants keep their names and codes, and new
+    // variants only append after them with the next code, so inserting a variant mid-enum,
+    // renumbering, renaming or deleting one fails here.
+    #[test]
+    fn error_codes_are_append_only() {
+        for (error, name, code) in DEPLOYED_ERROR_CODES {
+            assert_eq!(error.name(), name);
+            assert_eq!(u32::from(error), code, "{name} changed its deployed code");
+        }
 
-#[derive(Accounts)]
-pub struct ExecuteDisputeResolution<'info> {
-    #[account(seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
-
-    pub listing: Account<'info, Listing>,
-
-    #[account(
-        mut,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    /// CHECK: Buyer (validated via transaction.buyer)
-    #[account(
-        mut,
-        constraint = buyer.key() == transaction.buyer @ AppMarketError::InvalidBuyer
-    )]
-    pub buyer: AccountInfo<'info>,
-
-    /// CHECK: Seller to receive escrow rent (validated via transaction.seller)
-    #[account(
-        mut,
-        constraint = seller.key() == transaction.seller @ AppMarketError::InvalidSeller
-    )]
-    pub seller: AccountInfo<'info>,
-
-    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    #[account(
-        mut,
-        close = caller,
-        seeds = [b"dispute", transaction.key().as_ref()],
-        bump = dispute.bump
-    )]
-    pub dispute: Account<'info, Dispute>,
-
-    /// CHECK: Treasury - SECURITY: validated against config
-    #[account(
-        mut,
-        constraint = treasury.key() == config.treasury @ AppMarketError::InvalidTreasury
-    )]
-    pub treasury: AccountInfo<'info>,
-
-    /// Anyone can execute after timelock (typically admin or party)
-    pub caller: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+        let source = include_str!("errors.rs");
+        let variants: Vec<(&str, u32)> = source
+            .lines()
+            .filter_map(|line| line.trim().strip_suffix(',')?.split_once(" = "))
+            .map(|(name, code)| (name, code.parse().expect("numeric error discriminant")))
+            .collect();
+        assert_eq!(variants.len(), source.matches("#[msg(").count(), "every variant needs a code");
+
+        for (index, (name, code)) in variants.iter().enumerate() {
+            if let Some((_, deployed, _)) = DEPLOYED_ERROR_CODES.get(index) {
+                assert_eq!(name, deployed, "deployed error variants must stay first, in order");
+            }
+            assert_eq!(*code, index as u32, "{name}: new codes must append in order");
+        }
+    }
 
-#[derive(Accounts)]
-pub struct EmergencyRefund<'info> {
-    pub listing: Account<'info, Listing>,
-
-    // Escrow stays open until all pending withdrawals are cleared (close_escrow handles cleanup)
-    #[account(
-        mut,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    // Transaction stays open so close_escrow can verify terminal state later
-    #[account(
-        mut,
-        seeds = [b"transaction", listing.key().as_ref()],
-        bump = transaction.bump
-    )]
-    pub transaction: Account<'info, Transaction>,
-
-    #[account(mut)]
-    pub buyer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    // migrate_account recognises v0 accounts by size, so the legacy structs must keep the
+    // exact allocation of the deployed accounts (8 + INIT_SPACE of the original layouts)
+    #[test]
+    fn legacy_layouts_match_deployed_sizes() {
+        assert_eq!(8 + MarketConfigV0::INIT_SPACE, 222);
+        assert_eq!(8 + ListingV0::INIT_SPACE, 420);
+        assert_eq!(8 + TransactionV0::INIT_SPACE, 243);
+        assert_eq!(8 + DisputeV0::INIT_SPACE, 1704);
+        assert_eq!(8 + PendingWithdrawalV0::INIT_SPACE, 105);
+        assert_eq!(8 + OfferV0::INIT_SPACE, 98);
+        assert_eq!(8 + Escrow::INIT_SPACE - 1, 49);
+        assert_eq!(8 + OfferEscrow::INIT_SPACE - 1, 49);
+    }
 
-#[derive(Accounts)]
-pub struct CancelListing<'info> {
-    #[account(mut)]
-    pub listing: Account<'info, Listing>,
-
-    // SECURITY: Close escrow when cancelling (rent returns to seller)
-    #[account(
-        mut,
-        close = seller,
-        seeds = [b"escrow", listing.key().as_ref()],
-        bump = escrow.bump
-    )]
-    pub escrow: Account<'info, Escrow>,
-
-    #[account(mut)]
-    pub seller: Signer<'info>,
-}
+    #[test]
+    fn dissenting_ballots_do_not_reset_the_tally() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut votes = Vec::new();
 
-#[derive(Accounts)]
-pub struct SetPaused<'info> {
-    #[account(mut, seeds = [b"config"], bump = config.bump)]
-    pub config: Account<'info, MarketConfig>,
+        assert_eq!(cast_ballot(&mut votes, a, &DisputeResolution::FullRefund).unwrap(), vec![a]);
+        // A dissent is tallied separately and leaves the other outcome's ballots alone
+        assert_eq!(cast_ballot(&mut votes, b, &DisputeResolution::ReleaseToSeller).unwrap(), vec![b]);
+        assert_eq!(cast_ballot(&mut votes, c, &DisputeResolution::FullRefund).unwrap(), vec![a, c]);
 
-    pub admin: Signer<'info>,
-}
+        // Re-casting the same ballot is rejected; switching moves only the voter's own ballot
+        assert!(cast_ballot(&mut votes, b, &DisputeResolution::ReleaseToSeller).is_err());
+        assert_eq!(cast_ballot(&mut votes, b, &DisputeResolution::FullRefund).unwrap(), vec![a, b, c]);
+        assert_eq!(votes.len(), 3);
+    }
 
-// ============================================
-// STATE
-// ============================================
-
-#[account]
-#[derive(InitSpace)]
-pub struct MarketConfig {
-    pub admin: Pubkey,
-    pub treasury: Pubkey,
-    pub backend_authority: Pubkey,  // For verifying uploads
-    pub platform_fee_bps: u64,
-    pub dispute_fee_bps: u64,
-    pub total_volume: u64,
-    pub total_sales: u64,
-    pub paused: bool,
-    // SECURITY: Admin timelock fields
-    pub pending_treasury: Option<Pubkey>,
-    pub pending_treasury_at: Option<i64>,
-    pub pending_admin: Option<Pubkey>,
-    pub pending_admin_at: Option<i64>,
-    pub bump: u8,
-}
+    #[test]
+    fn v0_listing_migration_recovers_the_pda_salt() {
+        let seller = Pubkey::new_unique();
+        let old = ListingV0 {
+            seller,
+            listing_id: format!("{}-{}", seller, u64::MAX),
+            listing_type: ListingType::Auction,
+            starting_price: 1_000_000_000,
+            reserve_price: None,
+            buy_now_price: None,
+            current_bid: 0,
+            current_bidder: None,
+            created_at: 1_000,
+            auction_started: false,
+            auction_start_time: None,
+            end_time: 87_400,
+            status: ListingStatus::Active,
+            platform_fee_bps: 500,
+            dispute_fee_bps: 200,
+            requires_github: false,
+            required_github_username: String::new(),
+            withdrawal_count: 3,
+            offer_count: 2,
+            last_offer_buyer: None,
+            consecutive_offer_count: 1,
+            last_bidder: None,
+            consecutive_bid_count: 0,
+            payment_mint: None,
+            bump: 254,
+        };
 
-#[account]
-#[derive(InitSpace)]
-pub struct Listing {
-    pub seller: Pubkey,
-    #[max_len(64)]
-    pub listing_id: String,
-    pub listing_type: ListingType,
-    pub starting_price: u64,
-    pub reserve_price: Option<u64>,
-    pub buy_now_price: Option<u64>,
-    pub current_bid: u64,
-    pub current_bidder: Option<Pubkey>,
-    pub created_at: i64,
-    // SECURITY: Auction timing fields
-    pub auction_started: bool,
-    pub auction_start_time: Option<i64>,
-    pub end_time: i64,
-    pub status: ListingStatus,
-    // SECURITY: Lock fees at listing creation
-    pub platform_fee_bps: u64,
-    pub dispute_fee_bps: u64,
-    // GitHub requirements
-    pub requires_github: bool,
-    #[max_len(64)]
-    pub required_github_username: String,
-    // Withdrawal counter for unique PDA seeds
-    pub withdrawal_count: u64,
-    // Offer counter for tracking total offers
-    pub offer_count: u64,
-    // Track consecutive offers from same buyer
-    pub last_offer_buyer: Option<Pubkey>,
-    pub consecutive_offer_count: u64,
-    // Track consecutive bids from same bidder
-    pub last_bidder: Option<Pubkey>,
-    pub consecutive_bid_count: u64,
-    // Payment currency (None = SOL, Some = SPL token mint)
-    pub payment_mint: Option<Pubkey>,
-    pub bump: u8,
-}
+        let listing = Listing::from(old);
+        assert_eq!(listing.salt, u64::MAX);
+        assert_eq!(listing.listing_id(), format!("{}-{}", seller, u64::MAX));
+        assert_eq!(listing.duration_seconds, 86_400);
+        assert_eq!(listing.payout_wallet, seller);
+        assert_eq!(listing.quantity, 1);
+        assert_eq!(listing.platform_fee_bps, 500);
+        assert_eq!(listing.withdrawal_count, 3);
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Escrow {
-    pub listing: Pubkey,
-    pub amount: u64,
-    pub bump: u8,
-}
+    #[test]
+    fn stake_redemption_splits_yield_and_never_fails_short() {
+        // 1% yield, buyer keeps 80% of it
+        assert_eq!(
+            split_stake_redemption(1_000_000, 1_010_000, 8_000).unwrap(),
+            (1_000_000, 8_000, 2_000, 0)
+        );
+        // Withdrawal fees ate into the principal: everything redeemed goes back, no yield
+        assert_eq!(
+            split_stake_redemption(1_000_000, 997_000, 8_000).unwrap(),
+            (997_000, 0, 0, 3_000)
+        );
+        assert_eq!(
+            split_stake_redemption(1_000_000, 0, 8_000).unwrap(),
+            (0, 0, 0, 1_000_000)
+        );
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Transaction {
-    pub listing: Pubkey,
-    pub seller: Pubkey,
-    pub buyer: Pubkey,
-    pub sale_price: u64,
-    pub platform_fee: u64,
-    pub seller_proceeds: u64,
-    pub status: TransactionStatus,
-    pub transfer_deadline: i64,
-    pub created_at: i64,
-    // SECURITY: Seller confirmation fields
-    pub seller_confirmed_transfer: bool,
-    pub seller_confirmed_at: Option<i64>,
-    pub completed_at: Option<i64>,
-    // Upload verification
-    pub uploads_verified: bool,
-    pub verification_timestamp: Option<i64>,
-    #[max_len(64)]
-    pub verification_hash: String,
-    pub bump: u8,
-}
+    #[test]
+    fn stake_shortfall_comes_off_the_refund_and_the_fee_first() {
+        // 5% platform fee on a 1 SOL sale
+        let (sale_price, platform_fee, seller_proceeds) = (1_000_000_000, 50_000_000, 950_000_000);
+
+        // Within the fee: a refund returns less, a release leaves the seller whole (staking
+        // needs the seller's signature because past the fee it does not)
+        let (price, fee, proceeds) =
+            absorb_stake_shortfall(sale_price, platform_fee, seller_proceeds, 3_000_000).unwrap();
+        assert_eq!(price, 997_000_000);
+        assert_eq!(fee, 47_000_000);
+        assert_eq!(proceeds, seller_proceeds);
+        assert_eq!(fee + proceeds, price);
+
+        // Past the fee: the rest comes off the seller's proceeds
+        let (price, fee, proceeds) =
+            absorb_stake_shortfall(sale_price, platform_fee, seller_proceeds, 80_000_000).unwrap();
+        assert_eq!(price, 920_000_000);
+        assert_eq!(fee, 0);
+        assert_eq!(proceeds, 920_000_000);
+        assert_eq!(fee + proceeds, price);
+
+        assert!(absorb_stake_shortfall(sale_price, platform_fee, seller_proceeds, sale_price + 1).is_err());
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Dispute {
-    pub transaction: Pubkey,
-    pub initiator: Pubkey,
-    pub respondent: Pubkey,
-    #[max_len(500)]
-    pub reason: String,
-    pub status: DisputeStatus,
-    pub resolution: Option<DisputeResolution>,
-    #[max_len(1000)]
-    pub resolution_notes: Option<String>,
-    pub dispute_fee: u64,
-    pub created_at: i64,
-    pub resolved_at: Option<i64>,
-    // SECURITY: Timelock fields for dispute resolution
-    pub pending_resolution: Option<DisputeResolution>,
-    pub pending_buyer_amount: Option<u64>,
-    pub pending_seller_amount: Option<u64>,
-    pub pending_resolution_at: Option<i64>,
-    pub contested: bool,
-    pub bump: u8,
-}
+    #[test]
+    fn refunds_release_exactly_the_exposure_they_counted() {
+        let user = Pubkey::new_unique();
+        let withdrawal = |exposure| PendingWithdrawal {
+            version: ACCOUNT_LAYOUT_VERSION,
+            user,
+            listing: Pubkey::new_unique(),
+            amount: 2_000_000_000,
+            exposure,
+            withdrawal_id: 1,
+            created_at: 0,
+            expires_at: 3600,
+            rent_payer: user,
+            bump: 255,
+        };
+        let mut profile = UserProfile {
+            version: ACCOUNT_LAYOUT_VERSION,
+            user,
+            points: 0,
+            lifetime_points: 0,
+            total_purchases: 0,
+            total_sales: 0,
+            fee_discount_bps: 0,
+            locked_exposure: 3_000_000_000,
+            contact_key: None,
+            created_at: 0,
+            bump: 255,
+        };
 
-#[account]
-#[derive(InitSpace)]
-pub struct PendingWithdrawal {
-    pub user: Pubkey,
-    pub listing: Pubkey,
-    pub amount: u64,
-    pub withdrawal_id: u64,  // Unique ID from listing.withdrawal_count
-    pub created_at: i64,
-    pub expires_at: i64,  // Auto-expire after 1 hour
-    pub bump: u8,
-}
+        // A bid placed without a profile counted nothing, so none is needed or released
+        release_withdrawal_exposure(&withdrawal(0), None).unwrap();
+        release_withdrawal_exposure(&withdrawal(0), Some(&mut profile)).unwrap();
+        assert_eq!(profile.locked_exposure, 3_000_000_000);
 
+        // A counted bid cannot be refunded without releasing it
+        assert!(release_withdrawal_exposure(&withdrawal(2_000_000_000), None).is_err());
+        release_withdrawal_exposure(&withdrawal(2_000_000_000), Some(&mut profile)).unwrap();
+        assert_eq!(profile.locked_exposure, 1_000_000_000);
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Offer {
-    pub listing: Pubkey,
-    pub buyer: Pubkey,
-    pub amount: u64,
-    pub deadline: i64,
-    pub status: OfferStatus,
-    pub created_at: i64,
-    pub bump: u8,
-}
+    #[test]
+    fn fraction_buyout_pays_holders_pro_rata_and_the_seller_the_rest() {
+        // 10 SOL buyout at a 5% fee, 2 of 3 shares sold
+        let (fee, holder_pot, seller_amount) =
+            split_fraction_buyout(10_000_000_000, 500, 2, 3).unwrap();
+        assert_eq!(fee, 500_000_000);
+        assert_eq!(holder_pot, 6_333_333_333);
+        assert_eq!(seller_amount, 3_166_666_667);
+        assert_eq!(fee + holder_pot + seller_amount, 10_000_000_000);
+
+        let mut vault = FractionVault {
+            version: ACCOUNT_LAYOUT_VERSION,
+            listing: Pubkey::new_unique(),
+            deed: Pubkey::new_unique(),
+            seller: Pubkey::new_unique(),
+            share_mint: Pubkey::new_unique(),
+            total_shares: 3,
+            shares_sold: 2,
+            share_price: 3_000_000_000,
+            buyout_price: 10_000_000_000,
+            bought_out_by: Some(Pubkey::new_unique()),
+            holder_pot,
+            shares_redeemed: 0,
+            redeemed_amount: 0,
+            bump: 255,
+            mint_bump: 255,
+        };
 
-#[account]
-#[derive(InitSpace)]
-pub struct OfferEscrow {
-    pub offer: Pubkey,
-    pub amount: u64,
-    pub bump: u8,
-}
+        // Cannot redeem more shares than were sold
+        assert!(fraction_redemption(&vault, 3).is_err());
 
-// ============================================
-// ENUMS
-// ============================================
+        let first = fraction_redemption(&vault, 1).unwrap();
+        assert_eq!(first, 3_166_666_666);
+        vault.shares_redeemed = 1;
+        vault.redeemed_amount = first;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum ListingType {
-    Auction,
-    BuyNow,
-}
+        // The last share sweeps the rounding dust: the pot is paid out exactly
+        let last = fraction_redemption(&vault, 1).unwrap();
+        assert_eq!(first + last, holder_pot);
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum ListingStatus {
-    Active,
-    Ended,
-    Sold,
-    Cancelled,
-    InEscrow,
-    TransferPending,
-    Disputed,
-    Completed,
-    Refunded,
-}
+        // Nothing sold: the seller takes everything after the fee
+        let (_, holder_pot, seller_amount) =
+            split_fraction_buyout(10_000_000_000, 500, 0, 3).unwrap();
+        assert_eq!(holder_pot, 0);
+        assert_eq!(seller_amount, 9_500_000_000);
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum TransactionStatus {
-    Pending,
-    Paid,
-    InEscrow,
-    TransferPending,
-    TransferInProgress,
-    AwaitingConfirmation,
-    Disputed,
-    Completed,
-    Refunded,
-    Cancelled,
-}
+    fn sale(sale_price: u64, platform_fee: u64) -> Transaction {
+        Transaction::from(TransactionV0 {
+            listing: Pubkey::new_unique(),
+            seller: Pubkey::new_unique(),
+            buyer: Pubkey::new_unique(),
+            sale_price,
+            platform_fee,
+            seller_proceeds: sale_price - platform_fee,
+            status: TransactionStatus::Completed,
+            transfer_deadline: 0,
+            created_at: 0,
+            seller_confirmed_transfer: true,
+            seller_confirmed_at: Some(0),
+            completed_at: Some(0),
+            uploads_verified: true,
+            verification_timestamp: None,
+            verification_hash: String::new(),
+            bump: 255,
+        })
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum DisputeStatus {
-    Open,
-    UnderReview,
-    Resolved,
-}
+    #[test]
+    fn vesting_dispute_stops_the_clock() {
+        let mut transaction = sale(1_000_000_000, 0);
+        transaction.vesting_start = Some(1_000);
+        transaction.vesting_duration = 100;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum DisputeResolution {
-    FullRefund,
-    ReleaseToSeller,
-    PartialRefund { buyer_amount: u64, seller_amount: u64 },
-}
+        assert_eq!(transaction.vested_proceeds(1_050).unwrap(), 500_000_000);
+        assert_eq!(transaction.vested_proceeds(5_000).unwrap(), 1_000_000_000);
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
-pub enum OfferStatus {
-    Active,
-    Accepted,
-    Cancelled,
-    Expired,
-}
+        // Disputed at 25% of the earn-out: nothing vests past it, the rest stays frozen
+        transaction.vesting_disputed_at = Some(1_025);
+        assert_eq!(transaction.vested_proceeds(1_020).unwrap(), 200_000_000);
+        assert_eq!(transaction.vested_proceeds(5_000).unwrap(), 250_000_000);
 
-// ============================================
-// EVENTS
-// ============================================
-
-#[event]
-pub struct MarketplaceInitialized {
-    pub admin: Pubkey,
-    pub treasury: Pubkey,
-    pub backend_authority: Pubkey,
-    pub platform_fee_bps: u64,
-    pub dispute_fee_bps: u64,
-    pub timestamp: i64,
-}
+        transaction.vesting_start = None;
+        assert!(transaction.vested_proceeds(5_000).is_err());
+    }
 
-#[event]
-pub struct ListingCreated {
-    pub listing: Pubkey,
-    pub seller: Pubkey,
-    pub listing_id: String,
-    pub listing_type: ListingType,
-    pub starting_price: u64,
-    pub end_time: i64,
-    pub platform_fee_bps: u64,
-}
+    // Money-path tests run the real helpers against in-memory accounts with a fixed clock
+    // and default rent. CPIs are unavailable off-chain, so transfer paths are checked
+    // through the splits they pay out.
+    const NOW: i64 = 1_700_000_000;
+    const SOL: u64 = 1_000_000_000;
 
-#[event]
-pub struct BidPlaced {
-    pub listing: Pubkey,
-    pub bidder: Pubkey,
-    pub amount: u64,
-    pub timestamp: i64,
-}
+    struct TestRuntime;
 
-#[event]
-pub struct SaleCompleted {
-    pub listing: Pubkey,
-    pub transaction: Pubkey,
-    pub buyer: Pubkey,
-    pub seller: Pubkey,
-    pub amount: u64,
-    pub timestamp: i64,
-}
+    impl SyscallStubs for TestRuntime {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            let clock = Clock { unix_timestamp: NOW, ..Clock::default() };
+            unsafe { std::ptr::write(var_addr as *mut Clock, clock) };
+            0
+        }
 
-#[event]
-pub struct SellerConfirmedTransfer {
-    pub transaction: Pubkey,
-    pub seller: Pubkey,
-    pub timestamp: i64,
-}
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { std::ptr::write(var_addr as *mut Rent, Rent::default()) };
+            0
+        }
+    }
 
-#[event]
-pub struct UploadsVerified {
-    pub transaction: Pubkey,
-    pub verification_hash: String,
-    pub timestamp: i64,
-}
+    fn install_runtime() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            set_syscall_stubs(Box::new(TestRuntime));
+        });
+    }
 
-#[event]
-pub struct EmergencyVerification {
-    pub transaction: Pubkey,
-    pub verified_by: Pubkey,
-    pub verification_type: String, // "buyer_timeout" or "admin_override"
-    pub timestamp: i64,
-}
+    // Leaked so helpers can borrow it for 'info
+    fn test_account(key: Pubkey, owner: Pubkey, lamports: u64, data: Vec<u8>) -> &'static AccountInfo<'static> {
+        Box::leak(Box::new(AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            true,
+            Box::leak(Box::new(lamports)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            0,
+        )))
+    }
 
-#[event]
-pub struct DisputeResolutionProposed {
-    pub dispute: Pubkey,
-    pub resolution: DisputeResolution,
-    pub buyer_amount: u64,
-    pub seller_amount: u64,
-    pub executable_at: i64,
-    pub timestamp: i64,
-}
+    fn wallet(lamports: u64) -> &'static AccountInfo<'static> {
+        test_account(Pubkey::new_unique(), system_program::ID, lamports, Vec::new())
+    }
 
-#[event]
-pub struct DisputeContested {
-    pub dispute: Pubkey,
-    pub contested_by: Pubkey,
-    pub timestamp: i64,
-}
+    // Allocated at full size and funded with rent plus `amount`, as `init` would
+    fn program_account<T: AccountSerialize + Space>(value: &T, amount: u64) -> &'static AccountInfo<'static> {
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).unwrap();
+        data.resize(8 + T::INIT_SPACE, 0);
+        let lamports = Rent::default().minimum_balance(data.len()) + amount;
+        test_account(Pubkey::new_unique(), crate::ID, lamports, data)
+    }
 
-#[event]
-pub struct TransactionCompleted {
-    pub transaction: Pubkey,
-    pub seller: Pubkey,
-    pub buyer: Pubkey,
-    pub amount: u64,
-    pub platform_fee: u64,
-    pub timestamp: i64,
-}
+    // Lamports held above rent
+    fn held(info: &AccountInfo) -> u64 {
+        info.lamports() - Rent::default().minimum_balance(info.data_len())
+    }
 
-#[event]
-pub struct AuctionCancelled {
-    pub listing: Pubkey,
-    pub reason: String,
-}
+    fn escrow_account(listing: Pubkey, amount: u64) -> &'static AccountInfo<'static> {
+        program_account(&Escrow { version: ACCOUNT_LAYOUT_VERSION, listing, amount, bump: 255 }, amount)
+    }
 
-#[event]
-pub struct ListingExpired {
-    pub listing: Pubkey,
-    pub timestamp: i64,
-}
+    fn listing(seller: Pubkey, listing_type: ListingType, status: ListingStatus) -> Listing {
+        Listing::from(ListingV0 {
+            seller,
+            listing_id: format!("{}-{}", seller, 1),
+            listing_type,
+            starting_price: SOL,
+            reserve_price: None,
+            buy_now_price: None,
+            current_bid: 0,
+            current_bidder: None,
+            created_at: 0,
+            auction_started: false,
+            auction_start_time: None,
+            end_time: 86_400,
+            status,
+            platform_fee_bps: 500,
+            dispute_fee_bps: 200,
+            requires_github: false,
+            required_github_username: String::new(),
+            withdrawal_count: 0,
+            offer_count: 0,
+            last_offer_buyer: None,
+            consecutive_offer_count: 0,
+            last_bidder: None,
+            consecutive_bid_count: 0,
+            payment_mint: None,
+            bump: 255,
+        })
+    }
 
-#[event]
-pub struct DisputeOpened {
-    pub dispute: Pubkey,
-    pub transaction: Pubkey,
-    pub initiator: Pubkey,
-    pub reason: String,
-    pub timestamp: i64,
-}
+    #[test]
+    fn winning_bid_commit_moves_the_bid_into_the_escrow_once() {
+        install_runtime();
+        let bidder = Pubkey::new_unique();
+        let mut auction = listing(Pubkey::new_unique(), ListingType::Auction, ListingStatus::Active);
+        auction.uses_bid_escrows = true;
+        auction.auction_started = true;
+        auction.current_bid = 3 * SOL;
+        auction.current_bidder = Some(bidder);
+        let listing_info = program_account(&auction, 0);
+        let auction = Account::<Listing>::try_from(listing_info).unwrap();
+
+        // The bidder topped up past the winning bid; the surplus stays theirs
+        let bid_escrow_info = program_account(
+            &BidEscrow {
+                version: ACCOUNT_LAYOUT_VERSION,
+                listing: listing_info.key(),
+                bidder,
+                amount: 5 * SOL,
+                committed: false,
+                bump: 255,
+            },
+            5 * SOL,
+        );
+        let escrow_info = escrow_account(listing_info.key(), 0);
+        let mut bid_escrow = Account::<BidEscrow>::try_from(bid_escrow_info).unwrap();
+        let mut escrow = Account::<Escrow>::try_from(escrow_info).unwrap();
+        let total = bid_escrow_info.lamports() + escrow_info.lamports();
+
+        // Settlement retries commit again: the second pass moves nothing
+        for _ in 0..2 {
+            commit_winning_bid_funds(&auction, &mut escrow, Some(&mut bid_escrow), bidder, NOW).unwrap();
+            assert_eq!(escrow.amount, 3 * SOL);
+            assert_eq!(held(escrow_info), escrow.amount);
+            assert_eq!(bid_escrow.amount, 2 * SOL);
+            assert_eq!(held(bid_escrow_info), bid_escrow.amount);
+            assert_eq!(bid_escrow_info.lamports() + escrow_info.lamports(), total);
+        }
 
-#[event]
-pub struct DisputeResolved {
-    pub dispute: Pubkey,
-    pub transaction: Pubkey,
-    pub resolution: DisputeResolution,
-    pub notes: String,
-    pub timestamp: i64,
-}
+        assert!(commit_winning_bid_funds(&auction, &mut escrow, None, bidder, NOW).is_err());
+    }
 
-#[event]
-pub struct ContractPausedEvent {
-    pub paused: bool,
-    pub timestamp: i64,
-}
+    #[test]
+    fn platform_fee_insurance_cut_never_loses_a_lamport() {
+        for fee in [0, 1, 9_999, 500_000_001, u64::MAX / BASIS_POINTS_DIVISOR] {
+            for insurance_fee_bps in [0, 1, 1_000, MAX_INSURANCE_FEE_BPS as u16] {
+                let (insurance_cut, treasury_cut) = split_insurance_cut(fee, insurance_fee_bps).unwrap();
+                assert_eq!(insurance_cut + treasury_cut, fee);
+                assert_eq!(insurance_cut, fee * insurance_fee_bps as u64 / BASIS_POINTS_DIVISOR);
+            }
+        }
+    }
 
-#[event]
-pub struct TreasuryChangeProposed {
-    pub old_treasury: Pubkey,
-    pub new_treasury: Pubkey,
-    pub executable_at: i64,
-}
+    #[test]
+    fn settlement_tip_comes_out_of_the_fee_only() {
+        let sales = [(SOL, 0), (SOL, 1), (10 * SOL + 1, SOL / 2 + 1), (SOL, SOL)];
+        for (sale_price, platform_fee) in sales {
+            let mut transaction = sale(sale_price, platform_fee);
+            let tip = settlement_tip(platform_fee).unwrap();
+            assert!(tip <= platform_fee);
+            deduct_settlement_tip(&mut transaction, tip).unwrap();
+            assert_eq!(transaction.sale_price, sale_price - tip);
+            assert_eq!(transaction.seller_proceeds, sale_price - platform_fee);
+            assert_eq!(
+                transaction.platform_fee + transaction.seller_proceeds,
+                transaction.sale_price
+            );
+        }
 
-#[event]
-pub struct TreasuryChanged {
-    pub new_treasury: Pubkey,
-    pub timestamp: i64,
-}
+        // A deposit auction's escrowed deposit drops with the price
+        let mut transaction = sale(10 * SOL, SOL / 2);
+        transaction.deposit_amount = SOL;
+        let tip = settlement_tip(transaction.platform_fee).unwrap();
+        deduct_settlement_tip(&mut transaction, tip).unwrap();
+        assert_eq!(transaction.deposit_amount, SOL - tip);
+    }
 
-#[event]
-pub struct AdminChangeProposed {
-    pub old_admin: Pubkey,
-    pub new_admin: Pubkey,
-    pub executable_at: i64,
-}
+    #[test]
+    fn settlement_crank_checks_the_tip_against_the_escrow() {
+        install_runtime();
+        let mut auction = listing(Pubkey::new_unique(), ListingType::Auction, ListingStatus::Active);
+        auction.current_bid = 10 * SOL;
+        let tip = settlement_tip(SOL / 2).unwrap();
+
+        let funded = Account::<Escrow>::try_from(escrow_account(auction.seller, tip)).unwrap();
+        assert!(check_settlement_tip(&auction, &funded).is_ok());
+        // Never out of the escrow's rent
+        let short = Account::<Escrow>::try_from(escrow_account(auction.seller, tip - 1)).unwrap();
+        assert!(check_settlement_tip(&auction, &short).is_err());
+    }
 
-#[event]
-pub struct AdminChanged {
-    pub new_admin: Pubkey,
-    pub timestamp: i64,
-}
+    #[test]
+    fn release_crank_only_releases_a_fully_escrowed_sale() {
+        install_runtime();
+        let seller = wallet(0);
+        let mut sold = listing(seller.key(), ListingType::BuyNow, ListingStatus::InEscrow);
+        sold.current_bidder = Some(Pubkey::new_unique());
+        let listing_info = program_account(&sold, 0);
+
+        let mut transaction = sale(10 * SOL, SOL / 2);
+        transaction.listing = listing_info.key();
+        transaction.seller = seller.key();
+        transaction.status = TransactionStatus::InEscrow;
+        transaction.completed_at = None;
+        let transaction_info = program_account(&transaction, 0);
+        // The program id stands in for an omitted profile
+        let no_profile = test_account(crate::ID, crate::ID, 0, Vec::new());
+        let check = |escrow_info, has_insurance_vault| {
+            check_due_release(
+                listing_info,
+                transaction_info,
+                escrow_info,
+                seller,
+                no_profile,
+                no_profile,
+                Some(Pubkey::default()),
+                1_000,
+                has_insurance_vault,
+                &crate::ID,
+                NOW,
+            )
+        };
 
-#[event]
-pub struct WithdrawalCreated {
-    pub user: Pubkey,
-    pub listing: Pubkey,
-    pub amount: u64,
-    pub withdrawal_id: u64,
-    pub timestamp: i64,
-}
+        let escrow_info = escrow_account(listing_info.key(), 10 * SOL);
+        assert!(check(escrow_info, true).unwrap().is_some());
+        // The insurance slice has nowhere to go without the vault
+        assert!(check(escrow_info, false).is_err());
+        // Fee plus proceeds must be escrowed above rent
+        assert!(check(escrow_account(listing_info.key(), 10 * SOL - 1), true).is_err());
+        let drained = escrow_account(listing_info.key(), 10 * SOL);
+        **drained.try_borrow_mut_lamports().unwrap() -= 1;
+        assert!(check(drained, true).is_err());
+    }
 
-#[event]
-pub struct WithdrawalClaimed {
-    pub user: Pubkey,
-    pub listing: Pubkey,
-    pub amount: u64,
-    pub timestamp: i64,
-}
+    #[test]
+    fn recurring_periods_pay_out_exactly_what_was_funded() {
+        let (seller, treasury) = (wallet(0), wallet(0));
+        let recurring_info = program_account(
+            &RecurringEscrow {
+                version: ACCOUNT_LAYOUT_VERSION,
+                transaction: Pubkey::new_unique(),
+                listing: Pubkey::new_unique(),
+                buyer: Pubkey::new_unique(),
+                seller: seller.key(),
+                arbitrator: None,
+                amount_per_period: 333_333_333,
+                period_seconds: 30 * SECONDS_PER_DAY,
+                total_periods: 3,
+                funded_periods: 3,
+                settled_periods: 0,
+                platform_fee_bps: 250,
+                start_time: 0,
+                status: RecurringStatus::Active,
+                disputed_period: None,
+                rent_payer: Pubkey::new_unique(),
+                bump: 255,
+            },
+            999_999_999,
+        );
+        let mut recurring = Account::<RecurringEscrow>::try_from(recurring_info).unwrap();
 
-#[event]
-pub struct WithdrawalExpired {
-    pub user: Pubkey,
-    pub listing: Pubkey,
-    pub amount: u64,
-    pub expired_by: Pubkey,
-    pub timestamp: i64,
-}
+        for _ in 0..3 {
+            pay_recurring_period(&mut recurring, seller, treasury, Pubkey::new_unique(), NOW).unwrap();
+        }
+        assert_eq!(held(recurring_info), 0);
+        assert_eq!(seller.lamports() + treasury.lamports(), 999_999_999);
+        assert_eq!(treasury.lamports(), 3 * (333_333_333 * 250 / BASIS_POINTS_DIVISOR));
+        assert!(recurring.status == RecurringStatus::Completed);
+    }
 
-#[event]
-pub struct EscrowClosed {
-    pub listing: Pubkey,
-    pub closed_by: Pubkey,
-    pub timestamp: i64,
-}
+    #[test]
+    fn stake_unwind_accounts_for_every_lamport() {
+        for principal in [1, 999_999, SOL, 7 * SOL + 3] {
+            let redemptions = [0, principal / 2, principal - 1, principal, principal + 1, principal * 2];
+            for redeemed in redemptions {
+                for buyer_share_bps in [0, 1, 5_000, 8_000, 10_000] {
+                    let (to_escrow, buyer_yield, protocol_yield, shortfall) =
+                        split_stake_redemption(principal, redeemed, buyer_share_bps).unwrap();
+                    assert_eq!(to_escrow + buyer_yield + protocol_yield, redeemed);
+                    assert_eq!(to_escrow + shortfall, principal);
+
+                    // The shortfall comes off the sale without breaking fee + proceeds = price
+                    let platform_fee = principal / 20;
+                    let proceeds = principal - platform_fee;
+                    let (price, fee, proceeds) =
+                        absorb_stake_shortfall(principal, platform_fee, proceeds, shortfall).unwrap();
+                    assert_eq!(price, to_escrow);
+                    assert_eq!(fee + proceeds, price);
+                }
+            }
+        }
+    }
 
-#[event]
-pub struct OfferCreated {
-    pub offer: Pubkey,
-    pub listing: Pubkey,
-    pub buyer: Pubkey,
-    pub amount: u64,
-    pub deadline: i64,
-    pub timestamp: i64,
-}
+    #[test]
+    fn pool_refunds_return_the_vault_exactly() {
+        let contributions = [333_333_333, 666_666_667, 1_000_000_001];
+        let total_contributed: u64 = contributions.iter().sum();
+
+        // Whatever came back, in any claim order, the vault is paid out to the lamport
+        for returned in [0, 1, 1_234_567_891, total_contributed] {
+            for order in [[0, 1, 2], [2, 1, 0], [1, 2, 0]] {
+                let mut pool = BuyerPool {
+                    version: ACCOUNT_LAYOUT_VERSION,
+                    listing: Pubkey::new_unique(),
+                    creator: Pubkey::new_unique(),
+                    pool_id: 0,
+                    share_mint: Pubkey::new_unique(),
+                    target_amount: total_contributed,
+                    total_contributed,
+                    settled_amount: 0,
+                    deadline: 0,
+                    transaction: Some(Pubkey::new_unique()),
+                    returned_amount: None,
+                    bump: 255,
+                    vault_bump: 255,
+                    mint_bump: 255,
+                };
+                let mut vault_balance = returned;
+                for index in order {
+                    let amount = contributions[index];
+                    pool.returned_amount.get_or_insert(vault_balance);
+                    let refund = pool_refund(&pool, amount, vault_balance).unwrap();
+                    let pro_rata = returned as u128 * amount as u128 / total_contributed as u128;
+                    assert!(refund as u128 >= pro_rata);
+                    vault_balance -= refund;
+                    pool.settled_amount += amount;
+                }
+                assert_eq!(vault_balance, 0);
+            }
+        }
+    }
 
-#[event]
-pub struct OfferCancelled {
-    pub offer: Pubkey,
-    pub listing: Pubkey,
-    pub buyer: Pubkey,
-    pub timestamp: i64,
-}
+    #[test]
+    fn rent_to_own_settlements_split_every_lamport() {
+        for total_paid in [0, 1, 999, SOL, 12 * SOL + 7] {
+            for forfeiture_bps in [0, 1, 2_500, 9_999, 10_000] {
+                for platform_fee_bps in [0, 250, 500, 1_000] {
+                    let (seller_amount, platform_fee, buyer_refund) =
+                        split_rent_to_own_payments(total_paid, forfeiture_bps, platform_fee_bps).unwrap();
+                    assert_eq!(seller_amount + platform_fee + buyer_refund, total_paid);
+                }
+            }
+        }
 
-#[event]
-pub struct OfferExpired {
-    pub offer: Pubkey,
-    pub listing: Pubkey,
-    pub buyer: Pubkey,
-    pub timestamp: i64,
-}
+        // Completion: the buyer paid in full and gets nothing back
+        assert_eq!(
+            split_rent_to_own_payments(12 * SOL, BASIS_POINTS_DIVISOR as u16, 500).unwrap(),
+            (11_400_000_000, 600_000_000, 0)
+        );
+    }
 
-#[event]
-pub struct OfferAccepted {
-    pub offer: Pubkey,
-    pub listing: Pubkey,
-    pub transaction: Pubkey,
-    pub buyer: Pubkey,
-    pub seller: Pubkey,
-    pub amount: u64,
-    pub timestamp: i64,
-}
+    #[test]
+    fn dispute_and_appeal_rulings_pay_out_the_escrow_and_fee_exactly() {
+        // An appeal re-runs the same payout against the still-held escrow
+        let (sale_price, platform_fee, dispute_fee) = (10 * SOL + 1, SOL / 2 + 1, 200_000_001);
+        for buyer_amount in [0, 1, 3 * SOL, sale_price - 1, sale_price] {
+            let seller_amount = sale_price - buyer_amount;
+            for fee_disposition in [
+                FeeDisposition::Waived,
+                FeeDisposition::ChargedToSeller,
+                FeeDisposition::ChargedToBuyer,
+            ] {
+                let resolution = DisputeResolution::PartialRefundWithFee {
+                    buyer_amount,
+                    seller_amount,
+                    fee_disposition,
+                };
+                // A share too small to carry the fee is rejected, never overdrawn
+                if let Ok((to_buyer, to_seller, fee)) =
+                    split_partial_refund(&resolution, platform_fee, sale_price)
+                {
+                    assert_eq!(to_buyer + to_seller + fee, sale_price);
+                }
 
-// ============================================
-// ERRORS
-// ============================================
-
-#[error_code]
-pub enum AppMarketError {
-    #[msg("Invalid price: must be greater than 0")]
-    InvalidPrice,
-    #[msg("Invalid duration: must be between 1 second and 30 days")]
-    InvalidDuration,
-    #[msg("Listing is not active")]
-    ListingNotActive,
-    #[msg("Auction has ended")]
-    AuctionEnded,
-    #[msg("Auction has not ended yet")]
-    AuctionNotEnded,
-    #[msg("Listing has expired")]
-    ListingExpired,
-    #[msg("Listing has not expired yet")]
-    ListingNotExpired,
-    #[msg("Bid is too low")]
-    BidTooLow,
-    #[msg("Seller cannot bid on their own listing")]
-    SellerCannotBid,
-    #[msg("Seller cannot buy their own listing")]
-    SellerCannotBuy,
-    #[msg("Seller cannot make offers on their own listing")]
-    SellerCannotOffer,
-    #[msg("Buy now is not enabled for this listing")]
-    BuyNowNotEnabled,
-    #[msg("Invalid transaction status")]
-    InvalidTransactionStatus,
-    #[msg("Only the buyer can confirm receipt")]
-    NotBuyer,
-    #[msg("Only the seller can perform this action")]
-    NotSeller,
-    #[msg("Only admin can perform this action")]
-    NotAdmin,
-    #[msg("Not a party to this transaction")]
-    NotPartyToTransaction,
-    #[msg("Dispute is not open")]
-    DisputeNotOpen,
-    #[msg("Listing has bids and cannot be cancelled")]
-    HasBids,
-    #[msg("Math overflow occurred")]
-    MathOverflow,
-    #[msg("Invalid previous bidder address")]
-    InvalidPreviousBidder,
-    #[msg("Invalid treasury address")]
-    InvalidTreasury,
-    #[msg("Invalid seller address")]
-    InvalidSeller,
-    #[msg("Invalid buyer address")]
-    InvalidBuyer,
-    #[msg("Invalid bidder address")]
-    InvalidBidder,
-    #[msg("Insufficient escrow balance")]
-    InsufficientEscrowBalance,
-    #[msg("Escrow tracked amount doesn't match actual balance")]
-    EscrowBalanceMismatch,
-    #[msg("Insufficient balance")]
-    InsufficientBalance,
-    #[msg("Deadline has not passed yet")]
-    DeadlineNotPassed,
-    #[msg("Invalid refund amounts: total exceeds sale price or both amounts are zero")]
-    InvalidRefundAmounts,
-    #[msg("Unauthorized settlement: only seller, winner, or admin can settle")]
-    UnauthorizedSettlement,
-    #[msg("Bid increment too small: must be at least 5% or 0.1 SOL")]
-    BidIncrementTooSmall,
-    #[msg("Contract is paused")]
-    ContractPaused,
-    #[msg("Fee too high: platform fee capped at 10%, dispute fee at 5%")]
-    FeeTooHigh,
-    #[msg("No pending change to execute")]
-    NoPendingChange,
-    #[msg("Timelock has not expired: must wait 48 hours")]
-    TimelockNotExpired,
-    #[msg("Seller has confirmed transfer: buyer must open dispute if there's an issue")]
-    MustOpenDispute,
-    #[msg("Transfer already confirmed by seller")]
-    AlreadyConfirmed,
-    #[msg("Not the owner of this withdrawal")]
-    NotWithdrawalOwner,
-    #[msg("Not the owner of this offer")]
-    NotOfferOwner,
-    #[msg("Offer is not active")]
-    OfferNotActive,
-    #[msg("Offer has expired")]
-    OfferExpired,
-    #[msg("Offer has not expired yet")]
-    OfferNotExpired,
-    #[msg("Invalid deadline: must be in the future")]
-    InvalidDeadline,
-    #[msg("This is not an auction listing")]
-    NotAnAuction,
-    #[msg("Seller has not confirmed transfer yet")]
-    SellerNotConfirmed,
-    #[msg("Grace period has not expired: must wait 7 days after seller confirmation")]
-    GracePeriodNotExpired,
-    #[msg("Starting price must equal reserve price for reserve auctions")]
-    StartingPriceMustEqualReserve,
-    #[msg("Buy now price is required for BuyNow listings")]
-    BuyNowPriceRequired,
-    #[msg("Uploads not verified by backend")]
-    UploadsNotVerified,
-    #[msg("Uploads already verified")]
-    AlreadyVerified,
-    #[msg("Not backend authority")]
-    NotBackendAuthority,
-    #[msg("Bid below reserve price")]
-    BidBelowReserve,
-    #[msg("Cannot finalize disputed transaction")]
-    CannotFinalizeDisputed,
-    #[msg("Seller must sign to finalize")]
-    SellerMustSign,
-    #[msg("No bids to settle - use cancel_auction instead")]
-    NoBidsToSettle,
-    #[msg("Cannot cancel auction with active bids")]
-    CannotCancelWithBids,
-    #[msg("Cannot close escrow: pending withdrawals exist")]
-    PendingWithdrawalsExist,
-    #[msg("Transaction must be in Completed or Refunded state")]
-    TransactionNotComplete,
-    #[msg("Invalid GitHub username: max 39 chars, alphanumeric/hyphens, no start/end/consecutive hyphens")]
-    InvalidGithubUsername,
-    #[msg("Dispute deadline expired: must dispute within grace period")]
-    DisputeDeadlineExpired,
-    #[msg("Maximum bids per listing exceeded")]
-    MaxBidsExceeded,
-    #[msg("Maximum offers per listing exceeded")]
-    MaxOffersExceeded,
-    #[msg("Maximum consecutive offers from same buyer exceeded (max 10 without being outbid)")]
-    MaxConsecutiveOffersExceeded,
-    #[msg("Maximum consecutive bids from same bidder exceeded (max 10 without being outbid)")]
-    MaxConsecutiveBidsExceeded,
-    #[msg("Backend timeout not expired: must wait 30 days from seller confirmation")]
-    BackendTimeoutNotExpired,
-    #[msg("Only expected admin can initialize marketplace")]
-    NotExpectedAdmin,
-    #[msg("Partial refund amounts must equal sale price")]
-    PartialRefundMustEqualSalePrice,
-    #[msg("Dispute resolution timelock not expired: must wait 48 hours")]
-    DisputeTimelockNotExpired,
-    #[msg("Resolution already contested")]
-    AlreadyContested,
-    #[msg("Invalid offer seed: counter mismatch")]
-    InvalidOfferSeed,
-    #[msg("Invalid withdrawal ID: counter mismatch")]
-    InvalidWithdrawalId,
-    #[msg("Invalid payment mint: APP token fee discount requires actual SPL token transfer")]
-    InvalidPaymentMint,
-    #[msg("Invalid offer: offer does not belong to this listing")]
-    InvalidOffer,
-    #[msg("Unauthorized: only admin can perform this action")]
-    Unauthorized,
-    #[msg("Platform is paused")]
-    PlatformPaused,
-    #[msg("Withdrawal has not expired yet")]
-    WithdrawalNotExpired,
+                for policy in [
+                    DisputeFeePolicy::RefundOnFullRefund,
+                    DisputeFeePolicy::RefundPrevailingInitiator,
+                ] {
+                    for initiator_is_buyer in [true, false] {
+                        let (buyer, seller, treasury) = split_dispute_fee(
+                            &policy,
+                            &resolution,
+                            initiator_is_buyer,
+                            dispute_fee,
+                            sale_price,
+                        )
+                        .unwrap();
+                        assert_eq!(buyer + seller + treasury, dispute_fee);
+                    }
+                }
+            }
+        }
+    }
 }
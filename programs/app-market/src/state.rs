@@ -0,0 +1,1462 @@
+//! Program-owned account layouts
+
+use crate::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct MarketConfig {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub backend_authority: Pubkey,  // For verifying uploads
+    pub platform_fee_bps: u16,
+    pub dispute_fee_bps: u16,
+    pub total_volume: u64,
+    pub total_sales: u64,
+    pub paused: bool,
+    // SECURITY: Admin timelock fields
+    pub pending_treasury: Option<Pubkey>,
+    pub pending_treasury_at: Option<i64>,
+    pub pending_admin: Option<Pubkey>,
+    pub pending_admin_at: Option<i64>,
+    // Loyalty program rates (0 = disabled): points per SOL of SOL-denominated sales, points per bps
+    pub loyalty_points_per_sol: u64,
+    pub loyalty_points_per_bps: u64,
+    // Dispute appeals (window 0 = disabled)
+    pub appeal_window_seconds: i64,
+    pub appeal_fee_bps: u16,
+    // Dispute proposals require an ArbitratorSet quorum instead of the admin key
+    pub committee_resolution: bool,
+    // Arbitrator APP staking (min stake 0 = disabled)
+    pub min_arbitrator_stake: u64,
+    pub arbitrator_slash_bps: u16,
+    // Inactivity period after which a dispute initiator can claim the default ruling
+    pub dispute_sla_seconds: i64,
+    // How dispute fees are routed at execution (locked per dispute at open)
+    pub dispute_fee_policy: DisputeFeePolicy,
+    // Unclaimed withdrawals can be swept to treasury this long after expiry (0 = never)
+    pub escheat_period_seconds: i64,
+    // Share of each platform fee diverted to the insurance fund (0 = disabled)
+    pub insurance_fee_bps: u16,
+    // Fee-split vault receiving treasury fees while a fee split is active (None = treasury)
+    pub fee_vault: Option<Pubkey>,
+    // Hot monitoring key that can pause (never unpause or change anything else)
+    pub guardian: Option<Pubkey>,
+    // Why the marketplace is paused, and when user exits stop honouring the pause
+    #[max_len(200)] // MAX_PAUSE_REASON_LEN
+    pub pause_reason: String,
+    pub paused_until: Option<i64>,
+    // Volume circuit breaker (multiple 0 = disabled): a soft pause trips when today's sale
+    // volume, or a single slot's, exceeds multiple x the trailing daily average
+    pub circuit_breaker_multiple: u64,
+    pub circuit_breaker_tripped: bool,
+    pub volume_day_start: i64,
+    pub volume_today: u64,
+    pub volume_slot: u64,
+    pub volume_this_slot: u64,
+    // Exponential moving average of daily sale volume (~7 day window)
+    pub trailing_daily_volume: u64,
+    // Sanity cap on listing/sale prices (None = no cap); sellers above it need a PriceCapOverride
+    pub max_sale_price: Option<u64>,
+    // Paid featured placement: lamports per day (0 = promotions disabled) and total collected
+    pub promotion_fee_per_day: u64,
+    pub total_promotion_fees: u64,
+    // Cap on escrow a single wallet can have locked in open bids (0 = no cap)
+    pub max_bidder_exposure: u64,
+    // FeeSchedule exists: listing creation must pass it to lock per-category rates
+    pub has_fee_schedule: bool,
+    // Treasury vault withdrawals queued so far (next TreasuryWithdrawal id)
+    pub treasury_withdrawal_count: u64,
+    pub bump: u8,
+    // Anti-snipe extensions new auctions allow before end_time becomes hard (0 = no cap)
+    pub max_anti_snipe_extensions: u16,
+    // SPL stake pool buyers may stake their escrow into (None = escrow yield disabled)
+    pub escrow_stake_pool: Option<Pubkey>,
+    // Buyer's share of escrow staking yield; the rest goes to the fee destination
+    pub escrow_yield_buyer_bps: u16,
+    // Offer deadlines must fall this far ahead of make_offer (0 = compiled defaults)
+    pub min_offer_duration_seconds: i64,
+    pub max_offer_duration_seconds: i64,
+    // Disputes over sales below this take the small-claims fast track (0 = disabled)
+    pub small_claims_threshold: u64,
+    // Respondents may post a bond and risk default judgment without one (off by default)
+    pub respondent_bonds_enabled: bool,
+}
+
+impl MarketConfig {
+    /// Whether a dispute over a sale at this price qualifies for the small-claims fast track
+    pub fn is_small_claim(&self, sale_price: u64) -> bool {
+        self.small_claims_threshold > 0 && sale_price < self.small_claims_threshold
+    }
+
+    /// Shortest and longest offer lifetimes make_offer accepts
+    pub fn offer_duration_bounds(&self) -> (i64, i64) {
+        let or_default = |value: i64, default: i64| if value == 0 { default } else { value };
+        (
+            or_default(self.min_offer_duration_seconds, DEFAULT_MIN_OFFER_DURATION_SECONDS),
+            or_default(self.max_offer_duration_seconds, DEFAULT_MAX_OFFER_DURATION_SECONDS),
+        )
+    }
+
+    /// Where platform, dispute and appeal fees are paid: the fee-split vault while a
+    /// fee split is active, otherwise the treasury
+    pub fn fee_destination(&self) -> Pubkey {
+        self.fee_vault.unwrap_or(self.treasury)
+    }
+
+    /// Which pause flag blocks new activity, if any: paused by the admin/guardian, or
+    /// soft-paused by the volume circuit breaker
+    pub fn active_pause(&self) -> Option<PauseFlag> {
+        if self.paused {
+            Some(PauseFlag::Admin)
+        } else if self.circuit_breaker_tripped {
+            Some(PauseFlag::CircuitBreaker)
+        } else {
+            None
+        }
+    }
+
+    /// Fail with ContractPaused while new activity is paused
+    pub fn require_active(&self) -> Result<()> {
+        match self.active_pause() {
+            Some(flag) => Err(self.pause_error(flag)),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail with ContractPaused while user exits are paused (see exits_paused)
+    pub fn require_exits_open(&self, now: i64) -> Result<()> {
+        if self.exits_paused(now) {
+            return Err(self.pause_error(PauseFlag::Admin));
+        }
+        Ok(())
+    }
+
+    /// Every pause check returns ContractPaused; the log names the flag that blocked the call
+    fn pause_error(&self, flag: PauseFlag) -> Error {
+        msg!("Paused: flag {:?} (reason code {}): {}", flag, flag as u8, self.pause_reason);
+        AppMarketError::ContractPaused.into()
+    }
+
+    /// Add completed sale volume to the rolling windows and trip the circuit breaker
+    /// on an anomalous spike (the completing instruction itself still succeeds)
+    pub fn record_sale_volume(&mut self, amount: u64, actor: Pubkey) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let day = SECONDS_PER_DAY;
+
+        // Roll the daily window, folding finished days into the trailing average
+        if self.volume_day_start == 0 {
+            self.volume_day_start = now - now.rem_euclid(day);
+        }
+        let days_elapsed = (now - self.volume_day_start) / day;
+        if days_elapsed > 0 {
+            self.trailing_daily_volume = ((self.trailing_daily_volume as u128 * 6
+                + self.volume_today as u128)
+                / 7) as u64;
+            for _ in 1..days_elapsed.min(7) {
+                self.trailing_daily_volume = ((self.trailing_daily_volume as u128 * 6) / 7) as u64;
+            }
+            self.volume_today = 0;
+            self.volume_day_start += days_elapsed * day;
+        }
+        if self.volume_slot != clock.slot {
+            self.volume_slot = clock.slot;
+            self.volume_this_slot = 0;
+        }
+
+        self.volume_today = self.volume_today.saturating_add(amount);
+        self.volume_this_slot = self.volume_this_slot.saturating_add(amount);
+
+        // No baseline yet (or disabled): nothing to compare against
+        let average = self.trailing_daily_volume;
+        if self.circuit_breaker_multiple == 0 || average == 0 || self.circuit_breaker_tripped {
+            return Ok(());
+        }
+        let limit = average.saturating_mul(self.circuit_breaker_multiple);
+        if self.volume_today > limit || self.volume_this_slot > limit {
+            self.circuit_breaker_tripped = true;
+
+            emit!(CircuitBreakerTripped {
+                volume_today: self.volume_today,
+                volume_this_slot: self.volume_this_slot,
+                trailing_daily_volume: average,
+                actor,
+                timestamp: now,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Pause check for user-exit paths: once paused_until passes, exits work again
+    /// even if the admin never unpauses (the circuit breaker never blocks exits)
+    pub fn exits_paused(&self, now: i64) -> bool {
+        match self.paused_until {
+            Some(until) => self.paused && now < until,
+            None => self.paused,
+        }
+    }
+}
+
+/// APP tokens staked by an arbitrator (held in the stake_vault PDA)
+#[account]
+#[derive(InitSpace)]
+pub struct ArbitratorStake {
+    pub version: u8,
+    pub arbitrator: Pubkey,
+    pub amount: u64,
+    // Pending unstake request (cooldown runs from unstake_requested_at)
+    pub unstake_amount: u64,
+    pub unstake_requested_at: Option<i64>,
+    pub total_slashed: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl ArbitratorStake {
+    /// Stake counting toward voting eligibility (excludes amounts being unstaked)
+    pub fn active_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.unstake_amount)
+    }
+}
+
+/// Dispute resolution committee (singleton)
+#[account]
+#[derive(InitSpace)]
+pub struct ArbitratorSet {
+    pub version: u8,
+    #[max_len(7)] // MAX_ARBITRATORS
+    pub arbitrators: Vec<Pubkey>,
+    pub quorum: u8,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Listing {
+    pub version: u8,
+    pub seller: Pubkey,
+    // Listing PDA seed; the human-readable ID ("{seller}-{salt}") is only emitted in events
+    pub salt: u64,
+    pub listing_type: ListingType,
+    pub starting_price: u64,
+    pub reserve_price: Option<u64>,
+    pub buy_now_price: Option<u64>,
+    pub current_bid: u64,
+    pub current_bidder: Option<Pubkey>,
+    pub created_at: i64,
+    // SECURITY: Auction timing fields
+    pub auction_started: bool,
+    pub auction_start_time: Option<i64>,
+    // Scheduled drop: no bids, offers or purchases before this time
+    pub start_time: i64,
+    // Set while the seller has paused the listing (the window is extended on resume)
+    pub paused_at: Option<i64>,
+    // Delegated manager (broker/agency) acting for the seller; never receives funds
+    pub operator: Option<Pubkey>,
+    // Bids and purchases need a backend BuyerAttestation (anti-bot gate)
+    pub requires_verification: bool,
+    pub end_time: i64,
+    pub duration_seconds: i64,
+    pub status: ListingStatus,
+    // SECURITY: Lock fees at listing creation
+    pub platform_fee_bps: u16,
+    pub dispute_fee_bps: u16,
+    // GitHub requirements
+    pub requires_github: bool,
+    #[max_len(64)]
+    pub required_github_username: String,
+    // Withdrawal counter for unique PDA seeds
+    pub withdrawal_count: u64,
+    // Offer counter for tracking total offers
+    pub offer_count: u32,
+    // Track consecutive offers from same buyer
+    pub last_offer_buyer: Option<Pubkey>,
+    pub consecutive_offer_count: u32,
+    // Track consecutive bids from same bidder
+    pub last_bidder: Option<Pubkey>,
+    pub consecutive_bid_count: u32,
+    // Payment currency (None = SOL, Some = SPL token mint)
+    pub payment_mint: Option<Pubkey>,
+    // Decimals of prices on this listing (9 for SOL, the mint's decimals for SPL)
+    pub payment_decimals: u8,
+    // Deposit-mode auction: bids lock this share of the bid (0 = full-price escrow)
+    pub deposit_bps: u16,
+    // Earn-out: seller proceeds vest over this many days after completion (0 = none)
+    pub vesting_days: u16,
+    // Warranty: share of seller proceeds held in escrow after completion (0 = none)
+    pub holdback_bps: u16,
+    // Wallet receiving seller proceeds and escrow rent (changes are timelocked, pre-sale only)
+    pub payout_wallet: Pubkey,
+    pub pending_payout_wallet: Option<Pubkey>,
+    pub pending_payout_wallet_at: Option<i64>,
+    // Proceeds are split per the listing's PayoutSplit account (set once by create_payout_split)
+    pub has_payout_split: bool,
+    // Seller-designated neutral arbitrator for disputes (None = platform admin)
+    pub arbitrator: Option<Pubkey>,
+    // Times this listing was relisted in place (listing_id unchanged)
+    pub relist_count: u32,
+    // Multi-unit listings: units offered and units sold so far (also the next unit index)
+    pub quantity: u64,
+    pub units_sold: u64,
+    // Bids must update the listing's BidBook (set once by init_bid_book)
+    pub has_bid_book: bool,
+    // Bundle listings: identifiers of the bundled apps (empty = single app)
+    #[max_len(10)] // MAX_BUNDLE_ASSETS
+    pub bundle_assets: Vec<[u8; 32]>,
+    // Discovery tags (lowercase a-z, 0-9, '-'), editable until the first bid
+    #[max_len(5, 24)] // MAX_LISTING_TAGS, MAX_TAG_LEN
+    pub tags: Vec<String>,
+    // Vertical the app is listed under (selects the FeeSchedule rate)
+    pub category: Category,
+    // Paid featured placement ends at this time (0 = never promoted); indexers rank by it
+    pub featured_until: i64,
+    pub bump: u8,
+    // Bids sit in per-bidder BidEscrow PDAs, not the listing escrow (set once by enable_bid_escrows)
+    pub uses_bid_escrows: bool,
+    // Anti-snipe extensions so far, and the cap locked at creation (0 = no cap)
+    pub extension_count: u16,
+    pub max_extensions: u16,
+    // Offers below this are rejected at make_offer (0 = any amount)
+    pub min_offer: u64,
+    // Offers at or above this become a sale inside make_offer (None = seller accepts)
+    pub auto_accept_price: Option<u64>,
+    // Highest live offer (Offer account); cleared when it leaves and refilled by the next offer
+    pub best_offer: Option<Pubkey>,
+    pub best_offer_amount: u64,
+    // Buy-now price drop schedule: buy_now_price falls by price_drop_amount every
+    // price_drop_interval seconds since created_at, never below price_floor (0 interval = off)
+    pub price_drop_amount: u64,
+    pub price_drop_interval: i64,
+    pub price_floor: u64,
+    // Alternative buy-now prices in other currencies (empty = payment_mint only);
+    // bids and offers stay in the primary currency
+    #[max_len(4)] // MAX_ACCEPTED_CURRENCIES
+    pub accepted_currencies: Vec<CurrencyPrice>,
+    // Exposure the current high bid counts in its bidder's UserProfile (0 = no profile passed)
+    pub current_bid_exposure: u64,
+}
+
+impl Listing {
+    /// Human-readable listing ID for events and off-chain indexing
+    pub fn listing_id(&self) -> String {
+        format!("{}-{}", self.seller, self.salt)
+    }
+
+    /// Payment currency key for per-currency stats (Pubkey::default() = native SOL)
+    pub fn payment_currency(&self) -> Pubkey {
+        self.payment_mint.unwrap_or_default()
+    }
+
+    /// Listing sells several units via buy_unit (legacy listings have quantity 0)
+    pub fn is_multi_unit(&self) -> bool {
+        self.quantity > 1
+    }
+
+    /// Absolute minimum bid increment in this listing's price units
+    /// (0.1 SOL, or 0.1 whole tokens scaled to the payment mint's decimals)
+    pub fn min_bid_increment(&self) -> Result<u64> {
+        if self.payment_mint.is_none() {
+            return Ok(MIN_BID_INCREMENT_LAMPORTS);
+        }
+        scale_native_amount(MIN_BID_INCREMENT_LAMPORTS, self.payment_decimals)
+    }
+
+    /// Seller, or the operator the seller delegated listing management to
+    pub fn can_manage(&self, key: Pubkey) -> bool {
+        key == self.seller || Some(key) == self.operator
+    }
+
+    /// Scheduled start reached (legacy listings have start_time 0)
+    pub fn has_started(&self, now: i64) -> bool {
+        now >= self.start_time
+    }
+
+    /// OpenOffers listings never expire (end_time is i64::MAX)
+    pub fn is_open_ended(&self) -> bool {
+        self.listing_type == ListingType::OpenOffers
+    }
+
+    /// end_time for a sale window of `duration_seconds` opening at `start`
+    pub fn sale_window_end(&self, start: i64, duration_seconds: i64) -> Result<i64> {
+        if self.is_open_ended() {
+            return Ok(i64::MAX);
+        }
+        Ok(start
+            .checked_add(duration_seconds)
+            .ok_or(AppMarketError::MathOverflow)?)
+    }
+
+    /// Buy-now price at `now`, after any scheduled price drops
+    pub fn buy_now_price_at(&self, now: i64) -> Option<u64> {
+        let price = self.buy_now_price?;
+        if self.price_drop_interval == 0 {
+            return Some(price);
+        }
+        let steps = (now.saturating_sub(self.created_at) / self.price_drop_interval).max(0) as u64;
+        let dropped = steps.saturating_mul(self.price_drop_amount);
+        Some(price.saturating_sub(dropped).max(self.price_floor))
+    }
+
+    /// Buy-now price when paying in `currency` (Pubkey::default() = native SOL)
+    /// Price drops apply to the primary currency only.
+    pub fn buy_now_price_in(&self, currency: Pubkey, now: i64) -> Result<u64> {
+        if self.charges_primary_price(currency) {
+            return Ok(self.buy_now_price_at(now).ok_or(AppMarketError::BuyNowNotEnabled)?);
+        }
+        self.alternative_price(currency)
+    }
+
+    /// Listed buy-now price in `currency`, before any price drop (buyer pools lock this)
+    pub fn list_price_in(&self, currency: Pubkey) -> Result<u64> {
+        if self.charges_primary_price(currency) {
+            return Ok(self.buy_now_price.ok_or(AppMarketError::BuyNowNotEnabled)?);
+        }
+        self.alternative_price(currency)
+    }
+
+    /// Single-currency listings charge their own price whatever the currency, as before
+    fn charges_primary_price(&self, currency: Pubkey) -> bool {
+        currency == self.payment_currency() || self.accepted_currencies.is_empty()
+    }
+
+    fn alternative_price(&self, currency: Pubkey) -> Result<u64> {
+        Ok(self.accepted_currencies
+            .iter()
+            .find(|c| c.mint == currency)
+            .map(|c| c.price)
+            .ok_or(AppMarketError::CurrencyNotAccepted)?)
+    }
+
+    /// Record a new offer; returns true if it became the best offer (ties keep the earlier one)
+    pub fn note_offer(&mut self, offer: Pubkey, amount: u64) -> bool {
+        if self.best_offer.is_some() && amount <= self.best_offer_amount {
+            return false;
+        }
+        self.best_offer = Some(offer);
+        self.best_offer_amount = amount;
+        true
+    }
+
+    /// Forget an offer that is no longer live; returns true if it was the best offer
+    pub fn drop_offer(&mut self, offer: Pubkey) -> bool {
+        self.best_offer == Some(offer) && self.clear_best_offer()
+    }
+
+    /// Forget the best offer (the listing sold); returns true if there was one
+    pub fn clear_best_offer(&mut self) -> bool {
+        self.best_offer_amount = 0;
+        self.best_offer.take().is_some()
+    }
+}
+
+/// Highest bids on a listing, best first (one entry per bidder)
+#[account(zero_copy)]
+pub struct BidBook {
+    pub listing: Pubkey,
+    pub entries: [BidEntry; 5], // BID_BOOK_SIZE
+    pub count: u8,
+    pub bump: u8,
+    pub version: u8,
+    // Bids must also update the listing's BidHistory (set once by init_bid_history)
+    pub has_history: u8,
+    pub _padding: [u8; 4],
+}
+
+#[zero_copy]
+pub struct BidEntry {
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+impl BidBook {
+    pub fn clear(&mut self) {
+        self.entries = [BidEntry { bidder: Pubkey::default(), amount: 0 }; BID_BOOK_SIZE];
+        self.count = 0;
+    }
+
+    /// Record a new high bid: it goes on top, replacing the bidder's older entry
+    pub fn record_bid(&mut self, bidder: Pubkey, amount: u64) {
+        let count = self.count as usize;
+        let end = self.entries[..count]
+            .iter()
+            .position(|entry| entry.bidder == bidder)
+            .unwrap_or(count.min(BID_BOOK_SIZE - 1));
+        self.entries.copy_within(0..end, 1);
+        self.entries[0] = BidEntry { bidder, amount };
+        self.count = (count + usize::from(end == count)).min(BID_BOOK_SIZE) as u8;
+    }
+}
+
+/// Ring buffer of a listing's most recent bids (zero-copy, so recording a bid costs the
+/// same however long the history grows)
+#[account(zero_copy)]
+pub struct BidHistory {
+    pub listing: Pubkey,
+    pub entries: [BidRecord; 32], // BID_HISTORY_SIZE
+    // Bids recorded since the listing was last (re)activated; the newest is at
+    // entries[(total_bids - 1) % BID_HISTORY_SIZE]
+    pub total_bids: u64,
+    pub bump: u8,
+    pub version: u8,
+    pub _padding: [u8; 6],
+}
+
+#[zero_copy]
+pub struct BidRecord {
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+impl BidHistory {
+    pub fn clear(&mut self) {
+        self.total_bids = 0;
+    }
+
+    /// Append a bid, overwriting the oldest once the buffer is full
+    pub fn record_bid(&mut self, bidder: Pubkey, amount: u64, timestamp: i64) {
+        let slot = (self.total_bids % BID_HISTORY_SIZE as u64) as usize;
+        self.entries[slot] = BidRecord { bidder, amount, timestamp };
+        self.total_bids = self.total_bids.saturating_add(1);
+    }
+}
+
+/// One bidder's funds on a bid-escrow auction (see enable_bid_escrows)
+#[account]
+#[derive(InitSpace)]
+pub struct BidEscrow {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub bidder: Pubkey,
+    // Bid funds held, excluding rent
+    pub amount: u64,
+    // Winning bid already moved to the listing escrow; only any surplus remains
+    pub committed: bool,
+    pub bump: u8,
+}
+
+/// Escrowed SOL deposited into the configured stake pool (see stake_escrow). Releases and
+/// refunds unwind it in place (unwind_escrow_stake); unstake_escrow unwinds it early.
+#[account]
+#[derive(InitSpace)]
+pub struct EscrowStake {
+    pub version: u8,
+    pub escrow: Pubkey,
+    pub transaction: Pubkey,
+    pub stake_pool: Pubkey,
+    // Lamports taken from the escrow (owed back in full at unstake)
+    pub principal: u64,
+    // Pool tokens minted for the principal, held by the escrow_stake_tokens account
+    pub pool_tokens: u64,
+    pub rent_payer: Pubkey,
+    pub staked_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+/// Revenue-share payments after a sale: the buyer funds fixed periods ahead of time and
+/// each releases to the seller once it has run (see create_recurring_escrow). Period i is
+/// due at start_time + (i + 1) * period_seconds; the PDA holds funded, unsettled periods.
+#[account]
+#[derive(InitSpace)]
+pub struct RecurringEscrow {
+    pub version: u8,
+    pub transaction: Pubkey,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub seller: Pubkey,
+    // Resolves period disputes (None = platform admin)
+    pub arbitrator: Option<Pubkey>,
+    pub amount_per_period: u64,
+    pub period_seconds: i64,
+    pub total_periods: u16,
+    // Periods paid in by the buyer (waived periods count as funded)
+    pub funded_periods: u16,
+    // Periods released, skipped or refunded; the next one to settle is this index
+    pub settled_periods: u16,
+    // Platform fee on each release, locked from the listing
+    pub platform_fee_bps: u16,
+    pub start_time: i64,
+    pub status: RecurringStatus,
+    // Period held back from release until resolved
+    pub disputed_period: Option<u16>,
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl RecurringEscrow {
+    /// When period `index` may be released
+    pub fn due_at(&self, index: u16) -> Result<i64> {
+        (index as i64 + 1)
+            .checked_mul(self.period_seconds)
+            .and_then(|offset| self.start_time.checked_add(offset))
+            .ok_or(AppMarketError::MathOverflow.into())
+    }
+
+    /// Periods whose due time has passed by `now`
+    pub fn periods_due(&self, now: i64) -> u16 {
+        let elapsed = now.saturating_sub(self.start_time).max(0) / self.period_seconds;
+        elapsed.min(self.total_periods as i64) as u16
+    }
+}
+
+/// Rent-to-own sale of a listing: the buyer pays `upfront_amount` to take operational
+/// control while this PDA holds the listing's AppDeed, then `installments` payments one
+/// period apart. The final payment transfers the deed and pays the seller; a default
+/// returns the deed and splits what was paid by forfeiture_bps. Installment i is due at
+/// start_time + (i + 1) * period_seconds. The PDA holds every payment until then.
+#[account]
+#[derive(InitSpace)]
+pub struct RentToOwn {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Option<Pubkey>,
+    pub upfront_amount: u64,
+    pub installment_amount: u64,
+    pub installments: u16,
+    pub period_seconds: i64,
+    // Share of the payments the seller keeps on default (the buyer gets the rest back)
+    pub forfeiture_bps: u16,
+    pub installments_paid: u16,
+    pub total_paid: u64,
+    pub start_time: i64,
+    pub status: RentToOwnStatus,
+    pub deed: Pubkey,
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl RentToOwn {
+    /// When installment `index` is due
+    pub fn due_at(&self, index: u16) -> Result<i64> {
+        (index as i64 + 1)
+            .checked_mul(self.period_seconds)
+            .and_then(|offset| self.start_time.checked_add(offset))
+            .ok_or(AppMarketError::MathOverflow.into())
+    }
+}
+
+/// Crowdfunding pool buying a listing on behalf of its contributors. Any number of pools
+/// can target a listing (PDA per creator and pool_id); the creator signs buyer-side
+/// actions for the pool vault once it has bought (Transaction.buyer_delegate).
+#[account]
+#[derive(InitSpace)]
+pub struct BuyerPool {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub creator: Pubkey,
+    pub pool_id: u64,
+    pub share_mint: Pubkey,
+    pub target_amount: u64,
+    pub total_contributed: u64,
+    // Contributions already refunded or converted to shares
+    pub settled_amount: u64,
+    pub deadline: i64,
+    // Purchase transaction (None until the pool executes)
+    pub transaction: Option<Pubkey>,
+    // Vault balance snapshot after the sale is final (refunds owed pro rata)
+    pub returned_amount: Option<u64>,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub mint_bump: u8,
+}
+
+/// On-chain title record for a listed app (issue_app_deed). The app itself still moves
+/// off-chain; the deed records who holds title and only the program moves it: into a
+/// FractionVault or RentToOwn and back out, or to the buyer of a completed sale.
+#[account]
+#[derive(InitSpace)]
+pub struct AppDeed {
+    pub version: u8,
+    pub listing: Pubkey,
+    // Wallet, or the FractionVault / RentToOwn PDA holding it in custody
+    pub holder: Pubkey,
+    pub issued_at: i64,
+    pub bump: u8,
+}
+
+/// Fractionalized listing: holds the listing's AppDeed while share tokens are sold through
+/// the listing escrow. A buyout pays the shareholders' pot into this account and takes the deed.
+#[account]
+#[derive(InitSpace)]
+pub struct FractionVault {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub deed: Pubkey,
+    pub seller: Pubkey,
+    pub share_mint: Pubkey,
+    pub total_shares: u64,
+    pub shares_sold: u64,
+    pub share_price: u64,
+    pub buyout_price: u64,
+    pub bought_out_by: Option<Pubkey>,
+    // Buyout proceeds owed to holders of the sold shares (redeem_fraction_shares)
+    pub holder_pot: u64,
+    pub shares_redeemed: u64,
+    pub redeemed_amount: u64,
+    pub bump: u8,
+    pub mint_bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PoolContribution {
+    pub version: u8,
+    pub pool: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Listing proceeds split: recipient wallets are passed as remaining accounts, in order,
+/// to every instruction that pays seller proceeds
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutSplit {
+    pub version: u8,
+    pub listing: Pubkey,
+    #[max_len(5)] // MAX_SPLIT_RECIPIENTS
+    pub recipients: Vec<SplitRecipient>,
+    pub bump: u8,
+}
+
+/// One verification attempt on a transaction (rejected, or passed with a report)
+#[account]
+#[derive(InitSpace)]
+pub struct VerificationRound {
+    pub version: u8,
+    pub transaction: Pubkey,
+    pub round: u16,
+    pub passed: bool,
+    pub report: Option<VerificationReport>,
+    pub rejection_code: Option<u16>,
+    // Deliverables recorded on the transaction when this round was decided
+    pub deliverable_count: u8,
+    pub recorded_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct VerificationReport {
+    // Merkle root over the verified items (zero for emergency/legacy verification)
+    pub merkle_root: [u8; 32],
+    pub item_count: u16,
+    pub verifier: Pubkey,
+    pub method: VerificationMethod,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct SplitRecipient {
+    pub wallet: Pubkey,
+    pub bps: u64,
+}
+
+/// Backend proof that a buyer passed a human/KYC check (gates requires_verification listings)
+#[account]
+#[derive(InitSpace)]
+pub struct BuyerAttestation {
+    pub version: u8,
+    pub buyer: Pubkey,
+    pub attested_at: i64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+/// Ephemeral bidding key authorized by a buyer, spending from a pre-funded vault
+#[account]
+#[derive(InitSpace)]
+pub struct BidSession {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub max_spend: u64,
+    pub spent: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl BidSession {
+    /// Spend still available to the session key
+    pub fn remaining(&self) -> u64 {
+        self.max_spend.saturating_sub(self.spent)
+    }
+}
+
+/// Admin approval for one seller to list above config.max_sale_price
+#[account]
+#[derive(InitSpace)]
+pub struct PriceCapOverride {
+    pub version: u8,
+    pub seller: Pubkey,
+    pub max_price: u64,
+    pub approved_at: i64,
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+/// Platform fee split (SOL collected in the fee_vault PDA while active)
+#[account]
+#[derive(InitSpace)]
+pub struct FeeSplit {
+    pub version: u8,
+    // Current recipients (empty = inactive, fees go to the treasury)
+    #[max_len(5)] // MAX_FEE_SPLIT_RECIPIENTS
+    pub recipients: Vec<FeeRecipient>,
+    // SECURITY: Timelocked change (Some(empty) proposes disabling the split)
+    #[max_len(5)] // MAX_FEE_SPLIT_RECIPIENTS
+    pub pending_recipients: Option<Vec<FeeRecipient>>,
+    pub pending_at: Option<i64>,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct FeeRecipient {
+    pub wallet: Pubkey,
+    pub bps: u64,
+}
+
+/// Fixed buy-now price in a listing's alternative currency (raw units of that mint)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct CurrencyPrice {
+    // Pubkey::default() = native SOL
+    pub mint: Pubkey,
+    pub price: u64,
+}
+
+/// One arbitrator's ballot in the current committee round
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct ResolutionVote {
+    pub arbitrator: Pubkey,
+    pub resolution: DisputeResolution,
+}
+
+/// APP staker revenue share (SOL rewards in the rewards_vault PDA, APP in rewards_stake_vault)
+#[account]
+#[derive(InitSpace)]
+pub struct RewardsPool {
+    pub version: u8,
+    pub total_staked: u64,
+    // Cumulative rewards per staked APP unit, scaled by REWARD_PRECISION
+    pub reward_per_share: u128,
+    // Vault balance already folded into reward_per_share and not yet claimed
+    pub accounted_balance: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub stake_vault_bump: u8,
+}
+
+impl RewardsPool {
+    /// Fold fees that reached the vault since the last sync into reward_per_share.
+    /// With nothing staked, new fees wait for the next sync that has stakers.
+    pub fn sync(&mut self, available: u64) -> Result<()> {
+        let new_rewards = available.saturating_sub(self.accounted_balance);
+        if new_rewards == 0 || self.total_staked == 0 {
+            return Ok(());
+        }
+        let increment = (new_rewards as u128)
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(self.total_staked as u128)
+            .ok_or(AppMarketError::MathOverflow)?;
+        self.reward_per_share = self.reward_per_share
+            .checked_add(increment)
+            .ok_or(AppMarketError::MathOverflow)?;
+        self.accounted_balance = available;
+        Ok(())
+    }
+}
+
+/// APP tokens a user has staked for rewards
+#[account]
+#[derive(InitSpace)]
+pub struct RewardStake {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub amount: u64,
+    // amount * reward_per_share at the last settlement (scaled)
+    pub reward_debt: u128,
+    // Rewards settled but not yet claimed
+    pub pending_rewards: u64,
+    pub bump: u8,
+}
+
+impl RewardStake {
+    fn accrued(&self, reward_per_share: u128) -> Result<u128> {
+        (self.amount as u128)
+            .checked_mul(reward_per_share)
+            .ok_or(AppMarketError::MathOverflow.into())
+    }
+
+    /// Move rewards earned since the last settlement into pending_rewards
+    pub fn settle(&mut self, reward_per_share: u128) -> Result<()> {
+        let earned = self.accrued(reward_per_share)?
+            .saturating_sub(self.reward_debt)
+            / REWARD_PRECISION;
+        let earned = u64::try_from(earned).map_err(|_| AppMarketError::MathOverflow)?;
+        self.pending_rewards = self.pending_rewards
+            .checked_add(earned)
+            .ok_or(AppMarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Restart accrual from the current reward_per_share (call after settle and any amount change)
+    pub fn reset_debt(&mut self, reward_per_share: u128) -> Result<()> {
+        self.reward_debt = self.accrued(reward_per_share)?;
+        Ok(())
+    }
+}
+
+/// Platform insurance fund (SOL held in the insurance_vault PDA)
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceFund {
+    pub version: u8,
+    pub claims_filed: u64,
+    pub claims_paid: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+/// Buyer claim against the insurance fund (one per transaction)
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceClaim {
+    pub version: u8,
+    pub transaction: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+    #[max_len(500)]
+    pub reason: String,
+    pub status: ClaimStatus,
+    pub filed_at: i64,
+    pub resolved_at: Option<i64>,
+    pub payout: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl Escrow {
+    /// Increase tracked escrow (funds arrived) and emit the audit event
+    pub fn credit(&mut self, amount: u64, reason: EscrowChangeReason, actor: Pubkey) -> Result<()> {
+        self.amount = self.amount
+            .checked_add(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+        self.emit_change(amount as i128, reason, actor)
+    }
+
+    /// Decrease tracked escrow (funds left) and emit the audit event
+    pub fn debit(&mut self, amount: u64, reason: EscrowChangeReason, actor: Pubkey) -> Result<()> {
+        self.amount = self.amount
+            .checked_sub(amount)
+            .ok_or(AppMarketError::MathOverflow)?;
+        self.emit_change(-(amount as i128), reason, actor)
+    }
+
+    fn emit_change(&self, delta: i128, reason: EscrowChangeReason, actor: Pubkey) -> Result<()> {
+        emit!(EscrowBalanceChanged {
+            listing: self.listing,
+            delta: i64::try_from(delta).map_err(|_| AppMarketError::MathOverflow)?,
+            new_amount: self.amount,
+            reason,
+            actor,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Transaction {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    // Wallet that funded the purchase (differs from buyer for gift/third-party purchases)
+    pub payer: Pubkey,
+    pub sale_price: u64,
+    pub platform_fee: u64,
+    pub seller_proceeds: u64,
+    pub status: TransactionStatus,
+    pub transfer_deadline: i64,
+    pub created_at: i64,
+    // SECURITY: Seller confirmation fields
+    pub seller_confirmed_transfer: bool,
+    pub seller_confirmed_at: Option<i64>,
+    pub completed_at: Option<i64>,
+    // Upload verification
+    pub uploads_verified: bool,
+    pub verification_timestamp: Option<i64>,
+    // Legacy free-form hash (verify_uploads only); see verification_report
+    #[max_len(64)]
+    pub verification_hash: String,
+    // Typed record of how the uploads were verified
+    pub verification_report: Option<VerificationReport>,
+    // Backend rejected the uploads (reason code is defined off-chain)
+    pub uploads_rejected_at: Option<i64>,
+    pub rejection_code: Option<u16>,
+    // VerificationRound records written so far (next round index)
+    pub verification_rounds: u16,
+    // Deposit-mode auctions: deposit already escrowed and deadline to pay the remainder
+    pub deposit_amount: u64,
+    pub payment_deadline: Option<i64>,
+    // Streamed seller proceeds (vesting_duration 0 = paid in full at completion)
+    pub vesting_duration: i64,
+    pub vesting_start: Option<i64>,
+    pub vested_claimed: u64,
+    // Warranty holdback still escrowed after completion, claimable by the seller once released
+    pub holdback_amount: u64,
+    pub holdback_release_at: Option<i64>,
+    // Buyer opened a warranty dispute over the holdback (admin/arbitrator decides the split)
+    pub warranty_disputed_at: Option<i64>,
+    // Mutual cancellation proposal (either party proposes, the other accepts)
+    pub cancel_proposed_by: Option<Pubkey>,
+    pub cancel_relist: bool,
+    // Neutral arbitrator with authority over dispute proposals (None = platform admin)
+    pub arbitrator: Option<Pubkey>,
+    // Multi-unit sales: unit index (extra PDA seed); None for single-sale listings
+    pub unit_index: Option<u64>,
+    pub unit_index_seed: [u8; 8],
+    // Bundle transfer checklist: bit i set once bundle asset i is transferred
+    pub bundle_confirmed: u16,
+    // Content hashes / IPFS CIDs of delivered artifacts, recorded before seller confirmation
+    #[max_len(8, 64)] // MAX_DELIVERABLES, MAX_DELIVERABLE_LEN
+    pub deliverables: Vec<String>,
+    // Hash of the off-chain purchase agreement (canonical reference for disputes)
+    pub agreement_hash: Option<[u8; 32]>,
+    // Parties' X25519 contact keys at creation (refreshed via sync_contact_keys)
+    pub buyer_contact_key: Option<[u8; 32]>,
+    pub seller_contact_key: Option<[u8; 32]>,
+    // Bid escrow counted in the buyer's UserProfile exposure until release_bid_exposure
+    pub bid_exposure: u64,
+    pub bump: u8,
+    // Escrowed SOL is in the stake pool; payout paths unwind it first (unwind_escrow_stake)
+    pub escrow_staked: bool,
+    // Signs buyer-side actions for a buyer that cannot sign (a buyer pool's vault PDA)
+    pub buyer_delegate: Option<Pubkey>,
+    // Buyer disputed the earn-out: vesting stops here until resolve_vesting_dispute
+    pub vesting_disputed_at: Option<i64>,
+}
+
+impl Transaction {
+    /// Seller proceeds vested by `now` (linear from vesting_start; a vesting dispute stops the clock)
+    pub fn vested_proceeds(&self, now: i64) -> Result<u64> {
+        let vesting_start = self.vesting_start.ok_or(AppMarketError::NoVestingSchedule)?;
+        let until = self.vesting_disputed_at.map_or(now, |disputed_at| disputed_at.min(now));
+        let elapsed = until
+            .saturating_sub(vesting_start)
+            .clamp(0, self.vesting_duration);
+
+        // u128 intermediate: proceeds * elapsed can exceed u64
+        let vested = (self.seller_proceeds as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(AppMarketError::MathOverflow)?
+            .checked_div(self.vesting_duration as u128)
+            .ok_or(AppMarketError::MathOverflow)?;
+        u64::try_from(vested).map_err(|_| AppMarketError::MathOverflow.into())
+    }
+
+    /// Wallet that signs for the buyer side: the delegate when set, otherwise the buyer
+    pub fn buyer_signer(&self) -> Pubkey {
+        self.buyer_delegate.unwrap_or(self.buyer)
+    }
+
+    /// Whether `key` acts for the buyer side (funds still go to `buyer`)
+    pub fn is_buyer_signer(&self, key: &Pubkey) -> bool {
+        *key == self.buyer || self.buyer_delegate == Some(*key)
+    }
+
+    /// Extra PDA seed for unit sales; empty for single-sale transactions
+    pub fn unit_seed(&self) -> &[u8] {
+        if self.unit_index.is_some() {
+            &self.unit_index_seed
+        } else {
+            &[]
+        }
+    }
+
+    /// Mark uploads verified; a fixed delivery supersedes an earlier rejection
+    pub fn record_verification(&mut self, report: VerificationReport, now: i64) {
+        self.uploads_verified = true;
+        self.verification_timestamp = Some(now);
+        self.verification_report = Some(report);
+        self.uploads_rejected_at = None;
+        self.rejection_code = None;
+    }
+
+    /// Designated arbitrator, ignored if it is a party to the deal (e.g. the buyer)
+    pub fn designated_arbitrator(&self) -> Option<Pubkey> {
+        self.arbitrator
+            .filter(|arbitrator| *arbitrator != self.buyer && *arbitrator != self.seller)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub version: u8,
+    pub transaction: Pubkey,
+    pub initiator: Pubkey,
+    pub respondent: Pubkey,
+    #[max_len(500)]
+    pub reason: String,
+    pub status: DisputeStatus,
+    pub resolution: Option<DisputeResolution>,
+    #[max_len(1000)]
+    pub resolution_notes: Option<String>,
+    pub dispute_fee: u64,
+    pub created_at: i64,
+    pub resolved_at: Option<i64>,
+    // SECURITY: Timelock fields for dispute resolution
+    pub pending_resolution: Option<DisputeResolution>,
+    pub pending_buyer_amount: Option<u64>,
+    pub pending_seller_amount: Option<u64>,
+    pub pending_resolution_at: Option<i64>,
+    pub contested: bool,
+    // Appeal tracking (one appeal per dispute)
+    pub appeal_deadline: Option<i64>,
+    pub appealed_by: Option<Pubkey>,
+    pub appeal_fee: u64,
+    // Evidence submissions per party (index of the next Evidence PDA)
+    pub buyer_evidence_count: u8,
+    pub seller_evidence_count: u8,
+    // Committee ballots for the current round, one per arbitrator, tallied per outcome
+    #[max_len(7)] // MAX_ARBITRATORS
+    pub votes: Vec<ResolutionVote>,
+    // Staked panel behind the original ruling (not yet slashed) and appeal outcome
+    #[max_len(7)] // MAX_ARBITRATORS
+    pub ruling_arbitrators: Vec<Pubkey>,
+    pub appeal_overturned: bool,
+    // Pending resolution is the SLA default (initiator may execute without timelock)
+    pub sla_default: bool,
+    pub fee_policy: DisputeFeePolicy,
+    pub bump: u8,
+    // Respondent's matching fee, held with the initiator's until execution
+    pub respondent_bond: u64,
+    // Bond was posted (the fee, and so the bond, can be 0 on a small sale)
+    pub respondent_bonded: bool,
+    // Respondent must bond by then or risk default judgment (0 = bonds were off at open
+    // or legacy dispute: no window, no default judgment)
+    pub response_deadline: i64,
+    // Respondent's statement, recorded once before a resolution is proposed
+    pub response_status: Option<DisputeResponseStatus>,
+    #[max_len(500)]
+    pub response: String,
+    pub response_hash: Option<[u8; 32]>,
+    pub responded_at: Option<i64>,
+    // Typed grounds and initiator-assessed severity (None = legacy dispute)
+    pub category: Option<DisputeCategory>,
+    pub severity: Option<DisputeSeverity>,
+    // Sale was below the small-claims threshold at open time (shorter contest window)
+    pub small_claim: bool,
+    // Moderator behind the pending small-claim ruling; they may execute it once its
+    // contest window passes (None = admin or committee proposal)
+    pub small_claim_moderator: Option<Pubkey>,
+    // Final ruling has been paid out of escrow (held until the appeal window closes)
+    pub ruling_settled: bool,
+}
+
+impl Dispute {
+    /// Contest window for the pending proposal (appeals get a longer timelock)
+    pub fn resolution_timelock(&self) -> i64 {
+        if self.appealed_by.is_some() {
+            APPEAL_RESOLUTION_TIMELOCK_SECONDS
+        } else if self.small_claim {
+            SMALL_CLAIM_RESOLUTION_TIMELOCK_SECONDS
+        } else {
+            DISPUTE_RESOLUTION_TIMELOCK_SECONDS
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingWithdrawal {
+    pub version: u8,
+    pub user: Pubkey,
+    pub listing: Pubkey,
+    pub amount: u64,
+    // Part of amount counted in the user's UserProfile exposure (0 = none counted)
+    pub exposure: u64,
+    pub withdrawal_id: u64,  // Unique ID from listing.withdrawal_count
+    pub created_at: i64,
+    pub expires_at: i64,  // Auto-expire after 1 hour
+    // Who funded this PDA's rent (new bidder or sponsor); reimbursed when it closes
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct Offer {
+    pub version: u8,
+    pub listing: Pubkey,
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub deadline: i64,
+    pub status: OfferStatus,
+    pub created_at: i64,
+    // Optional third party who becomes the transaction buyer if accepted
+    pub recipient: Option<Pubkey>,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OfferEscrow {
+    pub version: u8,
+    pub offer: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Queued withdrawal from the program treasury vault (executable after the admin timelock)
+#[account]
+#[derive(InitSpace)]
+pub struct TreasuryWithdrawal {
+    pub version: u8,
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub requested_at: i64,
+    pub executable_at: i64,
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+/// Per-category platform fee rates (singleton), locked into listings at creation
+#[account]
+#[derive(InitSpace)]
+pub struct FeeSchedule {
+    pub version: u8,
+    // Rate for categories without an entry (None = config.platform_fee_bps)
+    pub default_fee_bps: Option<u64>,
+    #[max_len(15)] // CATEGORY_COUNT
+    pub category_fees: Vec<CategoryFee>,
+    pub bump: u8,
+}
+
+impl FeeSchedule {
+    /// Scheduled rate for a category, if any
+    pub fn fee_bps(&self, category: &Category) -> Option<u64> {
+        self.category_fees
+            .iter()
+            .find(|entry| entry.category == *category)
+            .map(|entry| entry.fee_bps)
+            .or(self.default_fee_bps)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct CategoryFee {
+    pub category: Category,
+    pub fee_bps: u64,
+}
+
+/// Completed-sale aggregates for one payment currency (config totals mix currencies)
+#[account]
+#[derive(InitSpace)]
+pub struct VolumeStats {
+    pub version: u8,
+    // SPL payment mint, or Pubkey::default() for native SOL
+    pub mint: Pubkey,
+    pub total_volume: u64,
+    pub total_sales: u64,
+    pub total_fees: u64,
+    pub last_sale_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl VolumeStats {
+    /// Count a completed sale (saturating, so stats never block a completion)
+    pub fn record(&mut self, transaction: &Transaction, now: i64) {
+        self.total_volume = self.total_volume.saturating_add(transaction.sale_price);
+        self.total_sales = self.total_sales.saturating_add(1);
+        self.total_fees = self.total_fees.saturating_add(transaction.platform_fee);
+        self.last_sale_at = Some(now);
+    }
+}
+
+/// Read-only mirror of the program's compiled behavioral constants, so clients
+/// fetch timing and increment rules instead of hardcoding them
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolParams {
+    pub version: u8,
+    // PROTOCOL_PARAMS_VERSION of the program build that last synced this account
+    pub params_version: u16,
+    // Auctions
+    pub max_auction_duration_seconds: i64,
+    pub min_bid_increment_bps: u64,
+    pub min_bid_increment_lamports: u64,
+    pub anti_snipe_window_seconds: i64,
+    pub anti_snipe_extension_seconds: i64,
+    pub settlement_grace_period_seconds: i64,
+    pub settlement_tip_bps: u64,
+    pub min_deposit_bps: u64,
+    pub deposit_payment_window_seconds: i64,
+    pub max_bids_per_listing: u64,
+    pub max_consecutive_bids: u32,
+    pub max_offers_per_listing: u32,
+    pub max_consecutive_offers: u32,
+    // Transfer and verification
+    pub transfer_deadline_seconds: i64,
+    pub finalize_grace_period_seconds: i64,
+    pub backend_timeout_seconds: i64,
+    pub verification_cure_period_seconds: i64,
+    pub warranty_period_seconds: i64,
+    pub insurance_claim_window_seconds: i64,
+    // Disputes
+    pub dispute_resolution_timelock_seconds: i64,
+    pub appeal_resolution_timelock_seconds: i64,
+    pub default_dispute_sla_seconds: i64,
+    // Governance and fee ceilings
+    pub admin_timelock_seconds: i64,
+    pub payout_wallet_timelock_seconds: i64,
+    pub max_pause_duration_seconds: i64,
+    pub max_platform_fee_bps: u64,
+    pub max_dispute_fee_bps: u64,
+    pub synced_at: i64,
+    pub bump: u8,
+    // Offers (admin-configured, mirrored from MarketConfig)
+    pub min_offer_duration_seconds: i64,
+    pub max_offer_duration_seconds: i64,
+    // 0 while respondent bonds are disabled
+    pub respondent_bond_window_seconds: i64,
+    // Small-claims fast track (threshold mirrored from MarketConfig)
+    pub small_claims_threshold: u64,
+    pub small_claim_resolution_timelock_seconds: i64,
+}
+
+impl ProtocolParams {
+    /// Overwrite every mirrored value with this build's constants and the config's limits
+    pub fn sync(&mut self, config: &MarketConfig, now: i64) {
+        self.params_version = PROTOCOL_PARAMS_VERSION;
+        self.max_auction_duration_seconds = MAX_AUCTION_DURATION_SECONDS;
+        self.min_bid_increment_bps = MIN_BID_INCREMENT_BPS;
+        self.min_bid_increment_lamports = MIN_BID_INCREMENT_LAMPORTS;
+        self.anti_snipe_window_seconds = ANTI_SNIPE_WINDOW;
+        self.anti_snipe_extension_seconds = ANTI_SNIPE_EXTENSION;
+        self.settlement_grace_period_seconds = SETTLEMENT_GRACE_PERIOD_SECONDS;
+        self.settlement_tip_bps = SETTLEMENT_TIP_BPS;
+        self.min_deposit_bps = MIN_DEPOSIT_BPS;
+        self.deposit_payment_window_seconds = DEPOSIT_PAYMENT_WINDOW_SECONDS;
+        self.max_bids_per_listing = MAX_BIDS_PER_LISTING;
+        self.max_consecutive_bids = MAX_CONSECUTIVE_BIDS;
+        self.max_offers_per_listing = MAX_OFFERS_PER_LISTING;
+        self.max_consecutive_offers = MAX_CONSECUTIVE_OFFERS;
+        self.transfer_deadline_seconds = TRANSFER_DEADLINE_SECONDS;
+        self.finalize_grace_period_seconds = FINALIZE_GRACE_PERIOD;
+        self.backend_timeout_seconds = BACKEND_TIMEOUT_SECONDS;
+        self.verification_cure_period_seconds = VERIFICATION_CURE_PERIOD_SECONDS;
+        self.warranty_period_seconds = WARRANTY_PERIOD_SECONDS;
+        self.insurance_claim_window_seconds = INSURANCE_CLAIM_WINDOW_SECONDS;
+        self.dispute_resolution_timelock_seconds = DISPUTE_RESOLUTION_TIMELOCK_SECONDS;
+        self.appeal_resolution_timelock_seconds = APPEAL_RESOLUTION_TIMELOCK_SECONDS;
+        self.default_dispute_sla_seconds = DEFAULT_DISPUTE_SLA_SECONDS;
+        self.admin_timelock_seconds = ADMIN_TIMELOCK_SECONDS;
+        self.payout_wallet_timelock_seconds = PAYOUT_WALLET_TIMELOCK_SECONDS;
+        self.max_pause_duration_seconds = MAX_PAUSE_DURATION_SECONDS;
+        self.max_platform_fee_bps = MAX_PLATFORM_FEE_BPS;
+        self.max_dispute_fee_bps = MAX_DISPUTE_FEE_BPS;
+        (self.min_offer_duration_seconds, self.max_offer_duration_seconds) =
+            config.offer_duration_bounds();
+        self.respondent_bond_window_seconds = if config.respondent_bonds_enabled {
+            RESPONDENT_BOND_WINDOW_SECONDS
+        } else {
+            0
+        };
+        self.small_claims_threshold = config.small_claims_threshold;
+        self.small_claim_resolution_timelock_seconds = SMALL_CLAIM_RESOLUTION_TIMELOCK_SECONDS;
+        self.synced_at = now;
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserProfile {
+    pub version: u8,
+    pub user: Pubkey,
+    // Loyalty points (spendable balance and all-time total), earned on SOL sales as buyer or
+    // seller and spent via redeem_points on the user's next listing fee
+    pub points: u64,
+    pub lifetime_points: u64,
+    pub total_purchases: u64,
+    pub total_sales: u64,
+    // Redeemed discount applied to the user's next listing
+    pub fee_discount_bps: u64,
+    // Escrow currently locked by this user's bids (checked against config.max_bidder_exposure)
+    pub locked_exposure: u64,
+    // X25519 public key for end-to-end encrypted handover messaging
+    pub contact_key: Option<[u8; 32]>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Per-seller signed order counter (orders signed with an older nonce are void)
+#[account]
+#[derive(InitSpace)]
+pub struct OrderNonce {
+    pub version: u8,
+    pub seller: Pubkey,
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+/// Marker that a single signed order (seller + salt) was cancelled
+#[account]
+#[derive(InitSpace)]
+pub struct OrderCancellation {
+    pub version: u8,
+    pub seller: Pubkey,
+    pub salt: u64,
+    pub cancelled_at: i64,
+    pub bump: u8,
+}
+
+/// Seller-signed sell order, settled on-chain by fulfill_signed_order
+/// The ed25519 signature covers the borsh encoding of this struct
+/// One entry of create_listings_batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchListingParams {
+    pub salt: u64,
+    pub listing_type: ListingType,
+    pub starting_price: u64,
+    pub reserve_price: Option<u64>,
+    pub buy_now_price: Option<u64>,
+    pub duration_seconds: i64,
+    pub category: Category,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SignedOrder {
+    // Program the order is valid for (prevents cross-deployment replay)
+    pub market: Pubkey,
+    pub seller: Pubkey,
+    // Listing PDA seed (one fill per salt)
+    pub salt: u64,
+    // Must equal the seller's OrderNonce (increment_nonce voids all outstanding orders)
+    pub nonce: u64,
+    pub price: u64,
+    pub expires_at: i64,
+    // Private order: only this wallet may fill (None = anyone)
+    pub buyer: Option<Pubkey>,
+    pub vesting_days: u16,
+    pub arbitrator: Option<Pubkey>,
+}